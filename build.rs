@@ -1,8 +1,9 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 fn main() {
-    println!("cargo:rerun-if-changed=src/rules/");
+    println!("cargo:rerun-if-changed=src/");
+    println!("cargo:rerun-if-changed=Cargo.lock");
 
     let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
     let rules_dir = Path::new(&manifest_dir).join("src/rules");
@@ -23,7 +24,52 @@ fn main() {
         })
         .collect();
 
+    // Each rule's spec.md is embedded so SARIF rule help and `inspequte explain` work
+    // without the source tree.
+    let specs: String = modules
+        .iter()
+        .filter_map(|m| {
+            let spec_path = rules_dir.join(m).join("spec.md");
+            spec_path
+                .is_file()
+                .then(|| format!("    ({:?}, include_str!({:?})),\n", m, spec_path))
+        })
+        .collect();
+    let specs = format!("pub(crate) static RULE_SPECS: &[(&str, &str)] = &[\n{specs}];\n");
+
     let out_dir = std::env::var("OUT_DIR").unwrap();
     let dest = Path::new(&out_dir).join("rule_modules.rs");
     fs::write(dest, content).unwrap();
+    let dest = Path::new(&out_dir).join("rule_specs.rs");
+    fs::write(dest, specs).unwrap();
+
+    // Cache keys include this fingerprint so results written by one build are never read by
+    // a build with different sources or dependencies, even at the same package version.
+    let manifest_dir = Path::new(&manifest_dir);
+    let mut inputs = vec![manifest_dir.join("Cargo.lock")];
+    collect_files(&manifest_dir.join("src"), &mut inputs);
+    inputs.sort();
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for input in inputs {
+        let relative = input.strip_prefix(manifest_dir).unwrap_or(&input);
+        let bytes = fs::read(&input).unwrap_or_default();
+        for field in [relative.to_string_lossy().as_bytes(), bytes.as_slice()] {
+            for byte in (field.len() as u64).to_le_bytes().iter().chain(field) {
+                hash ^= u64::from(*byte);
+                hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+            }
+        }
+    }
+    println!("cargo:rustc-env=INSPEQUTE_BUILD_FINGERPRINT={hash:016x}");
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
 }
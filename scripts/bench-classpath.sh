@@ -31,18 +31,31 @@ log_file="${log_dir}/classpath.log"
 otel_url="${OTEL_ENDPOINT:-}"
 mkdir -p "${log_dir}"
 
-cargo build >/dev/null
+binary="${INSPEQUTE_BIN:-}"
+if [ -z "${binary}" ]; then
+  cargo build --release >/dev/null
+  binary="./target/release/inspequte"
+fi
 
-echo "bench: input=${input} repeat=${repeat}" | tee -a "${log_file}"
+echo "bench: input=${input} repeat=${repeat} binary=${binary}" | tee -a "${log_file}"
 i=1
 while [ "${i}" -le "${repeat}" ]; do
   otel_args=""
   if [ -n "${otel_url}" ]; then
     otel_args="--otel ${otel_url}"
   fi
-  ./target/debug/inspequte --input "${input}" ${otel_args} ${classpath_args} \
-    1>/dev/null
-  echo "run ${i}: completed" | tee -a "${log_file}"
+  # Report wall time and peak resident set size of the analysis process.
+  usage="$(python3 -c '
+import resource, subprocess, sys, time
+started = time.monotonic()
+subprocess.run(sys.argv[1:], stdout=subprocess.DEVNULL, check=True)
+wall = time.monotonic() - started
+max_rss = resource.getrusage(resource.RUSAGE_CHILDREN).ru_maxrss
+if sys.platform == "darwin":
+    max_rss //= 1024
+print(f"wall={wall:.3f}s max_rss={max_rss // 1024}MiB")
+' "${binary}" --input "${input}" ${otel_args} ${classpath_args})"
+  echo "run ${i}: ${usage}" | tee -a "${log_file}"
   i=$((i + 1))
 done
 
@@ -53,6 +53,7 @@ mkdir -p "$(dirname "${output_file}")"
 meta_json="${bench_dir}/meta.json"
 guava_json="${bench_dir}/guava.json"
 sonarqube_json="${bench_dir}/sonarqube.json"
+classpath_json="${bench_dir}/classpath.json"
 
 json_get_or_default() {
   local file="$1"
@@ -89,6 +90,39 @@ render_results_table() {
   done < <(jq -r '.results | sort_by(.median)[] | [.command, .median, .mean, .min, .max] | @tsv' "${json_file}")
 }
 
+render_classpath_section() {
+  if [[ ! -f "${classpath_json}" ]]; then
+    return
+  fi
+
+  echo
+  echo "## Classpath Scan"
+  echo "- Peak resident set size and median wall time of a full inspequte run (all rules) over a small input with a large \`--classpath\`."
+  printf -- "- Command: \`%s\` (release build).\n" "$(json_get_or_default "${classpath_json}" '.command' 'unknown')"
+  printf -- "- Input: %s; classpath: %s.\n" "$(json_get_or_default "${classpath_json}" '.input' 'unknown')" "$(json_get_or_default "${classpath_json}" '.classpath' 'unknown')"
+  printf -- "- Environment: %s %s, %s.\n" "$(json_get_or_default "${classpath_json}" '.environment.os' 'unknown')" "$(json_get_or_default "${classpath_json}" '.environment.kernel' 'unknown')" "$(json_get_or_default "${classpath_json}" '.environment.cpu' 'unknown')"
+  printf -- "- Generated at (UTC): \`%s\`.\n" "$(json_get_or_default "${classpath_json}" '.generated_at_utc' 'unknown')"
+  local note
+  note="$(json_get_or_default "${classpath_json}" '.note' '')"
+  if [[ -n "${note}" ]]; then
+    printf -- "- %s\n" "${note}"
+  fi
+  echo
+  echo "Builds are listed in the order the changes were made; changes are against the first build."
+  echo
+  echo "| Build | Commit | Median wall (min-max) | Wall change | Peak RSS | RSS change |"
+  echo "| --- | --- | ---: | ---: | ---: | ---: |"
+  while IFS=$'\t' read -r build commit wall min_wall max_wall wall_change rss rss_change; do
+    printf "| %s | \`%s\` | %s (%s-%s) | %+.1f%% | %s MiB | %+.1f%% |\n" \
+      "${build}" "${commit}" "$(format_seconds "${wall}")" "$(format_seconds "${min_wall}")" \
+      "$(format_seconds "${max_wall}")" "${wall_change}" "${rss}" "${rss_change}"
+  done < <(jq -r '.results[0] as $first | .results[]
+    | [.build, .commit, .median_wall_s, .min_wall_s, .max_wall_s,
+       ((.median_wall_s / $first.median_wall_s - 1) * 100),
+       .max_rss_mib,
+       ((.max_rss_mib / $first.max_rss_mib - 1) * 100)] | @tsv' "${classpath_json}")
+}
+
 generated_at="$(json_get_or_default "${meta_json}" '.generated_at_utc' 'unknown')"
 env_os="$(json_get_or_default "${meta_json}" '.environment.os' 'unknown')"
 env_kernel="$(json_get_or_default "${meta_json}" '.environment.kernel' 'unknown')"
@@ -146,6 +180,7 @@ errorprone_version="$(json_get_or_default "${meta_json}" '.tools.error_prone' '2
   echo
   echo "## Results: SonarQube"
   render_results_table "${sonarqube_json}"
+  render_classpath_section
   echo
   echo "## Caveats and Fairness"
   echo "- Rule sets are aligned to nullness intent, not full one-to-one semantic equivalence."
@@ -156,6 +191,7 @@ errorprone_version="$(json_get_or_default "${meta_json}" '.tools.error_prone' '2
   echo "## Repro Command"
   echo '```bash'
   echo 'bash scripts/bench-nullness-compare.sh --dataset all --min-runs 5 --warmup 1'
+  echo 'bash scripts/bench-classpath.sh <input> 10 <classpath...>'
   echo 'bash scripts/render-performance-docs.sh'
   echo '```'
 } > "${output_file}"
@@ -0,0 +1,313 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tracing::warn;
+
+/// Bumped whenever the layout of cached entries changes.
+const CACHE_FORMAT_VERSION: u32 = 2;
+
+const FNV_OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+const FNV_PRIME: u128 = 0x0000000001000000000000000000013b;
+
+/// 128-bit FNV-1a digest of class bytes or of a cache key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct ContentHash(u128);
+
+impl ContentHash {
+    pub(crate) fn of(bytes: &[u8]) -> Self {
+        let mut hasher = KeyHasher {
+            hash: FNV_OFFSET_BASIS,
+        };
+        hasher.write(bytes);
+        Self(hasher.hash)
+    }
+
+    fn to_hex(self) -> String {
+        format!("{:032x}", self.0)
+    }
+}
+
+/// Builds cache keys from length-prefixed fields, seeded with the entry kind, the cache
+/// format, the inspequte version and a fingerprint of the sources it was built from so
+/// entries never outlive the code that wrote them.
+pub(crate) struct KeyHasher {
+    hash: u128,
+}
+
+impl KeyHasher {
+    pub(crate) fn new(kind: CacheKind) -> Self {
+        let mut hasher = Self {
+            hash: FNV_OFFSET_BASIS,
+        };
+        hasher.write_u64(u64::from(CACHE_FORMAT_VERSION));
+        hasher.write_str(env!("CARGO_PKG_VERSION"));
+        hasher.write_str(env!("INSPEQUTE_BUILD_FINGERPRINT"));
+        hasher.write_str(kind.dir_name());
+        hasher
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.hash ^= u128::from(*byte);
+            self.hash = self.hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    pub(crate) fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    pub(crate) fn write_str(&mut self, value: &str) {
+        self.write_u64(value.len() as u64);
+        self.write(value.as_bytes());
+    }
+
+    pub(crate) fn write_hash(&mut self, value: ContentHash) {
+        self.write(&value.0.to_le_bytes());
+    }
+
+    pub(crate) fn finish(&self) -> ContentHash {
+        ContentHash(self.hash)
+    }
+}
+
+/// Kinds of entries kept in the cache, each in its own subdirectory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CacheKind {
+    /// Parsed IR of one class file, keyed by the class bytes.
+    Class,
+    /// Results of one rule for one analysis target class.
+    RuleResults,
+    /// Whether a rule's results can be cached per class at all.
+    RuleCacheability,
+}
+
+impl CacheKind {
+    fn dir_name(self) -> &'static str {
+        match self {
+            CacheKind::Class => "classes",
+            CacheKind::RuleResults => "results",
+            CacheKind::RuleCacheability => "rules",
+        }
+    }
+}
+
+/// Hit and miss counts of one cache run, reported in the SARIF invocation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub(crate) struct CacheStats {
+    pub(crate) class_hits: usize,
+    pub(crate) class_misses: usize,
+    pub(crate) result_hits: usize,
+    pub(crate) result_misses: usize,
+}
+
+/// On-disk cache of parsed classes and per-class rule results under `--cache-dir`.
+///
+/// Entries are JSON files named by their key, so a cache directory can be shared between
+/// runs and deleted at any time. Unreadable or corrupt entries count as misses, and failing
+/// to write an entry only logs a warning.
+pub(crate) struct AnalysisCache {
+    root: PathBuf,
+    class_hits: AtomicUsize,
+    class_misses: AtomicUsize,
+    result_hits: AtomicUsize,
+    result_misses: AtomicUsize,
+    /// Content hashes of the classes scanned in this run, keyed by internal name. A name
+    /// defined by several artifacts keeps every hash.
+    class_hashes: Mutex<BTreeMap<String, BTreeSet<ContentHash>>>,
+}
+
+impl AnalysisCache {
+    pub(crate) fn open(root: &Path) -> Result<Self> {
+        fs::create_dir_all(root)
+            .with_context(|| format!("failed to create cache directory {}", root.display()))?;
+        Ok(Self {
+            root: root.to_path_buf(),
+            class_hits: AtomicUsize::new(0),
+            class_misses: AtomicUsize::new(0),
+            result_hits: AtomicUsize::new(0),
+            result_misses: AtomicUsize::new(0),
+            class_hashes: Mutex::new(BTreeMap::new()),
+        })
+    }
+
+    pub(crate) fn load<T: DeserializeOwned>(&self, kind: CacheKind, key: ContentHash) -> Option<T> {
+        let value = fs::read(self.entry_path(kind, key))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok());
+        let counter = match (kind, value.is_some()) {
+            (CacheKind::Class, true) => Some(&self.class_hits),
+            (CacheKind::Class, false) => Some(&self.class_misses),
+            (CacheKind::RuleResults, true) => Some(&self.result_hits),
+            (CacheKind::RuleResults, false) => Some(&self.result_misses),
+            (CacheKind::RuleCacheability, _) => None,
+        };
+        if let Some(counter) = counter {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+        value
+    }
+
+    pub(crate) fn store<T: Serialize>(&self, kind: CacheKind, key: ContentHash, value: &T) {
+        let path = self.entry_path(kind, key);
+        if let Err(err) = write_entry(&path, value) {
+            warn!("failed to write cache entry {}: {err:#}", path.display());
+        }
+    }
+
+    /// Remember the content hash of a scanned class for building rule result keys.
+    pub(crate) fn record_class(&self, name: &str, hash: ContentHash) {
+        self.class_hashes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(name.to_string())
+            .or_default()
+            .insert(hash);
+    }
+
+    /// Hashes of every scanned class, combined per name; classes scanned through another
+    /// cache or built in memory are absent.
+    pub(crate) fn class_hashes(&self) -> BTreeMap<String, ContentHash> {
+        self.class_hashes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(name, hashes)| {
+                let hash = match hashes.first() {
+                    Some(hash) if hashes.len() == 1 => *hash,
+                    _ => {
+                        let mut hasher = KeyHasher::new(CacheKind::Class);
+                        for hash in hashes {
+                            hasher.write_hash(*hash);
+                        }
+                        hasher.finish()
+                    }
+                };
+                (name.clone(), hash)
+            })
+            .collect()
+    }
+
+    pub(crate) fn stats(&self) -> CacheStats {
+        CacheStats {
+            class_hits: self.class_hits.load(Ordering::Relaxed),
+            class_misses: self.class_misses.load(Ordering::Relaxed),
+            result_hits: self.result_hits.load(Ordering::Relaxed),
+            result_misses: self.result_misses.load(Ordering::Relaxed),
+        }
+    }
+
+    fn entry_path(&self, kind: CacheKind, key: ContentHash) -> PathBuf {
+        let hex = key.to_hex();
+        self.root
+            .join(kind.dir_name())
+            .join(&hex[..2])
+            .join(format!("{hex}.json"))
+    }
+}
+
+/// Write through a temporary file so concurrent runs never observe a partial entry.
+fn write_entry<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    static NEXT_TEMP_ID: AtomicUsize = AtomicUsize::new(0);
+    let parent = path
+        .parent()
+        .context("cache entry without parent directory")?;
+    fs::create_dir_all(parent)?;
+    let bytes = serde_json::to_vec(value)?;
+    let temp = parent.join(format!(
+        ".{}-{}.tmp",
+        std::process::id(),
+        NEXT_TEMP_ID.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&temp, bytes)?;
+    if let Err(err) = fs::rename(&temp, path) {
+        let _ = fs::remove_file(&temp);
+        return Err(err.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn content_hash_distinguishes_bytes() {
+        assert_eq!(ContentHash::of(b"abc"), ContentHash::of(b"abc"));
+        assert_ne!(ContentHash::of(b"abc"), ContentHash::of(b"abd"));
+        assert_eq!(
+            ContentHash::of(b"").to_hex(),
+            "6c62272e07bb014262b821756295c58d"
+        );
+    }
+
+    #[test]
+    fn key_hasher_separates_fields() {
+        let mut left = KeyHasher::new(CacheKind::RuleResults);
+        left.write_str("ab");
+        left.write_str("c");
+        let mut right = KeyHasher::new(CacheKind::RuleResults);
+        right.write_str("a");
+        right.write_str("bc");
+        assert_ne!(left.finish(), right.finish());
+    }
+
+    #[test]
+    fn store_and_load_round_trip_and_count_hits() {
+        let dir = tempdir().expect("temp dir");
+        let cache = AnalysisCache::open(&dir.path().join("cache")).expect("open cache");
+        let key = ContentHash::of(b"class");
+
+        assert_eq!(cache.load::<Vec<String>>(CacheKind::Class, key), None);
+        cache.store(CacheKind::Class, key, &vec!["a".to_string()]);
+        assert_eq!(
+            cache.load::<Vec<String>>(CacheKind::Class, key),
+            Some(vec!["a".to_string()])
+        );
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                class_hits: 1,
+                class_misses: 1,
+                result_hits: 0,
+                result_misses: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn corrupt_entries_are_misses() {
+        let dir = tempdir().expect("temp dir");
+        let cache = AnalysisCache::open(dir.path()).expect("open cache");
+        let key = ContentHash::of(b"results");
+        let path = cache.entry_path(CacheKind::RuleResults, key);
+        fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        fs::write(&path, b"{not json").expect("write entry");
+
+        assert_eq!(cache.load::<Vec<String>>(CacheKind::RuleResults, key), None);
+        assert_eq!(cache.stats().result_misses, 1);
+    }
+
+    #[test]
+    fn class_hashes_combine_duplicate_names() {
+        let dir = tempdir().expect("temp dir");
+        let cache = AnalysisCache::open(dir.path()).expect("open cache");
+        let first = ContentHash::of(b"first");
+        let second = ContentHash::of(b"second");
+        cache.record_class("com/example/A", first);
+        cache.record_class("com/example/B", first);
+        cache.record_class("com/example/B", second);
+
+        let hashes = cache.class_hashes();
+        assert_eq!(hashes["com/example/A"], first);
+        assert_ne!(hashes["com/example/B"], first);
+        assert_ne!(hashes["com/example/B"], second);
+    }
+}
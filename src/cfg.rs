@@ -1,9 +1,13 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 use anyhow::{Context, Result};
 
-use crate::ir::{BasicBlock, ControlFlowGraph, EdgeKind, FlowEdge, Instruction};
+use crate::ir::{
+    BasicBlock, ControlFlowGraph, DuplicatedCode, EdgeKind, ExceptionHandler, FlowEdge,
+    Instruction, InstructionKind, LineNumber,
+};
 use crate::opcodes;
+use crate::symbol::JAVA_LANG_THROWABLE;
 
 /// Build a control flow graph from bytecode instructions.
 pub(crate) fn build_cfg(
@@ -96,7 +100,628 @@ pub(crate) fn build_cfg(
         }
     }
 
-    Ok(ControlFlowGraph { blocks, edges })
+    Ok(ControlFlowGraph {
+        blocks,
+        edges,
+        duplicates: DuplicatedCode::default(),
+    })
+}
+
+/// Recognize code javac duplicated for `finally` blocks and try-with-resources.
+///
+/// A `finally` body is found in each catch-any handler shaped `astore n; <body>; aload n;
+/// athrow`, and its copies are the other instruction runs with the same opcodes, operands and
+/// source lines. A resource close is the range guarded by a `Throwable.addSuppressed` handler;
+/// javac attributes its copies to different lines, so they must match byte for byte instead.
+pub(crate) fn find_duplicated_code(
+    cfg: &ControlFlowGraph,
+    code: &[u8],
+    instructions: &[Instruction],
+    handlers: &[ExceptionHandler],
+    line_numbers: &[LineNumber],
+) -> DuplicatedCode {
+    let index_of: BTreeMap<u32, usize> = instructions
+        .iter()
+        .enumerate()
+        .map(|(idx, inst)| (inst.offset, idx))
+        .collect();
+    let mut finally_offsets = BTreeSet::new();
+    let mut cleanup_offsets = BTreeSet::new();
+    let mut canonical_offsets = BTreeMap::new();
+
+    let mut handler_pcs = BTreeSet::new();
+    for handler in handlers {
+        if !handler_pcs.insert((handler.handler_pc, handler.catch_type.is_none())) {
+            continue;
+        }
+        let Some(&handler_index) = index_of.get(&handler.handler_pc) else {
+            continue;
+        };
+        let (template, offsets, lines) = if handler.catch_type.is_none() {
+            let Some(body) = finally_body(code, instructions, handler_index) else {
+                continue;
+            };
+            (body, &mut finally_offsets, line_numbers)
+        } else if adds_suppressed(instructions, handler_index) {
+            let start = instructions.partition_point(|inst| inst.offset < handler.start_pc);
+            let end = instructions.partition_point(|inst| inst.offset < handler.end_pc);
+            cleanup_offsets.extend(
+                instructions[handler_index..(handler_index + 4).min(instructions.len())]
+                    .iter()
+                    .map(|inst| inst.offset),
+            );
+            (start..end, &mut cleanup_offsets, &[][..])
+        } else {
+            continue;
+        };
+        if template.is_empty() {
+            continue;
+        }
+
+        let mut copies = vec![template.start];
+        let length = template.len();
+        let mut start = 0;
+        while start + length <= instructions.len() {
+            let overlaps_template = start < template.end && template.start < start + length;
+            if !overlaps_template
+                && (0..length).all(|i| {
+                    same_instruction(code, instructions, lines, template.start + i, start + i)
+                })
+            {
+                copies.push(start);
+                start += length;
+            } else {
+                start += 1;
+            }
+        }
+        copies.sort_unstable();
+        for copy in &copies {
+            offsets.extend(
+                instructions[*copy..*copy + length]
+                    .iter()
+                    .map(|inst| inst.offset),
+            );
+        }
+        let first = copies[0];
+        for copy in &copies[1..] {
+            for i in 0..length {
+                canonical_offsets
+                    .entry(instructions[copy + i].offset)
+                    .or_insert(instructions[first + i].offset);
+            }
+        }
+    }
+
+    // javac 9 and 10 close resources through a synthetic `$closeResource(Throwable,
+    // AutoCloseable)` helper called once per exit path.
+    let mut close_resource_lines = BTreeMap::new();
+    for inst in instructions {
+        if let InstructionKind::Invoke(call) = &inst.kind
+            && call.name == "$closeResource"
+        {
+            cleanup_offsets.insert(inst.offset);
+            let line = line_for_offset(line_numbers, inst.offset);
+            if let Some(first) = close_resource_lines.get(&line) {
+                canonical_offsets.entry(inst.offset).or_insert(*first);
+            } else {
+                close_resource_lines.insert(line, inst.offset);
+            }
+        }
+    }
+
+    // Nested `finally` blocks copy each other's copies; point every copy at the earliest one.
+    let mut resolved = BTreeMap::new();
+    for (offset, mut canonical) in canonical_offsets.iter().map(|(k, v)| (*k, *v)) {
+        while let Some(next) = canonical_offsets.get(&canonical)
+            && *next < canonical
+        {
+            canonical = *next;
+        }
+        if canonical < offset {
+            resolved.insert(offset, canonical);
+        }
+    }
+
+    DuplicatedCode {
+        canonical_offsets: resolved,
+        finally_blocks: blocks_containing(cfg, &finally_offsets),
+        resource_cleanup_blocks: blocks_containing(cfg, &cleanup_offsets),
+    }
+}
+
+/// Instruction range of a catch-any handler's `finally` body, between the exception store
+/// and the rethrow.
+fn finally_body(
+    code: &[u8],
+    instructions: &[Instruction],
+    handler_index: usize,
+) -> Option<std::ops::Range<usize>> {
+    let slot = reference_local(code, &instructions[handler_index], opcodes::ASTORE)?;
+    for idx in handler_index + 1..instructions.len() {
+        let opcode = instructions[idx].opcode;
+        if opcode == opcodes::ATHROW {
+            let rethrows = idx > handler_index + 1
+                && reference_local(code, &instructions[idx - 1], opcodes::ALOAD) == Some(slot);
+            return rethrows.then_some(handler_index + 1..idx - 1);
+        }
+        if is_exit_opcode(opcode) {
+            return None;
+        }
+    }
+    None
+}
+
+/// Whether a handler starts with javac's `astore t2; aload t; aload t2;
+/// invokevirtual Throwable.addSuppressed` sequence.
+fn adds_suppressed(instructions: &[Instruction], handler_index: usize) -> bool {
+    instructions
+        .iter()
+        .skip(handler_index)
+        .take(4)
+        .any(|inst| match &inst.kind {
+            InstructionKind::Invoke(call) => {
+                call.owner == JAVA_LANG_THROWABLE && call.name == "addSuppressed"
+            }
+            _ => false,
+        })
+}
+
+/// Local slot read by `aload` or written by `astore`, matching the given long form.
+fn reference_local(code: &[u8], inst: &Instruction, long_form: u8) -> Option<usize> {
+    let short_base = if long_form == opcodes::ALOAD {
+        opcodes::ALOAD_0
+    } else {
+        opcodes::ASTORE_0
+    };
+    if inst.opcode == long_form {
+        return code
+            .get(inst.offset as usize + 1)
+            .map(|slot| *slot as usize);
+    }
+    (short_base..=short_base + 3)
+        .contains(&inst.opcode)
+        .then(|| (inst.opcode - short_base) as usize)
+}
+
+/// Whether two instructions are copies of the same source code.
+///
+/// Branch offsets always differ between copies, so only their opcode is compared. When
+/// `line_numbers` is given, copies must map to the same source lines and local slots may
+/// differ; otherwise every other instruction must match byte for byte.
+fn same_instruction(
+    code: &[u8],
+    instructions: &[Instruction],
+    line_numbers: &[LineNumber],
+    left: usize,
+    right: usize,
+) -> bool {
+    let (a, b) = (&instructions[left], &instructions[right]);
+    if !line_numbers.is_empty() {
+        if line_for_offset(line_numbers, a.offset) != line_for_offset(line_numbers, b.offset) {
+            return false;
+        }
+        if let (Some(family_a), Some(family_b)) = (local_family(a.opcode), local_family(b.opcode)) {
+            return family_a == family_b;
+        }
+    }
+    if a.opcode != b.opcode {
+        return false;
+    }
+    if branch_targets_opcode(a.opcode) {
+        return true;
+    }
+    let bytes = |idx: usize| {
+        let start = instructions[idx].offset as usize;
+        let end = instructions
+            .get(idx + 1)
+            .map_or(code.len(), |next| next.offset as usize);
+        code.get(start..end)
+    };
+    bytes(left) == bytes(right)
+}
+
+/// Normalized opcode of a local variable load or store, ignoring the slot.
+fn local_family(opcode: u8) -> Option<u8> {
+    match opcode {
+        // iload..aload and istore..astore with an explicit slot operand.
+        0x15..=0x19 | 0x36..=0x3a => Some(opcode),
+        // iload_0..aload_3, grouped by type.
+        0x1a..=0x2d => Some(0x15 + (opcode - 0x1a) / 4),
+        // istore_0..astore_3, grouped by type.
+        0x3b..=0x4e => Some(0x36 + (opcode - 0x3b) / 4),
+        // iinc, ret and wide carry slot operands as well.
+        0x84 | 0xa9 | 0xc4 => Some(opcode),
+        _ => None,
+    }
+}
+
+fn branch_targets_opcode(opcode: u8) -> bool {
+    matches!(
+        opcode,
+        0x99..=0xa8 | opcodes::TABLESWITCH | opcodes::LOOKUPSWITCH | 0xc6..=0xc9
+    )
+}
+
+fn line_for_offset(line_numbers: &[LineNumber], offset: u32) -> Option<u32> {
+    line_numbers
+        .iter()
+        .take_while(|entry| entry.start_pc <= offset)
+        .last()
+        .map(|entry| entry.line)
+}
+
+fn blocks_containing(cfg: &ControlFlowGraph, offsets: &BTreeSet<u32>) -> BTreeSet<u32> {
+    cfg.blocks
+        .iter()
+        .filter(|block| {
+            offsets
+                .range(block.start_offset..block.end_offset)
+                .next()
+                .is_some()
+        })
+        .map(|block| block.start_offset)
+        .collect()
+}
+
+/// Dominance, post-dominance and loop structure of a method's control flow graph.
+///
+/// Exception handlers contribute an edge from every block overlapping their protected range,
+/// so a `finally` handler does not post-dominate its `try` body unless every normal path also
+/// runs it. Offsets passed to the queries may be any instruction offset; they are mapped to
+/// the enclosing basic block, and instructions in the same block are ordered by offset.
+#[derive(Clone, Debug)]
+pub(crate) struct ControlFlowStructure {
+    starts: Vec<u32>,
+    ends: Vec<u32>,
+    successors: Vec<Vec<usize>>,
+    idom: Vec<Option<usize>>,
+    ipdom: Vec<Option<usize>>,
+    loops: Vec<NaturalLoop>,
+    innermost_loop: Vec<Option<usize>>,
+}
+
+/// Natural loop formed by the back edges into a single header block.
+#[derive(Clone, Debug)]
+#[allow(dead_code)]
+pub(crate) struct NaturalLoop {
+    pub(crate) header: u32,
+    /// Blocks whose back edge jumps to the header.
+    pub(crate) latches: Vec<u32>,
+    /// Start offsets of every block in the loop, header included.
+    pub(crate) blocks: BTreeSet<u32>,
+    /// Index into `ControlFlowStructure::loops` of the enclosing loop.
+    pub(crate) parent: Option<usize>,
+    /// Nesting depth, where an outermost loop has depth 1.
+    pub(crate) depth: usize,
+}
+
+/// Compute dominator trees and natural loops for a control flow graph.
+///
+/// Irreducible cycles (entered other than through a dominating header) are not reported as
+/// loops, and blocks that cannot reach a return or throw have no post-dominator.
+pub(crate) fn build_flow_structure(
+    cfg: &ControlFlowGraph,
+    handlers: &[ExceptionHandler],
+) -> ControlFlowStructure {
+    let starts: Vec<u32> = cfg.blocks.iter().map(|block| block.start_offset).collect();
+    let ends: Vec<u32> = cfg.blocks.iter().map(|block| block.end_offset).collect();
+    let index: BTreeMap<u32, usize> = starts
+        .iter()
+        .enumerate()
+        .map(|(idx, start)| (*start, idx))
+        .collect();
+    let count = starts.len();
+
+    let mut successors = vec![BTreeSet::new(); count];
+    for edge in &cfg.edges {
+        if let (Some(from), Some(to)) = (index.get(&edge.from), index.get(&edge.to)) {
+            successors[*from].insert(*to);
+        }
+    }
+    for handler in handlers {
+        let Some(target) = index.get(&handler.handler_pc) else {
+            continue;
+        };
+        for (idx, block) in cfg.blocks.iter().enumerate() {
+            if block.start_offset < handler.end_pc && block.end_offset > handler.start_pc {
+                successors[idx].insert(*target);
+            }
+        }
+    }
+    let successors: Vec<Vec<usize>> = successors
+        .into_iter()
+        .map(|targets| targets.into_iter().collect())
+        .collect();
+    let mut predecessors = vec![Vec::new(); count];
+    for (from, targets) in successors.iter().enumerate() {
+        for to in targets {
+            predecessors[*to].push(from);
+        }
+    }
+
+    let idom = if count == 0 {
+        Vec::new()
+    } else {
+        dominator_tree(0, &successors, &predecessors)
+    };
+
+    // Post-dominators are dominators of the reversed graph rooted at a virtual exit node
+    // that every block without successors flows into.
+    let exit = count;
+    let mut reversed_successors = predecessors.clone();
+    let mut reversed_predecessors = successors.clone();
+    let exits: Vec<usize> = (0..count)
+        .filter(|idx| successors[*idx].is_empty())
+        .collect();
+    for block in &exits {
+        reversed_predecessors[*block].push(exit);
+    }
+    reversed_successors.push(exits);
+    reversed_predecessors.push(Vec::new());
+    let mut ipdom = dominator_tree(exit, &reversed_successors, &reversed_predecessors);
+    ipdom.truncate(count);
+    for entry in &mut ipdom {
+        if *entry == Some(exit) {
+            *entry = None;
+        }
+    }
+
+    let loops = natural_loops(&starts, &successors, &predecessors, &idom);
+    let mut innermost_loop = vec![None; count];
+    for (loop_index, natural_loop) in loops.iter().enumerate() {
+        for start in &natural_loop.blocks {
+            let block = index[start];
+            let deeper = innermost_loop[block]
+                .is_none_or(|current: usize| loops[current].depth < natural_loop.depth);
+            if deeper {
+                innermost_loop[block] = Some(loop_index);
+            }
+        }
+    }
+
+    ControlFlowStructure {
+        starts,
+        ends,
+        successors,
+        idom,
+        ipdom,
+        loops,
+        innermost_loop,
+    }
+}
+
+#[allow(dead_code)]
+impl ControlFlowStructure {
+    /// Start offset of the basic block containing `offset`.
+    pub(crate) fn block_containing(&self, offset: u32) -> Option<u32> {
+        self.block_index(offset).map(|idx| self.starts[idx])
+    }
+
+    /// Whether the instruction at `offset` can execute at all.
+    pub(crate) fn is_reachable(&self, offset: u32) -> bool {
+        self.block_index(offset)
+            .is_some_and(|idx| idx == 0 || self.idom[idx].is_some())
+    }
+
+    /// Whether every path from the method entry to `offset` passes through `dominator`.
+    pub(crate) fn dominates(&self, dominator: u32, offset: u32) -> bool {
+        let (Some(a), Some(b)) = (self.block_index(dominator), self.block_index(offset)) else {
+            return false;
+        };
+        if a == b {
+            return dominator <= offset;
+        }
+        ancestor_in_tree(&self.idom, a, b)
+    }
+
+    /// Whether every path from `offset` to a method exit passes through `post_dominator`.
+    pub(crate) fn post_dominates(&self, post_dominator: u32, offset: u32) -> bool {
+        let (Some(a), Some(b)) = (self.block_index(post_dominator), self.block_index(offset))
+        else {
+            return false;
+        };
+        if a == b {
+            return post_dominator >= offset;
+        }
+        ancestor_in_tree(&self.ipdom, a, b)
+    }
+
+    /// Start offset of the immediate dominator of the block containing `offset`.
+    pub(crate) fn immediate_dominator(&self, offset: u32) -> Option<u32> {
+        let idx = self.block_index(offset)?;
+        self.idom[idx].map(|parent| self.starts[parent])
+    }
+
+    /// Start offset of the immediate post-dominator of the block containing `offset`.
+    pub(crate) fn immediate_post_dominator(&self, offset: u32) -> Option<u32> {
+        let idx = self.block_index(offset)?;
+        self.ipdom[idx].map(|parent| self.starts[parent])
+    }
+
+    /// Whether control can flow from `from` to `to` along one or more edges.
+    ///
+    /// Unlike dominance, a block only reaches itself when it sits on a cycle.
+    pub(crate) fn can_reach(&self, from: u32, to: u32) -> bool {
+        let (Some(source), Some(target)) = (self.block_index(from), self.block_index(to)) else {
+            return false;
+        };
+        if source == target && from < to {
+            return true;
+        }
+        let mut visited = vec![false; self.starts.len()];
+        let mut stack = self.successors[source].clone();
+        while let Some(block) = stack.pop() {
+            if block == target {
+                return true;
+            }
+            if !std::mem::replace(&mut visited[block], true) {
+                stack.extend(self.successors[block].iter().copied());
+            }
+        }
+        false
+    }
+
+    /// Natural loops ordered by header offset.
+    pub(crate) fn loops(&self) -> &[NaturalLoop] {
+        &self.loops
+    }
+
+    /// The most deeply nested loop containing `offset`.
+    pub(crate) fn innermost_loop(&self, offset: u32) -> Option<&NaturalLoop> {
+        let idx = self.block_index(offset)?;
+        self.innermost_loop[idx].map(|loop_index| &self.loops[loop_index])
+    }
+
+    /// Number of loops enclosing `offset`; zero outside any loop.
+    pub(crate) fn loop_depth(&self, offset: u32) -> usize {
+        self.innermost_loop(offset)
+            .map_or(0, |natural_loop| natural_loop.depth)
+    }
+
+    fn block_index(&self, offset: u32) -> Option<usize> {
+        let idx = self.starts.partition_point(|start| *start <= offset);
+        let idx = idx.checked_sub(1)?;
+        (offset < self.ends[idx]).then_some(idx)
+    }
+}
+
+/// Immediate dominators of every node reachable from `root` (Cooper, Harvey and Kennedy).
+fn dominator_tree(
+    root: usize,
+    successors: &[Vec<usize>],
+    predecessors: &[Vec<usize>],
+) -> Vec<Option<usize>> {
+    let count = successors.len();
+    let mut postorder = Vec::with_capacity(count);
+    let mut visited = vec![false; count];
+    let mut stack = vec![(root, 0usize)];
+    visited[root] = true;
+    while let Some((node, next)) = stack.last_mut() {
+        if let Some(successor) = successors[*node].get(*next).copied() {
+            *next += 1;
+            if !visited[successor] {
+                visited[successor] = true;
+                stack.push((successor, 0));
+            }
+        } else {
+            postorder.push(*node);
+            stack.pop();
+        }
+    }
+    let mut postorder_number = vec![usize::MAX; count];
+    for (number, node) in postorder.iter().enumerate() {
+        postorder_number[*node] = number;
+    }
+
+    let mut idom = vec![None; count];
+    idom[root] = Some(root);
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for node in postorder.iter().rev().copied() {
+            if node == root {
+                continue;
+            }
+            let mut new_idom = None;
+            for predecessor in &predecessors[node] {
+                if idom[*predecessor].is_none() {
+                    continue;
+                }
+                new_idom = Some(match new_idom {
+                    None => *predecessor,
+                    Some(current) => intersect(&idom, &postorder_number, current, *predecessor),
+                });
+            }
+            if new_idom.is_some() && idom[node] != new_idom {
+                idom[node] = new_idom;
+                changed = true;
+            }
+        }
+    }
+    idom[root] = None;
+    idom
+}
+
+fn intersect(
+    idom: &[Option<usize>],
+    postorder_number: &[usize],
+    mut left: usize,
+    mut right: usize,
+) -> usize {
+    while left != right {
+        while postorder_number[left] < postorder_number[right] {
+            left = idom[left].expect("processed node has a dominator");
+        }
+        while postorder_number[right] < postorder_number[left] {
+            right = idom[right].expect("processed node has a dominator");
+        }
+    }
+    left
+}
+
+fn ancestor_in_tree(tree: &[Option<usize>], ancestor: usize, mut node: usize) -> bool {
+    while let Some(parent) = tree[node] {
+        if parent == ancestor {
+            return true;
+        }
+        node = parent;
+    }
+    false
+}
+
+fn natural_loops(
+    starts: &[u32],
+    successors: &[Vec<usize>],
+    predecessors: &[Vec<usize>],
+    idom: &[Option<usize>],
+) -> Vec<NaturalLoop> {
+    let mut latches_by_header: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (from, targets) in successors.iter().enumerate() {
+        let reachable = from == 0 || idom[from].is_some();
+        for to in targets {
+            if reachable && (*to == from || ancestor_in_tree(idom, *to, from)) {
+                latches_by_header.entry(*to).or_default().push(from);
+            }
+        }
+    }
+
+    let mut loops: Vec<NaturalLoop> = latches_by_header
+        .into_iter()
+        .map(|(header, latches)| {
+            let mut body = BTreeSet::from([header]);
+            let mut stack = latches.clone();
+            while let Some(block) = stack.pop() {
+                if body.insert(block) {
+                    stack.extend(predecessors[block].iter().copied());
+                }
+            }
+            NaturalLoop {
+                header: starts[header],
+                latches: latches.into_iter().map(|latch| starts[latch]).collect(),
+                blocks: body.into_iter().map(|block| starts[block]).collect(),
+                parent: None,
+                depth: 1,
+            }
+        })
+        .collect();
+
+    // Natural loops with distinct headers are either disjoint or nested, so the enclosing
+    // loop is the smallest other loop containing this header.
+    for idx in 0..loops.len() {
+        loops[idx].parent = (0..loops.len())
+            .filter(|other| *other != idx && loops[*other].blocks.contains(&loops[idx].header))
+            .min_by_key(|other| loops[*other].blocks.len());
+    }
+    for idx in 0..loops.len() {
+        let mut depth = 1;
+        let mut parent = loops[idx].parent;
+        while let Some(outer) = parent {
+            depth += 1;
+            parent = loops[outer].parent;
+        }
+        loops[idx].depth = depth;
+    }
+    loops
 }
 
 fn next_block_start(blocks: &[BasicBlock], offset: u32) -> Option<u32> {
@@ -204,6 +829,8 @@ fn read_i32(code: &[u8], offset: usize) -> Result<i32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scan::scan_inputs;
+    use crate::test_harness::{JvmTestHarness, Language, SourceFile};
 
     #[test]
     fn tableswitch_targets_collects_default_and_offsets() {
@@ -233,4 +860,228 @@ mod tests {
 
         assert_eq!(targets, vec![16, 4, 12]);
     }
+
+    fn graph(blocks: &[(u32, u32)], edges: &[(u32, u32)]) -> ControlFlowGraph {
+        ControlFlowGraph {
+            blocks: blocks
+                .iter()
+                .map(|(start, end)| BasicBlock {
+                    start_offset: *start,
+                    end_offset: *end,
+                    instructions: Vec::new(),
+                })
+                .collect(),
+            edges: edges
+                .iter()
+                .map(|(from, to)| FlowEdge {
+                    from: *from,
+                    to: *to,
+                    kind: EdgeKind::Branch,
+                })
+                .collect(),
+            duplicates: Default::default(),
+        }
+    }
+
+    #[test]
+    fn flow_structure_computes_dominators_of_diamond() {
+        let cfg = graph(
+            &[(0, 10), (10, 20), (20, 30), (30, 40)],
+            &[(0, 10), (0, 20), (10, 30), (20, 30)],
+        );
+
+        let structure = build_flow_structure(&cfg, &[]);
+
+        assert_eq!(structure.immediate_dominator(30), Some(0));
+        assert_eq!(structure.immediate_post_dominator(0), Some(30));
+        assert_eq!(structure.immediate_post_dominator(30), None);
+        assert!(structure.dominates(0, 35));
+        assert!(structure.dominates(2, 5));
+        assert!(!structure.dominates(5, 2));
+        assert!(!structure.dominates(10, 30));
+        assert!(structure.post_dominates(30, 10));
+        assert!(!structure.post_dominates(10, 0));
+        assert!(structure.loops().is_empty());
+    }
+
+    #[test]
+    fn flow_structure_nests_natural_loops() {
+        let cfg = graph(
+            &[(0, 10), (10, 20), (20, 30), (30, 40), (40, 50), (50, 60)],
+            &[
+                (0, 10),
+                (10, 20),
+                (10, 50),
+                (20, 30),
+                (20, 40),
+                (30, 20),
+                (40, 10),
+            ],
+        );
+
+        let structure = build_flow_structure(&cfg, &[]);
+
+        let loops = structure.loops();
+        assert_eq!(loops.len(), 2);
+        assert_eq!(loops[0].header, 10);
+        assert_eq!(loops[0].latches, vec![40]);
+        assert_eq!(loops[0].blocks, BTreeSet::from([10, 20, 30, 40]));
+        assert_eq!(loops[0].depth, 1);
+        assert_eq!(loops[1].header, 20);
+        assert_eq!(loops[1].blocks, BTreeSet::from([20, 30]));
+        assert_eq!(loops[1].parent, Some(0));
+        assert_eq!(structure.loop_depth(35), 2);
+        assert_eq!(structure.loop_depth(45), 1);
+        assert_eq!(structure.loop_depth(55), 0);
+        assert_eq!(
+            structure
+                .innermost_loop(25)
+                .map(|natural_loop| natural_loop.header),
+            Some(20)
+        );
+        assert!(structure.can_reach(30, 30));
+        assert!(structure.can_reach(40, 20));
+        assert!(!structure.can_reach(50, 10));
+        assert!(!structure.can_reach(0, 0));
+    }
+
+    #[test]
+    fn flow_structure_follows_exception_handlers() {
+        let cfg = graph(&[(0, 10), (10, 20), (20, 30), (30, 40)], &[(0, 10)]);
+        let handler = ExceptionHandler {
+            start_pc: 0,
+            end_pc: 10,
+            handler_pc: 20,
+            catch_type: None,
+        };
+
+        let structure = build_flow_structure(&cfg, &[handler]);
+
+        assert!(structure.is_reachable(25));
+        assert!(!structure.is_reachable(30));
+        assert_eq!(structure.immediate_dominator(20), Some(0));
+        assert_eq!(structure.immediate_post_dominator(0), None);
+        assert!(!structure.post_dominates(20, 0));
+        assert!(!structure.post_dominates(10, 0));
+        assert_eq!(structure.block_containing(15), Some(10));
+        assert_eq!(structure.block_containing(40), None);
+    }
+
+    #[test]
+    fn flow_structure_leaves_infinite_loop_without_post_dominator() {
+        let cfg = graph(&[(0, 10), (10, 20)], &[(0, 10), (10, 10)]);
+
+        let structure = build_flow_structure(&cfg, &[]);
+
+        assert_eq!(structure.immediate_post_dominator(0), None);
+        assert!(!structure.post_dominates(10, 0));
+        assert_eq!(structure.loops().len(), 1);
+        assert_eq!(structure.loops()[0].latches, vec![10]);
+    }
+
+    fn compile_method(contents: &str, method_name: &str) -> crate::ir::Method {
+        let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
+        let sources = vec![SourceFile {
+            path: "com/example/ClassA.java".to_string(),
+            contents: contents.to_string(),
+        }];
+        let output = harness
+            .compile(Language::Java, &sources, &[])
+            .expect("compile sources");
+        scan_inputs(&[output.classes_dir().to_path_buf()], &[], None, None)
+            .expect("scan classes")
+            .classes
+            .into_iter()
+            .flat_map(|class| class.methods)
+            .find(|method| method.name == method_name)
+            .expect("method")
+    }
+
+    fn call_offsets(method: &crate::ir::Method, name: &str) -> Vec<u32> {
+        method
+            .calls
+            .iter()
+            .filter(|call| call.name == name)
+            .map(|call| call.offset)
+            .collect()
+    }
+
+    #[test]
+    fn duplicated_code_maps_finally_copies_to_first_copy() {
+        let method = compile_method(
+            r#"
+package com.example;
+public class ClassA {
+    public int methodX(int varOne) {
+        try {
+            if (varOne > 0) {
+                return varOne;
+            }
+            return Integer.parseInt("1");
+        } finally {
+            System.gc();
+        }
+    }
+}
+"#,
+            "methodX",
+        );
+
+        let offsets = call_offsets(&method, "gc");
+        assert_eq!(offsets.len(), 3, "gc calls: {offsets:?}");
+        assert!(!method.cfg.is_duplicate(offsets[0]));
+        assert_eq!(method.cfg.canonical_offset(offsets[1]), offsets[0]);
+        assert_eq!(method.cfg.canonical_offset(offsets[2]), offsets[0]);
+        assert_eq!(
+            method
+                .source_calls()
+                .filter(|call| call.name == "gc")
+                .count(),
+            1
+        );
+        let handler = method.exception_handlers[0].handler_pc;
+        assert!(method.cfg.is_finally_block(handler));
+        assert!(!method.cfg.is_finally_block(0));
+    }
+
+    #[test]
+    fn duplicated_code_marks_try_with_resources_cleanup() {
+        let method = compile_method(
+            r#"
+package com.example;
+import java.io.InputStream;
+public class ClassA {
+    public int methodX(InputStream varOne) throws Exception {
+        try (InputStream varTwo = varOne) {
+            return varTwo.read();
+        }
+    }
+}
+"#,
+            "methodX",
+        );
+
+        let offsets = call_offsets(&method, "close");
+        assert_eq!(offsets.len(), 2, "close calls: {offsets:?}");
+        assert_eq!(method.cfg.canonical_offset(offsets[1]), offsets[0]);
+        let suppressed = call_offsets(&method, "addSuppressed");
+        let cleanup_block = method
+            .cfg
+            .blocks
+            .iter()
+            .find(|block| block.start_offset <= suppressed[0] && suppressed[0] < block.end_offset)
+            .expect("addSuppressed block");
+        assert!(
+            method
+                .cfg
+                .is_resource_cleanup_block(cleanup_block.start_offset)
+        );
+        assert_eq!(
+            method
+                .source_calls()
+                .filter(|call| call.name == "close")
+                .count(),
+            1
+        );
+    }
 }
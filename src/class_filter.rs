@@ -0,0 +1,190 @@
+use anyhow::Result;
+
+use crate::ir::Class;
+
+/// Selects which analysis target classes produce findings.
+///
+/// Classes that do not pass the filter stay available as dependency classes, so type
+/// resolution across them still works.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ClassFilter {
+    include: Vec<ClassPattern>,
+    exclude: Vec<ClassPattern>,
+    exclude_generated: bool,
+}
+
+impl ClassFilter {
+    /// Build a filter from `--include`/`--exclude` patterns; each argument may hold several
+    /// comma-separated patterns.
+    pub(crate) fn new(
+        include: &[String],
+        exclude: &[String],
+        exclude_generated: bool,
+    ) -> Result<Self> {
+        Ok(Self {
+            include: parse_patterns("--include", include)?,
+            exclude: parse_patterns("--exclude", exclude)?,
+            exclude_generated,
+        })
+    }
+
+    /// Whether `class` stays an analysis target.
+    pub(crate) fn matches(&self, class: &Class) -> bool {
+        if self.exclude_generated && class.is_generated {
+            return false;
+        }
+        let name = class.name.replace('/', ".");
+        (self.include.is_empty() || self.include.iter().any(|pattern| pattern.matches(&name)))
+            && !self.exclude.iter().any(|pattern| pattern.matches(&name))
+    }
+}
+
+fn parse_patterns(flag: &str, args: &[String]) -> Result<Vec<ClassPattern>> {
+    let mut patterns = Vec::new();
+    for arg in args {
+        for pattern in arg.split(',') {
+            let pattern = pattern.trim();
+            if pattern.is_empty() {
+                anyhow::bail!("empty pattern in {flag}");
+            }
+            patterns.push(ClassPattern::new(pattern));
+        }
+    }
+    Ok(patterns)
+}
+
+/// Glob over dotted binary class names: `*` matches within one package segment and `**`
+/// across segments. A pattern naming a class also matches its nested classes.
+#[derive(Clone, Debug)]
+struct ClassPattern {
+    pattern: String,
+}
+
+impl ClassPattern {
+    fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.replace('/', "."),
+        }
+    }
+
+    fn matches(&self, class_name: &str) -> bool {
+        let pattern = self.pattern.as_bytes();
+        if glob_matches(pattern, class_name.as_bytes()) {
+            return true;
+        }
+        class_name
+            .match_indices('$')
+            .any(|(index, _)| glob_matches(pattern, &class_name.as_bytes()[..index]))
+    }
+}
+
+fn glob_matches(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern {
+        [] => name.is_empty(),
+        [b'*', b'*', rest @ ..] => (0..=name.len()).any(|skip| glob_matches(rest, &name[skip..])),
+        [b'*', rest @ ..] => {
+            let segment = name
+                .iter()
+                .position(|&byte| byte == b'.')
+                .unwrap_or(name.len());
+            (0..=segment).any(|skip| glob_matches(rest, &name[skip..]))
+        }
+        [byte, rest @ ..] => name.first() == Some(byte) && glob_matches(rest, &name[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class(name: &str, is_generated: bool) -> Class {
+        Class {
+            name: name.into(),
+            source_file: None,
+            super_name: None,
+            interfaces: Vec::new(),
+            type_parameters: Vec::new(),
+            referenced_classes: Vec::new(),
+            fields: Vec::new(),
+            methods: Vec::new(),
+            annotation_defaults: Vec::new(),
+            artifact_index: 0,
+            is_record: false,
+            is_generated,
+            header_location: None,
+        }
+    }
+
+    fn pattern_matches(pattern: &str, class_name: &str) -> bool {
+        ClassPattern::new(pattern).matches(class_name)
+    }
+
+    #[test]
+    fn double_star_spans_packages_and_single_star_one_segment() {
+        assert!(pattern_matches(
+            "com.example.generated.**",
+            "com.example.generated.Foo"
+        ));
+        assert!(pattern_matches(
+            "com.example.generated.**",
+            "com.example.generated.sub.Bar"
+        ));
+        assert!(!pattern_matches(
+            "com.example.generated.**",
+            "com.example.Foo"
+        ));
+        assert!(pattern_matches("com.example.*", "com.example.Foo"));
+        assert!(!pattern_matches("com.example.*", "com.example.sub.Foo"));
+        assert!(pattern_matches("com.*.dto.*Dto", "com.example.dto.UserDto"));
+        assert!(pattern_matches("**.*Test", "com.example.FooTest"));
+        assert!(pattern_matches("com/example/*", "com.example.Foo"));
+    }
+
+    #[test]
+    fn class_pattern_covers_nested_classes() {
+        assert!(pattern_matches(
+            "com.example.Outer",
+            "com.example.Outer$Inner"
+        ));
+        assert!(pattern_matches(
+            "com.example.Outer",
+            "com.example.Outer$Inner$1"
+        ));
+        assert!(!pattern_matches(
+            "com.example.Outer",
+            "com.example.OuterOther"
+        ));
+    }
+
+    #[test]
+    fn filter_applies_includes_then_excludes() {
+        let filter = ClassFilter::new(
+            &["com.example.**".to_string()],
+            &["com.example.generated.**,com.example.Legacy".to_string()],
+            false,
+        )
+        .expect("filter");
+
+        assert!(filter.matches(&class("com/example/Service", false)));
+        assert!(!filter.matches(&class("com/example/generated/Model", false)));
+        assert!(!filter.matches(&class("com/example/Legacy$Helper", false)));
+        assert!(!filter.matches(&class("org/other/Service", false)));
+        assert!(filter.matches(&class("com/example/Generated", true)));
+    }
+
+    #[test]
+    fn filter_excludes_generated_classes_when_enabled() {
+        let filter = ClassFilter::new(&[], &[], true).expect("filter");
+
+        assert!(filter.matches(&class("com/example/Service", false)));
+        assert!(!filter.matches(&class("com/example/ServiceImpl", true)));
+    }
+
+    #[test]
+    fn rejects_empty_patterns() {
+        let error = ClassFilter::new(&[], &["com.example.**,".to_string()], false)
+            .expect_err("empty pattern");
+
+        assert_eq!(error.to_string(), "empty pattern in --exclude");
+    }
+}
@@ -27,7 +27,7 @@ pub(crate) fn resolve_classpath(
     let mut class_map: BTreeMap<String, Vec<i64>> = BTreeMap::new();
     for class in classes {
         class_map
-            .entry(class.name.clone())
+            .entry(class.name.to_string())
             .or_default()
             .push(class.artifact_index);
     }
@@ -65,8 +65,8 @@ pub(crate) fn resolve_classpath(
             if is_platform_class(reference) {
                 continue;
             }
-            if !class_names.contains(reference) {
-                missing.insert(reference.clone());
+            if !class_names.contains(reference.as_str()) {
+                missing.insert(*reference);
             }
         }
     }
@@ -116,20 +116,22 @@ mod tests {
     fn resolve_classpath_accepts_java_references() {
         let classes = vec![
             Class {
-                name: "com/example/Foo".to_string(),
+                name: "com/example/Foo".into(),
                 source_file: None,
                 super_name: None,
                 interfaces: Vec::new(),
                 type_parameters: Vec::new(),
-                referenced_classes: vec!["java/lang/Object".to_string()],
+                referenced_classes: vec!["java/lang/Object".into()],
                 fields: Vec::new(),
                 methods: Vec::new(),
                 annotation_defaults: Vec::new(),
                 artifact_index: 0,
                 is_record: false,
+                is_generated: false,
+                header_location: None,
             },
             Class {
-                name: "com/example/Bar".to_string(),
+                name: "com/example/Bar".into(),
                 source_file: None,
                 super_name: None,
                 interfaces: Vec::new(),
@@ -140,6 +142,8 @@ mod tests {
                 annotation_defaults: Vec::new(),
                 artifact_index: 1,
                 is_record: false,
+                is_generated: false,
+                header_location: None,
             },
         ];
 
@@ -151,17 +155,19 @@ mod tests {
     #[test]
     fn resolve_classpath_allows_missing_classes() {
         let classes = vec![Class {
-            name: "com/example/Foo".to_string(),
+            name: "com/example/Foo".into(),
             source_file: None,
             super_name: None,
             interfaces: Vec::new(),
             type_parameters: Vec::new(),
-            referenced_classes: vec!["com/example/Bar".to_string()],
+            referenced_classes: vec!["com/example/Bar".into()],
             fields: Vec::new(),
             methods: Vec::new(),
             annotation_defaults: Vec::new(),
             artifact_index: 0,
             is_record: false,
+            is_generated: false,
+            header_location: None,
         }];
 
         let result = resolve_classpath(&classes, &[], false);
@@ -177,7 +183,7 @@ mod tests {
         ];
         let classes = vec![
             Class {
-                name: "com/example/Foo".to_string(),
+                name: "com/example/Foo".into(),
                 source_file: None,
                 super_name: None,
                 interfaces: Vec::new(),
@@ -188,9 +194,11 @@ mod tests {
                 annotation_defaults: Vec::new(),
                 artifact_index: 0,
                 is_record: false,
+                is_generated: false,
+                header_location: None,
             },
             Class {
-                name: "com/example/Foo".to_string(),
+                name: "com/example/Foo".into(),
                 source_file: None,
                 super_name: None,
                 interfaces: Vec::new(),
@@ -201,6 +209,8 @@ mod tests {
                 annotation_defaults: Vec::new(),
                 artifact_index: 1,
                 is_record: false,
+                is_generated: false,
+                header_location: None,
             },
         ];
 
@@ -218,7 +228,7 @@ mod tests {
     fn resolve_classpath_warns_for_duplicates() {
         let classes = vec![
             Class {
-                name: "com/example/Foo".to_string(),
+                name: "com/example/Foo".into(),
                 source_file: None,
                 super_name: None,
                 interfaces: Vec::new(),
@@ -229,9 +239,11 @@ mod tests {
                 annotation_defaults: Vec::new(),
                 artifact_index: 0,
                 is_record: false,
+                is_generated: false,
+                header_location: None,
             },
             Class {
-                name: "com/example/Foo".to_string(),
+                name: "com/example/Foo".into(),
                 source_file: None,
                 super_name: None,
                 interfaces: Vec::new(),
@@ -242,6 +254,8 @@ mod tests {
                 annotation_defaults: Vec::new(),
                 artifact_index: 1,
                 is_record: false,
+                is_generated: false,
+                header_location: None,
             },
         ];
 
@@ -267,7 +281,7 @@ mod tests {
         ];
         let classes = vec![
             Class {
-                name: "com/example/Foo".to_string(),
+                name: "com/example/Foo".into(),
                 source_file: None,
                 super_name: None,
                 interfaces: Vec::new(),
@@ -278,9 +292,11 @@ mod tests {
                 annotation_defaults: Vec::new(),
                 artifact_index: 0,
                 is_record: false,
+                is_generated: false,
+                header_location: None,
             },
             Class {
-                name: "com/example/Foo".to_string(),
+                name: "com/example/Foo".into(),
                 source_file: None,
                 super_name: None,
                 interfaces: Vec::new(),
@@ -291,6 +307,8 @@ mod tests {
                 annotation_defaults: Vec::new(),
                 artifact_index: 1,
                 is_record: false,
+                is_generated: false,
+                header_location: None,
             },
         ];
 
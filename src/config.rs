@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::rule_options::{
+    RawRuleOptions, RuleOptions, merge_raw_rule_options, resolve_rule_options,
+};
+use crate::rules::{RuleMetadata, all_rules};
+
+/// Config file passed with `--config`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub(crate) struct ConfigFile {
+    #[serde(default)]
+    pub(crate) rule_options: RawRuleOptions,
+}
+
+/// Read and parse a `--config` file, reporting the offending field path on errors.
+pub(crate) fn load_config_file(path: &Path) -> Result<ConfigFile> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("failed to read --config file {}", path.display()))?;
+    let mut deserializer = serde_json::Deserializer::from_str(&content);
+    serde_path_to_error::deserialize(&mut deserializer).map_err(|error| {
+        let field = error.path().to_string();
+        let inner = error.into_inner();
+        if field.is_empty() {
+            anyhow::anyhow!("invalid --config file {}: {inner}", path.display())
+        } else {
+            anyhow::anyhow!(
+                "invalid --config file {} at {field}: {inner}",
+                path.display()
+            )
+        }
+    })
+}
+
+/// Resolve the effective rule options: the `--config` file first, then options given inline
+/// in a JSON request, which replace file values option by option.
+pub(crate) fn resolve_configured_rule_options(
+    config: Option<&Path>,
+    inline: &RawRuleOptions,
+) -> Result<BTreeMap<String, RuleOptions>> {
+    let metadata: Vec<RuleMetadata> = all_rules().iter().map(|rule| rule.metadata()).collect();
+    let mut raw = RawRuleOptions::new();
+    if let Some(path) = config {
+        let file = load_config_file(path)?;
+        let origin = format!("--config file {}", path.display());
+        resolve_rule_options(&file.rule_options, &metadata, &origin)?;
+        raw = file.rule_options;
+    }
+    resolve_rule_options(inline, &metadata, "--json payload")?;
+    merge_raw_rule_options(&mut raw, inline.clone());
+    resolve_rule_options(&raw, &metadata, "rule options")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use serde_json::json;
+
+    use super::*;
+
+    fn write_config(name: &str, content: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock")
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("inspequte-config-{name}-{nanos}.json"));
+        fs::write(&path, content).expect("write config");
+        path
+    }
+
+    #[test]
+    fn inline_options_replace_config_file_options() {
+        let path = write_config(
+            "merge",
+            r#"{"ruleOptions": {"MAGIC_NUMBER": {"allowedValues": [42]},
+                "THREAD_SLEEP_CALL": {"ignoredPackages": ["com.example.retry"]}}}"#,
+        );
+        let inline = serde_json::from_value(json!({"MAGIC_NUMBER": {"allowedValues": [7]}}))
+            .expect("inline options");
+
+        let options =
+            resolve_configured_rule_options(Some(&path), &inline).expect("resolve options");
+        fs::remove_file(&path).expect("remove config");
+
+        assert_eq!(options["MAGIC_NUMBER"].integer_list("allowedValues"), &[7]);
+        assert_eq!(
+            options["THREAD_SLEEP_CALL"].string_list("ignoredPackages"),
+            &["com.example.retry".to_string()]
+        );
+    }
+
+    #[test]
+    fn config_file_errors_name_the_file_and_field() {
+        let path = write_config(
+            "invalid",
+            r#"{"ruleOptions": {"MAGIC_NUMBER": {"allowedValues": [1.5]}}}"#,
+        );
+
+        let error = resolve_configured_rule_options(Some(&path), &RawRuleOptions::new())
+            .expect_err("invalid option");
+        let unknown_field = write_config("unknown", r#"{"rules": []}"#);
+        let parse_error = load_config_file(&unknown_field).expect_err("unknown field");
+        fs::remove_file(&path).expect("remove config");
+        fs::remove_file(&unknown_field).expect("remove config");
+
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "invalid --config file {} at ruleOptions.MAGIC_NUMBER.allowedValues[0]: expected an array of integers",
+                path.display()
+            )
+        );
+        assert!(
+            parse_error.to_string().starts_with(&format!(
+                "invalid --config file {} at rules: unknown field `rules`",
+                unknown_field.display()
+            )),
+            "{parse_error}"
+        );
+    }
+}
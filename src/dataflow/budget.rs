@@ -0,0 +1,195 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use opentelemetry::KeyValue;
+
+use crate::ir::Method;
+use crate::symbol::Symbol;
+use crate::telemetry::add_current_span_event;
+
+/// Steps between wall-clock checks, keeping `Instant::now` off the hot path.
+const DEADLINE_CHECK_INTERVAL: u64 = 1024;
+
+/// Limits shared by every worklist analysis a rule runs; both are off by default, so an
+/// unconfigured run reports every finding.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct AnalysisBudget {
+    /// States a single method analysis may process; `None` disables the limit.
+    pub(crate) max_method_steps: Option<u64>,
+    /// Wall-clock time one rule may spend in worklist analyses; `None` disables the limit,
+    /// which keeps results deterministic.
+    pub(crate) rule_timeout: Option<Duration>,
+}
+
+/// Budget that cut an analysis short.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BudgetLimit {
+    MethodSteps(u64),
+    RuleTimeout(Duration),
+}
+
+impl fmt::Display for BudgetLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BudgetLimit::MethodSteps(steps) => write!(f, "method step budget of {steps} steps"),
+            BudgetLimit::RuleTimeout(timeout) => {
+                write!(f, "rule timeout of {}s", timeout.as_secs_f64())
+            }
+        }
+    }
+}
+
+/// Method whose analysis was cut short, reported without findings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct BudgetExceeded {
+    pub(crate) class_name: Symbol,
+    pub(crate) method_name: Symbol,
+    pub(crate) descriptor: Symbol,
+    pub(crate) limit: BudgetLimit,
+}
+
+/// Budget state of the rule running on this thread.
+struct RuleBudget {
+    budget: AnalysisBudget,
+    deadline: Option<Instant>,
+    timed_out: bool,
+    exceeded: Vec<BudgetExceeded>,
+}
+
+thread_local! {
+    static RULE_BUDGET: RefCell<Option<RuleBudget>> = const { RefCell::new(None) };
+}
+
+/// Run `f` (one rule) under `budget`, returning the methods whose analysis was cut short.
+///
+/// Worklist analyses outside such a scope use [`AnalysisBudget::default`] and report nothing.
+pub(crate) fn with_rule_budget<T>(
+    budget: AnalysisBudget,
+    f: impl FnOnce() -> T,
+) -> (T, Vec<BudgetExceeded>) {
+    let previous = RULE_BUDGET.replace(Some(RuleBudget {
+        budget,
+        deadline: budget.rule_timeout.map(|timeout| Instant::now() + timeout),
+        timed_out: false,
+        exceeded: Vec::new(),
+    }));
+    let output = f();
+    let exceeded = RULE_BUDGET
+        .replace(previous)
+        .map(|scope| scope.exceeded)
+        .unwrap_or_default();
+    (output, exceeded)
+}
+
+/// Run `f`, a shared analysis whose result is cached for every rule, outside the running
+/// rule's budget.
+///
+/// Only the method step limit applies; a rule timeout or an earlier budget hit of the rule
+/// does not, hits are not reported to the rule, and the time spent does not count toward
+/// its timeout. A cached result therefore does not depend on which rule computed it.
+pub(crate) fn outside_rule_budget<T>(f: impl FnOnce() -> T) -> T {
+    let Some(budget) = RULE_BUDGET.with_borrow(|scope| scope.as_ref().map(|scope| scope.budget))
+    else {
+        return f();
+    };
+    let started = Instant::now();
+    let (output, _) = with_rule_budget(
+        AnalysisBudget {
+            rule_timeout: None,
+            ..budget
+        },
+        f,
+    );
+    let elapsed = started.elapsed();
+    RULE_BUDGET.with_borrow_mut(|scope| {
+        if let Some(deadline) = scope.as_mut().and_then(|scope| scope.deadline.as_mut()) {
+            *deadline += elapsed;
+        }
+    });
+    output
+}
+
+/// Step counter for one method analysis.
+pub(crate) struct MethodMeter<'a> {
+    method: &'a Method,
+    steps: u64,
+    budget: AnalysisBudget,
+    deadline: Option<Instant>,
+}
+
+impl<'a> MethodMeter<'a> {
+    /// Start metering `method`; `None` when the rule has run out of time, in which case the
+    /// method is skipped. Only the first skipped method is reported.
+    pub(crate) fn start(method: &'a Method) -> Option<Self> {
+        let (budget, deadline, timed_out) = RULE_BUDGET.with_borrow(|scope| match scope {
+            Some(scope) => (scope.budget, scope.deadline, scope.timed_out),
+            None => (AnalysisBudget::default(), None, false),
+        });
+        if timed_out {
+            return None;
+        }
+        let meter = Self {
+            method,
+            steps: 0,
+            budget,
+            deadline,
+        };
+        if meter.past_deadline() {
+            meter.record(meter.timeout());
+            return None;
+        }
+        Some(meter)
+    }
+
+    /// Count one processed state; returns the exhausted budget once a limit is hit, after
+    /// recording it for the running rule.
+    pub(crate) fn step(&mut self) -> Option<BudgetLimit> {
+        self.steps += 1;
+        let limit = if let Some(max_steps) = self.budget.max_method_steps
+            && self.steps > max_steps
+        {
+            BudgetLimit::MethodSteps(max_steps)
+        } else if self.steps.is_multiple_of(DEADLINE_CHECK_INTERVAL) && self.past_deadline() {
+            self.timeout()
+        } else {
+            return None;
+        };
+        self.record(limit);
+        Some(limit)
+    }
+
+    fn past_deadline(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    fn timeout(&self) -> BudgetLimit {
+        BudgetLimit::RuleTimeout(self.budget.rule_timeout.unwrap_or_default())
+    }
+
+    fn record(&self, limit: BudgetLimit) {
+        add_current_span_event(
+            "inspequte.analysis_budget_exceeded",
+            &[
+                KeyValue::new("inspequte.class", self.method.owner),
+                KeyValue::new(
+                    "inspequte.method",
+                    format!("{}{}", self.method.name, self.method.descriptor),
+                ),
+                KeyValue::new("inspequte.budget", limit.to_string()),
+            ],
+        );
+        RULE_BUDGET.with_borrow_mut(|scope| {
+            if let Some(scope) = scope {
+                scope.timed_out |= matches!(limit, BudgetLimit::RuleTimeout(_));
+                scope.exceeded.push(BudgetExceeded {
+                    class_name: self.method.owner,
+                    method_name: self.method.name,
+                    descriptor: self.method.descriptor,
+                    limit,
+                });
+            }
+        });
+    }
+}
@@ -0,0 +1,805 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use jdescriptor::{MethodDescriptor, TypeDescriptor};
+
+use crate::dataflow::lattice::{AbstractState, AbstractValue};
+use crate::dataflow::opcode_semantics::{
+    ApplyOutcome, SemanticsCoverage, SemanticsDebugConfig, SemanticsHooks, ValueDomain,
+    apply_semantics,
+};
+use crate::dataflow::stack_machine::{StackMachine, StackMachineConfig};
+use crate::dataflow::worklist::{
+    BlockEndStep, InstructionStep, WorklistSemantics, WorklistState, analyze_method_fixpoint,
+};
+use crate::descriptor::{ReturnKind, method_return_kind};
+use crate::ir::{CallKind, CallSite, Instruction, InstructionKind, Method};
+use crate::opcodes;
+use crate::symbol::{JAVA_LANG_STRING_BUFFER, JAVA_LANG_STRING_BUILDER, Symbol};
+
+const MAX_TRACKED_STACK_DEPTH: usize = 64;
+
+const WIDE: u8 = 0xc4;
+const IINC: u8 = 0x84;
+const CHECKCAST: u8 = 0xc0;
+const INEG: u8 = 0x74;
+const LNEG: u8 = 0x75;
+const I2L: u8 = 0x85;
+const L2I: u8 = 0x88;
+
+/// Compile-time constant held by an operand stack slot.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub(crate) enum ConstantValue {
+    Int(i32),
+    Long(i64),
+    /// `float` constant as raw bits, so constants stay `Eq` and `Ord`.
+    Float(u32),
+    /// `double` constant as raw bits.
+    Double(u64),
+    String(String),
+    /// Class literal, by internal name.
+    Class(String),
+    Null,
+}
+
+impl ConstantValue {
+    /// Value of a string constant.
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            ConstantValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Constants on the operand stack before each reachable instruction of one method.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MethodConstants {
+    /// Stack slots bottom to top, keyed by instruction offset; instructions without any
+    /// constant on the stack are omitted.
+    frames: BTreeMap<u32, Vec<Option<ConstantValue>>>,
+}
+
+impl MethodConstants {
+    /// Constant `depth` slots below the top of the stack (0 is the top) just before the
+    /// instruction at `offset` runs. Every value takes one slot, including `long` and `double`.
+    pub(crate) fn stack_value(&self, offset: u32, depth: usize) -> Option<&ConstantValue> {
+        let frame = self.frames.get(&offset)?;
+        let index = frame.len().checked_sub(depth + 1)?;
+        frame[index].as_ref()
+    }
+
+    /// Constant passed as argument `index` to the call at `offset`, whose descriptor takes
+    /// `arg_count` arguments.
+    pub(crate) fn argument(
+        &self,
+        offset: u32,
+        arg_count: usize,
+        index: usize,
+    ) -> Option<&ConstantValue> {
+        self.stack_value(offset, arg_count.checked_sub(index + 1)?)
+    }
+}
+
+/// Propagate constants through the stack and locals of `method`.
+///
+/// Tracks numeric, string, class and null constants, folds simple `int`/`long` arithmetic,
+/// and follows string concatenation through `StringBuilder`/`StringBuffer` chains and
+/// `makeConcatWithConstants`. Values meeting at a block entry stay constant only when they
+/// agree on every path. A method whose analysis exceeds the budget has no constants.
+///
+/// Values carry no origin, so `MAGIC_NUMBER`, which reports the literal instructions
+/// themselves, keeps its own scan; `STRING_FORMAT_LOCALE_MISSING` and
+/// `BIGDECIMAL_SET_SCALE_WITHOUT_ROUNDING` match call descriptors and need no constants.
+pub(crate) fn analyze_constants(method: &Method) -> Result<MethodConstants> {
+    let Some(entry_block) = method
+        .cfg
+        .blocks
+        .iter()
+        .map(|block| block.start_offset)
+        .min()
+    else {
+        return Ok(MethodConstants::default());
+    };
+    let semantics = ConstantSemantics { entry_block };
+    let frames = analyze_method_fixpoint(method, &semantics)?
+        .into_iter()
+        .collect();
+    Ok(MethodConstants { frames })
+}
+
+/// Abstract value of one stack or local slot.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+enum Slot {
+    Unknown,
+    Constant(ConstantValue),
+    /// Object allocated by the `new` at this offset, before its constructor ran.
+    Uninitialized(u32),
+    /// String builder allocated at `site`, with its contents while they are known.
+    Builder {
+        site: u32,
+        contents: Option<String>,
+    },
+}
+
+impl Slot {
+    fn constant(&self) -> Option<&ConstantValue> {
+        match self {
+            Slot::Constant(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl AbstractValue for Slot {
+    fn join(&self, other: &Self) -> Self {
+        match (self, other) {
+            _ if self == other => self.clone(),
+            (
+                Slot::Builder { site, .. },
+                Slot::Builder {
+                    site: other_site, ..
+                },
+            ) if site == other_site => Slot::Builder {
+                site: *site,
+                contents: None,
+            },
+            _ => Slot::Unknown,
+        }
+    }
+}
+
+/// Value-domain adapter for default opcode semantics.
+struct ConstantValueDomain;
+
+impl ValueDomain<Slot> for ConstantValueDomain {
+    fn unknown_value(&self) -> Slot {
+        Slot::Unknown
+    }
+
+    fn scalar_value(&self) -> Slot {
+        Slot::Unknown
+    }
+}
+
+/// Stack and locals at one program point of the constant propagation.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+struct ConstantState {
+    block_start: u32,
+    instruction_index: usize,
+    machine: StackMachine<Slot>,
+}
+
+impl WorklistState for ConstantState {
+    fn block_start(&self) -> u32 {
+        self.block_start
+    }
+
+    fn instruction_index(&self) -> usize {
+        self.instruction_index
+    }
+
+    fn set_position(&mut self, block_start: u32, instruction_index: usize) {
+        self.block_start = block_start;
+        self.instruction_index = instruction_index;
+    }
+}
+
+impl AbstractState for ConstantState {
+    type Value = StackMachine<Slot>;
+
+    fn value(&self) -> &Self::Value {
+        &self.machine
+    }
+
+    fn set_value(&mut self, value: Self::Value) {
+        self.machine = value;
+    }
+}
+
+/// Dataflow callbacks for constant propagation; each finding is the stack seen by one
+/// instruction.
+struct ConstantSemantics {
+    entry_block: u32,
+}
+
+impl WorklistSemantics for ConstantSemantics {
+    type State = ConstantState;
+    type Finding = (u32, Vec<Option<ConstantValue>>);
+
+    fn initial_states(&self, _method: &Method) -> Vec<Self::State> {
+        vec![ConstantState {
+            block_start: self.entry_block,
+            instruction_index: 0,
+            machine: StackMachine::with_config(
+                Slot::Unknown,
+                StackMachineConfig {
+                    max_stack_depth: Some(MAX_TRACKED_STACK_DEPTH),
+                    max_locals: None,
+                    max_symbolic_identities: None,
+                },
+            ),
+        }]
+    }
+
+    fn transfer_instruction(
+        &self,
+        method: &Method,
+        instruction: &Instruction,
+        state: &mut Self::State,
+    ) -> Result<InstructionStep<Self::Finding>> {
+        let stack = state.machine.stack_values();
+        let mut step = InstructionStep::continue_path();
+        if stack.iter().any(|slot| slot.constant().is_some()) {
+            let frame = stack.iter().map(|slot| slot.constant().cloned()).collect();
+            step = step.with_finding((instruction.offset, frame));
+        }
+
+        match &instruction.kind {
+            InstructionKind::Invoke(call) => handle_invoke(call, &mut state.machine)?,
+            InstructionKind::InvokeDynamic {
+                descriptor,
+                concat_recipe,
+                ..
+            } => handle_invoke_dynamic(descriptor, concat_recipe.as_deref(), &mut state.machine)?,
+            _ => apply_stack_effect(method, instruction, &mut state.machine),
+        }
+        Ok(step)
+    }
+
+    fn on_block_end(
+        &self,
+        method: &Method,
+        state: &Self::State,
+        successors: &[u32],
+    ) -> Result<BlockEndStep<Self::State, Self::Finding>> {
+        let mut step = BlockEndStep::terminal();
+        for successor in successors {
+            let mut next = state.clone();
+            next.set_position(*successor, 0);
+            let is_handler = method
+                .exception_handlers
+                .iter()
+                .any(|handler| handler.handler_pc == *successor);
+            if is_handler {
+                // A handler starts with only the caught exception on the stack.
+                next.machine.pop_n(next.machine.stack_len());
+                next.machine.push(Slot::Unknown);
+            }
+            step = step.with_next_state(next);
+        }
+        Ok(step)
+    }
+}
+
+fn apply_stack_effect(
+    method: &Method,
+    instruction: &Instruction,
+    machine: &mut StackMachine<Slot>,
+) {
+    let mut hook = ConstantSemanticsHook { instruction };
+    let mut coverage = SemanticsCoverage::default();
+    let _ = apply_semantics(
+        machine,
+        method,
+        instruction.offset as usize,
+        instruction.opcode,
+        &ConstantValueDomain,
+        &mut hook,
+        &mut coverage,
+        SemanticsDebugConfig::default(),
+    );
+}
+
+/// Hook that produces, moves and folds constants ahead of the default semantics.
+struct ConstantSemanticsHook<'a> {
+    instruction: &'a Instruction,
+}
+
+impl SemanticsHooks<Slot> for ConstantSemanticsHook<'_> {
+    fn pre_apply(
+        &mut self,
+        machine: &mut StackMachine<Slot>,
+        method: &Method,
+        offset: usize,
+        opcode: u8,
+    ) -> ApplyOutcome {
+        let operand = |index: usize| method.bytecode.get(offset + index).copied().unwrap_or(0);
+        match opcode {
+            opcodes::ACONST_NULL => machine.push(Slot::Constant(ConstantValue::Null)),
+            opcodes::ICONST_M1..=opcodes::ICONST_5 => machine.push(Slot::Constant(
+                ConstantValue::Int(i32::from(opcode) - i32::from(opcodes::ICONST_0)),
+            )),
+            opcodes::LCONST_0 | opcodes::LCONST_1 => machine.push(Slot::Constant(
+                ConstantValue::Long(i64::from(opcode - opcodes::LCONST_0)),
+            )),
+            opcodes::FCONST_0..=opcodes::FCONST_2 => machine.push(Slot::Constant(
+                ConstantValue::Float(f32::from(opcode - opcodes::FCONST_0).to_bits()),
+            )),
+            opcodes::DCONST_0 | opcodes::DCONST_1 => machine.push(Slot::Constant(
+                ConstantValue::Double(f64::from(opcode - opcodes::DCONST_0).to_bits()),
+            )),
+            opcodes::BIPUSH | opcodes::SIPUSH | opcodes::LDC | opcodes::LDC_W | opcodes::LDC2_W => {
+                machine.push(self.literal(opcode))
+            }
+            // iload..aload with an operand, then iload_0..aload_3.
+            0x15..=0x19 => machine.push(machine.load_local(operand(1) as usize)),
+            0x1a..=0x2d => machine.push(machine.load_local(usize::from((opcode - 0x1a) % 4))),
+            // istore..astore with an operand, then istore_0..astore_3.
+            0x36..=0x3a => {
+                let value = machine.pop();
+                machine.store_local(operand(1) as usize, value);
+            }
+            0x3b..=0x4e => {
+                let value = machine.pop();
+                machine.store_local(usize::from((opcode - 0x3b) % 4), value);
+            }
+            IINC => {
+                let index = operand(1) as usize;
+                let increment = i32::from(operand(2) as i8);
+                increment_local(machine, index, increment);
+            }
+            WIDE => {
+                let index = usize::from(u16::from_be_bytes([operand(2), operand(3)]));
+                match operand(1) {
+                    0x15..=0x19 => machine.push(machine.load_local(index)),
+                    0x36..=0x3a => {
+                        let value = machine.pop();
+                        machine.store_local(index, value);
+                    }
+                    IINC => {
+                        let increment = i32::from(i16::from_be_bytes([operand(4), operand(5)]));
+                        increment_local(machine, index, increment);
+                    }
+                    _ => return ApplyOutcome::NotHandled,
+                }
+            }
+            opcodes::IADD | opcodes::ISUB | opcodes::IMUL => {
+                let right = machine.pop();
+                let left = machine.pop();
+                let folded = match (left.constant(), right.constant()) {
+                    (Some(ConstantValue::Int(left)), Some(ConstantValue::Int(right))) => {
+                        Slot::Constant(ConstantValue::Int(match opcode {
+                            opcodes::IADD => left.wrapping_add(*right),
+                            opcodes::ISUB => left.wrapping_sub(*right),
+                            _ => left.wrapping_mul(*right),
+                        }))
+                    }
+                    _ => Slot::Unknown,
+                };
+                machine.push(folded);
+            }
+            opcodes::LADD | opcodes::LSUB | opcodes::LMUL => {
+                let right = machine.pop();
+                let left = machine.pop();
+                let folded = match (left.constant(), right.constant()) {
+                    (Some(ConstantValue::Long(left)), Some(ConstantValue::Long(right))) => {
+                        Slot::Constant(ConstantValue::Long(match opcode {
+                            opcodes::LADD => left.wrapping_add(*right),
+                            opcodes::LSUB => left.wrapping_sub(*right),
+                            _ => left.wrapping_mul(*right),
+                        }))
+                    }
+                    _ => Slot::Unknown,
+                };
+                machine.push(folded);
+            }
+            INEG | LNEG | I2L | L2I => {
+                let folded = match (opcode, machine.pop().constant()) {
+                    (INEG, Some(ConstantValue::Int(value))) => {
+                        ConstantValue::Int(value.wrapping_neg())
+                    }
+                    (LNEG, Some(ConstantValue::Long(value))) => {
+                        ConstantValue::Long(value.wrapping_neg())
+                    }
+                    (I2L, Some(ConstantValue::Int(value))) => {
+                        ConstantValue::Long(i64::from(*value))
+                    }
+                    (L2I, Some(ConstantValue::Long(value))) => ConstantValue::Int(*value as i32),
+                    _ => {
+                        machine.push(Slot::Unknown);
+                        return ApplyOutcome::Applied;
+                    }
+                };
+                machine.push(Slot::Constant(folded));
+            }
+            // A cast keeps the reference, so a constant stays constant.
+            CHECKCAST => {}
+            opcodes::NEW => machine.push(Slot::Uninitialized(self.instruction.offset)),
+            // A long or double is a single value here, so pop2 and dup2 act on one slot.
+            opcodes::POP2 if is_wide_constant(machine.peek()) => {
+                machine.pop();
+            }
+            0x5c if is_wide_constant(machine.peek()) => {
+                if let Some(value) = machine.peek().cloned() {
+                    machine.push(value);
+                }
+            }
+            _ => return ApplyOutcome::NotHandled,
+        }
+        ApplyOutcome::Applied
+    }
+}
+
+impl ConstantSemanticsHook<'_> {
+    /// Slot pushed by a literal-loading instruction.
+    fn literal(&self, opcode: u8) -> Slot {
+        let is_wide = opcode == opcodes::LDC2_W;
+        let value = match &self.instruction.kind {
+            InstructionKind::ConstString(value) => ConstantValue::String(value.clone()),
+            InstructionKind::ConstClass(name) => ConstantValue::Class(name.clone()),
+            InstructionKind::ConstInt(value) if is_wide => ConstantValue::Long(*value),
+            InstructionKind::ConstInt(value) => ConstantValue::Int(*value as i32),
+            InstructionKind::ConstFloat(value) if is_wide => ConstantValue::Double(value.to_bits()),
+            InstructionKind::ConstFloat(value) => ConstantValue::Float((*value as f32).to_bits()),
+            _ => return Slot::Unknown,
+        };
+        Slot::Constant(value)
+    }
+}
+
+fn is_wide_constant(slot: Option<&Slot>) -> bool {
+    matches!(
+        slot,
+        Some(Slot::Constant(
+            ConstantValue::Long(_) | ConstantValue::Double(_)
+        ))
+    )
+}
+
+fn increment_local(machine: &mut StackMachine<Slot>, index: usize, increment: i32) {
+    let value = match machine.load_local(index) {
+        Slot::Constant(ConstantValue::Int(value)) => {
+            Slot::Constant(ConstantValue::Int(value.wrapping_add(increment)))
+        }
+        _ => Slot::Unknown,
+    };
+    machine.store_local(index, value);
+}
+
+fn handle_invoke(call: &CallSite, machine: &mut StackMachine<Slot>) -> Result<()> {
+    let descriptor =
+        MethodDescriptor::from_str(&call.descriptor).context("parse call descriptor")?;
+    let param_types = descriptor.parameter_types();
+    let mut args: Vec<Slot> = (0..param_types.len()).map(|_| machine.pop()).collect();
+    args.reverse();
+    let receiver = (call.kind != CallKind::Static).then(|| machine.pop());
+
+    if is_string_builder(call.owner)
+        && let Some(receiver) = &receiver
+    {
+        return handle_string_builder_call(call, param_types, &args, receiver, machine);
+    }
+
+    for arg in &args {
+        forget_builder_contents(machine, arg);
+    }
+    if call.name == "<init>"
+        && let Some(Slot::Uninitialized(site)) = receiver
+    {
+        set_site(machine, site, Slot::Unknown);
+    }
+    if method_return_kind(&call.descriptor)? != ReturnKind::Void {
+        machine.push(Slot::Unknown);
+    }
+    Ok(())
+}
+
+fn handle_string_builder_call(
+    call: &CallSite,
+    param_types: &[TypeDescriptor],
+    args: &[Slot],
+    receiver: &Slot,
+    machine: &mut StackMachine<Slot>,
+) -> Result<()> {
+    match (call.name.as_str(), receiver) {
+        ("<init>", Slot::Uninitialized(site)) => {
+            let contents = match (param_types, args) {
+                ([], []) | ([TypeDescriptor::Integer], [_]) => Some(String::new()),
+                ([TypeDescriptor::Object(_)], [arg]) => arg
+                    .constant()
+                    .and_then(ConstantValue::as_str)
+                    .map(str::to_string),
+                _ => None,
+            };
+            set_site(
+                machine,
+                *site,
+                Slot::Builder {
+                    site: *site,
+                    contents,
+                },
+            );
+        }
+        ("append", Slot::Builder { site, contents }) => {
+            let contents = match (contents, param_types, args) {
+                (Some(contents), [param_type], [arg]) => {
+                    render(arg, param_type).map(|suffix| format!("{contents}{suffix}"))
+                }
+                _ => None,
+            };
+            let builder = Slot::Builder {
+                site: *site,
+                contents,
+            };
+            set_site(machine, *site, builder.clone());
+            machine.push(builder);
+        }
+        ("toString", Slot::Builder { contents, .. }) if args.is_empty() => {
+            machine.push(match contents {
+                Some(contents) => Slot::Constant(ConstantValue::String(contents.clone())),
+                None => Slot::Unknown,
+            });
+        }
+        _ => {
+            forget_builder_contents(machine, receiver);
+            if method_return_kind(&call.descriptor)? != ReturnKind::Void {
+                machine.push(Slot::Unknown);
+            }
+        }
+    }
+    for arg in args {
+        forget_builder_contents(machine, arg);
+    }
+    Ok(())
+}
+
+fn handle_invoke_dynamic(
+    descriptor: &str,
+    concat_recipe: Option<&str>,
+    machine: &mut StackMachine<Slot>,
+) -> Result<()> {
+    let parsed = MethodDescriptor::from_str(descriptor).context("parse call descriptor")?;
+    let param_types = parsed.parameter_types();
+    let mut args: Vec<Slot> = (0..param_types.len()).map(|_| machine.pop()).collect();
+    args.reverse();
+    for arg in &args {
+        forget_builder_contents(machine, arg);
+    }
+    if method_return_kind(descriptor)? == ReturnKind::Void {
+        return Ok(());
+    }
+    let concatenated = concat_recipe.and_then(|recipe| {
+        let mut rendered = param_types
+            .iter()
+            .zip(&args)
+            .map(|(ty, arg)| render(arg, ty));
+        let mut result = String::with_capacity(recipe.len());
+        for ch in recipe.chars() {
+            if ch == '\u{1}' {
+                result.push_str(&rendered.next()??);
+            } else {
+                result.push(ch);
+            }
+        }
+        Some(result)
+    });
+    machine.push(match concatenated {
+        Some(value) => Slot::Constant(ConstantValue::String(value)),
+        None => Slot::Unknown,
+    });
+    Ok(())
+}
+
+fn is_string_builder(owner: Symbol) -> bool {
+    owner == JAVA_LANG_STRING_BUILDER || owner == JAVA_LANG_STRING_BUFFER
+}
+
+/// Text that string concatenation produces for `slot` passed as a `ty` argument.
+fn render(slot: &Slot, ty: &TypeDescriptor) -> Option<String> {
+    match (ty, slot.constant()?) {
+        (TypeDescriptor::Boolean, ConstantValue::Int(value)) => Some((*value != 0).to_string()),
+        (TypeDescriptor::Char, ConstantValue::Int(value)) => {
+            char::from_u32(*value as u32).map(String::from)
+        }
+        (
+            TypeDescriptor::Byte | TypeDescriptor::Short | TypeDescriptor::Integer,
+            ConstantValue::Int(value),
+        ) => Some(value.to_string()),
+        (TypeDescriptor::Long, ConstantValue::Long(value)) => Some(value.to_string()),
+        (TypeDescriptor::Object(_), ConstantValue::String(value)) => Some(value.clone()),
+        (TypeDescriptor::Object(_), ConstantValue::Null) => Some("null".to_string()),
+        _ => None,
+    }
+}
+
+/// Replace every alias of the object allocated at `site` with `slot`.
+fn set_site(machine: &mut StackMachine<Slot>, site: u32, slot: Slot) {
+    machine.rewrite_values(|value| {
+        if matches!(value, Slot::Uninitialized(alias) | Slot::Builder { site: alias, .. } if *alias == site)
+        {
+            *value = slot.clone();
+        }
+    });
+}
+
+/// A builder handed to unknown code may be changed there.
+fn forget_builder_contents(machine: &mut StackMachine<Slot>, slot: &Slot) {
+    if let Slot::Builder { site, .. } = slot {
+        set_site(
+            machine,
+            *site,
+            Slot::Builder {
+                site: *site,
+                contents: None,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConstantValue, MethodConstants, analyze_constants};
+    use crate::ir::{Class, Method};
+    use crate::scan::scan_inputs;
+    use crate::test_harness::{JvmTestHarness, Language, SourceFile};
+
+    fn compile_class(contents: &str) -> Class {
+        let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
+        let sources = vec![SourceFile {
+            path: "com/example/ClassA.java".to_string(),
+            contents: contents.to_string(),
+        }];
+        let output = harness
+            .compile(Language::Java, &sources, &[])
+            .expect("compile sources");
+        let scan = scan_inputs(&[output.classes_dir().to_path_buf()], &[], None, None)
+            .expect("scan classes");
+        scan.classes
+            .into_iter()
+            .find(|class| class.name == "com/example/ClassA")
+            .expect("compiled class")
+    }
+
+    fn method<'a>(class: &'a Class, name: &str) -> &'a Method {
+        class
+            .methods
+            .iter()
+            .find(|method| method.name == name)
+            .expect("method")
+    }
+
+    /// First argument of every call to `callee`, in bytecode order.
+    fn first_arguments(method: &Method, callee: &str) -> Vec<Option<ConstantValue>> {
+        let constants: MethodConstants = analyze_constants(method).expect("constants");
+        method
+            .calls
+            .iter()
+            .filter(|call| call.name == callee)
+            .map(|call| {
+                let arg_count =
+                    crate::descriptor::method_param_count(&call.descriptor).expect("descriptor");
+                constants.argument(call.offset, arg_count, 0).cloned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn propagates_literals_through_locals() {
+        let class = compile_class(
+            r#"
+package com.example;
+
+class ClassA {
+    void methodX() {
+        int varOne = 42;
+        long varTwo = 7L;
+        String varThree = "text";
+        sinkInt(varOne);
+        sinkLong(varTwo);
+        sinkObject(varThree);
+        sinkObject(ClassA.class);
+        sinkObject(null);
+    }
+
+    static void sinkInt(int value) {}
+    static void sinkLong(long value) {}
+    static void sinkObject(Object value) {}
+}
+"#,
+        );
+        let method = method(&class, "methodX");
+
+        assert_eq!(
+            first_arguments(method, "sinkInt"),
+            vec![Some(ConstantValue::Int(42))]
+        );
+        assert_eq!(
+            first_arguments(method, "sinkLong"),
+            vec![Some(ConstantValue::Long(7))]
+        );
+        assert_eq!(
+            first_arguments(method, "sinkObject"),
+            vec![
+                Some(ConstantValue::String("text".to_string())),
+                Some(ConstantValue::Class("com/example/ClassA".to_string())),
+                Some(ConstantValue::Null),
+            ]
+        );
+    }
+
+    #[test]
+    fn folds_string_concatenation() {
+        let class = compile_class(
+            r#"
+package com.example;
+
+class ClassA {
+    void methodX() {
+        int varOne = 3;
+        String varTwo = "a";
+        sink("value " + varOne + ' ' + varTwo + true);
+        StringBuilder varThree = new StringBuilder("b");
+        varThree.append(1).append('c');
+        sink(varThree.toString());
+    }
+
+    static void sink(String value) {}
+}
+"#,
+        );
+
+        assert_eq!(
+            first_arguments(method(&class, "methodX"), "sink"),
+            vec![
+                Some(ConstantValue::String("value 3 atrue".to_string())),
+                Some(ConstantValue::String("b1c".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn drops_values_that_differ_between_paths() {
+        let class = compile_class(
+            r#"
+package com.example;
+
+class ClassA {
+    void methodX(boolean flag) {
+        int varOne = flag ? 1 : 2;
+        int varTwo = flag ? 5 : 5;
+        sink(varOne);
+        sink(varTwo);
+        for (int varThree = 0; varThree < 10; varThree++) {
+            sink(varThree);
+        }
+    }
+
+    static void sink(int value) {}
+}
+"#,
+        );
+
+        assert_eq!(
+            first_arguments(method(&class, "methodX"), "sink"),
+            vec![None, Some(ConstantValue::Int(5)), None]
+        );
+    }
+
+    #[test]
+    fn forgets_builder_contents_passed_to_unknown_code() {
+        let class = compile_class(
+            r#"
+package com.example;
+
+class ClassA {
+    void methodX() {
+        StringBuilder varOne = new StringBuilder();
+        varOne.append("a");
+        mutate(varOne);
+        sink(varOne.toString());
+    }
+
+    static void mutate(StringBuilder value) {}
+    static void sink(String value) {}
+}
+"#,
+        );
+
+        assert_eq!(
+            first_arguments(method(&class, "methodX"), "sink"),
+            vec![None]
+        );
+    }
+}
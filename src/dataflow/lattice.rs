@@ -0,0 +1,54 @@
+use std::collections::BTreeSet;
+
+use crate::dataflow::worklist::WorklistState;
+
+/// Element of a join-semilattice, such as the abstract value of one local or stack slot.
+pub(crate) trait AbstractValue: Clone + Eq {
+    /// Least upper bound of `self` and `other`.
+    fn join(&self, other: &Self) -> Self;
+
+    /// Upper bound of `self` and `other` that stops infinite ascending chains; lattices of
+    /// finite height can keep the default, which is plain [`AbstractValue::join`].
+    fn widen(&self, other: &Self) -> Self {
+        self.join(other)
+    }
+}
+
+/// Worklist state whose non-positional part forms a lattice, so states meeting at a block
+/// entry can be merged by [`crate::dataflow::worklist::analyze_method_fixpoint`].
+pub(crate) trait AbstractState: WorklistState {
+    type Value: AbstractValue;
+
+    fn value(&self) -> &Self::Value;
+    fn set_value(&mut self, value: Self::Value);
+}
+
+/// Unit lattice for analyses that only track reachability.
+impl AbstractValue for () {
+    fn join(&self, _other: &Self) -> Self {}
+}
+
+/// Set union, for "may" analyses over finite universes such as instruction offsets.
+impl<T> AbstractValue for BTreeSet<T>
+where
+    T: Clone + Ord,
+{
+    fn join(&self, other: &Self) -> Self {
+        self.union(other).cloned().collect()
+    }
+}
+
+/// Pointwise product of two lattices.
+impl<A, B> AbstractValue for (A, B)
+where
+    A: AbstractValue,
+    B: AbstractValue,
+{
+    fn join(&self, other: &Self) -> Self {
+        (self.0.join(&other.0), self.1.join(&other.1))
+    }
+
+    fn widen(&self, other: &Self) -> Self {
+        (self.0.widen(&other.0), self.1.widen(&other.1))
+    }
+}
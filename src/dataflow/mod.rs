@@ -1,3 +1,9 @@
+pub(crate) mod budget;
+pub(crate) mod constants;
+pub(crate) mod lattice;
 pub(crate) mod opcode_semantics;
 pub(crate) mod stack_machine;
+pub(crate) mod summaries;
+pub(crate) mod taint;
+pub(crate) mod types;
 pub(crate) mod worklist;
@@ -465,8 +465,9 @@ mod tests {
 
     fn empty_method(bytecode: Vec<u8>) -> Method {
         Method {
-            name: "MethodX".to_string(),
-            descriptor: "()V".to_string(),
+            owner: "com/example/ClassX".into(),
+            name: "MethodX".into(),
+            descriptor: "()V".into(),
             signature: None,
             access: MethodAccess {
                 is_public: false,
@@ -475,6 +476,8 @@ mod tests {
                 is_abstract: false,
                 is_synthetic: false,
                 is_bridge: false,
+                is_private: false,
+                is_final: false,
             },
             nullness: MethodNullness {
                 return_nullness: Nullness::Unknown,
@@ -486,12 +489,14 @@ mod tests {
             cfg: ControlFlowGraph {
                 blocks: Vec::new(),
                 edges: Vec::new(),
+                duplicates: Default::default(),
             },
             calls: Vec::new(),
             string_literals: Vec::new(),
             exception_handlers: Vec::new(),
             local_variables: Vec::new(),
             local_variable_types: Vec::<LocalVariableType>::new(),
+            flow_structure: Default::default(),
         }
     }
 
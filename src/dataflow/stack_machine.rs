@@ -1,5 +1,7 @@
 use std::collections::{BTreeMap, BTreeSet};
 
+use crate::dataflow::lattice::AbstractValue;
+
 /// Configuration for stack/local simulation budgets.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
 pub(crate) struct StackMachineConfig {
@@ -97,6 +99,11 @@ where
         self.locals.insert(index, value);
     }
 
+    /// Returns tracked local bindings in index order.
+    pub(crate) fn locals(&self) -> impl Iterator<Item = (usize, &V)> {
+        self.locals.iter().map(|(index, value)| (*index, value))
+    }
+
     /// Keeps local bindings that match the predicate.
     pub(crate) fn retain_locals<F>(&mut self, mut predicate: F)
     where
@@ -225,9 +232,59 @@ where
     }
 }
 
+/// Slot-wise join. Stacks of different heights keep only their common top slots, and a
+/// local bound on one side only is joined with the default value.
+impl<V> AbstractValue for StackMachine<V>
+where
+    V: AbstractValue,
+{
+    fn join(&self, other: &Self) -> Self {
+        self.combine(other, V::join)
+    }
+
+    fn widen(&self, other: &Self) -> Self {
+        self.combine(other, V::widen)
+    }
+}
+
+impl<V> StackMachine<V>
+where
+    V: Clone,
+{
+    fn combine<F>(&self, other: &Self, mut combine_values: F) -> Self
+    where
+        F: FnMut(&V, &V) -> V,
+    {
+        let height = self.stack.len().min(other.stack.len());
+        let stack = self.stack[self.stack.len() - height..]
+            .iter()
+            .zip(&other.stack[other.stack.len() - height..])
+            .map(|(left, right)| combine_values(left, right))
+            .collect();
+        let mut locals = BTreeMap::new();
+        for index in self.locals.keys().chain(other.locals.keys()) {
+            if locals.contains_key(index) {
+                continue;
+            }
+            let left = self.locals.get(index).unwrap_or(&self.default_value);
+            let right = other.locals.get(index).unwrap_or(&other.default_value);
+            locals.insert(*index, combine_values(left, right));
+        }
+        Self {
+            stack,
+            locals,
+            default_value: self.default_value.clone(),
+            config: self.config,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
+
     use super::{StackMachine, StackMachineConfig};
+    use crate::dataflow::lattice::AbstractValue;
 
     /// Test value type for stack machine unit tests.
     #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
@@ -373,4 +430,22 @@ mod tests {
         assert_eq!(machine.load_local(1), TestValue::Unknown);
         assert_eq!(machine.load_local(2), TestValue::Symbol(20));
     }
+
+    #[test]
+    fn join_merges_common_stack_top_and_locals() {
+        let mut left = StackMachine::new(BTreeSet::new());
+        left.push(BTreeSet::from([1]));
+        left.push(BTreeSet::from([2]));
+        left.store_local(0, BTreeSet::from([3]));
+
+        let mut right = StackMachine::new(BTreeSet::new());
+        right.push(BTreeSet::from([4]));
+        right.store_local(1, BTreeSet::from([5]));
+
+        let joined = left.join(&right);
+
+        assert_eq!(joined.stack_values(), &[BTreeSet::from([2, 4])]);
+        assert_eq!(joined.load_local(0), BTreeSet::from([3]));
+        assert_eq!(joined.load_local(1), BTreeSet::from([5]));
+    }
 }
@@ -0,0 +1,926 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use jdescriptor::MethodDescriptor;
+
+use crate::dataflow::lattice::{AbstractState, AbstractValue};
+use crate::dataflow::opcode_semantics::{
+    ApplyOutcome, SemanticsCoverage, SemanticsDebugConfig, SemanticsHooks, ValueDomain,
+    apply_semantics,
+};
+use crate::dataflow::stack_machine::{StackMachine, StackMachineConfig};
+use crate::dataflow::worklist::{
+    BlockEndStep, InstructionStep, WorklistSemantics, WorklistState, analyze_method_fixpoint,
+};
+use crate::descriptor::{ReturnKind, method_param_start_slots, method_return_kind};
+use crate::ir::{CallKind, CallSite, Class, Instruction, InstructionKind, Method};
+use crate::opcodes;
+use crate::symbol::{JAVA_LANG_OBJECT, Symbol};
+
+const MAX_TRACKED_STACK_DEPTH: usize = 64;
+
+/// Rounds a recursive call cycle may take to stabilize before its methods lose all facts.
+const MAX_CYCLE_ROUNDS: usize = 16;
+
+const WIDE: u8 = 0xc4;
+const CHECKCAST: u8 = 0xc0;
+
+/// Facts about one method that hold for every call that reaches its body.
+///
+/// The default summary knows nothing, which is also what a method gets when its body could
+/// not be analyzed.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct MethodSummary {
+    /// Every normal return yields a non-null reference.
+    pub(crate) returns_non_null: bool,
+    /// Arguments closed on every normal return, counting the receiver of an instance method
+    /// as argument 0.
+    pub(crate) closes_arguments: BTreeSet<usize>,
+    /// The method never completes normally.
+    pub(crate) always_throws: bool,
+    /// The method writes no field, array element or monitor, and only calls methods that
+    /// are side-effect free themselves.
+    pub(crate) side_effect_free: bool,
+}
+
+impl MethodSummary {
+    /// Starting point for methods on a call cycle, refined downwards until it holds.
+    fn optimistic(argument_count: usize) -> Self {
+        Self {
+            returns_non_null: true,
+            closes_arguments: (0..argument_count).collect(),
+            always_throws: true,
+            side_effect_free: true,
+        }
+    }
+}
+
+/// Summaries of the analysis target methods, computed bottom-up over their call graph.
+#[derive(Debug, Default)]
+pub(crate) struct MethodSummaries {
+    resolver: CallResolver,
+    summaries: BTreeMap<String, MethodSummary>,
+}
+
+impl MethodSummaries {
+    /// Summarize every method with a body in `classes`.
+    ///
+    /// Callees are summarized before their callers; methods calling each other start from
+    /// an optimistic summary that is recomputed until the whole cycle agrees.
+    pub(crate) fn compute(classes: &[Class]) -> Self {
+        let resolver = CallResolver::new(classes);
+        let methods: Vec<(String, &Method)> = classes
+            .iter()
+            .flat_map(|class| {
+                class
+                    .methods
+                    .iter()
+                    .filter(|method| !method.bytecode.is_empty() && !method.cfg.blocks.is_empty())
+                    .map(|method| {
+                        (
+                            method_key(&class.name, &method.name, &method.descriptor),
+                            method,
+                        )
+                    })
+            })
+            .collect();
+        let index_by_key: BTreeMap<&str, usize> = methods
+            .iter()
+            .enumerate()
+            .map(|(index, (key, _))| (key.as_str(), index))
+            .collect();
+        let callees: Vec<BTreeSet<usize>> = methods
+            .iter()
+            .map(|(_, method)| {
+                method
+                    .calls
+                    .iter()
+                    .filter_map(|call| resolver.resolve(call))
+                    .filter_map(|key| index_by_key.get(key.as_str()).copied())
+                    .collect()
+            })
+            .collect();
+
+        let mut summaries = MethodSummaries {
+            resolver,
+            summaries: BTreeMap::new(),
+        };
+        for component in strongly_connected_components(&callees) {
+            let is_cycle = component.len() > 1 || callees[component[0]].contains(&component[0]);
+            if !is_cycle {
+                let (key, method) = &methods[component[0]];
+                let summary = summaries.summarize(method);
+                summaries.summaries.insert(key.clone(), summary);
+                continue;
+            }
+            for &index in &component {
+                let (key, method) = &methods[index];
+                summaries.summaries.insert(
+                    key.clone(),
+                    MethodSummary::optimistic(argument_count(method)),
+                );
+            }
+            let mut stable = false;
+            for _ in 0..MAX_CYCLE_ROUNDS {
+                let round: Vec<MethodSummary> = component
+                    .iter()
+                    .map(|&index| summaries.summarize(methods[index].1))
+                    .collect();
+                stable = true;
+                for (&index, summary) in component.iter().zip(round) {
+                    let previous = summaries
+                        .summaries
+                        .insert(methods[index].0.clone(), summary);
+                    stable &= previous.as_ref() == summaries.summaries.get(&methods[index].0);
+                }
+                if stable {
+                    break;
+                }
+            }
+            if !stable {
+                for &index in &component {
+                    summaries
+                        .summaries
+                        .insert(methods[index].0.clone(), MethodSummary::default());
+                }
+            }
+        }
+        summaries
+    }
+
+    /// Summary of the method `call` runs, when the call cannot be dispatched to an override
+    /// and lands on a summarized body.
+    pub(crate) fn for_call(&self, call: &CallSite) -> Option<&MethodSummary> {
+        self.summaries.get(&self.resolver.resolve(call)?)
+    }
+
+    /// Summary of `method` declared in `class`.
+    #[cfg(test)]
+    pub(crate) fn for_method(&self, class: &Class, method: &Method) -> Option<&MethodSummary> {
+        self.summaries
+            .get(&method_key(&class.name, &method.name, &method.descriptor))
+    }
+
+    fn summarize(&self, method: &Method) -> MethodSummary {
+        summarize_method(method, self).unwrap_or_default()
+    }
+}
+
+fn method_key(owner: &str, name: &str, descriptor: &str) -> String {
+    format!("{owner}.{name}{descriptor}")
+}
+
+fn argument_count(method: &Method) -> usize {
+    let params = method_param_start_slots(&method.descriptor).map_or(0, |slots| slots.len());
+    params + usize::from(!method.access.is_static)
+}
+
+/// How a declared method may be reached from a call site.
+#[derive(Clone, Copy, Debug)]
+struct Dispatch {
+    has_body: bool,
+    /// Virtual calls always land on this method: it is private or final.
+    is_exact: bool,
+}
+
+/// Static call resolution within the analysis target classes.
+#[derive(Debug, Default)]
+struct CallResolver {
+    methods: BTreeMap<String, Dispatch>,
+    super_names: BTreeMap<Symbol, Symbol>,
+}
+
+impl CallResolver {
+    fn new(classes: &[Class]) -> Self {
+        let mut resolver = CallResolver::default();
+        for class in classes {
+            if let Some(super_name) = class.super_name {
+                resolver.super_names.insert(class.name, super_name);
+            }
+            for method in &class.methods {
+                resolver.methods.insert(
+                    method_key(&class.name, &method.name, &method.descriptor),
+                    Dispatch {
+                        has_body: !method.bytecode.is_empty() && !method.cfg.blocks.is_empty(),
+                        is_exact: method.access.is_private || method.access.is_final,
+                    },
+                );
+            }
+        }
+        resolver
+    }
+
+    /// Key of the only method `call` can run, walking up the superclass chain from the
+    /// call's owner like the JVM does.
+    fn resolve(&self, call: &CallSite) -> Option<String> {
+        let mut owner = call.owner;
+        loop {
+            let key = method_key(&owner, &call.name, &call.descriptor);
+            if let Some(dispatch) = self.methods.get(&key) {
+                let is_fixed = match call.kind {
+                    CallKind::Static | CallKind::Special => true,
+                    CallKind::Virtual | CallKind::Interface => dispatch.is_exact,
+                };
+                return (is_fixed && dispatch.has_body).then_some(key);
+            }
+            owner = *self.super_names.get(&owner)?;
+        }
+    }
+}
+
+/// Tarjan's algorithm over `edges`, returning components callees-first.
+fn strongly_connected_components(edges: &[BTreeSet<usize>]) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+    let mut order = vec![UNVISITED; edges.len()];
+    let mut low_link = vec![0; edges.len()];
+    let mut on_stack = vec![false; edges.len()];
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+    let mut next_order = 0;
+
+    for root in 0..edges.len() {
+        if order[root] != UNVISITED {
+            continue;
+        }
+        // Each frame is a node and the iterator over its remaining successors.
+        let mut frames = vec![(root, edges[root].iter())];
+        order[root] = next_order;
+        low_link[root] = next_order;
+        next_order += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        while let Some((node, successors)) = frames.last_mut() {
+            let node = *node;
+            if let Some(&next) = successors.next() {
+                if order[next] == UNVISITED {
+                    order[next] = next_order;
+                    low_link[next] = next_order;
+                    next_order += 1;
+                    stack.push(next);
+                    on_stack[next] = true;
+                    frames.push((next, edges[next].iter()));
+                } else if on_stack[next] {
+                    low_link[node] = low_link[node].min(order[next]);
+                }
+                continue;
+            }
+            frames.pop();
+            if let Some((parent, _)) = frames.last() {
+                low_link[*parent] = low_link[*parent].min(low_link[node]);
+            }
+            if low_link[node] == order[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                component.sort_unstable();
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+/// Analyze one method body against the summaries known so far.
+fn summarize_method(method: &Method, summaries: &MethodSummaries) -> Result<MethodSummary> {
+    let Some(entry_block) = method
+        .cfg
+        .blocks
+        .iter()
+        .map(|block| block.start_offset)
+        .min()
+    else {
+        return Ok(MethodSummary::default());
+    };
+    let mut machine = StackMachine::with_config(
+        Value::Unknown,
+        StackMachineConfig {
+            max_stack_depth: Some(MAX_TRACKED_STACK_DEPTH),
+            max_locals: None,
+            max_symbolic_identities: None,
+        },
+    );
+    let receiver_slots = usize::from(!method.access.is_static);
+    if receiver_slots == 1 {
+        machine.store_local(0, Value::Argument(0));
+    }
+    for (index, slot) in method_param_start_slots(&method.descriptor)?
+        .into_iter()
+        .enumerate()
+    {
+        machine.store_local(
+            receiver_slots + usize::from(slot),
+            Value::Argument(receiver_slots + index),
+        );
+    }
+    let semantics = SummarySemantics {
+        entry_state: SummaryState {
+            block_start: entry_block,
+            instruction_index: 0,
+            frame: Frame {
+                machine,
+                closed: BTreeSet::new(),
+            },
+        },
+        is_static: method.access.is_static,
+        summaries,
+    };
+
+    let mut entered = false;
+    let mut side_effect_free = !method.access.is_synchronized;
+    let mut returns = Vec::new();
+    for fact in analyze_method_fixpoint(method, &semantics)? {
+        match fact {
+            BodyFact::Entered => entered = true,
+            BodyFact::SideEffect => side_effect_free = false,
+            BodyFact::Return { non_null, closed } => returns.push((non_null, closed)),
+        }
+    }
+    // An analysis cut short by its budget reports nothing, not even entering the body.
+    if !entered {
+        return Ok(MethodSummary::default());
+    }
+    let returns_reference = method_return_kind(&method.descriptor)? == ReturnKind::Reference;
+    let closes_arguments = returns
+        .iter()
+        .map(|(_, closed)| closed.clone())
+        .reduce(|all, closed| &all & &closed)
+        .unwrap_or_default();
+    Ok(MethodSummary {
+        returns_non_null: returns_reference
+            && !returns.is_empty()
+            && returns.iter().all(|(non_null, _)| *non_null),
+        closes_arguments,
+        always_throws: returns.is_empty(),
+        side_effect_free,
+    })
+}
+
+/// Abstract value of one stack or local slot.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+enum Value {
+    Unknown,
+    NonNull,
+    /// Argument of the analyzed method, numbered like [`MethodSummary::closes_arguments`].
+    Argument(usize),
+}
+
+impl AbstractValue for Value {
+    fn join(&self, other: &Self) -> Self {
+        if self == other { *self } else { Value::Unknown }
+    }
+}
+
+/// Value-domain adapter for default opcode semantics.
+struct SummaryValueDomain;
+
+impl ValueDomain<Value> for SummaryValueDomain {
+    fn unknown_value(&self) -> Value {
+        Value::Unknown
+    }
+
+    fn scalar_value(&self) -> Value {
+        Value::Unknown
+    }
+}
+
+/// Stack, locals and arguments closed on every path reaching one program point.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+struct Frame {
+    machine: StackMachine<Value>,
+    closed: BTreeSet<usize>,
+}
+
+impl AbstractValue for Frame {
+    fn join(&self, other: &Self) -> Self {
+        Frame {
+            machine: self.machine.join(&other.machine),
+            closed: &self.closed & &other.closed,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+struct SummaryState {
+    block_start: u32,
+    instruction_index: usize,
+    frame: Frame,
+}
+
+impl WorklistState for SummaryState {
+    fn block_start(&self) -> u32 {
+        self.block_start
+    }
+
+    fn instruction_index(&self) -> usize {
+        self.instruction_index
+    }
+
+    fn set_position(&mut self, block_start: u32, instruction_index: usize) {
+        self.block_start = block_start;
+        self.instruction_index = instruction_index;
+    }
+}
+
+impl AbstractState for SummaryState {
+    type Value = Frame;
+
+    fn value(&self) -> &Self::Value {
+        &self.frame
+    }
+
+    fn set_value(&mut self, value: Self::Value) {
+        self.frame = value;
+    }
+}
+
+/// What one path through a method body contributes to its summary.
+#[derive(Clone, Debug)]
+enum BodyFact {
+    /// The analysis reached the first instruction.
+    Entered,
+    Return {
+        non_null: bool,
+        closed: BTreeSet<usize>,
+    },
+    SideEffect,
+}
+
+struct SummarySemantics<'a> {
+    entry_state: SummaryState,
+    is_static: bool,
+    summaries: &'a MethodSummaries,
+}
+
+impl WorklistSemantics for SummarySemantics<'_> {
+    type State = SummaryState;
+    type Finding = BodyFact;
+
+    fn initial_states(&self, _method: &Method) -> Vec<Self::State> {
+        vec![self.entry_state.clone()]
+    }
+
+    fn transfer_instruction(
+        &self,
+        method: &Method,
+        instruction: &Instruction,
+        state: &mut Self::State,
+    ) -> Result<InstructionStep<Self::Finding>> {
+        let mut facts = Vec::new();
+        if instruction.offset == self.entry_state.block_start && state.instruction_index == 1 {
+            facts.push(BodyFact::Entered);
+        }
+        let frame = &mut state.frame;
+        let mut terminate = false;
+        match instruction.opcode {
+            opcodes::IRETURN..=opcodes::RETURN => {
+                let value = match instruction.opcode {
+                    opcodes::RETURN => Value::Unknown,
+                    _ => frame.machine.pop(),
+                };
+                let non_null = match value {
+                    Value::NonNull => true,
+                    Value::Argument(0) => !self.is_static,
+                    _ => false,
+                };
+                facts.push(BodyFact::Return {
+                    non_null,
+                    closed: frame.closed.clone(),
+                });
+                terminate = true;
+            }
+            opcodes::PUTSTATIC
+            | opcodes::PUTFIELD
+            | opcodes::MONITORENTER
+            | opcodes::MONITOREXIT
+            | 0x4f..=0x56 => {
+                facts.push(BodyFact::SideEffect);
+                apply_stack_effect(method, instruction, &mut frame.machine);
+            }
+            _ => match &instruction.kind {
+                InstructionKind::Invoke(call) => {
+                    terminate = self.handle_invoke(call, frame, &mut facts)?;
+                }
+                InstructionKind::InvokeDynamic {
+                    descriptor,
+                    impl_method,
+                    concat_recipe,
+                } => {
+                    let param_count = MethodDescriptor::from_str(descriptor)
+                        .context("parse call descriptor")?
+                        .parameter_types()
+                        .len();
+                    frame.machine.pop_n(param_count);
+                    // Lambdas and string concatenation only create a new object.
+                    let is_known_factory = impl_method.is_some() || concat_recipe.is_some();
+                    if method_return_kind(descriptor)? != ReturnKind::Void {
+                        frame.machine.push(if is_known_factory {
+                            Value::NonNull
+                        } else {
+                            Value::Unknown
+                        });
+                    }
+                    if !is_known_factory {
+                        facts.push(BodyFact::SideEffect);
+                    }
+                }
+                _ => apply_stack_effect(method, instruction, &mut frame.machine),
+            },
+        }
+
+        let mut step = if terminate {
+            InstructionStep::terminate_path()
+        } else {
+            InstructionStep::continue_path()
+        };
+        for fact in facts {
+            step = step.with_finding(fact);
+        }
+        Ok(step)
+    }
+
+    fn on_block_end(
+        &self,
+        method: &Method,
+        state: &Self::State,
+        successors: &[u32],
+    ) -> Result<BlockEndStep<Self::State, Self::Finding>> {
+        let mut step = BlockEndStep::terminal();
+        for successor in successors {
+            let mut next = state.clone();
+            next.set_position(*successor, 0);
+            let is_handler = method
+                .exception_handlers
+                .iter()
+                .any(|handler| handler.handler_pc == *successor);
+            if is_handler {
+                // A handler starts with only the caught exception on the stack.
+                let machine = &mut next.frame.machine;
+                machine.pop_n(machine.stack_len());
+                machine.push(Value::NonNull);
+            }
+            step = step.with_next_state(next);
+        }
+        Ok(step)
+    }
+}
+
+impl SummarySemantics<'_> {
+    /// Apply a call to `frame`, returning whether the path ends because the callee never
+    /// returns.
+    fn handle_invoke(
+        &self,
+        call: &CallSite,
+        frame: &mut Frame,
+        facts: &mut Vec<BodyFact>,
+    ) -> Result<bool> {
+        let param_count = MethodDescriptor::from_str(&call.descriptor)
+            .context("parse call descriptor")?
+            .parameter_types()
+            .len();
+        let mut arguments: Vec<Value> = (0..param_count).map(|_| frame.machine.pop()).collect();
+        if call.kind != CallKind::Static {
+            arguments.push(frame.machine.pop());
+        }
+        arguments.reverse();
+
+        let summary = self.summaries.for_call(call);
+        let closed_arguments = match summary {
+            Some(summary) => summary.closes_arguments.iter().copied().collect(),
+            None if is_close_call(call) => vec![0],
+            None => Vec::new(),
+        };
+        for index in closed_arguments {
+            if let Some(Value::Argument(argument)) = arguments.get(index) {
+                frame.closed.insert(*argument);
+            }
+        }
+        let is_side_effect_free = match summary {
+            Some(summary) => summary.side_effect_free,
+            None => call.owner == JAVA_LANG_OBJECT && call.name == "<init>",
+        };
+        if !is_side_effect_free {
+            facts.push(BodyFact::SideEffect);
+        }
+        if summary.is_some_and(|summary| summary.always_throws) {
+            return Ok(true);
+        }
+        if method_return_kind(&call.descriptor)? != ReturnKind::Void {
+            let returns_non_null = summary.is_some_and(|summary| summary.returns_non_null);
+            frame.machine.push(if returns_non_null {
+                Value::NonNull
+            } else {
+                Value::Unknown
+            });
+        }
+        Ok(false)
+    }
+}
+
+fn is_close_call(call: &CallSite) -> bool {
+    call.kind != CallKind::Static && call.name == "close" && call.descriptor == "()V"
+}
+
+fn apply_stack_effect(
+    method: &Method,
+    instruction: &Instruction,
+    machine: &mut StackMachine<Value>,
+) {
+    let mut hook = SummarySemanticsHook { instruction };
+    let mut coverage = SemanticsCoverage::default();
+    let _ = apply_semantics(
+        machine,
+        method,
+        instruction.offset as usize,
+        instruction.opcode,
+        &SummaryValueDomain,
+        &mut hook,
+        &mut coverage,
+        SemanticsDebugConfig::default(),
+    );
+}
+
+/// Hook that tracks argument aliases and freshly created objects.
+struct SummarySemanticsHook<'a> {
+    instruction: &'a Instruction,
+}
+
+impl SemanticsHooks<Value> for SummarySemanticsHook<'_> {
+    fn pre_apply(
+        &mut self,
+        machine: &mut StackMachine<Value>,
+        method: &Method,
+        offset: usize,
+        opcode: u8,
+    ) -> ApplyOutcome {
+        let operand = |index: usize| method.bytecode.get(offset + index).copied().unwrap_or(0);
+        match opcode {
+            // iload..aload with an operand, then iload_0..aload_3.
+            0x15..=0x19 => machine.push(machine.load_local(operand(1) as usize)),
+            0x1a..=0x2d => machine.push(machine.load_local(usize::from((opcode - 0x1a) % 4))),
+            // istore..astore with an operand, then istore_0..astore_3.
+            0x36..=0x3a => {
+                let value = machine.pop();
+                machine.store_local(operand(1) as usize, value);
+            }
+            0x3b..=0x4e => {
+                let value = machine.pop();
+                machine.store_local(usize::from((opcode - 0x3b) % 4), value);
+            }
+            WIDE => {
+                let index = usize::from(u16::from_be_bytes([operand(2), operand(3)]));
+                match operand(1) {
+                    0x15..=0x19 => machine.push(machine.load_local(index)),
+                    0x36..=0x3a => {
+                        let value = machine.pop();
+                        machine.store_local(index, value);
+                    }
+                    _ => return ApplyOutcome::NotHandled,
+                }
+            }
+            opcodes::NEW => machine.push(Value::NonNull),
+            opcodes::NEWARRAY | opcodes::ANEWARRAY => {
+                machine.pop();
+                machine.push(Value::NonNull);
+            }
+            opcodes::LDC | opcodes::LDC_W
+                if matches!(
+                    self.instruction.kind,
+                    InstructionKind::ConstString(_) | InstructionKind::ConstClass(_)
+                ) =>
+            {
+                machine.push(Value::NonNull)
+            }
+            // A cast keeps the reference.
+            CHECKCAST => {}
+            _ => return ApplyOutcome::NotHandled,
+        }
+        ApplyOutcome::Applied
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::{MethodSummaries, MethodSummary};
+    use crate::ir::Class;
+    use crate::scan::scan_inputs;
+    use crate::test_harness::{JvmTestHarness, Language, SourceFile};
+
+    fn compile_classes(contents: &str) -> Vec<Class> {
+        let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
+        let sources = vec![SourceFile {
+            path: "com/example/ClassA.java".to_string(),
+            contents: contents.to_string(),
+        }];
+        let output = harness
+            .compile(Language::Java, &sources, &[])
+            .expect("compile sources");
+        scan_inputs(&[output.classes_dir().to_path_buf()], &[], None, None)
+            .expect("scan classes")
+            .classes
+    }
+
+    fn summary<'a>(
+        summaries: &'a MethodSummaries,
+        classes: &'a [Class],
+        class_name: &str,
+        method_name: &str,
+    ) -> &'a MethodSummary {
+        let class = classes
+            .iter()
+            .find(|class| class.name == class_name)
+            .expect("class");
+        let method = class
+            .methods
+            .iter()
+            .find(|method| method.name == method_name)
+            .expect("method");
+        summaries.for_method(class, method).expect("summary")
+    }
+
+    #[test]
+    fn infers_non_null_returns_through_helpers() {
+        let classes = compile_classes(
+            r#"
+package com.example;
+
+class ClassA {
+    private String literal() {
+        return "text";
+    }
+
+    private Object allocated(boolean varOne) {
+        return varOne ? new Object() : literal();
+    }
+
+    private String maybeNull(boolean varOne) {
+        return varOne ? "text" : null;
+    }
+
+    private String passThrough(String varOne) {
+        return varOne;
+    }
+
+    String overridable() {
+        return "text";
+    }
+
+    String viaOverridable() {
+        return overridable();
+    }
+}
+"#,
+        );
+        let summaries = MethodSummaries::compute(&classes);
+        let returns_non_null =
+            |name: &str| summary(&summaries, &classes, "com/example/ClassA", name).returns_non_null;
+
+        assert!(returns_non_null("literal"));
+        assert!(returns_non_null("allocated"));
+        assert!(returns_non_null("overridable"));
+        assert!(!returns_non_null("maybeNull"));
+        assert!(!returns_non_null("passThrough"));
+        assert!(!returns_non_null("viaOverridable"));
+    }
+
+    #[test]
+    fn infers_closed_arguments_on_every_return() {
+        let classes = compile_classes(
+            r#"
+package com.example;
+
+import java.io.Closeable;
+import java.io.IOException;
+
+class ClassA {
+    static void closeQuietly(Closeable varOne) {
+        try {
+            varOne.close();
+        } catch (IOException varTwo) {
+        }
+    }
+
+    static void closeSecond(Closeable varOne, Closeable varTwo) {
+        closeQuietly(varTwo);
+    }
+
+    static void closeSometimes(Closeable varOne, boolean varTwo) throws IOException {
+        if (varTwo) {
+            varOne.close();
+        }
+    }
+
+    final void closeSelf() throws IOException {
+        if (this instanceof Closeable) {
+            ((Closeable) this).close();
+        }
+    }
+}
+"#,
+        );
+        let summaries = MethodSummaries::compute(&classes);
+        let closes = |name: &str| {
+            summary(&summaries, &classes, "com/example/ClassA", name)
+                .closes_arguments
+                .clone()
+        };
+
+        assert_eq!(closes("closeQuietly"), BTreeSet::from([0]));
+        assert_eq!(closes("closeSecond"), BTreeSet::from([1]));
+        assert_eq!(closes("closeSometimes"), BTreeSet::new());
+        assert_eq!(closes("closeSelf"), BTreeSet::new());
+    }
+
+    #[test]
+    fn infers_methods_that_always_throw() {
+        let classes = compile_classes(
+            r#"
+package com.example;
+
+class ClassA {
+    static void fail(String varOne) {
+        throw new IllegalStateException(varOne);
+    }
+
+    static int failThrough(int varOne) {
+        fail("value " + varOne);
+        return varOne;
+    }
+
+    static int checked(int varOne) {
+        if (varOne < 0) {
+            fail("negative");
+        }
+        return varOne;
+    }
+}
+"#,
+        );
+        let summaries = MethodSummaries::compute(&classes);
+        let always_throws =
+            |name: &str| summary(&summaries, &classes, "com/example/ClassA", name).always_throws;
+
+        assert!(always_throws("fail"));
+        assert!(always_throws("failThrough"));
+        assert!(!always_throws("checked"));
+    }
+
+    #[test]
+    fn infers_side_effect_freedom_across_recursion() {
+        let classes = compile_classes(
+            r#"
+package com.example;
+
+class ClassA {
+    private int counter;
+
+    static boolean isEven(int varOne) {
+        return varOne == 0 || isOdd(varOne - 1);
+    }
+
+    static boolean isOdd(int varOne) {
+        return varOne != 0 && isEven(varOne - 1);
+    }
+
+    static int factorial(int varOne) {
+        return varOne <= 1 ? 1 : varOne * factorial(varOne - 1);
+    }
+
+    int current() {
+        return counter;
+    }
+
+    void increment() {
+        counter++;
+    }
+
+    static int countDown(ClassA varOne, int varTwo) {
+        if (varTwo > 0) {
+            varOne.increment();
+            return countDown(varOne, varTwo - 1);
+        }
+        return 0;
+    }
+}
+"#,
+        );
+        let summaries = MethodSummaries::compute(&classes);
+        let get = |name: &str| summary(&summaries, &classes, "com/example/ClassA", name);
+
+        assert!(get("isEven").side_effect_free);
+        assert!(get("isOdd").side_effect_free);
+        assert!(!get("isEven").always_throws);
+        assert!(get("factorial").side_effect_free);
+        assert!(!get("factorial").always_throws);
+        assert!(get("current").side_effect_free);
+        assert!(!get("increment").side_effect_free);
+        assert!(!get("countDown").side_effect_free);
+    }
+}
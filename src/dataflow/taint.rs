@@ -0,0 +1,649 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use jdescriptor::MethodDescriptor;
+
+use crate::dataflow::opcode_semantics::{
+    ApplyOutcome, SemanticsCoverage, SemanticsDebugConfig, SemanticsHooks, ValueDomain,
+    apply_semantics,
+};
+use crate::dataflow::stack_machine::{StackMachine, StackMachineConfig};
+use crate::dataflow::worklist::{
+    BlockEndStep, InstructionStep, TraceStep, WorklistSemantics, WorklistState,
+    analyze_method_with_trace,
+};
+use crate::descriptor::{ReturnKind, method_return_kind};
+use crate::ir::{CallKind, CallSite, Instruction, InstructionKind, Method};
+use crate::opcodes;
+
+const MAX_TRACKED_STACK_DEPTH: usize = 64;
+
+const WIDE: u8 = 0xc4;
+const CHECKCAST: u8 = 0xc0;
+
+/// Method named in a taint table.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MethodPattern {
+    /// Internal name of the class the call is compiled against.
+    pub(crate) owner: &'static str,
+    pub(crate) name: &'static str,
+    /// Exact descriptor, or `None` for every overload.
+    pub(crate) descriptor: Option<&'static str>,
+}
+
+impl MethodPattern {
+    /// Every overload of `owner.name`.
+    pub(crate) const fn any(owner: &'static str, name: &'static str) -> Self {
+        Self {
+            owner,
+            name,
+            descriptor: None,
+        }
+    }
+
+    fn matches(&self, call: &CallSite) -> bool {
+        call.owner == self.owner
+            && call.name == self.name
+            && self
+                .descriptor
+                .is_none_or(|descriptor| call.descriptor == descriptor)
+    }
+}
+
+/// Call whose argument must not carry untrusted data.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TaintSink {
+    pub(crate) method: MethodPattern,
+    /// Declared parameter checked at the call, starting from 0 and not counting the receiver.
+    pub(crate) argument: usize,
+}
+
+/// Sources, sinks and sanitizers of one taint analysis.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TaintSpec {
+    /// Calls whose result is untrusted.
+    pub(crate) sources: &'static [MethodPattern],
+    pub(crate) sinks: &'static [TaintSink],
+    /// Calls whose result is trusted whatever they were given.
+    pub(crate) sanitizers: &'static [MethodPattern],
+}
+
+/// Values read from a servlet request, which a remote client controls.
+pub(crate) const SERVLET_REQUEST_SOURCES: &[MethodPattern] = &[
+    MethodPattern::any("javax/servlet/ServletRequest", "getParameter"),
+    MethodPattern::any("javax/servlet/ServletRequest", "getParameterValues"),
+    MethodPattern::any("javax/servlet/http/HttpServletRequest", "getParameter"),
+    MethodPattern::any(
+        "javax/servlet/http/HttpServletRequest",
+        "getParameterValues",
+    ),
+    MethodPattern::any("javax/servlet/http/HttpServletRequest", "getHeader"),
+    MethodPattern::any("javax/servlet/http/HttpServletRequest", "getQueryString"),
+    MethodPattern::any("javax/servlet/http/HttpServletRequest", "getPathInfo"),
+    MethodPattern::any("javax/servlet/http/HttpServletRequest", "getRequestURI"),
+    MethodPattern::any("jakarta/servlet/ServletRequest", "getParameter"),
+    MethodPattern::any("jakarta/servlet/ServletRequest", "getParameterValues"),
+    MethodPattern::any("jakarta/servlet/http/HttpServletRequest", "getParameter"),
+    MethodPattern::any(
+        "jakarta/servlet/http/HttpServletRequest",
+        "getParameterValues",
+    ),
+    MethodPattern::any("jakarta/servlet/http/HttpServletRequest", "getHeader"),
+    MethodPattern::any("jakarta/servlet/http/HttpServletRequest", "getQueryString"),
+    MethodPattern::any("jakarta/servlet/http/HttpServletRequest", "getPathInfo"),
+    MethodPattern::any("jakarta/servlet/http/HttpServletRequest", "getRequestURI"),
+];
+
+/// Classes whose methods carry taint from their receiver and arguments to their result, and
+/// from their arguments into a receiver under construction or being appended to.
+const PROPAGATING_OWNERS: &[&str] = &[
+    "java/lang/String",
+    "java/lang/StringBuilder",
+    "java/lang/StringBuffer",
+    "java/io/File",
+    "java/net/URI",
+    "java/net/URL",
+    "java/nio/file/Path",
+    "java/nio/file/Paths",
+];
+
+/// Untrusted data reaching a sink on some path through one method.
+#[derive(Clone, Debug)]
+pub(crate) struct TaintFlow {
+    /// Offset of the source call.
+    pub(crate) source_offset: u32,
+    /// Offset of the sink call.
+    pub(crate) sink_offset: u32,
+    pub(crate) sink: TaintSink,
+    /// Path from the method entry to the sink.
+    pub(crate) trace: Vec<TraceStep>,
+}
+
+/// Find paths on which a value returned by a source of `spec` reaches a sink argument.
+///
+/// Taint follows locals, fields of `this`, array elements, string building
+/// (`StringBuilder`/`StringBuffer`, `invokedynamic` concatenation and the methods of a few
+/// value classes such as `String` and `File`). Results of other calls are trusted. Each
+/// source and sink pair is reported once, with the shortest path reaching it.
+pub(crate) fn analyze_taint(method: &Method, spec: &TaintSpec) -> Result<Vec<TaintFlow>> {
+    let Some(entry_block) = method
+        .cfg
+        .blocks
+        .iter()
+        .map(|block| block.start_offset)
+        .min()
+    else {
+        return Ok(Vec::new());
+    };
+    let semantics = TaintSemantics {
+        entry_block,
+        is_static: method.access.is_static,
+        spec,
+    };
+    let mut flows = BTreeMap::new();
+    for traced in analyze_method_with_trace(method, &semantics)? {
+        let (source_offset, sink_offset, sink) = traced.finding;
+        flows
+            .entry((source_offset, sink_offset))
+            .or_insert(TaintFlow {
+                source_offset,
+                sink_offset,
+                sink: spec.sinks[sink],
+                trace: traced.trace,
+            });
+    }
+    Ok(flows.into_values().collect())
+}
+
+/// Abstract value of one stack or local slot.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+enum Value {
+    Unknown,
+    /// The receiver of an instance method.
+    This,
+    /// Object allocated by the `new` at this offset and not tainted yet.
+    New(u32),
+    /// Untrusted data returned by the source call at this offset.
+    Tainted(u32),
+}
+
+impl Value {
+    fn origin(self) -> Option<u32> {
+        match self {
+            Value::Tainted(origin) => Some(origin),
+            _ => None,
+        }
+    }
+}
+
+/// Value-domain adapter for default opcode semantics.
+struct TaintValueDomain;
+
+impl ValueDomain<Value> for TaintValueDomain {
+    fn unknown_value(&self) -> Value {
+        Value::Unknown
+    }
+
+    fn scalar_value(&self) -> Value {
+        Value::Unknown
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+struct TaintState {
+    block_start: u32,
+    instruction_index: usize,
+    machine: StackMachine<Value>,
+    /// Fields of `this` written on this path, by name and descriptor.
+    fields: BTreeMap<String, Value>,
+}
+
+impl WorklistState for TaintState {
+    fn block_start(&self) -> u32 {
+        self.block_start
+    }
+
+    fn instruction_index(&self) -> usize {
+        self.instruction_index
+    }
+
+    fn set_position(&mut self, block_start: u32, instruction_index: usize) {
+        self.block_start = block_start;
+        self.instruction_index = instruction_index;
+    }
+}
+
+/// Dataflow callbacks for taint tracking; each finding is `(source, sink call, sink index)`.
+struct TaintSemantics<'a> {
+    entry_block: u32,
+    is_static: bool,
+    spec: &'a TaintSpec,
+}
+
+impl WorklistSemantics for TaintSemantics<'_> {
+    type State = TaintState;
+    type Finding = (u32, u32, usize);
+
+    fn initial_states(&self, _method: &Method) -> Vec<Self::State> {
+        let mut machine = StackMachine::with_config(
+            Value::Unknown,
+            StackMachineConfig {
+                max_stack_depth: Some(MAX_TRACKED_STACK_DEPTH),
+                max_locals: None,
+                max_symbolic_identities: None,
+            },
+        );
+        if !self.is_static {
+            machine.store_local(0, Value::This);
+        }
+        vec![TaintState {
+            block_start: self.entry_block,
+            instruction_index: 0,
+            machine,
+            fields: BTreeMap::new(),
+        }]
+    }
+
+    fn transfer_instruction(
+        &self,
+        method: &Method,
+        instruction: &Instruction,
+        state: &mut Self::State,
+    ) -> Result<InstructionStep<Self::Finding>> {
+        let mut step = InstructionStep::continue_path();
+        match &instruction.kind {
+            InstructionKind::Invoke(call) => {
+                for finding in self.handle_invoke(call, &mut state.machine)? {
+                    step = step.with_finding(finding);
+                }
+            }
+            InstructionKind::InvokeDynamic {
+                descriptor,
+                concat_recipe,
+                ..
+            } => {
+                let param_count = MethodDescriptor::from_str(descriptor)
+                    .context("parse call descriptor")?
+                    .parameter_types()
+                    .len();
+                let args: Vec<Value> = (0..param_count).map(|_| state.machine.pop()).collect();
+                if method_return_kind(descriptor)? != ReturnKind::Void {
+                    let origin = concat_recipe
+                        .as_ref()
+                        .and_then(|_| args.iter().rev().find_map(|arg| arg.origin()));
+                    state
+                        .machine
+                        .push(origin.map_or(Value::Unknown, Value::Tainted));
+                }
+            }
+            InstructionKind::FieldAccess(field) if instruction.opcode == opcodes::GETFIELD => {
+                let receiver = state.machine.pop();
+                let key = format!("{}:{}", field.name, field.descriptor);
+                let value = match receiver {
+                    Value::This => state.fields.get(&key).copied(),
+                    _ => None,
+                };
+                state.machine.push(value.unwrap_or(Value::Unknown));
+            }
+            InstructionKind::FieldAccess(field) if instruction.opcode == opcodes::PUTFIELD => {
+                let value = state.machine.pop();
+                if state.machine.pop() == Value::This {
+                    let key = format!("{}:{}", field.name, field.descriptor);
+                    state.fields.insert(key, value);
+                }
+            }
+            _ => apply_stack_effect(method, instruction, &mut state.machine),
+        }
+        Ok(step)
+    }
+
+    fn on_block_end(
+        &self,
+        method: &Method,
+        state: &Self::State,
+        successors: &[u32],
+    ) -> Result<BlockEndStep<Self::State, Self::Finding>> {
+        let mut step = BlockEndStep::terminal();
+        for successor in successors {
+            let mut next = state.clone();
+            next.set_position(*successor, 0);
+            let is_handler = method
+                .exception_handlers
+                .iter()
+                .any(|handler| handler.handler_pc == *successor);
+            if is_handler {
+                // A handler starts with only the caught exception on the stack.
+                next.machine.pop_n(next.machine.stack_len());
+                next.machine.push(Value::Unknown);
+            }
+            step = step.with_next_state(next);
+        }
+        Ok(step)
+    }
+}
+
+impl TaintSemantics<'_> {
+    /// Apply a call to `machine`, returning the sinks it feeds untrusted data.
+    fn handle_invoke(
+        &self,
+        call: &CallSite,
+        machine: &mut StackMachine<Value>,
+    ) -> Result<Vec<(u32, u32, usize)>> {
+        let param_count = MethodDescriptor::from_str(&call.descriptor)
+            .context("parse call descriptor")?
+            .parameter_types()
+            .len();
+        let mut args: Vec<Value> = (0..param_count).map(|_| machine.pop()).collect();
+        args.reverse();
+        let receiver = (call.kind != CallKind::Static).then(|| machine.pop());
+
+        let findings = self
+            .spec
+            .sinks
+            .iter()
+            .enumerate()
+            .filter(|(_, sink)| sink.method.matches(call))
+            .filter_map(|(index, sink)| {
+                let origin = args.get(sink.argument)?.origin()?;
+                Some((origin, call.offset, index))
+            })
+            .collect();
+
+        let propagates = PROPAGATING_OWNERS.contains(&call.owner.as_str());
+        let incoming = args
+            .iter()
+            .chain(&receiver)
+            .find_map(|value| value.origin());
+        if propagates
+            && let Some(origin) = incoming
+            && let Some(Value::New(site)) = receiver
+        {
+            // A constructor or builder call stores the untrusted data in the receiver.
+            taint_allocation(machine, site, origin);
+        }
+
+        if method_return_kind(&call.descriptor)? != ReturnKind::Void {
+            let result = if self.spec.sources.iter().any(|source| source.matches(call)) {
+                Value::Tainted(call.offset)
+            } else if self
+                .spec
+                .sanitizers
+                .iter()
+                .any(|sanitizer| sanitizer.matches(call))
+            {
+                Value::Unknown
+            } else if propagates {
+                match (incoming, receiver) {
+                    (Some(origin), _) => Value::Tainted(origin),
+                    // Builders return themselves, so the result aliases the receiver.
+                    (None, Some(Value::New(site))) if returns_own_type(call) => Value::New(site),
+                    _ => Value::Unknown,
+                }
+            } else {
+                Value::Unknown
+            };
+            machine.push(result);
+        }
+        Ok(findings)
+    }
+}
+
+fn returns_own_type(call: &CallSite) -> bool {
+    call.descriptor
+        .rsplit_once(')')
+        .is_some_and(|(_, return_type)| {
+            return_type
+                .strip_prefix('L')
+                .and_then(|name| name.strip_suffix(';'))
+                == Some(call.owner.as_str())
+        })
+}
+
+/// Mark every alias of the object allocated at `site` as holding untrusted data.
+fn taint_allocation(machine: &mut StackMachine<Value>, site: u32, origin: u32) {
+    machine.rewrite_values(|value| {
+        if *value == Value::New(site) {
+            *value = Value::Tainted(origin);
+        }
+    });
+}
+
+fn apply_stack_effect(
+    method: &Method,
+    instruction: &Instruction,
+    machine: &mut StackMachine<Value>,
+) {
+    let mut hook = TaintSemanticsHook { instruction };
+    let mut coverage = SemanticsCoverage::default();
+    let _ = apply_semantics(
+        machine,
+        method,
+        instruction.offset as usize,
+        instruction.opcode,
+        &TaintValueDomain,
+        &mut hook,
+        &mut coverage,
+        SemanticsDebugConfig::default(),
+    );
+}
+
+/// Hook that moves values through locals and arrays and names fresh allocations.
+struct TaintSemanticsHook<'a> {
+    instruction: &'a Instruction,
+}
+
+impl SemanticsHooks<Value> for TaintSemanticsHook<'_> {
+    fn pre_apply(
+        &mut self,
+        machine: &mut StackMachine<Value>,
+        method: &Method,
+        offset: usize,
+        opcode: u8,
+    ) -> ApplyOutcome {
+        let operand = |index: usize| method.bytecode.get(offset + index).copied().unwrap_or(0);
+        match opcode {
+            // iload..aload with an operand, then iload_0..aload_3.
+            0x15..=0x19 => machine.push(machine.load_local(operand(1) as usize)),
+            0x1a..=0x2d => machine.push(machine.load_local(usize::from((opcode - 0x1a) % 4))),
+            // istore..astore with an operand, then istore_0..astore_3.
+            0x36..=0x3a => {
+                let value = machine.pop();
+                machine.store_local(operand(1) as usize, value);
+            }
+            0x3b..=0x4e => {
+                let value = machine.pop();
+                machine.store_local(usize::from((opcode - 0x3b) % 4), value);
+            }
+            WIDE => {
+                let index = usize::from(u16::from_be_bytes([operand(2), operand(3)]));
+                match operand(1) {
+                    0x15..=0x19 => machine.push(machine.load_local(index)),
+                    0x36..=0x3a => {
+                        let value = machine.pop();
+                        machine.store_local(index, value);
+                    }
+                    _ => return ApplyOutcome::NotHandled,
+                }
+            }
+            opcodes::NEW => machine.push(Value::New(self.instruction.offset)),
+            // An element of an untrusted array is untrusted.
+            opcodes::AALOAD => {
+                machine.pop();
+                let array = machine.pop();
+                machine.push(array.origin().map_or(Value::Unknown, Value::Tainted));
+            }
+            opcodes::AASTORE => {
+                let value = machine.pop();
+                machine.pop();
+                let array = machine.pop();
+                if let (Some(origin), Value::New(site)) = (value.origin(), array) {
+                    taint_allocation(machine, site, origin);
+                }
+            }
+            opcodes::ANEWARRAY => {
+                machine.pop();
+                machine.push(Value::New(self.instruction.offset));
+            }
+            // A cast keeps the reference.
+            CHECKCAST => {}
+            _ => return ApplyOutcome::NotHandled,
+        }
+        ApplyOutcome::Applied
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MethodPattern, TaintSink, TaintSpec, analyze_taint};
+    use crate::ir::Class;
+    use crate::scan::scan_inputs;
+    use crate::test_harness::{JvmTestHarness, Language, SourceFile};
+
+    const SPEC: TaintSpec = TaintSpec {
+        sources: &[MethodPattern::any("com/example/Input", "read")],
+        sinks: &[TaintSink {
+            method: MethodPattern {
+                owner: "com/example/Output",
+                name: "write",
+                descriptor: Some("(ILjava/lang/String;)V"),
+            },
+            argument: 1,
+        }],
+        sanitizers: &[MethodPattern::any("com/example/Input", "escape")],
+    };
+
+    fn compile_class(contents: &str) -> Class {
+        let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
+        let sources = vec![
+            SourceFile {
+                path: "com/example/Input.java".to_string(),
+                contents: r#"
+package com.example;
+public class Input {
+    public static String read() { return "data"; }
+    public static String escape(String value) { return value; }
+}
+"#
+                .to_string(),
+            },
+            SourceFile {
+                path: "com/example/Output.java".to_string(),
+                contents: r#"
+package com.example;
+public class Output {
+    public static void write(int level, String value) {}
+    public static void write(String value) {}
+}
+"#
+                .to_string(),
+            },
+            SourceFile {
+                path: "com/example/ClassA.java".to_string(),
+                contents: contents.to_string(),
+            },
+        ];
+        let output = harness
+            .compile(Language::Java, &sources, &[])
+            .expect("compile sources");
+        let scan = scan_inputs(&[output.classes_dir().to_path_buf()], &[], None, None)
+            .expect("scan classes");
+        scan.classes
+            .into_iter()
+            .find(|class| class.name == "com/example/ClassA")
+            .expect("compiled class")
+    }
+
+    /// Line of the source and of the sink of every flow in `method_name`.
+    fn flow_lines(class: &Class, method_name: &str) -> Vec<(Option<u32>, Option<u32>)> {
+        let method = class
+            .methods
+            .iter()
+            .find(|method| method.name == method_name)
+            .expect("method");
+        analyze_taint(method, &SPEC)
+            .expect("taint analysis")
+            .iter()
+            .map(|flow| {
+                (
+                    method.line_for_offset(flow.source_offset),
+                    method.line_for_offset(flow.sink_offset),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn follows_locals_fields_and_string_building() {
+        let class = compile_class(
+            r#"
+package com.example;
+
+class ClassA {
+    private String saved;
+
+    void direct() {
+        String varOne = Input.read();
+        Output.write(1, varOne);
+    }
+
+    void throughField() {
+        saved = Input.read();
+        Output.write(1, "[" + saved.toUpperCase() + "]");
+    }
+
+    void throughBuilder(boolean varOne) {
+        StringBuilder varTwo = new StringBuilder();
+        if (varOne) {
+            varTwo.append(Input.read());
+        }
+        Output.write(1, varTwo.toString());
+    }
+}
+"#,
+        );
+
+        assert_eq!(flow_lines(&class, "direct"), vec![(Some(8), Some(9))]);
+        assert_eq!(
+            flow_lines(&class, "throughField"),
+            vec![(Some(13), Some(14))]
+        );
+        assert_eq!(
+            flow_lines(&class, "throughBuilder"),
+            vec![(Some(20), Some(22))]
+        );
+    }
+
+    #[test]
+    fn stops_at_sanitizers_other_arguments_and_other_overloads() {
+        let class = compile_class(
+            r#"
+package com.example;
+
+class ClassA {
+    void sanitized() {
+        Output.write(1, Input.escape(Input.read()));
+    }
+
+    void otherArgument() {
+        Output.write(Input.read().length(), "fixed");
+    }
+
+    void otherOverload() {
+        Output.write(Input.read());
+    }
+
+    void overwritten() {
+        String varOne = Input.read();
+        varOne = "fixed";
+        Output.write(1, varOne);
+    }
+}
+"#,
+        );
+
+        for method in ["sanitized", "otherArgument", "otherOverload", "overwritten"] {
+            assert!(flow_lines(&class, method).is_empty(), "{method}");
+        }
+    }
+}
@@ -0,0 +1,789 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use jdescriptor::{MethodDescriptor, TypeDescriptor};
+
+use crate::dataflow::lattice::{AbstractState, AbstractValue};
+use crate::dataflow::opcode_semantics::{
+    ApplyOutcome, SemanticsCoverage, SemanticsDebugConfig, SemanticsHooks, ValueDomain,
+    apply_semantics,
+};
+use crate::dataflow::stack_machine::{StackMachine, StackMachineConfig};
+use crate::dataflow::worklist::{
+    BlockEndStep, InstructionStep, WorklistSemantics, WorklistState, analyze_method_fixpoint,
+};
+use crate::ir::{CallKind, CallSite, FieldRef, Instruction, InstructionKind, Method};
+use crate::opcodes;
+
+const MAX_TRACKED_STACK_DEPTH: usize = 64;
+
+const WIDE: u8 = 0xc4;
+const DUP2: u8 = 0x5c;
+
+/// Static type inferred for a reference held by a stack or local slot.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub(crate) struct InferredType {
+    /// Internal class name, or an array descriptor such as `[Ljava/lang/String;`.
+    pub(crate) class_name: String,
+    /// Generic signature from the `LocalVariableTypeTable`, for values read from a local
+    /// variable that has one.
+    pub(crate) signature: Option<String>,
+    /// Whether the runtime class is exactly `class_name` rather than possibly a subtype.
+    pub(crate) exact: bool,
+    pub(crate) origin: TypeOrigin,
+}
+
+/// Where a reference came from.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub(crate) enum TypeOrigin {
+    /// Method parameter in this local slot; slot 0 is `this` for instance methods.
+    Parameter(usize),
+    /// Object or array allocated at this offset.
+    Allocation(u32),
+    /// Value returned by this call.
+    Call(CallSite),
+    /// Value read from this field.
+    Field(FieldRef),
+    /// String or class literal.
+    Literal,
+    /// Exception caught by a handler.
+    CaughtException,
+    /// Known only from a `checkcast` at this offset.
+    Cast(u32),
+    /// Known only from the declared type of a local variable.
+    Declared,
+    /// Not tracked, or different on the paths that meet here.
+    Unknown,
+}
+
+/// Reference types on the operand stack and in locals before each reachable instruction of
+/// one method.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MethodTypes {
+    /// Keyed by instruction offset; instructions without any typed slot are omitted.
+    frames: BTreeMap<u32, TypeFrame>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct TypeFrame {
+    /// Stack slots bottom to top.
+    stack: Vec<Option<InferredType>>,
+    locals: BTreeMap<usize, InferredType>,
+}
+
+impl MethodTypes {
+    /// Type `depth` slots below the top of the stack (0 is the top) just before the
+    /// instruction at `offset` runs. Every value takes one slot, including `long` and `double`.
+    pub(crate) fn stack_type(&self, offset: u32, depth: usize) -> Option<&InferredType> {
+        let frame = self.frames.get(&offset)?;
+        let index = frame.stack.len().checked_sub(depth + 1)?;
+        frame.stack[index].as_ref()
+    }
+
+    /// Type of local variable slot `index` just before the instruction at `offset` runs.
+    #[allow(dead_code)]
+    pub(crate) fn local_type(&self, offset: u32, index: usize) -> Option<&InferredType> {
+        self.frames.get(&offset)?.locals.get(&index)
+    }
+
+    /// Type passed as argument `index` to the call at `offset`, whose descriptor takes
+    /// `arg_count` arguments.
+    pub(crate) fn argument(
+        &self,
+        offset: u32,
+        arg_count: usize,
+        index: usize,
+    ) -> Option<&InferredType> {
+        self.stack_type(offset, arg_count.checked_sub(index + 1)?)
+    }
+
+    /// Type of the receiver of an instance call.
+    pub(crate) fn receiver(&self, call: &CallSite) -> Option<&InferredType> {
+        if call.kind == CallKind::Static {
+            return None;
+        }
+        let descriptor = MethodDescriptor::from_str(&call.descriptor).ok()?;
+        self.stack_type(call.offset, descriptor.parameter_types().len())
+    }
+}
+
+/// Infer reference types through the stack and locals of a method of `class_name`.
+///
+/// Types come from the method and call descriptors, `new` and array allocations, field
+/// descriptors, `checkcast`, literals, exception handler catch types, and the
+/// `LocalVariableTable`; values read from a local with a `LocalVariableTypeTable` entry also
+/// carry its generic signature. Values meeting at a block entry keep their type only when
+/// every path agrees on the class. A method whose analysis exceeds the budget has no types.
+pub(crate) fn analyze_types(class_name: &str, method: &Method) -> Result<MethodTypes> {
+    let Some(entry_block) = method
+        .cfg
+        .blocks
+        .iter()
+        .map(|block| block.start_offset)
+        .min()
+    else {
+        return Ok(MethodTypes::default());
+    };
+    let semantics = TypeSemantics {
+        entry_block,
+        entry_machine: entry_machine(class_name, method)?,
+    };
+    let frames = analyze_method_fixpoint(method, &semantics)?
+        .into_iter()
+        .collect();
+    Ok(MethodTypes { frames })
+}
+
+/// Abstract value of one stack or local slot.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+enum Slot {
+    Unknown,
+    /// A `long` or `double` constant, field or call result, which `pop2` and `dup2` treat
+    /// as one value.
+    Wide,
+    Reference(InferredType),
+}
+
+impl Slot {
+    fn reference(&self) -> Option<&InferredType> {
+        match self {
+            Slot::Reference(ty) => Some(ty),
+            _ => None,
+        }
+    }
+}
+
+impl AbstractValue for Slot {
+    fn join(&self, other: &Self) -> Self {
+        match (self, other) {
+            _ if self == other => self.clone(),
+            (Slot::Reference(left), Slot::Reference(right))
+                if left.class_name == right.class_name =>
+            {
+                Slot::Reference(InferredType {
+                    class_name: left.class_name.clone(),
+                    signature: (left.signature == right.signature)
+                        .then(|| left.signature.clone())
+                        .flatten(),
+                    exact: left.exact && right.exact,
+                    origin: if left.origin == right.origin {
+                        left.origin.clone()
+                    } else {
+                        TypeOrigin::Unknown
+                    },
+                })
+            }
+            _ => Slot::Unknown,
+        }
+    }
+}
+
+/// Value-domain adapter for default opcode semantics.
+struct TypeValueDomain;
+
+impl ValueDomain<Slot> for TypeValueDomain {
+    fn unknown_value(&self) -> Slot {
+        Slot::Unknown
+    }
+
+    fn scalar_value(&self) -> Slot {
+        Slot::Unknown
+    }
+}
+
+/// Stack and locals at one program point of the type inference.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+struct TypeState {
+    block_start: u32,
+    instruction_index: usize,
+    machine: StackMachine<Slot>,
+}
+
+impl WorklistState for TypeState {
+    fn block_start(&self) -> u32 {
+        self.block_start
+    }
+
+    fn instruction_index(&self) -> usize {
+        self.instruction_index
+    }
+
+    fn set_position(&mut self, block_start: u32, instruction_index: usize) {
+        self.block_start = block_start;
+        self.instruction_index = instruction_index;
+    }
+}
+
+impl AbstractState for TypeState {
+    type Value = StackMachine<Slot>;
+
+    fn value(&self) -> &Self::Value {
+        &self.machine
+    }
+
+    fn set_value(&mut self, value: Self::Value) {
+        self.machine = value;
+    }
+}
+
+/// Dataflow callbacks for type inference; each finding is the frame seen by one instruction.
+struct TypeSemantics {
+    entry_block: u32,
+    entry_machine: StackMachine<Slot>,
+}
+
+impl WorklistSemantics for TypeSemantics {
+    type State = TypeState;
+    type Finding = (u32, TypeFrame);
+
+    fn initial_states(&self, method: &Method) -> Vec<Self::State> {
+        let mut states = vec![TypeState {
+            block_start: self.entry_block,
+            instruction_index: 0,
+            machine: self.entry_machine.clone(),
+        }];
+        // The control flow graph has no exception edges, so each handler is entered on its
+        // own with the caught exception and only `this` known; the local variable tables
+        // still type the other locals.
+        let handler_pcs: BTreeSet<u32> = method
+            .exception_handlers
+            .iter()
+            .map(|handler| handler.handler_pc)
+            .collect();
+        for handler_pc in handler_pcs {
+            let mut machine = self.entry_machine.clone();
+            let this = machine.load_local(0);
+            machine.retain_locals(|_, _| false);
+            if !method.access.is_static {
+                machine.store_local(0, this);
+            }
+            machine.push(caught_exception(method, handler_pc));
+            states.push(TypeState {
+                block_start: handler_pc,
+                instruction_index: 0,
+                machine,
+            });
+        }
+        states
+    }
+
+    fn transfer_instruction(
+        &self,
+        method: &Method,
+        instruction: &Instruction,
+        state: &mut Self::State,
+    ) -> Result<InstructionStep<Self::Finding>> {
+        let frame = TypeFrame {
+            stack: state
+                .machine
+                .stack_values()
+                .iter()
+                .map(|slot| slot.reference().cloned())
+                .collect(),
+            locals: state
+                .machine
+                .locals()
+                .filter_map(|(index, slot)| Some((index, slot.reference()?.clone())))
+                .collect(),
+        };
+        let mut step = InstructionStep::continue_path();
+        if frame.stack.iter().any(Option::is_some) || !frame.locals.is_empty() {
+            step = step.with_finding((instruction.offset, frame));
+        }
+
+        match &instruction.kind {
+            InstructionKind::Invoke(call) => handle_invoke(call, &mut state.machine)?,
+            InstructionKind::InvokeDynamic { descriptor, .. } => {
+                let parsed =
+                    MethodDescriptor::from_str(descriptor).context("parse call descriptor")?;
+                state.machine.pop_n(parsed.parameter_types().len());
+                if let Some(value) = slot_for(parsed.return_type(), TypeOrigin::Unknown) {
+                    state.machine.push(value);
+                }
+            }
+            _ => apply_stack_effect(method, instruction, &mut state.machine),
+        }
+        Ok(step)
+    }
+
+    fn on_block_end(
+        &self,
+        method: &Method,
+        state: &Self::State,
+        successors: &[u32],
+    ) -> Result<BlockEndStep<Self::State, Self::Finding>> {
+        let mut step = BlockEndStep::terminal();
+        for successor in successors {
+            let mut next = state.clone();
+            next.set_position(*successor, 0);
+            if method
+                .exception_handlers
+                .iter()
+                .any(|handler| handler.handler_pc == *successor)
+            {
+                // A handler starts with only the caught exception on the stack.
+                next.machine.pop_n(next.machine.stack_len());
+                next.machine.push(caught_exception(method, *successor));
+            }
+            step = step.with_next_state(next);
+        }
+        Ok(step)
+    }
+}
+
+/// Locals on entry: `this` and the declared parameter types.
+fn entry_machine(class_name: &str, method: &Method) -> Result<StackMachine<Slot>> {
+    let mut machine = StackMachine::with_config(
+        Slot::Unknown,
+        StackMachineConfig {
+            max_stack_depth: Some(MAX_TRACKED_STACK_DEPTH),
+            max_locals: None,
+            max_symbolic_identities: None,
+        },
+    );
+    let mut index = 0;
+    if !method.access.is_static {
+        machine.store_local(index, reference(class_name, TypeOrigin::Parameter(index)));
+        index += 1;
+    }
+    let descriptor =
+        MethodDescriptor::from_str(&method.descriptor).context("parse method descriptor")?;
+    for param in descriptor.parameter_types() {
+        if let Some(value) = slot_for(param, TypeOrigin::Parameter(index)) {
+            machine.store_local(index, value);
+        }
+        index += match param {
+            TypeDescriptor::Long | TypeDescriptor::Double => 2,
+            _ => 1,
+        };
+    }
+    Ok(machine)
+}
+
+/// Exception entering the handler at `handler_pc`; a handler shared by several catch types
+/// only knows it holds a `Throwable`.
+fn caught_exception(method: &Method, handler_pc: u32) -> Slot {
+    let mut catch_types = method
+        .exception_handlers
+        .iter()
+        .filter(|handler| handler.handler_pc == handler_pc)
+        .map(|handler| handler.catch_type.as_deref());
+    let class_name = match catch_types.next().flatten() {
+        Some(name) if catch_types.all(|other| other == Some(name)) => name,
+        _ => "java/lang/Throwable",
+    };
+    reference(class_name, TypeOrigin::CaughtException)
+}
+
+fn reference(class_name: &str, origin: TypeOrigin) -> Slot {
+    Slot::Reference(InferredType {
+        class_name: class_name.to_string(),
+        signature: None,
+        exact: false,
+        origin,
+    })
+}
+
+fn exact_reference(class_name: String, origin: TypeOrigin) -> Slot {
+    Slot::Reference(InferredType {
+        class_name,
+        signature: None,
+        exact: true,
+        origin,
+    })
+}
+
+/// Slot holding a value of a descriptor type, or `None` for `void`.
+fn slot_for(ty: &TypeDescriptor, origin: TypeOrigin) -> Option<Slot> {
+    Some(match ty {
+        TypeDescriptor::Void => return None,
+        TypeDescriptor::Long | TypeDescriptor::Double => Slot::Wide,
+        TypeDescriptor::Object(name) => reference(name, origin),
+        TypeDescriptor::Array(..) => reference(&ty.to_string(), origin),
+        _ => Slot::Unknown,
+    })
+}
+
+fn handle_invoke(call: &CallSite, machine: &mut StackMachine<Slot>) -> Result<()> {
+    let descriptor =
+        MethodDescriptor::from_str(&call.descriptor).context("parse call descriptor")?;
+    machine.pop_n(descriptor.parameter_types().len());
+    if call.kind != CallKind::Static {
+        machine.pop();
+    }
+    if let Some(value) = slot_for(descriptor.return_type(), TypeOrigin::Call(call.clone())) {
+        machine.push(value);
+    }
+    Ok(())
+}
+
+fn apply_stack_effect(
+    method: &Method,
+    instruction: &Instruction,
+    machine: &mut StackMachine<Slot>,
+) {
+    let mut hook = TypeSemanticsHook { instruction };
+    let mut coverage = SemanticsCoverage::default();
+    let _ = apply_semantics(
+        machine,
+        method,
+        instruction.offset as usize,
+        instruction.opcode,
+        &TypeValueDomain,
+        &mut hook,
+        &mut coverage,
+        SemanticsDebugConfig::default(),
+    );
+}
+
+/// Hook that produces and moves typed values ahead of the default semantics.
+struct TypeSemanticsHook<'a> {
+    instruction: &'a Instruction,
+}
+
+impl SemanticsHooks<Slot> for TypeSemanticsHook<'_> {
+    fn pre_apply(
+        &mut self,
+        machine: &mut StackMachine<Slot>,
+        method: &Method,
+        offset: usize,
+        opcode: u8,
+    ) -> ApplyOutcome {
+        let operand = |index: usize| method.bytecode.get(offset + index).copied().unwrap_or(0);
+        let type_operand = || match &self.instruction.kind {
+            InstructionKind::TypeRef(name) => Some(name.clone()),
+            _ => None,
+        };
+        match opcode {
+            opcodes::LDC | opcodes::LDC_W | opcodes::LDC2_W => {
+                machine.push(match &self.instruction.kind {
+                    InstructionKind::ConstString(_) => {
+                        exact_reference("java/lang/String".to_string(), TypeOrigin::Literal)
+                    }
+                    InstructionKind::ConstClass(_) => {
+                        exact_reference("java/lang/Class".to_string(), TypeOrigin::Literal)
+                    }
+                    _ if opcode == opcodes::LDC2_W => Slot::Wide,
+                    _ => Slot::Unknown,
+                });
+            }
+            opcodes::LCONST_0 | opcodes::LCONST_1 | opcodes::DCONST_0 | opcodes::DCONST_1 => {
+                machine.push(Slot::Wide)
+            }
+            // iload..aload with an operand, then iload_0..aload_3.
+            0x15..=0x19 => {
+                let index = operand(1) as usize;
+                machine.push(self.load(machine, method, index));
+            }
+            0x1a..=0x2d => {
+                let index = usize::from((opcode - 0x1a) % 4);
+                machine.push(self.load(machine, method, index));
+            }
+            // istore..astore with an operand, then istore_0..astore_3.
+            0x36..=0x3a => {
+                let value = machine.pop();
+                machine.store_local(operand(1) as usize, value);
+            }
+            0x3b..=0x4e => {
+                let value = machine.pop();
+                machine.store_local(usize::from((opcode - 0x3b) % 4), value);
+            }
+            WIDE => {
+                let index = usize::from(u16::from_be_bytes([operand(2), operand(3)]));
+                match operand(1) {
+                    0x15..=0x19 => machine.push(self.load(machine, method, index)),
+                    0x36..=0x3a => {
+                        let value = machine.pop();
+                        machine.store_local(index, value);
+                    }
+                    _ => return ApplyOutcome::NotHandled,
+                }
+            }
+            opcodes::GETFIELD | opcodes::GETSTATIC => {
+                let InstructionKind::FieldAccess(field) = &self.instruction.kind else {
+                    return ApplyOutcome::NotHandled;
+                };
+                if opcode == opcodes::GETFIELD {
+                    machine.pop();
+                }
+                let value = TypeDescriptor::from_str(&field.descriptor)
+                    .ok()
+                    .and_then(|ty| slot_for(&ty, TypeOrigin::Field(field.clone())))
+                    .unwrap_or(Slot::Unknown);
+                machine.push(value);
+            }
+            opcodes::NEW => {
+                let Some(class_name) = type_operand() else {
+                    return ApplyOutcome::NotHandled;
+                };
+                machine.push(exact_reference(
+                    class_name,
+                    TypeOrigin::Allocation(self.instruction.offset),
+                ));
+            }
+            opcodes::NEWARRAY | opcodes::ANEWARRAY => {
+                let element = if opcode == opcodes::NEWARRAY {
+                    primitive_array_element(operand(1)).map(str::to_string)
+                } else {
+                    type_operand().map(|name| {
+                        if name.starts_with('[') {
+                            name
+                        } else {
+                            format!("L{name};")
+                        }
+                    })
+                };
+                let Some(element) = element else {
+                    return ApplyOutcome::NotHandled;
+                };
+                machine.pop();
+                machine.push(exact_reference(
+                    format!("[{element}"),
+                    TypeOrigin::Allocation(self.instruction.offset),
+                ));
+            }
+            opcodes::MULTIANEWARRAY => {
+                let Some(class_name) = type_operand() else {
+                    return ApplyOutcome::NotHandled;
+                };
+                machine.pop_n(operand(3) as usize);
+                machine.push(exact_reference(
+                    class_name,
+                    TypeOrigin::Allocation(self.instruction.offset),
+                ));
+            }
+            opcodes::AALOAD => {
+                machine.pop();
+                let element = machine
+                    .pop()
+                    .reference()
+                    .and_then(|array| array.class_name.strip_prefix('['))
+                    .and_then(|element| TypeDescriptor::from_str(element).ok())
+                    .and_then(|ty| slot_for(&ty, TypeOrigin::Unknown))
+                    .unwrap_or(Slot::Unknown);
+                machine.push(element);
+            }
+            opcodes::CHECKCAST => {
+                let Some(class_name) = type_operand() else {
+                    return ApplyOutcome::NotHandled;
+                };
+                let value = machine.pop();
+                let cast = match value {
+                    // An exact or identical type is at least as precise as the cast target.
+                    Slot::Reference(ty) if ty.exact || ty.class_name == class_name => {
+                        Slot::Reference(ty)
+                    }
+                    Slot::Reference(ty) => Slot::Reference(InferredType {
+                        class_name,
+                        signature: None,
+                        exact: false,
+                        origin: ty.origin,
+                    }),
+                    _ => reference(&class_name, TypeOrigin::Cast(self.instruction.offset)),
+                };
+                machine.push(cast);
+            }
+            opcodes::POP2 if machine.peek() == Some(&Slot::Wide) => {
+                machine.pop();
+            }
+            DUP2 if machine.peek() == Some(&Slot::Wide) => machine.push(Slot::Wide),
+            _ => return ApplyOutcome::NotHandled,
+        }
+        ApplyOutcome::Applied
+    }
+}
+
+impl TypeSemanticsHook<'_> {
+    /// Value of local `index`, typed from the local variable tables when the flow does not
+    /// know it.
+    fn load(&self, machine: &StackMachine<Slot>, method: &Method, index: usize) -> Slot {
+        let offset = self.instruction.offset;
+        let in_scope = |start_pc: u32, length: u32, slot: u16| {
+            usize::from(slot) == index && start_pc <= offset && offset < start_pc + length
+        };
+        let mut value = machine.load_local(index);
+        if value == Slot::Unknown
+            && let Some(declared) = method
+                .local_variables
+                .iter()
+                .find(|local| in_scope(local.start_pc, local.length, local.index))
+            && let Ok(ty) = TypeDescriptor::from_str(&declared.descriptor)
+            && let Some(typed) = slot_for(&ty, TypeOrigin::Declared)
+        {
+            value = typed;
+        }
+        if let Slot::Reference(ty) = &mut value
+            && let Some(generic) = method
+                .local_variable_types
+                .iter()
+                .find(|local| in_scope(local.start_pc, local.length, local.index))
+        {
+            ty.signature = Some(generic.signature.clone());
+        }
+        value
+    }
+}
+
+/// Element descriptor of a `newarray` primitive array type code.
+fn primitive_array_element(type_code: u8) -> Option<&'static str> {
+    Some(match type_code {
+        4 => "Z",
+        5 => "C",
+        6 => "F",
+        7 => "D",
+        8 => "B",
+        9 => "S",
+        10 => "I",
+        11 => "J",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InferredType, MethodTypes, TypeOrigin, analyze_types};
+    use crate::ir::{CallSite, Method};
+    use crate::scan::scan_inputs;
+    use crate::test_harness::{JvmTestHarness, Language, SourceFile};
+
+    fn analyze(contents: &str, method_name: &str) -> (Method, MethodTypes) {
+        let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
+        let sources = vec![SourceFile {
+            path: "com/example/ClassA.java".to_string(),
+            contents: contents.to_string(),
+        }];
+        let output = harness
+            .compile(Language::Java, &sources, &[])
+            .expect("compile sources");
+        let class = scan_inputs(&[output.classes_dir().to_path_buf()], &[], None, None)
+            .expect("scan classes")
+            .classes
+            .into_iter()
+            .find(|class| class.name == "com/example/ClassA")
+            .expect("class");
+        let method = class
+            .methods
+            .iter()
+            .find(|method| method.name == method_name)
+            .expect("method")
+            .clone();
+        let types = analyze_types(&class.name, &method).expect("type inference");
+        (method, types)
+    }
+
+    fn call<'a>(method: &'a Method, name: &str) -> &'a CallSite {
+        method
+            .calls
+            .iter()
+            .find(|call| call.name == name)
+            .expect("call")
+    }
+
+    fn receiver<'a>(method: &Method, types: &'a MethodTypes, name: &str) -> &'a InferredType {
+        types.receiver(call(method, name)).expect("receiver type")
+    }
+
+    #[test]
+    fn infers_factory_result_with_generic_signature() {
+        let (method, types) = analyze(
+            r#"
+package com.example;
+import java.util.List;
+public class ClassA {
+    public void methodX() {
+        List<String> varOne = List.of("a");
+        varOne.add("b");
+    }
+}
+"#,
+            "methodX",
+        );
+
+        let list = receiver(&method, &types, "add");
+        assert_eq!(list.class_name, "java/util/List");
+        assert!(!list.exact);
+        assert_eq!(
+            list.signature.as_deref(),
+            Some("Ljava/util/List<Ljava/lang/String;>;")
+        );
+        assert!(matches!(&list.origin, TypeOrigin::Call(factory) if factory.name == "of"));
+        let add = call(&method, "add");
+        let argument = types.argument(add.offset, 1, 0).expect("argument type");
+        assert_eq!(argument.class_name, "java/lang/String");
+        assert_eq!(argument.origin, TypeOrigin::Literal);
+    }
+
+    #[test]
+    fn infers_allocations_parameters_casts_and_handlers() {
+        let (method, types) = analyze(
+            r#"
+package com.example;
+import java.util.ArrayList;
+import java.util.Map;
+public class ClassA {
+    private Map<String, Object> cache;
+
+    public Object methodX(Object varOne, long varTwo) {
+        ArrayList<String> varThree = new ArrayList<>();
+        varThree.clear();
+        ((CharSequence) varOne).length();
+        this.cache.isEmpty();
+        try {
+            return varOne.toString();
+        } catch (IllegalStateException varFour) {
+            return varFour.getMessage();
+        }
+    }
+}
+"#,
+            "methodX",
+        );
+
+        let list = receiver(&method, &types, "clear");
+        assert_eq!(list.class_name, "java/util/ArrayList");
+        assert!(list.exact);
+        assert!(matches!(list.origin, TypeOrigin::Allocation(_)));
+
+        let cast = receiver(&method, &types, "length");
+        assert_eq!(cast.class_name, "java/lang/CharSequence");
+        assert_eq!(cast.origin, TypeOrigin::Parameter(1));
+
+        let this = types
+            .local_type(call(&method, "toString").offset, 0)
+            .expect("this type");
+        assert_eq!(this.class_name, "com/example/ClassA");
+        assert_eq!(this.origin, TypeOrigin::Parameter(0));
+
+        let field = receiver(&method, &types, "isEmpty");
+        assert_eq!(field.class_name, "java/util/Map");
+        assert!(matches!(&field.origin, TypeOrigin::Field(field) if field.name == "cache"));
+
+        let caught = receiver(&method, &types, "getMessage");
+        assert_eq!(caught.class_name, "java/lang/IllegalStateException");
+        assert_eq!(caught.origin, TypeOrigin::CaughtException);
+    }
+
+    #[test]
+    fn forgets_types_that_differ_between_paths() {
+        let (method, types) = analyze(
+            r#"
+package com.example;
+import java.util.ArrayList;
+import java.util.LinkedList;
+import java.util.List;
+public class ClassA {
+    public void methodX(boolean varOne) {
+        List<String> varTwo = varOne ? new ArrayList<>() : new LinkedList<>();
+        varTwo.add("a");
+    }
+}
+"#,
+            "methodX",
+        );
+
+        let list = receiver(&method, &types, "add");
+        assert_eq!(list.class_name, "java/util/List");
+        assert_eq!(list.origin, TypeOrigin::Declared);
+    }
+}
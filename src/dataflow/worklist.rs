@@ -1,7 +1,10 @@
+use std::collections::btree_map::Entry;
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
 
 use anyhow::Result;
 
+use crate::dataflow::budget::MethodMeter;
+use crate::dataflow::lattice::{AbstractState, AbstractValue};
 use crate::ir::{BasicBlock, Instruction, Method};
 
 /// Program-point state tracked by the worklist engine.
@@ -56,6 +59,11 @@ impl<S, F> BlockEndStep<S, F> {
         self.findings.push(finding);
         self
     }
+
+    pub(crate) fn with_next_state(mut self, state: S) -> Self {
+        self.next_states.push(state);
+        self
+    }
 }
 
 impl<S, F> BlockEndStep<S, F>
@@ -104,18 +112,252 @@ pub(crate) trait WorklistSemantics {
     }
 }
 
+/// Program point on the path that led to a finding.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct TraceStep {
+    pub(crate) block_start: u32,
+    pub(crate) offset: u32,
+}
+
+/// Bare program point, for analyses that run their own solver but record paths with the
+/// shared [`Tracer`].
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) struct ProgramPoint {
+    block_start: u32,
+    instruction_index: usize,
+}
+
+impl ProgramPoint {
+    pub(crate) fn new(block_start: u32, instruction_index: usize) -> Self {
+        Self {
+            block_start,
+            instruction_index,
+        }
+    }
+}
+
+impl WorklistState for ProgramPoint {
+    fn block_start(&self) -> u32 {
+        self.block_start
+    }
+
+    fn instruction_index(&self) -> usize {
+        self.instruction_index
+    }
+
+    fn set_position(&mut self, block_start: u32, instruction_index: usize) {
+        *self = Self::new(block_start, instruction_index);
+    }
+}
+
+/// Finding paired with the block/offset trail through the CFG that produced it.
+///
+/// The trail starts at the initial state, records every block entered along the way, and
+/// ends at the instruction (or block end) where the finding was emitted.
+#[derive(Clone, Debug)]
+pub(crate) struct TracedFinding<F> {
+    pub(crate) finding: F,
+    pub(crate) trace: Vec<TraceStep>,
+}
+
+/// Merges at one program point before joins are replaced by widening.
+const WIDENING_DELAY: usize = 3;
+
 /// Deterministic intraprocedural worklist runner for bytecode dataflow analyses.
+///
+/// Runs under the [`crate::dataflow::budget`] of the current rule: a method that exceeds the
+/// step budget, or is reached after the rule timed out, yields no findings and is reported
+/// by the engine instead.
 pub(crate) fn analyze_method<S>(method: &Method, semantics: &S) -> Result<Vec<S::Finding>>
 where
     S: WorklistSemantics,
 {
+    let findings = run_worklist(method, semantics, false)?;
+    Ok(findings.into_iter().map(|traced| traced.finding).collect())
+}
+
+/// Same as [`analyze_method`], additionally recording the path that reached each finding.
+///
+/// Each state remembers the state it was first reached from, so the trail is the
+/// breadth-first (shortest) path to the emitting state.
+pub(crate) fn analyze_method_with_trace<S>(
+    method: &Method,
+    semantics: &S,
+) -> Result<Vec<TracedFinding<S::Finding>>>
+where
+    S: WorklistSemantics,
+{
+    run_worklist(method, semantics, true)
+}
+
+/// Monotone dataflow solve over the same semantics as [`analyze_method`].
+///
+/// Instead of exploring paths, keeps one state per program point and joins states that meet
+/// there, switching to widening once a point has been merged [`WIDENING_DELAY`] times, so the
+/// solve terminates whenever widening does. Findings are collected afterwards by replaying
+/// each reached block once from its fixed entry state; findings from intermediate states are
+/// never reported. Budget handling matches [`analyze_method`].
+pub(crate) fn analyze_method_fixpoint<S>(method: &Method, semantics: &S) -> Result<Vec<S::Finding>>
+where
+    S: WorklistSemantics,
+    S::State: AbstractState,
+{
+    let Some(mut meter) = MethodMeter::start(method) else {
+        return Ok(Vec::new());
+    };
+    let graph = MethodGraph::new(method);
+    let mut solver = FixpointSolver::default();
+
+    for state in semantics.initial_states(method) {
+        solver.merge(semantics, state);
+    }
+    while let Some(position) = solver.pending.pop_first() {
+        let state = solver.entries[&position].clone();
+        let Some(run) = run_block(method, &graph, semantics, state, Some(&mut meter))? else {
+            return Ok(Vec::new());
+        };
+        for next in run.next_states {
+            solver.merge(semantics, next);
+        }
+    }
+
+    let mut findings = Vec::new();
+    for state in solver.entries.into_values() {
+        if let Some(run) = run_block(method, &graph, semantics, state, None)? {
+            findings.extend(run.findings);
+        }
+    }
+    Ok(findings)
+}
+
+/// Entry states and pending program points of a fixpoint solve, keyed by
+/// `(block_start, instruction_index)`.
+struct FixpointSolver<S> {
+    entries: BTreeMap<(u32, usize), S>,
+    merges: BTreeMap<(u32, usize), usize>,
+    pending: BTreeSet<(u32, usize)>,
+}
+
+impl<S> Default for FixpointSolver<S> {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            merges: BTreeMap::new(),
+            pending: BTreeSet::new(),
+        }
+    }
+}
+
+impl<S> FixpointSolver<S>
+where
+    S: AbstractState,
+{
+    /// Fold `state` into the entry state at its position, scheduling the position again
+    /// when the entry state grew.
+    fn merge<W>(&mut self, semantics: &W, mut state: S)
+    where
+        W: WorklistSemantics<State = S>,
+    {
+        semantics.canonicalize_state(&mut state);
+        let position = (state.block_start(), state.instruction_index());
+        let mut entry = match self.entries.entry(position) {
+            Entry::Vacant(vacant) => {
+                vacant.insert(state);
+                self.pending.insert(position);
+                return;
+            }
+            Entry::Occupied(occupied) => occupied,
+        };
+        let merges = self.merges.entry(position).or_default();
+        *merges += 1;
+        let current = entry.get().value();
+        let merged = if *merges > WIDENING_DELAY {
+            current.widen(state.value())
+        } else {
+            current.join(state.value())
+        };
+        if merged != *current {
+            let entry_state = entry.get_mut();
+            entry_state.set_value(merged);
+            semantics.canonicalize_state(entry_state);
+            self.pending.insert(position);
+        }
+    }
+}
+
+/// Findings and successor states produced by running one block to its end.
+struct BlockRun<S, F> {
+    findings: Vec<F>,
+    next_states: Vec<S>,
+}
+
+/// Run the block containing `state` from its position to the end; `None` when `meter` ran
+/// out of budget.
+fn run_block<S>(
+    method: &Method,
+    graph: &MethodGraph<'_>,
+    semantics: &S,
+    mut state: S::State,
+    mut meter: Option<&mut MethodMeter<'_>>,
+) -> Result<Option<BlockRun<S::State, S::Finding>>>
+where
+    S: WorklistSemantics,
+{
+    let mut findings = Vec::new();
+    let Some(block) = graph.blocks.get(&state.block_start()) else {
+        return Ok(Some(BlockRun {
+            findings,
+            next_states: Vec::new(),
+        }));
+    };
+    for index in state.instruction_index()..block.instructions.len() {
+        if let Some(meter) = meter.as_deref_mut()
+            && meter.step().is_some()
+        {
+            return Ok(None);
+        }
+        state.set_position(block.start_offset, index + 1);
+        let step =
+            semantics.transfer_instruction(method, &block.instructions[index], &mut state)?;
+        findings.extend(step.findings);
+        if step.terminate_path {
+            return Ok(Some(BlockRun {
+                findings,
+                next_states: Vec::new(),
+            }));
+        }
+        semantics.canonicalize_state(&mut state);
+    }
+
+    let end_step =
+        semantics.on_block_end(method, &state, graph.successors_for(block.start_offset))?;
+    findings.extend(end_step.findings);
+    Ok(Some(BlockRun {
+        findings,
+        next_states: end_step.next_states,
+    }))
+}
+
+fn run_worklist<S>(
+    method: &Method,
+    semantics: &S,
+    record_trace: bool,
+) -> Result<Vec<TracedFinding<S::Finding>>>
+where
+    S: WorklistSemantics,
+{
+    let Some(mut meter) = MethodMeter::start(method) else {
+        return Ok(Vec::new());
+    };
     let graph = MethodGraph::new(method);
     let mut queue = VecDeque::new();
     let mut visited = BTreeSet::new();
+    let mut tracer = Tracer::new(record_trace);
     let mut findings = Vec::new();
 
     for mut state in semantics.initial_states(method) {
         semantics.canonicalize_state(&mut state);
+        tracer.record(None, &state);
         queue.push_back(state);
     }
 
@@ -124,6 +366,9 @@ where
         if !visited.insert(state.clone()) {
             continue;
         }
+        if meter.step().is_some() {
+            return Ok(Vec::new());
+        }
 
         let Some(block) = graph.blocks.get(&state.block_start()) else {
             continue;
@@ -135,7 +380,16 @@ where
                 &state,
                 graph.successors_for(state.block_start()),
             )?;
-            enqueue_block_end_step(semantics, end_step, &mut queue, &mut findings);
+            let trace = tracer.trace(&graph, &state, None);
+            enqueue_block_end_step(
+                semantics,
+                end_step,
+                &state,
+                trace,
+                &mut tracer,
+                &mut queue,
+                &mut findings,
+            );
             continue;
         }
 
@@ -144,7 +398,10 @@ where
         next_state.set_position(state.block_start(), state.instruction_index() + 1);
 
         let step = semantics.transfer_instruction(method, instruction, &mut next_state)?;
-        findings.extend(step.findings);
+        if !step.findings.is_empty() {
+            let trace = tracer.trace(&graph, &state, None);
+            push_traced(&mut findings, step.findings, &trace);
+        }
         if step.terminate_path {
             continue;
         }
@@ -154,6 +411,7 @@ where
             continue;
         };
         if next_state.instruction_index() < next_block.instructions.len() {
+            tracer.record(Some(&state), &next_state);
             queue.push_back(next_state);
             continue;
         }
@@ -163,12 +421,94 @@ where
             &next_state,
             graph.successors_for(next_state.block_start()),
         )?;
-        enqueue_block_end_step(semantics, end_step, &mut queue, &mut findings);
+        let trace = tracer.trace(&graph, &state, Some(&next_state));
+        enqueue_block_end_step(
+            semantics,
+            end_step,
+            &state,
+            trace,
+            &mut tracer,
+            &mut queue,
+            &mut findings,
+        );
     }
 
     Ok(findings)
 }
 
+/// Parent links between states, kept only when traces are requested.
+pub(crate) struct Tracer<S> {
+    enabled: bool,
+    parents: BTreeMap<S, Option<S>>,
+}
+
+impl<S> Tracer<S>
+where
+    S: WorklistState,
+{
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            parents: BTreeMap::new(),
+        }
+    }
+
+    /// Remember the first state `state` was reached from; later arrivals keep the first link,
+    /// which also guarantees the links never form a cycle.
+    pub(crate) fn record(&mut self, parent: Option<&S>, state: &S) {
+        if self.enabled && !self.parents.contains_key(state) {
+            self.parents.insert(state.clone(), parent.cloned());
+        }
+    }
+
+    /// Rebuild the trail ending at `state`, optionally extended by a `tail` state that was
+    /// never enqueued (a block end reached directly after a transfer).
+    fn trace(&self, graph: &MethodGraph<'_>, state: &S, tail: Option<&S>) -> Vec<TraceStep> {
+        if !self.enabled {
+            return Vec::new();
+        }
+        let mut chain: Vec<&S> = tail.into_iter().collect();
+        let mut current = Some(state);
+        while let Some(node) = current {
+            chain.push(node);
+            current = self.parents.get(node).and_then(Option::as_ref);
+        }
+        chain.reverse();
+
+        let mut trace: Vec<TraceStep> = Vec::new();
+        let last = chain.len().saturating_sub(1);
+        for (index, node) in chain.into_iter().enumerate() {
+            if index != 0 && index != last && node.instruction_index() != 0 {
+                continue;
+            }
+            let Some(offset) = graph.offset_for(node.block_start(), node.instruction_index())
+            else {
+                continue;
+            };
+            let step = TraceStep {
+                block_start: node.block_start(),
+                offset,
+            };
+            if trace.last() != Some(&step) {
+                trace.push(step);
+            }
+        }
+        trace
+    }
+
+    /// [`Tracer::trace`] for solvers outside this module, which hold no `MethodGraph`.
+    pub(crate) fn trace_in(&self, method: &Method, state: &S, tail: Option<&S>) -> Vec<TraceStep> {
+        self.trace(&MethodGraph::new(method), state, tail)
+    }
+}
+
+fn push_traced<F>(findings: &mut Vec<TracedFinding<F>>, emitted: Vec<F>, trace: &[TraceStep]) {
+    findings.extend(emitted.into_iter().map(|finding| TracedFinding {
+        finding,
+        trace: trace.to_vec(),
+    }));
+}
+
 /// CFG lookup tables used by the worklist loop.
 struct MethodGraph<'a> {
     blocks: BTreeMap<u32, &'a BasicBlock>,
@@ -197,6 +537,17 @@ impl<'a> MethodGraph<'a> {
         Self { blocks, successors }
     }
 
+    /// Offset of the instruction at `instruction_index`, or of the block's last instruction
+    /// when the index points past the end.
+    fn offset_for(&self, block_start: u32, instruction_index: usize) -> Option<u32> {
+        let block = self.blocks.get(&block_start)?;
+        block
+            .instructions
+            .get(instruction_index)
+            .or_else(|| block.instructions.last())
+            .map(|instruction| instruction.offset)
+    }
+
     fn successors_for(&self, block_start: u32) -> &[u32] {
         self.successors
             .get(&block_start)
@@ -208,14 +559,18 @@ impl<'a> MethodGraph<'a> {
 fn enqueue_block_end_step<S>(
     semantics: &S,
     step: BlockEndStep<S::State, S::Finding>,
+    parent: &S::State,
+    trace: Vec<TraceStep>,
+    tracer: &mut Tracer<S::State>,
     queue: &mut VecDeque<S::State>,
-    findings: &mut Vec<S::Finding>,
+    findings: &mut Vec<TracedFinding<S::Finding>>,
 ) where
     S: WorklistSemantics,
 {
-    findings.extend(step.findings);
+    push_traced(findings, step.findings, &trace);
     for mut state in step.next_states {
         semantics.canonicalize_state(&mut state);
+        tracer.record(Some(parent), &state);
         queue.push_back(state);
     }
 }
@@ -223,10 +578,17 @@ fn enqueue_block_end_step<S>(
 #[cfg(test)]
 mod tests {
     use std::cell::Cell;
+    use std::collections::BTreeSet;
+    use std::time::Duration;
 
     use anyhow::Result;
 
-    use super::{BlockEndStep, InstructionStep, WorklistSemantics, WorklistState, analyze_method};
+    use super::{
+        BlockEndStep, InstructionStep, TraceStep, WorklistSemantics, WorklistState, analyze_method,
+        analyze_method_fixpoint, analyze_method_with_trace,
+    };
+    use crate::dataflow::budget::{AnalysisBudget, BudgetLimit, with_rule_budget};
+    use crate::dataflow::lattice::{AbstractState, AbstractValue};
     use crate::ir::{
         BasicBlock, CallSite, ControlFlowGraph, EdgeKind, FlowEdge, Instruction, InstructionKind,
         LineNumber, LocalVariableType, Method, MethodAccess, MethodNullness, Nullness,
@@ -383,10 +745,104 @@ mod tests {
         }
     }
 
+    /// Upper bound on a counter; `None` is top, reached only by widening.
+    #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+    struct CounterBound(Option<u32>);
+
+    impl AbstractValue for CounterBound {
+        fn join(&self, other: &Self) -> Self {
+            match (self.0, other.0) {
+                (Some(left), Some(right)) => CounterBound(Some(left.max(right))),
+                _ => CounterBound(None),
+            }
+        }
+
+        fn widen(&self, other: &Self) -> Self {
+            if self.join(other) == *self {
+                *self
+            } else {
+                CounterBound(None)
+            }
+        }
+    }
+
+    /// State used by fixpoint tests: blocks entered so far plus a counter bound.
+    #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+    struct LatticeState {
+        block_start: u32,
+        instruction_index: usize,
+        value: (BTreeSet<u32>, CounterBound),
+    }
+
+    impl WorklistState for LatticeState {
+        fn block_start(&self) -> u32 {
+            self.block_start
+        }
+
+        fn instruction_index(&self) -> usize {
+            self.instruction_index
+        }
+
+        fn set_position(&mut self, block_start: u32, instruction_index: usize) {
+            self.block_start = block_start;
+            self.instruction_index = instruction_index;
+        }
+    }
+
+    impl AbstractState for LatticeState {
+        type Value = (BTreeSet<u32>, CounterBound);
+
+        fn value(&self) -> &Self::Value {
+            &self.value
+        }
+
+        fn set_value(&mut self, value: Self::Value) {
+            self.value = value;
+        }
+    }
+
+    /// Semantics recording visited blocks and counting every transfer, reporting the state
+    /// seen at instructions listed in `report_offsets`.
+    struct CountingSemantics {
+        report_offsets: Vec<u32>,
+        transfer_calls: Cell<usize>,
+    }
+
+    impl WorklistSemantics for CountingSemantics {
+        type State = LatticeState;
+        type Finding = (u32, BTreeSet<u32>, CounterBound);
+
+        fn initial_states(&self, _method: &Method) -> Vec<Self::State> {
+            vec![LatticeState {
+                block_start: 0,
+                instruction_index: 0,
+                value: (BTreeSet::new(), CounterBound(Some(0))),
+            }]
+        }
+
+        fn transfer_instruction(
+            &self,
+            _method: &Method,
+            instruction: &Instruction,
+            state: &mut Self::State,
+        ) -> Result<InstructionStep<Self::Finding>> {
+            self.transfer_calls.set(self.transfer_calls.get() + 1);
+            let (blocks, counter) = &mut state.value;
+            blocks.insert(state.block_start);
+            counter.0 = counter.0.map(|count| count + 1);
+            let step = InstructionStep::continue_path();
+            if self.report_offsets.contains(&instruction.offset) {
+                return Ok(step.with_finding((instruction.offset, blocks.clone(), *counter)));
+            }
+            Ok(step)
+        }
+    }
+
     fn build_method(blocks: Vec<BasicBlock>, edges: Vec<FlowEdge>) -> Method {
         Method {
-            name: "MethodX".to_string(),
-            descriptor: "()V".to_string(),
+            owner: "com/example/ClassX".into(),
+            name: "MethodX".into(),
+            descriptor: "()V".into(),
             signature: None,
             access: MethodAccess {
                 is_public: false,
@@ -395,6 +851,8 @@ mod tests {
                 is_abstract: false,
                 is_synthetic: false,
                 is_bridge: false,
+                is_private: false,
+                is_final: false,
             },
             nullness: MethodNullness {
                 return_nullness: Nullness::Unknown,
@@ -403,12 +861,17 @@ mod tests {
             type_use: None,
             bytecode: Vec::new(),
             line_numbers: Vec::<LineNumber>::new(),
-            cfg: ControlFlowGraph { blocks, edges },
+            cfg: ControlFlowGraph {
+                blocks,
+                edges,
+                duplicates: Default::default(),
+            },
             calls: Vec::<CallSite>::new(),
             string_literals: Vec::new(),
             exception_handlers: Vec::new(),
             local_variables: Vec::new(),
             local_variable_types: Vec::<LocalVariableType>::new(),
+            flow_structure: Default::default(),
         }
     }
 
@@ -522,4 +985,225 @@ mod tests {
 
         assert!(findings.contains(&20), "expected handler block traversal");
     }
+
+    #[test]
+    fn records_block_trail_for_findings() {
+        let method = build_method(
+            vec![block(0, &[0, 1]), block(10, &[10]), block(20, &[20, 21])],
+            vec![
+                FlowEdge {
+                    from: 0,
+                    to: 10,
+                    kind: EdgeKind::FallThrough,
+                },
+                FlowEdge {
+                    from: 10,
+                    to: 20,
+                    kind: EdgeKind::Branch,
+                },
+            ],
+        );
+
+        let findings =
+            analyze_method_with_trace(&method, &ExceptionEdgeSemantics).expect("worklist run");
+        let traced = findings
+            .iter()
+            .find(|traced| traced.finding == 21)
+            .expect("finding at offset 21");
+
+        assert_eq!(
+            traced.trace,
+            vec![
+                TraceStep {
+                    block_start: 0,
+                    offset: 0,
+                },
+                TraceStep {
+                    block_start: 10,
+                    offset: 10,
+                },
+                TraceStep {
+                    block_start: 20,
+                    offset: 20,
+                },
+                TraceStep {
+                    block_start: 20,
+                    offset: 21,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn drops_findings_of_method_over_step_budget() {
+        let method = build_method(vec![block(0, &[0, 1, 2])], Vec::new());
+        let budget = AnalysisBudget {
+            max_method_steps: Some(2),
+            rule_timeout: None,
+        };
+
+        let (findings, exceeded) = with_rule_budget(budget, || {
+            analyze_method(&method, &ExceptionEdgeSemantics).expect("worklist run")
+        });
+
+        assert!(findings.is_empty());
+        assert_eq!(exceeded.len(), 1);
+        assert_eq!(exceeded[0].class_name, "com/example/ClassX");
+        assert_eq!(exceeded[0].method_name, "MethodX");
+        assert_eq!(exceeded[0].descriptor, "()V");
+        assert_eq!(exceeded[0].limit, BudgetLimit::MethodSteps(2));
+    }
+
+    #[test]
+    fn default_budget_does_not_cap_method_steps() {
+        let offsets: Vec<u32> = (0..64).collect();
+        let method = build_method(vec![block(0, &offsets)], Vec::new());
+
+        let (_, exceeded) = with_rule_budget(AnalysisBudget::default(), || {
+            analyze_method(&method, &ExceptionEdgeSemantics).expect("worklist run")
+        });
+
+        assert!(exceeded.is_empty());
+    }
+
+    #[test]
+    fn skips_methods_after_rule_timeout() {
+        let first = build_method(vec![block(0, &[0])], Vec::new());
+        let mut second = build_method(vec![block(0, &[0])], Vec::new());
+        second.name = "MethodY".into();
+        let budget = AnalysisBudget {
+            rule_timeout: Some(Duration::ZERO),
+            ..AnalysisBudget::default()
+        };
+
+        let (findings, exceeded) = with_rule_budget(budget, || {
+            [&first, &second].map(|method| {
+                analyze_method(method, &ExceptionEdgeSemantics).expect("worklist run")
+            })
+        });
+
+        assert!(findings.iter().all(Vec::is_empty));
+        assert_eq!(
+            exceeded.len(),
+            1,
+            "only the first skipped method is reported"
+        );
+        assert_eq!(exceeded[0].method_name, first.name);
+        assert_eq!(exceeded[0].limit, BudgetLimit::RuleTimeout(Duration::ZERO));
+    }
+
+    #[test]
+    fn default_budget_applies_outside_rule_scope() {
+        let method = build_method(vec![block(0, &[0, 1])], Vec::new());
+
+        let findings = analyze_method(&method, &ExceptionEdgeSemantics).expect("worklist run");
+
+        assert_eq!(findings, vec![0, 1]);
+    }
+
+    #[test]
+    fn fixpoint_joins_states_at_merge() {
+        let method = build_method(
+            vec![
+                block(0, &[0]),
+                block(1, &[10]),
+                block(2, &[20, 21]),
+                block(3, &[30]),
+            ],
+            vec![
+                FlowEdge {
+                    from: 0,
+                    to: 1,
+                    kind: EdgeKind::Branch,
+                },
+                FlowEdge {
+                    from: 0,
+                    to: 2,
+                    kind: EdgeKind::FallThrough,
+                },
+                FlowEdge {
+                    from: 1,
+                    to: 3,
+                    kind: EdgeKind::FallThrough,
+                },
+                FlowEdge {
+                    from: 2,
+                    to: 3,
+                    kind: EdgeKind::FallThrough,
+                },
+            ],
+        );
+        let semantics = CountingSemantics {
+            report_offsets: vec![30],
+            transfer_calls: Cell::new(0),
+        };
+
+        let findings = analyze_method_fixpoint(&method, &semantics).expect("fixpoint run");
+
+        assert_eq!(
+            findings,
+            vec![(30, BTreeSet::from([0, 1, 2, 3]), CounterBound(Some(4)))]
+        );
+    }
+
+    #[test]
+    fn fixpoint_widens_unbounded_loop() {
+        let method = build_method(
+            vec![block(0, &[0]), block(10, &[10]), block(20, &[20])],
+            vec![
+                FlowEdge {
+                    from: 0,
+                    to: 10,
+                    kind: EdgeKind::FallThrough,
+                },
+                FlowEdge {
+                    from: 10,
+                    to: 10,
+                    kind: EdgeKind::Branch,
+                },
+                FlowEdge {
+                    from: 10,
+                    to: 20,
+                    kind: EdgeKind::FallThrough,
+                },
+            ],
+        );
+        let semantics = CountingSemantics {
+            report_offsets: vec![10, 20],
+            transfer_calls: Cell::new(0),
+        };
+
+        let findings = analyze_method_fixpoint(&method, &semantics).expect("fixpoint run");
+
+        assert_eq!(
+            findings,
+            vec![
+                (10, BTreeSet::from([0, 10]), CounterBound(None)),
+                (20, BTreeSet::from([0, 10, 20]), CounterBound(None)),
+            ],
+            "only the fixed states are reported"
+        );
+        assert!(semantics.transfer_calls.get() < 20);
+    }
+
+    #[test]
+    fn fixpoint_respects_step_budget() {
+        let method = build_method(vec![block(0, &[0, 1, 2])], Vec::new());
+        let semantics = CountingSemantics {
+            report_offsets: vec![2],
+            transfer_calls: Cell::new(0),
+        };
+        let budget = AnalysisBudget {
+            max_method_steps: Some(2),
+            rule_timeout: None,
+        };
+
+        let (findings, exceeded) = with_rule_budget(budget, || {
+            analyze_method_fixpoint(&method, &semantics).expect("fixpoint run")
+        });
+
+        assert!(findings.is_empty());
+        assert_eq!(exceeded.len(), 1);
+        assert_eq!(exceeded[0].limit, BudgetLimit::MethodSteps(2));
+    }
 }
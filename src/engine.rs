@@ -1,27 +1,158 @@
+use std::any::Any;
+use std::borrow::Cow;
+use std::cell::Cell;
 use std::collections::{BTreeMap, BTreeSet};
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
 use std::time::Instant;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use opentelemetry::Context as OtelContext;
 use opentelemetry::KeyValue;
 use rayon::prelude::*;
+use serde_json::json;
 use serde_sarif::sarif::Artifact;
-use serde_sarif::sarif::{MultiformatMessageString, ReportingDescriptor, Result as SarifResult};
-
-use crate::ir::Class;
-use crate::rules::{Rule, RuleMetadata};
+use serde_sarif::sarif::{
+    Location, MultiformatMessageString, Notification, PropertyBag, ReportingDescriptor,
+    ReportingDescriptorReference, Result as SarifResult,
+};
+
+use tracing::{error, warn};
+
+use crate::cache::{AnalysisCache, CacheKind, ContentHash, KeyHasher};
+use crate::class_filter::ClassFilter;
+use crate::dataflow::budget::{
+    AnalysisBudget, BudgetExceeded, BudgetLimit, outside_rule_budget, with_rule_budget,
+};
+use crate::dataflow::constants::{MethodConstants, analyze_constants};
+use crate::dataflow::summaries::MethodSummaries;
+use crate::dataflow::types::{MethodTypes, analyze_types};
+use crate::fix::{has_pending_fix, resolve_pending_fix};
+use crate::ir::{Class, Method};
+use crate::rule_options::{EMPTY_RULE_OPTIONS, RuleOptions};
+use crate::rules::{
+    Rule, RuleMetadata, add_related_locations, class_location, location_class_name,
+    related_location, result_message, rule_spec,
+};
+use crate::scan::read_method_bodies;
+use crate::symbol::Symbol;
+use crate::taxonomy::{cwe_relationships, cwe_tag};
 use crate::telemetry::{Telemetry, with_span};
 
+/// Per-run state of the rule running on a thread, read by the `AnalysisContext` accessors.
+///
+/// The engine runs each rule to completion on one thread and rules do not read the context
+/// from threads of their own, so the scope set by `Engine::run_rule` is the one its accessor
+/// calls see. `run_rule` asserts that scopes never nest.
+#[derive(Clone, Copy)]
+struct RuleScope {
+    /// Index into `AnalysisContext::target_subsets` restricting the analysis target classes.
+    subset: Option<usize>,
+    /// Whether the rule read facts spanning several classes, which keeps its results out of
+    /// the per-class cache.
+    whole_program: bool,
+}
+
+thread_local! {
+    static RULE_SCOPE: Cell<Option<RuleScope>> = const { Cell::new(None) };
+}
+
+/// Record that the rule running on this thread depends on more than its target classes.
+fn mark_whole_program() {
+    RULE_SCOPE.set(RULE_SCOPE.get().map(|scope| RuleScope {
+        whole_program: true,
+        ..scope
+    }));
+}
+
+/// SARIF `uriBaseId` for source locations resolved through `--source-root`.
+pub(crate) const SRCROOT_URI_BASE_ID: &str = "%SRCROOT%";
+
+/// Source directory used to map analysis target classes to their source files.
+#[derive(Clone, Debug)]
+pub(crate) struct SourceRoot {
+    path: PathBuf,
+    /// URI prefix for files under this root: relative to `%SRCROOT%` when the root lives
+    /// below it, otherwise an absolute `file://` URI.
+    uri_prefix: String,
+}
+
+impl SourceRoot {
+    pub(crate) fn new(path: &Path, srcroot: &Path) -> Self {
+        let uri_prefix = match path.strip_prefix(srcroot) {
+            Ok(relative) if relative.as_os_str().is_empty() => String::new(),
+            Ok(relative) => format!("{}/", path_to_uri_path(relative)),
+            Err(_) => directory_uri(path),
+        };
+        Self {
+            path: path.to_path_buf(),
+            uri_prefix,
+        }
+    }
+}
+
+/// `file://` URI for a directory, with the trailing slash SARIF requires for base URIs.
+pub(crate) fn directory_uri(path: &Path) -> String {
+    format!("file://{}/", path_to_uri_path(path))
+}
+
+/// Resolve a location against `%SRCROOT%` when its URI is one of `source_root_uris`, the
+/// relative URIs produced by the source root mapping.
+fn tag_source_root_location(location: &mut Location, source_root_uris: &BTreeSet<String>) {
+    let artifact_location = location
+        .physical_location
+        .as_mut()
+        .and_then(|physical| physical.artifact_location.as_mut());
+    if let Some(artifact_location) = artifact_location
+        && artifact_location.uri_base_id.is_none()
+        && artifact_location
+            .uri
+            .as_ref()
+            .is_some_and(|uri| source_root_uris.contains(uri))
+    {
+        artifact_location.uri_base_id = Some(SRCROOT_URI_BASE_ID.to_string());
+    }
+}
+
+fn path_to_uri_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
 /// Inputs shared by analysis rules.
 pub(crate) struct AnalysisContext {
     analysis_target_classes: Vec<Class>,
     dependency_classes: Vec<Class>,
     class_artifact_uri_cache: BTreeMap<i64, BTreeMap<String, String>>,
+    /// Classes whose locations were mapped to a source root.
+    source_mapped_classes: BTreeMap<String, SourceMappedClass>,
+    /// URIs of the mapped source files that are relative to `%SRCROOT%`.
+    source_root_uris: BTreeSet<String>,
+    /// Source files read to resolve suggested fixes, keyed by class name; `None` when the
+    /// file could not be read.
+    source_texts: Mutex<BTreeMap<String, Option<Arc<str>>>>,
     telemetry: Option<Arc<Telemetry>>,
     has_slf4j: bool,
     has_log4j2: bool,
+    /// Validated `ruleOptions`, keyed by rule ID.
+    rule_options: BTreeMap<String, RuleOptions>,
+    /// Constant propagation results shared by all rules, keyed by `Method` address.
+    method_constants: Mutex<BTreeMap<usize, Arc<MethodConstants>>>,
+    /// Type inference results shared by all rules, keyed by `Method` address.
+    method_types: Mutex<BTreeMap<usize, Arc<MethodTypes>>>,
+    /// Interprocedural summaries of the analysis target methods, computed on first use.
+    method_summaries: OnceLock<MethodSummaries>,
+    /// Copies of the analysis target classes that cached rules still have to analyze.
+    target_subsets: Vec<Vec<Class>>,
+}
+
+/// Compiled class file and source file of a class mapped through `--source-root`.
+#[derive(Clone, Debug)]
+struct SourceMappedClass {
+    class_uri: String,
+    source_uri: String,
+    source_path: PathBuf,
 }
 
 /// Timing breakdown for context construction.
@@ -36,6 +167,8 @@ pub(crate) struct ContextTimings {
 /// Analysis engine that executes configured rules.
 pub(crate) struct Engine {
     rules: Vec<Box<dyn Rule + Sync>>,
+    budget: AnalysisBudget,
+    cache: Option<Arc<AnalysisCache>>,
 }
 
 impl Engine {
@@ -59,45 +192,103 @@ impl Engine {
             rules.retain(|rule| allowed.contains(rule.metadata().id));
         }
         rules.sort_by_key(|a| a.metadata().id);
-        Ok(Self { rules })
+        Ok(Self {
+            rules,
+            budget: AnalysisBudget::default(),
+            cache: None,
+        })
     }
 
-    pub(crate) fn analyze(&self, context: AnalysisContext) -> Result<EngineOutput> {
+    /// Limits applied to the worklist analyses of every rule.
+    pub(crate) fn with_analysis_budget(mut self, budget: AnalysisBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Reuse per-class rule results from `cache` for classes whose content, dependencies and
+    /// location did not change; see [`Engine::analyze`].
+    pub(crate) fn with_cache(mut self, cache: Arc<AnalysisCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Run every rule against `context`. A rule that fails or panics contributes no results
+    /// and is reported as a tool execution notification; the other rules still run. Methods
+    /// whose analysis exceeded the budget are reported as warning notifications.
+    ///
+    /// With a cache, each rule only analyzes the target classes without cached results, and
+    /// the output matches an uncached run. Rules reading facts that span several classes are
+    /// always run on every class.
+    pub(crate) fn analyze(&self, mut context: AnalysisContext) -> Result<EngineOutput> {
+        let plans = match self.cache.as_deref() {
+            Some(cache) => self.plan_cached_rules(cache, &mut context),
+            None => self.rules.iter().map(|_| None).collect(),
+        };
         let parent_context = OtelContext::current();
         let mut rule_outputs: Vec<RuleOutput> = self
             .rules
             .par_iter()
-            .map(|rule| {
+            .zip(plans.par_iter())
+            .map(|(rule, plan)| {
                 let metadata = rule.metadata();
-                let rule_span_attributes = [KeyValue::new("inspequte.rule_id", metadata.id)];
-                let mut rule_results = match context.telemetry() {
-                    Some(telemetry) => telemetry.in_span_with_parent(
-                        &format!("rule:{}", metadata.id),
-                        &rule_span_attributes,
-                        &parent_context,
-                        || rule.run(&context),
-                    )?,
-                    None => rule.run(&context)?,
+                let started_at = Instant::now();
+                let run = match (self.cache.as_deref(), plan) {
+                    (Some(cache), Some(plan)) => {
+                        self.run_cached_rule(rule.as_ref(), &context, &parent_context, cache, plan)
+                    }
+                    _ => self.run_rule(rule.as_ref(), &context, &parent_context, None),
                 };
+                let budget_exceeded = run
+                    .budget_exceeded
+                    .iter()
+                    .map(|exceeded| context.budget_exceeded_message(metadata.id, exceeded))
+                    .collect();
+                let duration_ms = started_at.elapsed().as_millis();
+                let mut rule_results = run.results;
                 for result in &mut rule_results {
-                    if result.rule_id.is_none() {
-                        result.rule_id = Some(metadata.id.to_string());
-                    }
+                    context.link_source_root_locations(result);
                 }
-                Ok(RuleOutput {
+                RuleOutput {
                     id: metadata.id.to_string(),
                     descriptor: rule_descriptor(&metadata),
+                    stats: RuleRunStats {
+                        rule_id: metadata.id.to_string(),
+                        duration_ms,
+                        target_classes: run.target_classes,
+                        target_methods: run.target_methods,
+                        result_count: rule_results.len(),
+                    },
                     results: rule_results,
-                })
+                    failure: run.failure,
+                    budget_exceeded,
+                }
             })
-            .collect::<Result<Vec<_>>>()?;
+            .collect();
 
         rule_outputs.sort_by(|left, right| left.id.cmp(&right.id));
-        let mut rules = Vec::with_capacity(rule_outputs.len());
+        let rules: Vec<ReportingDescriptor> = rule_outputs
+            .iter()
+            .map(|output| output.descriptor.clone())
+            .collect();
         let mut results = Vec::new();
+        let mut rule_stats = Vec::with_capacity(rule_outputs.len());
+        let mut notifications = Vec::new();
         for output in rule_outputs {
-            rules.push(output.descriptor);
+            if let Some(failure) = output.failure {
+                error!("rule {} failed: {failure}", output.id);
+                notifications.push(rule_notification(
+                    &rules,
+                    &output.id,
+                    "error",
+                    format!("Rule {} failed: {failure}", output.id),
+                ));
+            }
+            for message in output.budget_exceeded {
+                warn!("{message}");
+                notifications.push(rule_notification(&rules, &output.id, "warning", message));
+            }
             results.extend(output.results);
+            rule_stats.push(output.stats);
         }
 
         results.sort_by(|left, right| {
@@ -108,7 +299,298 @@ impl Engine {
             left_id.cmp(right_id).then(left_msg.cmp(&right_msg))
         });
 
-        Ok(EngineOutput { rules, results })
+        Ok(EngineOutput {
+            rules,
+            results,
+            rule_stats,
+            notifications,
+        })
+    }
+
+    /// Run `rule` once, over the target classes in `context.target_subsets[subset]` when
+    /// `subset` is set.
+    fn run_rule(
+        &self,
+        rule: &(dyn Rule + Sync),
+        context: &AnalysisContext,
+        parent_context: &OtelContext,
+        subset: Option<usize>,
+    ) -> RuleRun {
+        let metadata = rule.metadata();
+        let rule_span_attributes = [KeyValue::new("inspequte.rule_id", metadata.id)];
+        let outer = RULE_SCOPE.replace(Some(RuleScope {
+            subset,
+            whole_program: false,
+        }));
+        assert!(
+            outer.is_none(),
+            "rule {} started while another rule runs on this thread",
+            metadata.id
+        );
+        let (outcome, budget_exceeded) = with_rule_budget(self.budget, || {
+            panic::catch_unwind(AssertUnwindSafe(|| match context.telemetry() {
+                Some(telemetry) => telemetry.in_span_with_parent(
+                    &format!("rule:{}", metadata.id),
+                    &rule_span_attributes,
+                    parent_context,
+                    || rule.run(context),
+                ),
+                None => rule.run(context),
+            }))
+        });
+        let whole_program = RULE_SCOPE.take().is_some_and(|scope| scope.whole_program);
+        let classes = match subset {
+            Some(subset) => context.target_subsets[subset].as_slice(),
+            None => context.analysis_target_classes.as_slice(),
+        };
+        let target_classes = classes.len();
+        let target_methods = classes.iter().map(|class| class.methods.len()).sum();
+        let (mut results, failure) = match outcome {
+            Ok(Ok(results)) => (results, None),
+            Ok(Err(error)) => (Vec::new(), Some(format!("{error:#}"))),
+            Err(payload) => (
+                Vec::new(),
+                Some(format!("panicked: {}", panic_message(payload.as_ref()))),
+            ),
+        };
+        for result in &mut results {
+            if result.rule_id.is_none() {
+                result.rule_id = Some(metadata.id.to_string());
+            }
+        }
+        RuleRun {
+            results,
+            failure,
+            budget_exceeded,
+            whole_program,
+            target_classes,
+            target_methods,
+        }
+    }
+
+    /// Run `rule` on the classes `plan` has no cached results for, store their results, and
+    /// merge them with the cached ones in analysis target order.
+    fn run_cached_rule(
+        &self,
+        rule: &(dyn Rule + Sync),
+        context: &AnalysisContext,
+        parent_context: &OtelContext,
+        cache: &AnalysisCache,
+        plan: &RuleCachePlan,
+    ) -> RuleRun {
+        let stale: Vec<usize> = (0..plan.cached.len())
+            .filter(|index| plan.cached[*index].is_none())
+            .collect();
+        if stale.is_empty() {
+            return RuleRun {
+                results: plan.cached.iter().flatten().flatten().cloned().collect(),
+                failure: None,
+                budget_exceeded: Vec::new(),
+                whole_program: false,
+                target_classes: 0,
+                target_methods: 0,
+            };
+        }
+        let run = self.run_rule(rule, context, parent_context, plan.subset);
+        if run.failure.is_some() {
+            return run;
+        }
+        let run_classes = match plan.subset {
+            Some(subset) => context.target_subsets[subset].as_slice(),
+            None => context.analysis_target_classes.as_slice(),
+        };
+        let grouped = match run.whole_program {
+            true => None,
+            false => group_results_by_class(&run.results, run_classes),
+        };
+        let Some(grouped) = grouped else {
+            cache.store(CacheKind::RuleCacheability, plan.rule_key, &false);
+            return match plan.subset {
+                Some(_) => self.run_rule(rule, context, parent_context, None),
+                None => run,
+            };
+        };
+        // After a timeout every later method is skipped without a record, so no class of the
+        // run is known to be complete.
+        let timed_out = run
+            .budget_exceeded
+            .iter()
+            .any(|exceeded| matches!(exceeded.limit, BudgetLimit::RuleTimeout(_)));
+        let cut_short: BTreeSet<Symbol> = run
+            .budget_exceeded
+            .iter()
+            .map(|exceeded| exceeded.class_name)
+            .collect();
+        let mut fresh = vec![None; plan.cached.len()];
+        for (run_index, results) in grouped.into_iter().enumerate() {
+            let target_index = stale[run_index];
+            if !timed_out && !cut_short.contains(&run_classes[run_index].name) {
+                cache.store(
+                    CacheKind::RuleResults,
+                    plan.class_keys[target_index],
+                    &results,
+                );
+            }
+            fresh[target_index] = Some(results);
+        }
+        let results = plan
+            .cached
+            .iter()
+            .zip(fresh)
+            .flat_map(|(cached, fresh)| fresh.or_else(|| cached.clone()).unwrap_or_default())
+            .collect();
+        RuleRun { results, ..run }
+    }
+
+    /// Look up the cached results of every rule and stage the target classes each rule still
+    /// has to analyze in `context.target_subsets`.
+    fn plan_cached_rules(
+        &self,
+        cache: &AnalysisCache,
+        context: &mut AnalysisContext,
+    ) -> Vec<Option<RuleCachePlan>> {
+        let class_hashes = cache.class_hashes();
+        let fingerprints: Option<Vec<ContentHash>> = context
+            .analysis_target_classes
+            .iter()
+            .map(|class| context.class_fingerprint(class, &class_hashes))
+            .collect();
+        let Some(fingerprints) = fingerprints else {
+            return self.rules.iter().map(|_| None).collect();
+        };
+        let mut plans: Vec<Option<RuleCachePlan>> = self
+            .rules
+            .par_iter()
+            .map(|rule| {
+                let rule_key = context.rule_cache_key(&rule.metadata(), self.budget);
+                if cache.load::<bool>(CacheKind::RuleCacheability, rule_key) == Some(false) {
+                    return None;
+                }
+                let class_keys: Vec<ContentHash> = fingerprints
+                    .iter()
+                    .map(|fingerprint| {
+                        let mut key = KeyHasher::new(CacheKind::RuleResults);
+                        key.write_hash(rule_key);
+                        key.write_hash(*fingerprint);
+                        key.finish()
+                    })
+                    .collect();
+                let cached = class_keys
+                    .iter()
+                    .map(|key| cache.load(CacheKind::RuleResults, *key))
+                    .collect();
+                Some(RuleCachePlan {
+                    rule_key,
+                    class_keys,
+                    cached,
+                    subset: None,
+                })
+            })
+            .collect();
+
+        // Rules with the same stale classes share one copy of them.
+        let mut subsets: BTreeMap<Vec<usize>, usize> = BTreeMap::new();
+        for plan in plans.iter_mut().flatten() {
+            let stale: Vec<usize> = (0..plan.cached.len())
+                .filter(|index| plan.cached[*index].is_none())
+                .collect();
+            if stale.is_empty() || stale.len() == plan.cached.len() {
+                continue;
+            }
+            let next = subsets.len();
+            let subset = *subsets.entry(stale.clone()).or_insert(next);
+            if subset == next {
+                context.target_subsets.push(
+                    stale
+                        .iter()
+                        .map(|index| context.analysis_target_classes[*index].clone())
+                        .collect(),
+                );
+            }
+            plan.subset = Some(subset);
+        }
+        plans
+    }
+}
+
+/// Split the results of one rule run by the class their first location names, in `classes`
+/// order. Returns `None` when a result names no class of the run or results of different
+/// classes interleave, since then results cannot be cached per class.
+fn group_results_by_class(
+    results: &[SarifResult],
+    classes: &[Class],
+) -> Option<Vec<Vec<SarifResult>>> {
+    let mut indices = BTreeMap::new();
+    for (index, class) in classes.iter().enumerate() {
+        indices.entry(class.name.as_str()).or_insert(index);
+    }
+    let mut grouped = vec![Vec::new(); classes.len()];
+    let mut last = 0;
+    for result in results {
+        let class_name = result
+            .locations
+            .as_ref()
+            .and_then(|locations| locations.first())
+            .and_then(location_class_name)?;
+        let index = *indices.get(class_name)?;
+        if index < last {
+            return None;
+        }
+        last = index;
+        grouped[index].push(result.clone());
+    }
+    Some(grouped)
+}
+
+/// Outcome of running one rule, before source root linking.
+struct RuleRun {
+    results: Vec<SarifResult>,
+    failure: Option<String>,
+    budget_exceeded: Vec<BudgetExceeded>,
+    /// Whether the rule read facts spanning several classes.
+    whole_program: bool,
+    target_classes: usize,
+    target_methods: usize,
+}
+
+/// Cached results of one rule, looked up before the rules run.
+struct RuleCachePlan {
+    rule_key: ContentHash,
+    /// Result cache key of each analysis target class.
+    class_keys: Vec<ContentHash>,
+    /// Cached results of each analysis target class; `None` for classes to analyze.
+    cached: Vec<Option<Vec<SarifResult>>>,
+    /// Index into `AnalysisContext::target_subsets` when only some classes are stale.
+    subset: Option<usize>,
+}
+
+/// Notification about a rule, tied to its descriptor in `tool.driver.rules` (`rules`) by
+/// looking the rule ID up there.
+fn rule_notification(
+    rules: &[ReportingDescriptor],
+    rule_id: &str,
+    level: &str,
+    message: String,
+) -> Notification {
+    let mut associated_rule = ReportingDescriptorReference::builder().id(rule_id).build();
+    associated_rule.index = rules
+        .iter()
+        .position(|descriptor| descriptor.id == rule_id)
+        .map(|index| index as i64);
+    Notification::builder()
+        .message(result_message(message))
+        .level(json!(level))
+        .associated_rule(associated_rule)
+        .build()
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic payload"
     }
 }
 
@@ -116,23 +598,47 @@ struct RuleOutput {
     id: String,
     descriptor: ReportingDescriptor,
     results: Vec<SarifResult>,
+    stats: RuleRunStats,
+    failure: Option<String>,
+    /// Messages for methods whose analysis was cut short by the budget.
+    budget_exceeded: Vec<String>,
+}
+
+/// Execution statistics for one rule.
+#[derive(Clone, Debug)]
+pub(crate) struct RuleRunStats {
+    pub(crate) rule_id: String,
+    pub(crate) duration_ms: u128,
+    /// Analysis target classes the rule ran on; classes whose results came from the cache
+    /// are not counted.
+    pub(crate) target_classes: usize,
+    /// Methods of those classes.
+    pub(crate) target_methods: usize,
+    pub(crate) result_count: usize,
 }
 
 /// Aggregated SARIF payload from rule execution.
 pub(crate) struct EngineOutput {
     pub(crate) rules: Vec<ReportingDescriptor>,
     pub(crate) results: Vec<SarifResult>,
+    /// Per-rule statistics, ordered like `rules`.
+    pub(crate) rule_stats: Vec<RuleRunStats>,
+    /// Rule failures (`error`) and analyses cut short by the budget (`warning`).
+    pub(crate) notifications: Vec<Notification>,
 }
 
 #[cfg(test)]
 pub(crate) fn build_context(classes: Vec<Class>, artifacts: &[Artifact]) -> AnalysisContext {
-    let (context, _) = build_context_with_timings(classes, artifacts, None);
+    let (context, _) =
+        build_context_with_timings(classes, artifacts, &[], &ClassFilter::default(), None);
     context
 }
 
 pub(crate) fn build_context_with_timings(
     classes: Vec<Class>,
     artifacts: &[Artifact],
+    source_roots: &[SourceRoot],
+    class_filter: &ClassFilter,
     telemetry: Option<Arc<Telemetry>>,
 ) -> (AnalysisContext, ContextTimings) {
     let call_graph_duration_ms = 0;
@@ -144,13 +650,23 @@ pub(crate) fn build_context_with_timings(
         || analyze_artifacts(artifacts),
     );
     let (has_slf4j, has_log4j2) = detect_logging_frameworks(&classes, telemetry.as_deref());
-    let (analysis_target_classes, dependency_classes) =
-        partition_classes(classes, &analysis_target_artifacts, &artifact_parents);
-    let class_artifact_uri_cache = build_class_artifact_uri_cache(
+    let (analysis_target_classes, dependency_classes) = partition_classes(
+        classes,
+        &analysis_target_artifacts,
+        &artifact_parents,
+        class_filter,
+    );
+    let (class_artifact_uri_cache, source_mapped_classes) = build_class_artifact_uri_cache(
         &analysis_target_classes,
         &dependency_classes,
         &artifact_uris,
+        source_roots,
     );
+    let source_root_uris = source_mapped_classes
+        .values()
+        .filter(|mapped| !mapped.source_uri.contains(':'))
+        .map(|mapped| mapped.source_uri.clone())
+        .collect();
     let artifact_duration_ms = artifact_started_at.elapsed().as_millis();
     let timings = ContextTimings {
         call_graph_duration_ms,
@@ -163,15 +679,49 @@ pub(crate) fn build_context_with_timings(
         analysis_target_classes,
         dependency_classes,
         class_artifact_uri_cache,
+        source_mapped_classes,
+        source_root_uris,
+        source_texts: Mutex::new(BTreeMap::new()),
         telemetry,
         has_slf4j,
         has_log4j2,
+        rule_options: BTreeMap::new(),
+        method_constants: Mutex::new(BTreeMap::new()),
+        method_types: Mutex::new(BTreeMap::new()),
+        method_summaries: OnceLock::new(),
+        target_subsets: Vec::new(),
     };
     (context, timings)
 }
 
+/// Per-method analysis result cached by `Method` address.
+fn cached_method_analysis<T>(
+    cache: &Mutex<BTreeMap<usize, Arc<T>>>,
+    method: &Method,
+    compute: impl FnOnce() -> Result<T>,
+) -> Result<Arc<T>> {
+    let key = std::ptr::from_ref(method) as usize;
+    let cached = cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&key)
+        .cloned();
+    if let Some(result) = cached {
+        return Ok(result);
+    }
+    // Computed outside the lock so rules analyzing different methods do not wait on each
+    // other; a concurrent computation of the same method yields the same result.
+    let result = Arc::new(outside_rule_budget(compute)?);
+    Ok(cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .entry(key)
+        .or_insert(result)
+        .clone())
+}
+
 fn rule_descriptor(metadata: &RuleMetadata) -> ReportingDescriptor {
-    ReportingDescriptor::builder()
+    let mut descriptor = ReportingDescriptor::builder()
         .id(metadata.id)
         .name(metadata.name)
         .short_description(
@@ -179,20 +729,57 @@ fn rule_descriptor(metadata: &RuleMetadata) -> ReportingDescriptor {
                 .text(metadata.description)
                 .build(),
         )
-        .build()
+        .build();
+    if let Some(spec) = rule_spec(metadata.id) {
+        let full_description = spec
+            .summary()
+            .unwrap_or_else(|| metadata.description.to_string());
+        // The spec is Markdown; plain-text consumers get the summary and the spec link.
+        let help_text = format!("{full_description} See {}", spec.help_uri());
+        descriptor.full_description = Some(
+            MultiformatMessageString::builder()
+                .text(full_description)
+                .build(),
+        );
+        descriptor.help = Some(
+            MultiformatMessageString::builder()
+                .text(help_text)
+                .markdown(spec.markdown())
+                .build(),
+        );
+        descriptor.help_uri = Some(spec.help_uri());
+    }
+    let mut tags: Vec<String> = metadata.tags.iter().map(|tag| tag.to_string()).collect();
+    tags.extend(metadata.cwe.iter().map(|id| cwe_tag(*id)));
+    let mut additional_properties =
+        BTreeMap::from([("precision".to_string(), json!(metadata.precision.as_str()))]);
+    if let Some(severity) = metadata.security_severity {
+        // GitHub code scanning expects the score as a string.
+        additional_properties.insert(
+            "security-severity".to_string(),
+            json!(format!("{severity:.1}")),
+        );
+    }
+    descriptor.properties = Some(PropertyBag {
+        tags: Some(tags),
+        additional_properties,
+    });
+    if !metadata.cwe.is_empty() {
+        descriptor.relationships = Some(cwe_relationships(metadata.cwe));
+    }
+    descriptor
 }
 
 impl AnalysisContext {
     pub(crate) fn analysis_target_classes(&self) -> &[Class] {
-        &self.analysis_target_classes
-    }
-
-    #[allow(dead_code)]
-    pub(crate) fn dependency_classes(&self) -> &[Class] {
-        &self.dependency_classes
+        match RULE_SCOPE.get().and_then(|scope| scope.subset) {
+            Some(subset) => &self.target_subsets[subset],
+            None => &self.analysis_target_classes,
+        }
     }
 
     pub(crate) fn all_classes(&self) -> impl Iterator<Item = &Class> {
+        mark_whole_program();
         self.analysis_target_classes
             .iter()
             .chain(self.dependency_classes.iter())
@@ -212,10 +799,101 @@ impl AnalysisContext {
     pub(crate) fn class_artifact_uri(&self, class: &Class) -> Option<String> {
         self.class_artifact_uri_cache
             .get(&class.artifact_index)
-            .and_then(|by_name| by_name.get(&class.name))
+            .and_then(|by_name| by_name.get(class.name.as_str()))
             .cloned()
     }
 
+    /// Describe a method analysis cut short by the budget, naming the method's class.
+    fn budget_exceeded_message(&self, rule_id: &str, exceeded: &BudgetExceeded) -> String {
+        let method = format!(
+            "{}.{}{}",
+            exceeded.class_name, exceeded.method_name, exceeded.descriptor
+        );
+        match exceeded.limit {
+            BudgetLimit::MethodSteps(_) => format!(
+                "Rule {rule_id} stopped analyzing {method}: exceeded the {}",
+                exceeded.limit
+            ),
+            BudgetLimit::RuleTimeout(_) => format!(
+                "Rule {rule_id} stopped analyzing {method}: exceeded the {}; later methods were skipped",
+                exceeded.limit
+            ),
+        }
+    }
+
+    /// Artifact URI of a target or dependency class looked up by internal name.
+    pub(crate) fn class_artifact_uri_by_name(&self, class_name: &str) -> Option<String> {
+        self.all_classes()
+            .find(|class| class.name == class_name)
+            .and_then(|class| self.class_artifact_uri(class))
+    }
+
+    /// Tag source-root-relative locations with `%SRCROOT%`, keep the compiled class file
+    /// as a related location, and resolve suggested fixes against the source file.
+    fn link_source_root_locations(&self, result: &mut SarifResult) {
+        let mapped = self.link_source_root_class(result);
+        if !has_pending_fix(result) {
+            return;
+        }
+        let source = mapped.and_then(|(class_name, mapped)| self.source_text(class_name, mapped));
+        resolve_pending_fix(result, source.as_deref());
+    }
+
+    /// Returns the result's class when it is mapped to a source root.
+    fn link_source_root_class(
+        &self,
+        result: &mut SarifResult,
+    ) -> Option<(String, &SourceMappedClass)> {
+        let flow_locations = result
+            .code_flows
+            .iter_mut()
+            .flatten()
+            .flat_map(|code_flow| code_flow.thread_flows.iter_mut())
+            .flat_map(|thread_flow| thread_flow.locations.iter_mut())
+            .filter_map(|thread_flow_location| thread_flow_location.location.as_mut());
+        for location in flow_locations {
+            tag_source_root_location(location, &self.source_root_uris);
+        }
+        for location in result.related_locations.iter_mut().flatten() {
+            tag_source_root_location(location, &self.source_root_uris);
+        }
+        let locations = result.locations.as_mut()?;
+        for location in locations.iter_mut() {
+            tag_source_root_location(location, &self.source_root_uris);
+        }
+        let class_name = locations.first().and_then(location_class_name)?.to_string();
+        let mapped = self.source_mapped_classes.get(&class_name)?;
+        let related = related_location(
+            class_location(&class_name, Some(&mapped.class_uri)),
+            "Compiled class file",
+        );
+        add_related_locations(result, [related]);
+        Some((class_name, mapped))
+    }
+
+    /// Source text of a mapped class, read once per run.
+    fn source_text(&self, class_name: String, mapped: &SourceMappedClass) -> Option<Arc<str>> {
+        self.source_texts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(class_name)
+            .or_insert_with(|| fs::read_to_string(&mapped.source_path).ok().map(Arc::from))
+            .clone()
+    }
+
+    /// Apply validated per-rule options.
+    pub(crate) fn with_rule_options(mut self, rule_options: BTreeMap<String, RuleOptions>) -> Self {
+        self.rule_options = rule_options;
+        self
+    }
+
+    /// Options configured for a rule, empty when the user set none.
+    pub(crate) fn rule_options(&self, rule_id: &str) -> &RuleOptions {
+        self.rule_options
+            .get(rule_id)
+            .unwrap_or(&EMPTY_RULE_OPTIONS)
+    }
+
     pub(crate) fn has_slf4j(&self) -> bool {
         self.has_slf4j
     }
@@ -223,19 +901,120 @@ impl AnalysisContext {
     pub(crate) fn has_log4j2(&self) -> bool {
         self.has_log4j2
     }
+
+    /// Constants reaching each instruction of `method`, computed on first use and shared by
+    /// every rule. `method` must belong to this context.
+    pub(crate) fn method_constants(&self, method: &Method) -> Result<Arc<MethodConstants>> {
+        cached_method_analysis(&self.method_constants, method, || analyze_constants(method))
+    }
+
+    /// Reference types on the stack and in locals at each instruction of `method`, computed
+    /// on first use and shared by every rule. `method` must be declared by `class` in this
+    /// context.
+    pub(crate) fn method_types(&self, class: &Class, method: &Method) -> Result<Arc<MethodTypes>> {
+        cached_method_analysis(&self.method_types, method, || {
+            analyze_types(&class.name, method)
+        })
+    }
+
+    /// Per-method facts (non-null returns, closed arguments, methods that never return
+    /// normally, side-effect freedom) for the analysis target classes, computed bottom-up
+    /// over their call graph the first time any rule asks.
+    pub(crate) fn method_summaries(&self) -> &MethodSummaries {
+        mark_whole_program();
+        outside_rule_budget(|| {
+            self.method_summaries
+                .get_or_init(|| MethodSummaries::compute(&self.analysis_target_classes))
+        })
+    }
+
+    /// Methods of `class` with their bodies. Dependency classes are scanned as headers
+    /// only, so their class file is re-read and parsed on every call; callers needing the
+    /// bodies repeatedly should keep the result.
+    #[allow(dead_code)]
+    pub(crate) fn method_bodies<'a>(&self, class: &'a Class) -> Result<Cow<'a, [Method]>> {
+        match &class.header_location {
+            Some(location) => read_method_bodies(location)
+                .map(Cow::Owned)
+                .with_context(|| format!("failed to parse method bodies of {}", class.name)),
+            None => Ok(Cow::Borrowed(&class.methods)),
+        }
+    }
 }
 
+impl AnalysisContext {
+    /// Everything a class's results may depend on when a rule only reads that class: its
+    /// bytes, its artifact URI, and the bytes of the classes it references. `None` when a
+    /// hash is missing, e.g. for classes not read through the cache.
+    fn class_fingerprint(
+        &self,
+        class: &Class,
+        class_hashes: &BTreeMap<String, ContentHash>,
+    ) -> Option<ContentHash> {
+        let mut key = KeyHasher::new(CacheKind::RuleResults);
+        key.write_str(&class.name);
+        key.write_hash(*class_hashes.get(class.name.as_str())?);
+        key.write_str(self.class_artifact_uri(class).as_deref().unwrap_or(""));
+        for referenced in &class.referenced_classes {
+            key.write_str(referenced);
+            match class_hashes.get(referenced.as_str()) {
+                Some(hash) => {
+                    key.write_u64(1);
+                    key.write_hash(*hash);
+                }
+                None => key.write_u64(0),
+            }
+        }
+        Some(key.finish())
+    }
+
+    /// Rule configuration and classpath-wide facts shared by every class key of a rule.
+    fn rule_cache_key(&self, metadata: &RuleMetadata, budget: AnalysisBudget) -> ContentHash {
+        let mut key = KeyHasher::new(CacheKind::RuleResults);
+        key.write_str(metadata.id);
+        key.write_str(&json!(self.rule_options(metadata.id)).to_string());
+        key.write_u64(budget.max_method_steps.unwrap_or(0));
+        key.write_u64(u64::from(self.has_slf4j));
+        key.write_u64(u64::from(self.has_log4j2));
+        key.finish()
+    }
+}
+
+type ClassArtifactUriCache = BTreeMap<i64, BTreeMap<String, String>>;
+
 fn build_class_artifact_uri_cache(
     analysis_target_classes: &[Class],
     dependency_classes: &[Class],
     artifact_uris: &BTreeMap<i64, String>,
-) -> BTreeMap<i64, BTreeMap<String, String>> {
+    source_roots: &[SourceRoot],
+) -> (ClassArtifactUriCache, BTreeMap<String, SourceMappedClass>) {
     let mut class_artifact_uri_cache = BTreeMap::new();
+    let mut source_mapped_classes = BTreeMap::new();
     let mut path_exists_cache = BTreeMap::new();
-    for class in analysis_target_classes
-        .iter()
-        .chain(dependency_classes.iter())
-    {
+    for class in analysis_target_classes {
+        let Some(class_uri) = class_file_uri(artifact_uris, class) else {
+            continue;
+        };
+        let uri = match source_root_uri(source_roots, class) {
+            Some((source_uri, source_path)) => {
+                let mapped = SourceMappedClass {
+                    class_uri,
+                    source_uri: source_uri.clone(),
+                    source_path,
+                };
+                source_mapped_classes.insert(class.name.to_string(), mapped);
+                source_uri
+            }
+            None => {
+                class_source_uri(&class_uri, class, &mut path_exists_cache).unwrap_or(class_uri)
+            }
+        };
+        class_artifact_uri_cache
+            .entry(class.artifact_index)
+            .or_insert_with(BTreeMap::new)
+            .insert(class.name.to_string(), uri);
+    }
+    for class in dependency_classes {
         let Some(uri) = compute_class_artifact_uri(artifact_uris, class, &mut path_exists_cache)
         else {
             continue;
@@ -243,9 +1022,27 @@ fn build_class_artifact_uri_cache(
         class_artifact_uri_cache
             .entry(class.artifact_index)
             .or_insert_with(BTreeMap::new)
-            .insert(class.name.clone(), uri);
+            .insert(class.name.to_string(), uri);
     }
-    class_artifact_uri_cache
+    (class_artifact_uri_cache, source_mapped_classes)
+}
+
+/// Resolve `<package>/<SourceFile>` against the configured source roots, in order,
+/// returning the location URI and the source file path.
+fn source_root_uri(source_roots: &[SourceRoot], class: &Class) -> Option<(String, PathBuf)> {
+    if source_roots.is_empty() {
+        return None;
+    }
+    let source_name = class.source_file.as_deref()?;
+    let relative = match class.name.rsplit_once('/') {
+        Some((package, _)) => format!("{package}/{source_name}"),
+        None => source_name.to_string(),
+    };
+    source_roots.iter().find_map(|root| {
+        let path = root.path.join(&relative);
+        path.is_file()
+            .then(|| (format!("{}{relative}", root.uri_prefix), path))
+    })
 }
 
 fn compute_class_artifact_uri(
@@ -253,6 +1050,11 @@ fn compute_class_artifact_uri(
     class: &Class,
     path_exists_cache: &mut BTreeMap<String, bool>,
 ) -> Option<String> {
+    let class_uri = class_file_uri(artifact_uris, class)?;
+    class_source_uri(&class_uri, class, path_exists_cache).or(Some(class_uri))
+}
+
+fn class_file_uri(artifact_uris: &BTreeMap<i64, String>, class: &Class) -> Option<String> {
     let uri = artifact_uris.get(&class.artifact_index)?;
     let class_uri = if uri.ends_with(".class") {
         uri.to_string()
@@ -265,8 +1067,7 @@ fn compute_class_artifact_uri(
     } else {
         return None;
     };
-
-    class_source_uri(&class_uri, class, path_exists_cache).or(Some(class_uri))
+    Some(class_uri)
 }
 
 fn class_source_uri(
@@ -417,23 +1218,25 @@ fn analyze_artifacts(
     (analysis_targets, parents, uris)
 }
 
+/// Split classes into analysis targets and dependencies. Classes from analysis target
+/// artifacts (or all classes when no artifact is marked) are targets unless `class_filter`
+/// rejects them, in which case they remain available as dependencies.
 fn partition_classes(
     classes: Vec<Class>,
     analysis_target_artifacts: &BTreeSet<i64>,
     artifact_parents: &BTreeMap<i64, i64>,
+    class_filter: &ClassFilter,
 ) -> (Vec<Class>, Vec<Class>) {
-    if analysis_target_artifacts.is_empty() {
-        return (classes, Vec::new());
-    }
-
     let mut analysis_target_classes = Vec::new();
     let mut dependency_classes = Vec::new();
     for class in classes {
-        if is_analysis_target_artifact(
-            class.artifact_index,
-            analysis_target_artifacts,
-            artifact_parents,
-        ) {
+        let in_target_artifact = analysis_target_artifacts.is_empty()
+            || is_analysis_target_artifact(
+                class.artifact_index,
+                analysis_target_artifacts,
+                artifact_parents,
+            );
+        if in_target_artifact && class_filter.matches(&class) {
             analysis_target_classes.push(class);
         } else {
             dependency_classes.push(class);
@@ -462,16 +1265,24 @@ fn is_analysis_target_artifact(
 mod tests {
     use std::fs;
     use std::path::{Path, PathBuf};
-    use std::time::{SystemTime, UNIX_EPOCH};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
     use serde_json::json;
     use serde_sarif::sarif::{ArtifactLocation, ArtifactRoles};
 
     use super::*;
+    use crate::dataflow::budget::MethodMeter;
+    use crate::dataflow::constants::ConstantValue;
+    use crate::fix::FixSuggestion;
+    use crate::ir::{
+        BasicBlock, ControlFlowGraph, Instruction, InstructionKind, MethodAccess, MethodNullness,
+    };
+    use crate::opcodes;
+    use crate::rules::result_message;
 
     fn class_with_artifact(name: &str, artifact_index: i64) -> Class {
         Class {
-            name: name.to_string(),
+            name: name.into(),
             source_file: None,
             super_name: None,
             interfaces: Vec::new(),
@@ -482,6 +1293,8 @@ mod tests {
             annotation_defaults: Vec::new(),
             artifact_index,
             is_record: false,
+            is_generated: false,
+            header_location: None,
         }
     }
 
@@ -539,7 +1352,7 @@ mod tests {
             .map(|class| class.name.as_str())
             .collect::<Vec<_>>();
         let dependency_names = context
-            .dependency_classes()
+            .dependency_classes
             .iter()
             .map(|class| class.name.as_str())
             .collect::<Vec<_>>();
@@ -581,14 +1394,47 @@ mod tests {
             .collect::<Vec<_>>();
 
         assert_eq!(context.analysis_target_classes().len(), 2);
-        assert!(context.dependency_classes().is_empty());
+        assert!(context.dependency_classes.is_empty());
         assert_eq!(all_names, vec!["com/example/ClassA", "com/example/ClassB"]);
     }
 
+    #[test]
+    fn partition_classes_keeps_filtered_targets_as_dependencies() {
+        let mut generated = class_with_artifact("com/example/ServiceImpl", 0);
+        generated.is_generated = true;
+        let classes = vec![
+            class_with_artifact("com/example/Service", 0),
+            class_with_artifact("com/example/generated/Model", 0),
+            generated,
+            class_with_artifact("org/lib/Helper", 1),
+        ];
+        let analysis_target_artifacts = BTreeSet::from([0]);
+        let filter =
+            ClassFilter::new(&[], &["com.example.generated.**".to_string()], true).expect("filter");
+
+        let (targets, dependencies) = partition_classes(
+            classes,
+            &analysis_target_artifacts,
+            &BTreeMap::new(),
+            &filter,
+        );
+
+        let names = |classes: &[Class]| classes.iter().map(|class| class.name).collect::<Vec<_>>();
+        assert_eq!(names(&targets), vec!["com/example/Service"]);
+        assert_eq!(
+            names(&dependencies),
+            vec![
+                "com/example/generated/Model",
+                "com/example/ServiceImpl",
+                "org/lib/Helper"
+            ]
+        );
+    }
+
     #[test]
     fn class_artifact_uri_uses_source_file_name_for_class_artifact() {
         let classes = vec![Class {
-            name: "com/example/ClassA".to_string(),
+            name: "com/example/ClassA".into(),
             source_file: Some("ClassA.java".to_string()),
             super_name: None,
             interfaces: Vec::new(),
@@ -599,6 +1445,8 @@ mod tests {
             annotation_defaults: Vec::new(),
             artifact_index: 0,
             is_record: false,
+            is_generated: false,
+            header_location: None,
         }];
         let artifacts = vec![
             Artifact::builder()
@@ -642,7 +1490,7 @@ mod tests {
     #[test]
     fn class_artifact_uri_uses_source_file_attribute_name_when_available() {
         let classes = vec![Class {
-            name: "com/example/FileAKt".to_string(),
+            name: "com/example/FileAKt".into(),
             source_file: Some("file_a.kt".to_string()),
             super_name: None,
             interfaces: Vec::new(),
@@ -653,6 +1501,8 @@ mod tests {
             annotation_defaults: Vec::new(),
             artifact_index: 0,
             is_record: false,
+            is_generated: false,
+            header_location: None,
         }];
         let artifacts = vec![
             Artifact::builder()
@@ -675,7 +1525,7 @@ mod tests {
     #[test]
     fn class_artifact_uri_uses_outer_source_file_for_inner_class() {
         let classes = vec![Class {
-            name: "com/example/ClassA$Inner".to_string(),
+            name: "com/example/ClassA$Inner".into(),
             source_file: Some("ClassA.java".to_string()),
             super_name: None,
             interfaces: Vec::new(),
@@ -686,6 +1536,8 @@ mod tests {
             annotation_defaults: Vec::new(),
             artifact_index: 0,
             is_record: false,
+            is_generated: false,
+            header_location: None,
         }];
         let artifacts = vec![
             Artifact::builder()
@@ -717,7 +1569,7 @@ mod tests {
             "backend/build/classes/kotlin/main/jp/skypencil/kosmo/backend/wal/LogWriter.class",
         );
         let classes = vec![Class {
-            name: "jp/skypencil/kosmo/backend/wal/LogWriter".to_string(),
+            name: "jp/skypencil/kosmo/backend/wal/LogWriter".into(),
             source_file: Some("LogWriter.kt".to_string()),
             super_name: None,
             interfaces: Vec::new(),
@@ -728,6 +1580,8 @@ mod tests {
             annotation_defaults: Vec::new(),
             artifact_index: 0,
             is_record: false,
+            is_generated: false,
+            header_location: None,
         }];
         let artifacts = vec![
             Artifact::builder()
@@ -759,7 +1613,7 @@ mod tests {
         let class_path =
             temp_dir.join("backend/build/classes/kotlin/custom/com/example/FileAKt.class");
         let classes = vec![Class {
-            name: "com/example/FileAKt".to_string(),
+            name: "com/example/FileAKt".into(),
             source_file: Some("file_a.kt".to_string()),
             super_name: None,
             interfaces: Vec::new(),
@@ -770,6 +1624,8 @@ mod tests {
             annotation_defaults: Vec::new(),
             artifact_index: 0,
             is_record: false,
+            is_generated: false,
+            header_location: None,
         }];
         let artifacts = vec![
             Artifact::builder()
@@ -800,7 +1656,7 @@ mod tests {
 
         let class_path = temp_dir.join("backend/build/classes/java/main/com/example/ClassA.class");
         let classes = vec![Class {
-            name: "com/example/ClassA".to_string(),
+            name: "com/example/ClassA".into(),
             source_file: Some("ClassA.java".to_string()),
             super_name: None,
             interfaces: Vec::new(),
@@ -811,6 +1667,8 @@ mod tests {
             annotation_defaults: Vec::new(),
             artifact_index: 0,
             is_record: false,
+            is_generated: false,
+            header_location: None,
         }];
         let artifacts = vec![
             Artifact::builder()
@@ -835,7 +1693,7 @@ mod tests {
     #[test]
     fn class_artifact_uri_falls_back_to_legacy_path_when_gradle_source_path_missing() {
         let classes = vec![Class {
-            name: "com/example/ClassA".to_string(),
+            name: "com/example/ClassA".into(),
             source_file: Some("ClassA.java".to_string()),
             super_name: None,
             interfaces: Vec::new(),
@@ -846,6 +1704,8 @@ mod tests {
             annotation_defaults: Vec::new(),
             artifact_index: 0,
             is_record: false,
+            is_generated: false,
+            header_location: None,
         }];
         let artifacts = vec![
             Artifact::builder()
@@ -878,7 +1738,7 @@ mod tests {
         let class_path =
             temp_dir.join("backend/build/classes/java/main/com/example/ClassA$Inner.class");
         let classes = vec![Class {
-            name: "com/example/ClassA$Inner".to_string(),
+            name: "com/example/ClassA$Inner".into(),
             source_file: Some("ClassA.java".to_string()),
             super_name: None,
             interfaces: Vec::new(),
@@ -889,6 +1749,8 @@ mod tests {
             annotation_defaults: Vec::new(),
             artifact_index: 0,
             is_record: false,
+            is_generated: false,
+            header_location: None,
         }];
         let artifacts = vec![
             Artifact::builder()
@@ -910,6 +1772,224 @@ mod tests {
         fs::remove_dir_all(&temp_dir).expect("cleanup temp dir");
     }
 
+    #[test]
+    fn class_artifact_uri_resolves_source_root_relative_to_srcroot() {
+        let temp_dir = make_temp_test_dir();
+        let source_root = temp_dir.join("src/main/java");
+        let source_path = source_root.join("com/example/ClassA.java");
+        fs::create_dir_all(source_path.parent().expect("source parent")).expect("create src dir");
+        fs::write(&source_path, "class ClassA {}").expect("write source");
+
+        let mut class = class_with_artifact("com/example/ClassA", 0);
+        class.source_file = Some("ClassA.java".to_string());
+        let artifacts = vec![
+            Artifact::builder()
+                .location(
+                    ArtifactLocation::builder()
+                        .uri("file:///tmp/app.jar")
+                        .build(),
+                )
+                .build(),
+        ];
+        let source_roots = vec![
+            SourceRoot::new(&temp_dir.join("src/test/java"), &temp_dir),
+            SourceRoot::new(&source_root, &temp_dir),
+        ];
+
+        let (context, _) = build_context_with_timings(
+            vec![class],
+            &artifacts,
+            &source_roots,
+            &ClassFilter::default(),
+            None,
+        );
+        let class = &context.analysis_target_classes()[0];
+        assert_eq!(
+            context.class_artifact_uri(class),
+            Some("src/main/java/com/example/ClassA.java".to_string())
+        );
+
+        let mut result = SarifResult::builder()
+            .message(result_message("message"))
+            .locations(vec![crate::rules::method_location_with_line(
+                "com/example/ClassA",
+                "run",
+                "()V",
+                context.class_artifact_uri(class).as_deref(),
+                Some(7),
+            )])
+            .build();
+        context.link_source_root_locations(&mut result);
+        let value = serde_json::to_value(&result).expect("serialize result");
+        let physical = &value["locations"][0]["physicalLocation"];
+        assert_eq!(physical["artifactLocation"]["uriBaseId"], "%SRCROOT%");
+        assert_eq!(physical["region"]["startLine"], 7);
+        assert_eq!(
+            value["relatedLocations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "file:///tmp/app.jar"
+        );
+        assert_eq!(
+            value["relatedLocations"][0]["logicalLocations"][0]["name"],
+            "com/example/ClassA"
+        );
+
+        fs::remove_dir_all(&temp_dir).expect("cleanup temp dir");
+    }
+
+    #[test]
+    fn link_source_root_locations_leaves_unmapped_relative_uris_untagged() {
+        let temp_dir = make_temp_test_dir();
+        let source_path = temp_dir.join("com/example/ClassA.java");
+        fs::create_dir_all(source_path.parent().expect("source parent")).expect("create src dir");
+        fs::write(&source_path, "class ClassA {}").expect("write source");
+        let mut class = class_with_artifact("com/example/ClassA", 0);
+        class.source_file = Some("ClassA.java".to_string());
+        let artifacts = vec![
+            Artifact::builder()
+                .location(
+                    ArtifactLocation::builder()
+                        .uri("file:///tmp/app.jar")
+                        .build(),
+                )
+                .build(),
+        ];
+        let (context, _) = build_context_with_timings(
+            vec![class],
+            &artifacts,
+            &[SourceRoot::new(&temp_dir, &temp_dir)],
+            &ClassFilter::default(),
+            None,
+        );
+
+        let mut result = SarifResult::builder()
+            .message(result_message("message"))
+            .locations(vec![crate::rules::method_location_with_line(
+                "com/example/ClassB",
+                "run",
+                "()V",
+                Some("com/example/ClassB.java"),
+                Some(3),
+            )])
+            .related_locations(vec![crate::rules::method_location_with_line(
+                "com/example/ClassA",
+                "run",
+                "()V",
+                Some("com/example/ClassA.java"),
+                Some(7),
+            )])
+            .build();
+        context.link_source_root_locations(&mut result);
+        let value = serde_json::to_value(&result).expect("serialize result");
+        assert!(
+            value["locations"][0]["physicalLocation"]["artifactLocation"]
+                .get("uriBaseId")
+                .is_none()
+        );
+        assert_eq!(
+            value["relatedLocations"][0]["physicalLocation"]["artifactLocation"]["uriBaseId"],
+            "%SRCROOT%"
+        );
+
+        fs::remove_dir_all(&temp_dir).expect("cleanup temp dir");
+    }
+
+    #[test]
+    fn suggested_fix_resolves_against_source_root_file() {
+        let temp_dir = make_temp_test_dir();
+        let source_path = temp_dir.join("com/example/ClassA.java");
+        fs::create_dir_all(source_path.parent().expect("source parent")).expect("create src dir");
+        fs::write(
+            &source_path,
+            "class ClassA {\n  boolean f(String s) { return s.trim().isEmpty(); }\n}\n",
+        )
+        .expect("write source");
+        let mut class = class_with_artifact("com/example/ClassA", 0);
+        class.source_file = Some("ClassA.java".to_string());
+        let artifacts = vec![
+            Artifact::builder()
+                .location(
+                    ArtifactLocation::builder()
+                        .uri("file:///tmp/app.jar")
+                        .build(),
+                )
+                .build(),
+        ];
+        let source_roots = vec![SourceRoot::new(&temp_dir, &temp_dir)];
+        let (context, _) = build_context_with_timings(
+            vec![class],
+            &artifacts,
+            &source_roots,
+            &ClassFilter::default(),
+            None,
+        );
+        let class = &context.analysis_target_classes()[0];
+        let artifact_uri = context.class_artifact_uri(class);
+        let location = |line| {
+            crate::rules::method_location_with_line(
+                "com/example/ClassA",
+                "f",
+                "(Ljava/lang/String;)Z",
+                artifact_uri.as_deref(),
+                Some(line),
+            )
+        };
+        let mut fixed = SarifResult::builder()
+            .message(result_message("message"))
+            .locations(vec![location(2)])
+            .build();
+        let mut unmatched = fixed.clone();
+        unmatched.locations = Some(vec![location(1)]);
+        let mut plain = fixed.clone();
+        context.link_source_root_locations(&mut plain);
+        assert!(
+            context
+                .source_texts
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .is_empty(),
+            "source file should only be read for results with a fix"
+        );
+        for result in [&mut fixed, &mut unmatched] {
+            FixSuggestion::new("Use isBlank()")
+                .replace(".trim().isEmpty()", ".isBlank()")
+                .attach(result);
+            context.link_source_root_locations(result);
+        }
+        assert_eq!(
+            context
+                .source_texts
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .len(),
+            1
+        );
+
+        let value = serde_json::to_value(&fixed).expect("serialize result");
+        let change = &value["fixes"][0]["artifactChanges"][0];
+        assert_eq!(change["artifactLocation"]["uri"], "com/example/ClassA.java");
+        assert_eq!(change["artifactLocation"]["uriBaseId"], "%SRCROOT%");
+        assert_eq!(
+            change["replacements"][0]["deletedRegion"]["startColumn"],
+            33
+        );
+        assert_eq!(
+            change["replacements"][0]["insertedContent"]["text"],
+            ".isBlank()"
+        );
+        let value = serde_json::to_value(&unmatched).expect("serialize result");
+        assert!(value.get("fixes").is_none());
+        assert_eq!(value["properties"]["suggestedFix"], "Use isBlank()");
+
+        fs::remove_dir_all(&temp_dir).expect("cleanup temp dir");
+    }
+
+    #[test]
+    fn source_root_outside_srcroot_uses_absolute_uri() {
+        let root = SourceRoot::new(Path::new("/opt/shared/src"), Path::new("/work/project"));
+
+        assert_eq!(root.uri_prefix, "file:///opt/shared/src/");
+    }
+
     #[test]
     fn new_with_allowed_rule_ids_restricts_rule_execution_set() {
         let allowed = BTreeSet::from(["SYSTEM_EXIT".to_string()]);
@@ -929,4 +2009,370 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    /// Test rule that reports one result per target class or fails in the requested way.
+    struct TestRule {
+        id: &'static str,
+        failure: Option<&'static str>,
+    }
+
+    impl Rule for TestRule {
+        fn metadata(&self) -> RuleMetadata {
+            RuleMetadata {
+                id: self.id,
+                name: "Test rule",
+                description: "Test rule",
+                tags: &["correctness"],
+                precision: crate::rules::RulePrecision::High,
+                security_severity: None,
+                cwe: &[],
+                requires: None,
+                options: &[],
+            }
+        }
+
+        fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
+            let classes = context.analysis_target_classes();
+            match self.failure {
+                Some("error") => anyhow::bail!("broken rule"),
+                Some(_) => panic!("rule panicked"),
+                None => Ok(classes
+                    .iter()
+                    .map(|class| {
+                        SarifResult::builder()
+                            .message(result_message(class.name.as_str()))
+                            .build()
+                    })
+                    .collect()),
+            }
+        }
+    }
+
+    #[test]
+    fn analyze_isolates_failing_rules_and_records_stats() {
+        let classes = vec![
+            class_with_artifact("com/example/ClassA", 0),
+            class_with_artifact("com/example/ClassB", 0),
+        ];
+        let engine = Engine {
+            budget: AnalysisBudget::default(),
+            cache: None,
+            rules: vec![
+                Box::new(TestRule {
+                    id: "A_ERROR",
+                    failure: Some("error"),
+                }),
+                Box::new(TestRule {
+                    id: "B_OK",
+                    failure: None,
+                }),
+                Box::new(TestRule {
+                    id: "C_PANIC",
+                    failure: Some("panic"),
+                }),
+            ],
+        };
+
+        let output = engine
+            .analyze(build_context(classes, &[]))
+            .expect("analysis");
+
+        assert_eq!(output.rules.len(), 3);
+        assert_eq!(output.results.len(), 2);
+        assert!(
+            output
+                .results
+                .iter()
+                .all(|result| result.rule_id.as_deref() == Some("B_OK"))
+        );
+        let stats = &output.rule_stats[1];
+        assert_eq!(stats.rule_id, "B_OK");
+        assert_eq!(stats.target_classes, 2);
+        assert_eq!(stats.target_methods, 0);
+        assert_eq!(stats.result_count, 2);
+        assert_eq!(output.rule_stats[0].result_count, 0);
+        let notifications = serde_json::to_value(&output.notifications).expect("notifications");
+        assert_eq!(
+            notifications,
+            json!([
+                {
+                    "associatedRule": {"id": "A_ERROR", "index": 0},
+                    "level": "error",
+                    "message": {"text": "Rule A_ERROR failed: broken rule"},
+                },
+                {
+                    "associatedRule": {"id": "C_PANIC", "index": 2},
+                    "level": "error",
+                    "message": {"text": "Rule C_PANIC failed: panicked: rule panicked"},
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn rule_notification_indexes_descriptor_by_rule_id() {
+        let rules: Vec<ReportingDescriptor> = ["B_RULE", "A_RULE"]
+            .into_iter()
+            .map(|id| ReportingDescriptor::builder().id(id).build())
+            .collect();
+
+        let found = rule_notification(&rules, "A_RULE", "error", "failed".to_string());
+        let missing = rule_notification(&rules, "C_RULE", "error", "failed".to_string());
+
+        let found = serde_json::to_value(&found).expect("notification");
+        assert_eq!(found["associatedRule"], json!({"id": "A_RULE", "index": 1}));
+        let missing = serde_json::to_value(&missing).expect("notification");
+        assert_eq!(missing["associatedRule"], json!({"id": "C_RULE"}));
+    }
+
+    /// Reports every analysis target class at its class location.
+    struct ClassLocationRule {
+        id: &'static str,
+        whole_program: bool,
+    }
+
+    impl Rule for ClassLocationRule {
+        fn metadata(&self) -> RuleMetadata {
+            RuleMetadata {
+                id: self.id,
+                ..TestRule {
+                    id: self.id,
+                    failure: None,
+                }
+                .metadata()
+            }
+        }
+
+        fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
+            if self.whole_program {
+                context.all_classes().count();
+            }
+            Ok(context
+                .analysis_target_classes()
+                .iter()
+                .map(|class| {
+                    SarifResult::builder()
+                        .message(result_message(class.name.as_str()))
+                        .locations(vec![class_location(&class.name, None)])
+                        .build()
+                })
+                .collect())
+        }
+    }
+
+    /// Reports every analysis target class with a method the rule budget lets it analyze.
+    struct MeteredRule;
+
+    impl Rule for MeteredRule {
+        fn metadata(&self) -> RuleMetadata {
+            TestRule {
+                id: "METERED",
+                failure: None,
+            }
+            .metadata()
+        }
+
+        fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
+            Ok(context
+                .analysis_target_classes()
+                .iter()
+                .filter(|class| {
+                    class
+                        .methods
+                        .iter()
+                        .any(|method| MethodMeter::start(method).is_some())
+                })
+                .map(|class| {
+                    SarifResult::builder()
+                        .message(result_message(class.name.as_str()))
+                        .locations(vec![class_location(&class.name, None)])
+                        .build()
+                })
+                .collect())
+        }
+    }
+
+    fn method_of(owner: &str) -> Method {
+        Method {
+            owner: owner.into(),
+            name: "run".into(),
+            descriptor: "()V".into(),
+            signature: None,
+            access: MethodAccess {
+                is_public: true,
+                is_static: false,
+                is_synchronized: false,
+                is_abstract: false,
+                is_synthetic: false,
+                is_bridge: false,
+                is_private: false,
+                is_final: false,
+            },
+            nullness: MethodNullness::unknown(0),
+            type_use: None,
+            bytecode: vec![opcodes::RETURN],
+            line_numbers: Vec::new(),
+            cfg: ControlFlowGraph {
+                blocks: Vec::new(),
+                edges: Vec::new(),
+                duplicates: Default::default(),
+            },
+            calls: Vec::new(),
+            string_literals: Vec::new(),
+            exception_handlers: Vec::new(),
+            local_variables: Vec::new(),
+            local_variable_types: Vec::new(),
+            flow_structure: Default::default(),
+        }
+    }
+
+    #[test]
+    fn shared_method_analyses_ignore_the_budget_of_the_rule_computing_them() {
+        let mut method = method_of("com/example/ClassA");
+        method.bytecode = vec![opcodes::ICONST_1, opcodes::IRETURN];
+        method.cfg.blocks = vec![BasicBlock {
+            start_offset: 0,
+            end_offset: 2,
+            instructions: vec![
+                Instruction {
+                    offset: 0,
+                    opcode: opcodes::ICONST_1,
+                    kind: InstructionKind::Other(opcodes::ICONST_1),
+                },
+                Instruction {
+                    offset: 1,
+                    opcode: opcodes::IRETURN,
+                    kind: InstructionKind::Other(opcodes::IRETURN),
+                },
+            ],
+        }];
+        let mut class = class_with_artifact("com/example/ClassA", 0);
+        class.methods = vec![method];
+        let context = build_context(vec![class], &[]);
+        let method = &context.analysis_target_classes()[0].methods[0];
+        let timed_out = AnalysisBudget {
+            rule_timeout: Some(Duration::ZERO),
+            ..AnalysisBudget::default()
+        };
+
+        // The timed-out rule computes the shared analyses first, then a healthy rule reads them.
+        let (_, exceeded) = with_rule_budget(timed_out, || {
+            assert!(MethodMeter::start(method).is_none());
+            context.method_constants(method).expect("constants");
+            context.method_summaries();
+        });
+        let (constants, healthy_exceeded) = with_rule_budget(AnalysisBudget::default(), || {
+            context.method_constants(method).expect("constants")
+        });
+
+        assert_eq!(exceeded.len(), 1);
+        assert!(healthy_exceeded.is_empty());
+        assert_eq!(constants.stack_value(1, 0), Some(&ConstantValue::Int(1)));
+    }
+
+    fn cached_engine(cache: &Arc<AnalysisCache>) -> Engine {
+        Engine {
+            budget: AnalysisBudget::default(),
+            cache: Some(cache.clone()),
+            rules: vec![
+                Box::new(ClassLocationRule {
+                    id: "LOCAL",
+                    whole_program: false,
+                }),
+                Box::new(ClassLocationRule {
+                    id: "WHOLE_PROGRAM",
+                    whole_program: true,
+                }),
+            ],
+        }
+    }
+
+    #[test]
+    fn analyze_with_cache_only_reruns_changed_classes() {
+        let cache_dir = make_temp_test_dir();
+        let classes = || {
+            vec![
+                class_with_artifact("com/example/ClassA", 0),
+                class_with_artifact("com/example/ClassB", 0),
+                class_with_artifact("com/example/ClassC", 0),
+            ]
+        };
+        let run = |changed: &[u8]| {
+            let cache = Arc::new(AnalysisCache::open(&cache_dir).expect("open cache"));
+            cache.record_class("com/example/ClassA", ContentHash::of(b"A"));
+            cache.record_class("com/example/ClassB", ContentHash::of(changed));
+            cache.record_class("com/example/ClassC", ContentHash::of(b"C"));
+            let output = cached_engine(&cache)
+                .analyze(build_context(classes(), &[]))
+                .expect("analysis");
+            let targets: Vec<usize> = output
+                .rule_stats
+                .iter()
+                .map(|stats| stats.target_classes)
+                .collect();
+            (
+                serde_json::to_value(&output.results).expect("results"),
+                targets,
+                cache.stats(),
+            )
+        };
+
+        let (cold, cold_targets, cold_stats) = run(b"B");
+        assert_eq!(cold_targets, vec![3, 3]);
+        assert_eq!(cold_stats.result_misses, 6);
+
+        let (warm, warm_targets, warm_stats) = run(b"B");
+        assert_eq!(warm, cold);
+        assert_eq!(warm_targets, vec![0, 3]);
+        assert_eq!(warm_stats.result_hits, 3);
+
+        let (changed, changed_targets, _) = run(b"B2");
+        assert_eq!(changed, cold);
+        assert_eq!(changed_targets, vec![1, 3]);
+        fs::remove_dir_all(&cache_dir).expect("cleanup temp dir");
+    }
+
+    #[test]
+    fn analyze_with_cache_skips_storing_runs_cut_short_by_rule_timeout() {
+        let cache_dir = make_temp_test_dir();
+        let classes = || {
+            ["com/example/ClassA", "com/example/ClassB"]
+                .map(|name| {
+                    let mut class = class_with_artifact(name, 0);
+                    class.methods = vec![method_of(name)];
+                    class
+                })
+                .to_vec()
+        };
+        let run = |rule_timeout: Option<Duration>| {
+            let cache = Arc::new(AnalysisCache::open(&cache_dir).expect("open cache"));
+            cache.record_class("com/example/ClassA", ContentHash::of(b"A"));
+            cache.record_class("com/example/ClassB", ContentHash::of(b"B"));
+            let engine = Engine {
+                budget: AnalysisBudget {
+                    rule_timeout,
+                    ..AnalysisBudget::default()
+                },
+                cache: Some(cache.clone()),
+                rules: vec![Box::new(MeteredRule)],
+            };
+            let output = engine
+                .analyze(build_context(classes(), &[]))
+                .expect("analysis");
+            (
+                serde_json::to_value(&output.results).expect("results"),
+                cache.stats(),
+            )
+        };
+
+        let (timed_out, _) = run(Some(Duration::ZERO));
+        assert_eq!(timed_out, json!([]));
+
+        let (warm, warm_stats) = run(None);
+        assert_eq!(warm_stats.result_hits, 0);
+        assert_eq!(warm.as_array().map(Vec::len), Some(2));
+        let (cold, _) = run(None);
+        assert_eq!(warm, cold);
+        fs::remove_dir_all(&cache_dir).expect("cleanup temp dir");
+    }
 }
@@ -0,0 +1,360 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use serde_sarif::sarif::{
+    ArtifactChange, ArtifactContent, ArtifactLocation, Fix, PropertyBag, Region, Replacement,
+    Result as SarifResult,
+};
+
+use crate::rules::result_message;
+
+/// Result property carrying a rule's pending [`FixSuggestion`] until the engine resolves it.
+const PENDING_FIX_PROPERTY: &str = "inspequte.pendingFix";
+
+/// Result property holding the fix description when no source edit could be located.
+const SUGGESTED_FIX_PROPERTY: &str = "suggestedFix";
+
+/// Java modifiers that may precede a field type.
+const JAVA_FIELD_MODIFIERS: &[&str] = &[
+    "public",
+    "protected",
+    "private",
+    "static",
+    "final",
+    "transient",
+    "volatile",
+];
+
+/// Mechanical fix a rule offers for a finding.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct FixSuggestion {
+    description: String,
+    edit: Option<SourceEdit>,
+}
+
+/// Source change applied once the finding is mapped to a source file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum SourceEdit {
+    /// Replace the first occurrence of `find` on the finding's line.
+    Replace { find: String, replacement: String },
+    /// Replace the modifiers of the field declaration named `field`.
+    FieldModifiers { field: String, modifiers: String },
+}
+
+impl FixSuggestion {
+    pub(crate) fn new(description: impl Into<String>) -> Self {
+        Self {
+            description: description.into(),
+            edit: None,
+        }
+    }
+
+    pub(crate) fn replace(
+        mut self,
+        find: impl Into<String>,
+        replacement: impl Into<String>,
+    ) -> Self {
+        self.edit = Some(SourceEdit::Replace {
+            find: find.into(),
+            replacement: replacement.into(),
+        });
+        self
+    }
+
+    pub(crate) fn field_modifiers(
+        mut self,
+        field: impl Into<String>,
+        modifiers: impl Into<String>,
+    ) -> Self {
+        self.edit = Some(SourceEdit::FieldModifiers {
+            field: field.into(),
+            modifiers: modifiers.into(),
+        });
+        self
+    }
+
+    /// Attach the suggestion to a rule result for the engine to resolve.
+    pub(crate) fn attach(self, result: &mut SarifResult) {
+        let properties = result.properties.get_or_insert_with(PropertyBag::default);
+        properties
+            .additional_properties
+            .insert(PENDING_FIX_PROPERTY.to_string(), json!(self));
+    }
+}
+
+/// Whether a rule attached a suggestion that still has to be resolved.
+pub(crate) fn has_pending_fix(result: &SarifResult) -> bool {
+    result.properties.as_ref().is_some_and(|properties| {
+        properties
+            .additional_properties
+            .contains_key(PENDING_FIX_PROPERTY)
+    })
+}
+
+/// Turn a pending fix into a SARIF `fix` when `source` (the text of the finding's source
+/// file) contains the edit target; otherwise keep only the description as a property.
+pub(crate) fn resolve_pending_fix(result: &mut SarifResult, source: Option<&str>) {
+    let Some(suggestion) = take_pending_fix(result) else {
+        return;
+    };
+    let physical = result
+        .locations
+        .as_ref()
+        .and_then(|locations| locations.first())
+        .and_then(|location| location.physical_location.as_ref());
+    let artifact_location = physical.and_then(|physical| physical.artifact_location.clone());
+    let line = physical
+        .and_then(|physical| physical.region.as_ref())
+        .and_then(|region| region.start_line);
+    let replacement = match (source, &suggestion.edit, artifact_location) {
+        (Some(source), Some(edit), Some(artifact_location)) => {
+            locate_edit(source, edit, line).map(|replacement| (artifact_location, replacement))
+        }
+        _ => None,
+    };
+    match replacement {
+        Some((artifact_location, replacement)) => {
+            result.fixes = Some(vec![build_fix(
+                &suggestion.description,
+                artifact_location,
+                replacement,
+            )]);
+        }
+        None => {
+            let properties = result.properties.get_or_insert_with(PropertyBag::default);
+            properties.additional_properties.insert(
+                SUGGESTED_FIX_PROPERTY.to_string(),
+                json!(suggestion.description),
+            );
+        }
+    }
+}
+
+fn take_pending_fix(result: &mut SarifResult) -> Option<FixSuggestion> {
+    let properties = result.properties.as_mut()?;
+    let value = properties
+        .additional_properties
+        .remove(PENDING_FIX_PROPERTY)?;
+    if properties.tags.is_none() && properties.additional_properties.is_empty() {
+        result.properties = None;
+    }
+    serde_json::from_value(value).ok()
+}
+
+fn build_fix(description: &str, artifact_location: ArtifactLocation, edit: LocatedEdit) -> Fix {
+    let region = Region::builder()
+        .start_line(edit.line)
+        .start_column(edit.start_column)
+        .end_line(edit.line)
+        .end_column(edit.end_column)
+        .build();
+    let replacement = Replacement::builder()
+        .deleted_region(region)
+        .inserted_content(ArtifactContent::builder().text(edit.text).build())
+        .build();
+    let change = ArtifactChange::builder()
+        .artifact_location(artifact_location)
+        .replacements(vec![replacement])
+        .build();
+    Fix::builder()
+        .description(result_message(description))
+        .artifact_changes(vec![change])
+        .build()
+}
+
+/// Replacement text for a single-line region; columns are 1-based UTF-16 code units.
+#[derive(Debug, PartialEq)]
+struct LocatedEdit {
+    line: i64,
+    start_column: i64,
+    end_column: i64,
+    text: String,
+}
+
+fn locate_edit(source: &str, edit: &SourceEdit, line: Option<i64>) -> Option<LocatedEdit> {
+    match edit {
+        SourceEdit::Replace { find, replacement } => {
+            let line = line?;
+            let text = source.lines().nth(usize::try_from(line - 1).ok()?)?;
+            let start = text.find(find.as_str())?;
+            Some(located(line, text, start, start + find.len(), replacement))
+        }
+        SourceEdit::FieldModifiers { field, modifiers } => {
+            source.lines().enumerate().find_map(|(index, text)| {
+                let (start, end) = field_modifier_span(text, field)?;
+                let replacement = if start == end {
+                    format!("{modifiers} ")
+                } else {
+                    modifiers.clone()
+                };
+                let line = i64::try_from(index + 1).ok()?;
+                Some(located(line, text, start, end, &replacement))
+            })
+        }
+    }
+}
+
+fn located(line: i64, text: &str, start: usize, end: usize, replacement: &str) -> LocatedEdit {
+    let column = |byte: usize| text[..byte].encode_utf16().count() as i64 + 1;
+    LocatedEdit {
+        line,
+        start_column: column(start),
+        end_column: column(end),
+        text: replacement.to_string(),
+    }
+}
+
+/// Byte span of the modifiers in a one-line `<modifiers> <Type> <field> (= ...|;)`
+/// declaration, or an empty span at the type when the field has no modifiers.
+fn field_modifier_span(text: &str, field: &str) -> Option<(usize, usize)> {
+    let mut search_from = 0;
+    while let Some(found) = text[search_from..].find(field) {
+        let name_start = search_from + found;
+        let name_end = name_start + field.len();
+        search_from = name_end;
+        let before = &text[..name_start];
+        let after = text[name_end..].trim_start();
+        if !before.ends_with(char::is_whitespace)
+            || !(after.starts_with('=') || after.starts_with(';'))
+        {
+            continue;
+        }
+        let tokens: Vec<(usize, &str)> = before
+            .split_whitespace()
+            .map(|token| (token.as_ptr() as usize - text.as_ptr() as usize, token))
+            .collect();
+        let Some((&(type_start, _), modifiers)) = tokens.split_last() else {
+            continue;
+        };
+        if !modifiers
+            .iter()
+            .all(|(_, token)| JAVA_FIELD_MODIFIERS.contains(token))
+        {
+            continue;
+        }
+        return match modifiers.first() {
+            Some(&(start, _)) => {
+                let (last_start, last) = modifiers[modifiers.len() - 1];
+                Some((start, last_start + last.len()))
+            }
+            None => Some((type_start, type_start)),
+        };
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_sarif::sarif::{Location, PhysicalLocation};
+
+    fn result_at_line(line: i64) -> SarifResult {
+        let location = Location::builder()
+            .physical_location(
+                PhysicalLocation::builder()
+                    .artifact_location(
+                        ArtifactLocation::builder()
+                            .uri("com/example/ClassA.java")
+                            .uri_base_id("%SRCROOT%")
+                            .build(),
+                    )
+                    .region(Region::builder().start_line(line).build())
+                    .build(),
+            )
+            .build();
+        SarifResult::builder()
+            .message(result_message("finding"))
+            .locations(vec![location])
+            .build()
+    }
+
+    #[test]
+    fn resolves_replace_edit_on_finding_line() {
+        let mut result = result_at_line(2);
+        FixSuggestion::new("Pass Locale.ROOT")
+            .replace("toLowerCase()", "toLowerCase(Locale.ROOT)")
+            .attach(&mut result);
+
+        resolve_pending_fix(
+            &mut result,
+            Some("class A {\n  String f(String s) { return s.toLowerCase(); }\n}\n"),
+        );
+
+        let value = serde_json::to_value(&result).expect("serialize result");
+        assert!(value.get("properties").is_none());
+        let fix = &value["fixes"][0];
+        assert_eq!(fix["description"]["text"], "Pass Locale.ROOT");
+        let change = &fix["artifactChanges"][0];
+        assert_eq!(change["artifactLocation"]["uriBaseId"], "%SRCROOT%");
+        let replacement = &change["replacements"][0];
+        assert_eq!(replacement["deletedRegion"]["startLine"], 2);
+        assert_eq!(replacement["deletedRegion"]["startColumn"], 33);
+        assert_eq!(replacement["deletedRegion"]["endColumn"], 46);
+        assert_eq!(
+            replacement["insertedContent"]["text"],
+            "toLowerCase(Locale.ROOT)"
+        );
+    }
+
+    #[test]
+    fn keeps_description_when_source_is_unavailable() {
+        let mut result = result_at_line(2);
+        FixSuggestion::new("Use isBlank()")
+            .replace("trim().isEmpty()", "isBlank()")
+            .attach(&mut result);
+
+        resolve_pending_fix(&mut result, None);
+
+        assert!(result.fixes.is_none());
+        let value = serde_json::to_value(&result).expect("serialize result");
+        assert_eq!(value["properties"]["suggestedFix"], "Use isBlank()");
+        assert!(value["properties"].get(PENDING_FIX_PROPERTY).is_none());
+    }
+
+    #[test]
+    fn field_modifier_span_covers_existing_modifiers() {
+        let text = "    public static Logger LOG = LoggerFactory.getLogger(A.class);";
+
+        let (start, end) = field_modifier_span(text, "LOG").expect("declaration");
+
+        assert_eq!(&text[start..end], "public static");
+        assert_eq!(field_modifier_span("    Logger LOG;", "LOG"), Some((4, 4)));
+        assert_eq!(field_modifier_span("    LOG.info(\"x\");", "LOG"), None);
+        assert_eq!(
+            field_modifier_span("    @Inject Logger LOG;", "LOG"),
+            None,
+            "annotations are not rewritten"
+        );
+    }
+
+    #[test]
+    fn resolves_field_modifier_edit_without_result_line() {
+        let mut result = result_at_line(1);
+        result.locations.as_mut().expect("locations")[0]
+            .physical_location
+            .as_mut()
+            .expect("physical")
+            .region = None;
+        FixSuggestion::new("Declare the logger private static final")
+            .field_modifiers("log", "private static final")
+            .attach(&mut result);
+
+        resolve_pending_fix(
+            &mut result,
+            Some("class A {\n  static org.slf4j.Logger log = null;\n}\n"),
+        );
+
+        let fixes = result.fixes.expect("fixes");
+        let replacement = &fixes[0].artifact_changes[0].replacements[0];
+        assert_eq!(replacement.deleted_region.start_line, Some(2));
+        assert_eq!(replacement.deleted_region.start_column, Some(3));
+        assert_eq!(replacement.deleted_region.end_column, Some(9));
+        assert_eq!(
+            replacement
+                .inserted_content
+                .as_ref()
+                .and_then(|content| content.text.as_deref()),
+            Some("private static final")
+        );
+    }
+}
@@ -1,23 +1,58 @@
 #![allow(dead_code)]
 
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cfg::{ControlFlowStructure, build_flow_structure};
+use crate::symbol::Symbol;
+
 /// Intermediate representation for parsed JVM classes and methods.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct Class {
-    pub(crate) name: String,
+    pub(crate) name: Symbol,
     pub(crate) source_file: Option<String>,
-    pub(crate) super_name: Option<String>,
-    pub(crate) interfaces: Vec<String>,
+    pub(crate) super_name: Option<Symbol>,
+    pub(crate) interfaces: Vec<Symbol>,
     pub(crate) type_parameters: Vec<TypeParameterUse>,
-    pub(crate) referenced_classes: Vec<String>,
+    pub(crate) referenced_classes: Vec<Symbol>,
     pub(crate) fields: Vec<Field>,
     pub(crate) methods: Vec<Method>,
     pub(crate) annotation_defaults: Vec<AnnotationDefaultValue>,
     pub(crate) artifact_index: i64,
     pub(crate) is_record: bool,
+    /// Whether the class carries a `@Generated`-style annotation.
+    pub(crate) is_generated: bool,
+    /// Where a dependency scanned as a header only was read from. Its `methods` then carry
+    /// signatures, nullness and type-use but no bodies; `AnalysisContext::method_bodies`
+    /// re-reads the class file from here to parse them on demand.
+    #[serde(skip)]
+    pub(crate) header_location: Option<ClassFileLocation>,
+}
+
+/// Class file on disk, or an entry of a JAR that may itself be nested in other JARs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ClassFileLocation {
+    pub(crate) file: Arc<Path>,
+    /// Archive entry names from the outermost JAR inward; empty for a plain class file.
+    pub(crate) entries: Vec<String>,
+}
+
+impl fmt::Display for ClassFileLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.file.display())?;
+        for entry in &self.entries {
+            write!(f, "!/{entry}")?;
+        }
+        Ok(())
+    }
 }
 
 /// Numeric default value from an annotation method's AnnotationDefault attribute.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct AnnotationDefaultValue {
     pub(crate) method_name: String,
     pub(crate) method_descriptor: String,
@@ -25,14 +60,14 @@ pub(crate) struct AnnotationDefaultValue {
 }
 
 /// Numeric type of an annotation default value.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) enum AnnotationDefaultNumeric {
     Int(i64),
-    Float(f64),
+    Float(#[serde(with = "f64_bits")] f64),
 }
 
 /// Field definition for a class.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct Field {
     pub(crate) name: String,
     pub(crate) descriptor: String,
@@ -42,7 +77,7 @@ pub(crate) struct Field {
 }
 
 /// Field access flags used for rule filtering.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub(crate) struct FieldAccess {
     pub(crate) is_static: bool,
     pub(crate) is_private: bool,
@@ -51,10 +86,12 @@ pub(crate) struct FieldAccess {
 }
 
 /// Intermediate representation for a method and its bytecode.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct Method {
-    pub(crate) name: String,
-    pub(crate) descriptor: String,
+    /// Internal name of the declaring class.
+    pub(crate) owner: Symbol,
+    pub(crate) name: Symbol,
+    pub(crate) descriptor: Symbol,
     pub(crate) signature: Option<String>,
     pub(crate) access: MethodAccess,
     pub(crate) nullness: MethodNullness,
@@ -67,10 +104,13 @@ pub(crate) struct Method {
     pub(crate) exception_handlers: Vec<ExceptionHandler>,
     pub(crate) local_variables: Vec<LocalVariable>,
     pub(crate) local_variable_types: Vec<LocalVariableType>,
+    /// Dominator and loop structure, computed on first use.
+    #[serde(skip)]
+    pub(crate) flow_structure: OnceLock<ControlFlowStructure>,
 }
 
 /// Local variable metadata from the LocalVariableTable attribute.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct LocalVariable {
     pub(crate) name: String,
     pub(crate) descriptor: String,
@@ -80,7 +120,7 @@ pub(crate) struct LocalVariable {
 }
 
 /// Local variable type metadata from the LocalVariableTypeTable attribute.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct LocalVariableType {
     pub(crate) name: String,
     pub(crate) signature: String,
@@ -91,7 +131,7 @@ pub(crate) struct LocalVariableType {
 }
 
 /// Method access flags used for rule filtering.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub(crate) struct MethodAccess {
     pub(crate) is_public: bool,
     pub(crate) is_static: bool,
@@ -99,10 +139,12 @@ pub(crate) struct MethodAccess {
     pub(crate) is_abstract: bool,
     pub(crate) is_synthetic: bool,
     pub(crate) is_bridge: bool,
+    pub(crate) is_private: bool,
+    pub(crate) is_final: bool,
 }
 
 /// Exception handler metadata from the Code attribute.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct ExceptionHandler {
     pub(crate) start_pc: u32,
     pub(crate) end_pc: u32,
@@ -111,21 +153,63 @@ pub(crate) struct ExceptionHandler {
 }
 
 /// Line number mapping entry from bytecode offsets to source lines.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct LineNumber {
     pub(crate) start_pc: u32,
     pub(crate) line: u32,
 }
 
 /// Basic block graph for method bytecode.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub(crate) struct ControlFlowGraph {
     pub(crate) blocks: Vec<BasicBlock>,
     pub(crate) edges: Vec<FlowEdge>,
+    pub(crate) duplicates: DuplicatedCode,
+}
+
+/// Code javac emits more than once for a single source construct.
+///
+/// `finally` bodies are inlined on every exit path and try-with-resources closes the resource
+/// on both the normal and the exceptional path, so one source statement can appear at several
+/// bytecode offsets.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct DuplicatedCode {
+    /// Offsets of instructions in a later copy mapped to the same instruction in the first copy.
+    pub(crate) canonical_offsets: BTreeMap<u32, u32>,
+    /// Start offsets of blocks holding any copy of an inlined `finally` body.
+    pub(crate) finally_blocks: BTreeSet<u32>,
+    /// Start offsets of blocks holding compiler-generated resource close or `addSuppressed` code.
+    pub(crate) resource_cleanup_blocks: BTreeSet<u32>,
+}
+
+impl ControlFlowGraph {
+    /// Offset of the first copy of the instruction at `offset`, or `offset` itself.
+    pub(crate) fn canonical_offset(&self, offset: u32) -> u32 {
+        self.duplicates
+            .canonical_offsets
+            .get(&offset)
+            .copied()
+            .unwrap_or(offset)
+    }
+
+    /// Whether the instruction at `offset` repeats code already emitted at a lower offset.
+    pub(crate) fn is_duplicate(&self, offset: u32) -> bool {
+        self.duplicates.canonical_offsets.contains_key(&offset)
+    }
+
+    pub(crate) fn is_finally_block(&self, block_start: u32) -> bool {
+        self.duplicates.finally_blocks.contains(&block_start)
+    }
+
+    pub(crate) fn is_resource_cleanup_block(&self, block_start: u32) -> bool {
+        self.duplicates
+            .resource_cleanup_blocks
+            .contains(&block_start)
+    }
 }
 
 /// Basic block covering a range of bytecode offsets.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct BasicBlock {
     pub(crate) start_offset: u32,
     pub(crate) end_offset: u32,
@@ -133,7 +217,7 @@ pub(crate) struct BasicBlock {
 }
 
 /// Edge between basic blocks.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct FlowEdge {
     pub(crate) from: u32,
     pub(crate) to: u32,
@@ -141,7 +225,7 @@ pub(crate) struct FlowEdge {
 }
 
 /// Edge classification used for CFG inspection.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub(crate) enum EdgeKind {
     FallThrough,
     Branch,
@@ -149,7 +233,7 @@ pub(crate) enum EdgeKind {
 }
 
 /// Bytecode instruction captured for analysis.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct Instruction {
     pub(crate) offset: u32,
     pub(crate) opcode: u8,
@@ -157,43 +241,49 @@ pub(crate) struct Instruction {
 }
 
 /// Instruction kinds needed for call graph construction.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) enum InstructionKind {
     Invoke(CallSite),
     FieldAccess(FieldRef),
     InvokeDynamic {
         descriptor: String,
         impl_method: Option<String>,
+        /// Recipe of a `StringConcatFactory.makeConcatWithConstants` call site, with its
+        /// `\u{2}` constants substituted; each remaining `\u{1}` stands for one argument.
+        concat_recipe: Option<String>,
     },
     ConstString(String),
     ConstClass(String),
     /// Integer or long constant loaded via bipush, sipush, or ldc/ldc2_w.
     ConstInt(i64),
     /// Float or double constant loaded via ldc/ldc2_w.
-    ConstFloat(f64),
+    ConstFloat(#[serde(with = "f64_bits")] f64),
+    /// Class operand of `new`, `checkcast`, `instanceof`, `anewarray` or `multianewarray`:
+    /// an internal name, or an array descriptor for array classes.
+    TypeRef(String),
     Other(u8),
 }
 
 /// Field access site resolved from bytecode constant pool.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub(crate) struct FieldRef {
-    pub(crate) owner: String,
-    pub(crate) name: String,
-    pub(crate) descriptor: String,
+    pub(crate) owner: Symbol,
+    pub(crate) name: Symbol,
+    pub(crate) descriptor: Symbol,
 }
 
 /// Call site extracted from bytecode.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub(crate) struct CallSite {
-    pub(crate) owner: String,
-    pub(crate) name: String,
-    pub(crate) descriptor: String,
+    pub(crate) owner: Symbol,
+    pub(crate) name: Symbol,
+    pub(crate) descriptor: Symbol,
     pub(crate) kind: CallKind,
     pub(crate) offset: u32,
 }
 
 /// Call opcode classification used by CHA.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 pub(crate) enum CallKind {
     Virtual,
     Interface,
@@ -202,7 +292,7 @@ pub(crate) enum CallKind {
 }
 
 /// Nullness classification used by JSpecify checks.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub(crate) enum Nullness {
     Unknown,
     NonNull,
@@ -210,14 +300,14 @@ pub(crate) enum Nullness {
 }
 
 /// Nullness annotations for a method signature.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct MethodNullness {
     pub(crate) return_nullness: Nullness,
     pub(crate) parameter_nullness: Vec<Nullness>,
 }
 
 /// Method type-use signature derived from generic metadata.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) struct MethodTypeUse {
     pub(crate) type_parameters: Vec<TypeParameterUse>,
     pub(crate) parameters: Vec<TypeUse>,
@@ -225,7 +315,7 @@ pub(crate) struct MethodTypeUse {
 }
 
 /// Type parameter metadata with optional bounds.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) struct TypeParameterUse {
     pub(crate) name: String,
     pub(crate) class_bound: Option<TypeUse>,
@@ -233,14 +323,14 @@ pub(crate) struct TypeParameterUse {
 }
 
 /// Type-use signature with nullness annotation metadata.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) struct TypeUse {
     pub(crate) nullness: Nullness,
     pub(crate) kind: TypeUseKind,
 }
 
 /// Kind of type-use signature entry.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) enum TypeUseKind {
     Base(char),
     Array(Box<TypeUse>),
@@ -251,7 +341,7 @@ pub(crate) enum TypeUseKind {
 }
 
 /// Class type metadata with generic arguments and inner class segments.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) struct ClassTypeUse {
     pub(crate) name: String,
     pub(crate) type_arguments: Vec<TypeUse>,
@@ -279,4 +369,30 @@ impl Method {
         }
         candidate
     }
+
+    /// Call sites excluding compiler-generated copies, for rules reporting one result per call.
+    pub(crate) fn source_calls(&self) -> impl Iterator<Item = &CallSite> {
+        self.calls
+            .iter()
+            .filter(|call| !self.cfg.is_duplicate(call.offset))
+    }
+
+    /// Dominators, post-dominators and loops of this method's control flow graph.
+    pub(crate) fn flow_structure(&self) -> &ControlFlowStructure {
+        self.flow_structure
+            .get_or_init(|| build_flow_structure(&self.cfg, &self.exception_handlers))
+    }
+}
+
+/// Stores floats by their bits, since JSON cannot represent NaN or infinities.
+mod f64_bits {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(value.to_bits())
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        u64::deserialize(deserializer).map(f64::from_bits)
+    }
 }
@@ -1,13 +1,21 @@
 mod baseline;
+mod cache;
 mod cfg;
+mod class_filter;
 mod classpath;
+mod config;
 mod dataflow;
 mod descriptor;
 mod engine;
+mod fix;
 mod ir;
 mod opcodes;
+mod report;
+mod rule_options;
 mod rules;
 mod scan;
+mod symbol;
+mod taxonomy;
 mod telemetry;
 #[cfg(test)]
 mod test_harness;
@@ -15,27 +23,44 @@ mod test_harness;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::fs::{self, File};
-use std::io::{self, Read, Write};
+use std::io::{self, IsTerminal, Read, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use opentelemetry::KeyValue;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
-use serde_sarif::sarif::Result as SarifResult;
 use serde_sarif::sarif::{
-    Artifact, Invocation, PropertyBag, ReportingDescriptor, Run, RunAutomationDetails, SCHEMA_URL,
-    Sarif, Tool, ToolComponent,
+    Artifact, ArtifactLocation, ConfigurationOverride, Invocation, Notification, PropertyBag,
+    ReportingDescriptor, Run, RunAutomationDetails, SCHEMA_URL, Sarif, Tool, ToolComponent,
+    ToolComponentReference,
 };
+use serde_sarif::sarif::{Result as SarifResult, ResultLevel};
 use tracing::error;
 
 use crate::baseline::{load_baseline, write_baseline};
+use crate::cache::{AnalysisCache, CacheStats};
+use crate::class_filter::ClassFilter;
 use crate::classpath::resolve_classpath;
-use crate::engine::{Engine, build_context_with_timings};
+use crate::config::resolve_configured_rule_options;
+use crate::dataflow::budget::AnalysisBudget;
+use crate::engine::{
+    Engine, RuleRunStats, SRCROOT_URI_BASE_ID, SourceRoot, build_context_with_timings,
+    directory_uri,
+};
+use crate::report::{OutputFormat, SourcePathIndex, write_report};
+use crate::rule_options::{
+    RawRuleOptions, RuleOptionSpec, RuleOptions, rule_configuration_overrides,
+};
+use crate::rules::{
+    DEFAULT_RESULT_LEVEL, RequiredLibrary, RuleMetadata, all_rules, resolve_rule_selectors,
+    rule_spec,
+};
 use crate::scan::scan_inputs;
+use crate::taxonomy::{CWE_TAXONOMY_NAME, cwe_taxonomy};
 use crate::telemetry::{Telemetry, current_trace_id, init_logging, with_span};
 
 const DEFAULT_BASELINE_PATH: &str = ".inspequte/baseline.json";
@@ -68,6 +93,14 @@ struct ScanArgs {
     input: InputArgs,
     #[arg(long, value_name = "PATH", conflicts_with = "json")]
     output: Option<PathBuf>,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = OutputFormat::Sarif,
+        conflicts_with = "json",
+        help = "Report format written to --output."
+    )]
+    format: OutputFormat,
     #[arg(
         long,
         value_name = "ID",
@@ -90,6 +123,18 @@ struct ScanArgs {
         help = "Rule IDs to run. Accepts comma-separated IDs and @file references (one rule ID per line). Repeatable."
     )]
     rules: Vec<String>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with = "json",
+        help = "JSON config file with per-rule options under \"ruleOptions\"."
+    )]
+    config: Option<PathBuf>,
+    /// Rule options given inline in a JSON request.
+    #[arg(skip)]
+    rule_options: RawRuleOptions,
+    #[command(flatten)]
+    budget: BudgetArgs,
     #[arg(
         long,
         value_name = "PATH",
@@ -103,6 +148,13 @@ struct ScanArgs {
         help = "Warn instead of failing when the same class name appears in multiple inputs. The class from the lexicographically first artifact path is used."
     )]
     allow_duplicate_classes: bool,
+    #[arg(
+        long,
+        value_name = "DIR",
+        conflicts_with = "json",
+        help = "Directory for the incremental analysis cache (e.g. .inspequte/cache). Unchanged classes reuse their parsed IR and rule results from earlier runs."
+    )]
+    cache_dir: Option<PathBuf>,
 }
 
 /// Input configuration shared by all commands.
@@ -124,6 +176,36 @@ struct InputArgs {
         help = "Classpath entries. Use @file to read paths (one per line)."
     )]
     classpath: Vec<String>,
+    #[arg(
+        long,
+        value_name = "DIR",
+        action = clap::ArgAction::Append,
+        conflicts_with = "json",
+        help = "Source directory used to map classes to source files (e.g. src/main/java). Repeatable; the first root containing the file wins."
+    )]
+    source_root: Vec<PathBuf>,
+    #[arg(
+        long,
+        value_name = "PATTERN[,PATTERN...]",
+        action = clap::ArgAction::Append,
+        conflicts_with = "json",
+        help = "Only report findings in input classes matching these globs (e.g. com.example.**). `*` matches within a package segment, `**` across segments. Repeatable."
+    )]
+    include: Vec<String>,
+    #[arg(
+        long,
+        value_name = "PATTERN[,PATTERN...]",
+        action = clap::ArgAction::Append,
+        conflicts_with = "json",
+        help = "Report no findings in input classes matching these globs (e.g. com.example.generated.**). Excluded classes are still used for type resolution. Repeatable."
+    )]
+    exclude: Vec<String>,
+    #[arg(
+        long,
+        conflicts_with = "json",
+        help = "Report no findings in input classes annotated with a @Generated-style annotation (any CLASS or RUNTIME retained annotation whose simple name contains \"Generated\")."
+    )]
+    exclude_generated: bool,
 }
 
 /// Expanded input configuration after resolving @file references.
@@ -131,13 +213,50 @@ struct InputArgs {
 struct ExpandedInputArgs {
     input: Vec<PathBuf>,
     classpath: Vec<PathBuf>,
+    source_roots: Vec<SourceRoot>,
+    /// Directory `%SRCROOT%` resolves to, set when source roots are configured.
+    srcroot: Option<PathBuf>,
+    class_filter: ClassFilter,
 }
 
 /// Subcommands supported by the CLI.
 #[derive(Subcommand, Debug)]
 enum Command {
     /// Create a baseline file containing all current findings.
-    Baseline(BaselineArgs),
+    Baseline(Box<BaselineArgs>),
+    /// Print the documentation for a rule.
+    Explain(ExplainArgs),
+    /// List all available rules with their metadata.
+    Rules(RulesArgs),
+}
+
+/// Arguments for listing rules.
+#[derive(Args, Debug, Clone)]
+struct RulesArgs {
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = RulesFormat::Text,
+        help = "Output format for the rule list."
+    )]
+    format: RulesFormat,
+}
+
+/// Output formats supported by the rules command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+enum RulesFormat {
+    Text,
+    Json,
+}
+
+/// Arguments for printing rule documentation.
+#[derive(Args, Debug, Clone)]
+struct ExplainArgs {
+    #[arg(
+        value_name = "RULE_ID",
+        help = "Rule ID to explain (e.g. SYSTEM_EXIT)."
+    )]
+    rule_id: String,
 }
 
 /// Arguments for creating a baseline file.
@@ -165,6 +284,55 @@ struct BaselineArgs {
         help = "Warn instead of failing when the same class name appears in multiple inputs. The class from the lexicographically first artifact path is used."
     )]
     allow_duplicate_classes: bool,
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with = "json",
+        help = "JSON config file with per-rule options under \"ruleOptions\"."
+    )]
+    config: Option<PathBuf>,
+    /// Rule options given inline in a JSON request.
+    #[arg(skip)]
+    rule_options: RawRuleOptions,
+    #[command(flatten)]
+    budget: BudgetArgs,
+    #[arg(
+        long,
+        value_name = "DIR",
+        conflicts_with = "json",
+        help = "Directory for the incremental analysis cache (e.g. .inspequte/cache). Unchanged classes reuse their parsed IR and rule results from earlier runs."
+    )]
+    cache_dir: Option<PathBuf>,
+}
+
+/// Limits for the dataflow analyses of each rule.
+#[derive(Args, Debug, Clone)]
+struct BudgetArgs {
+    #[arg(
+        long,
+        value_name = "STEPS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        conflicts_with = "json",
+        help = "States a rule's dataflow analysis may process per method before giving up on that method. Unlimited by default."
+    )]
+    max_method_steps: Option<u64>,
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        conflicts_with = "json",
+        help = "Wall-clock seconds each rule may spend in dataflow analyses; methods reached afterwards are skipped. Off by default to keep results deterministic."
+    )]
+    rule_timeout: Option<u64>,
+}
+
+impl BudgetArgs {
+    fn analysis_budget(&self) -> AnalysisBudget {
+        AnalysisBudget {
+            max_method_steps: self.max_method_steps,
+            rule_timeout: self.rule_timeout.map(Duration::from_secs),
+        }
+    }
 }
 
 /// Supported command kinds in JSON request mode.
@@ -173,6 +341,7 @@ struct BaselineArgs {
 enum JsonCommand {
     Scan,
     Baseline,
+    Rules,
 }
 
 /// JSON request schema for agent-oriented CLI execution.
@@ -180,15 +349,30 @@ enum JsonCommand {
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 struct JsonRequest {
     command: JsonCommand,
+    #[serde(default)]
     input: Vec<String>,
     #[serde(default)]
     classpath: Vec<String>,
     #[serde(default)]
+    source_roots: Vec<String>,
+    #[serde(default)]
     rules: Vec<String>,
     baseline: Option<String>,
     output: Option<String>,
+    format: Option<OutputFormat>,
     #[serde(default)]
     allow_duplicate_classes: bool,
+    #[serde(default)]
+    rule_options: RawRuleOptions,
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+    #[serde(default)]
+    exclude_generated: bool,
+    max_method_steps: Option<u64>,
+    rule_timeout_seconds: Option<u64>,
+    cache_dir: Option<String>,
 }
 
 /// Internal normalized request selected from CLI flags or JSON input.
@@ -196,6 +380,8 @@ struct JsonRequest {
 enum ExecutionRequest {
     Scan(ScanArgs),
     Baseline(BaselineArgs),
+    Explain(ExplainArgs),
+    Rules(RulesArgs),
 }
 
 fn main() -> std::process::ExitCode {
@@ -213,6 +399,8 @@ fn run(cli: Cli) -> Result<()> {
     match resolve_execution_request(cli)? {
         ExecutionRequest::Scan(args) => run_scan(args),
         ExecutionRequest::Baseline(args) => run_baseline(args),
+        ExecutionRequest::Explain(args) => run_explain(args),
+        ExecutionRequest::Rules(args) => run_rules(args),
     }
 }
 
@@ -224,7 +412,9 @@ fn resolve_execution_request(cli: Cli) -> Result<ExecutionRequest> {
         return parse_json_execution_request(&json_arg);
     }
     match cli.command {
-        Some(Command::Baseline(args)) => Ok(ExecutionRequest::Baseline(args)),
+        Some(Command::Baseline(args)) => Ok(ExecutionRequest::Baseline(*args)),
+        Some(Command::Explain(args)) => Ok(ExecutionRequest::Explain(args)),
+        Some(Command::Rules(args)) => Ok(ExecutionRequest::Rules(args)),
         None => Ok(ExecutionRequest::Scan(cli.scan)),
     }
 }
@@ -269,20 +459,44 @@ fn parse_json_request(content: &str) -> Result<JsonRequest> {
 }
 
 fn build_execution_request_from_json(request: JsonRequest) -> Result<ExecutionRequest> {
-    if request.input.is_empty() {
+    if request.command == JsonCommand::Rules {
+        reject_rules_request_fields(&request)?;
+    } else if request.input.is_empty() {
         anyhow::bail!("invalid --json payload at input: expected at least one path");
     }
 
+    if request.max_method_steps == Some(0) {
+        anyhow::bail!("invalid --json payload at maxMethodSteps: expected at least 1");
+    }
+    if request.rule_timeout_seconds == Some(0) {
+        anyhow::bail!("invalid --json payload at ruleTimeoutSeconds: expected at least 1");
+    }
+    let budget = BudgetArgs {
+        max_method_steps: request.max_method_steps,
+        rule_timeout: request.rule_timeout_seconds,
+    };
     let input = InputArgs {
         input: request.input,
         classpath: request.classpath,
+        source_root: request
+            .source_roots
+            .into_iter()
+            .map(PathBuf::from)
+            .collect(),
+        include: request.include,
+        exclude: request.exclude,
+        exclude_generated: request.exclude_generated,
     };
 
     match request.command {
+        JsonCommand::Rules => Ok(ExecutionRequest::Rules(RulesArgs {
+            format: RulesFormat::Json,
+        })),
         JsonCommand::Scan => {
             let scan = ScanArgs {
                 input,
                 output: request.output.map(PathBuf::from),
+                format: request.format.unwrap_or_default(),
                 automation_details_id: None,
                 otel: None,
                 rules: request.rules,
@@ -291,6 +505,10 @@ fn build_execution_request_from_json(request: JsonRequest) -> Result<ExecutionRe
                     .map(PathBuf::from)
                     .unwrap_or_else(|| PathBuf::from(DEFAULT_BASELINE_PATH)),
                 allow_duplicate_classes: request.allow_duplicate_classes,
+                config: None,
+                rule_options: request.rule_options,
+                budget,
+                cache_dir: request.cache_dir.map(PathBuf::from),
             };
             Ok(ExecutionRequest::Scan(scan))
         }
@@ -305,6 +523,11 @@ fn build_execution_request_from_json(request: JsonRequest) -> Result<ExecutionRe
                     "invalid --json payload at rules: only supported when command is \"scan\""
                 );
             }
+            if request.format.is_some() {
+                anyhow::bail!(
+                    "invalid --json payload at format: only supported when command is \"scan\""
+                );
+            }
             let baseline = BaselineArgs {
                 input,
                 output: request
@@ -313,6 +536,10 @@ fn build_execution_request_from_json(request: JsonRequest) -> Result<ExecutionRe
                     .unwrap_or_else(|| PathBuf::from(DEFAULT_BASELINE_PATH)),
                 otel: None,
                 allow_duplicate_classes: request.allow_duplicate_classes,
+                config: None,
+                rule_options: request.rule_options,
+                budget,
+                cache_dir: request.cache_dir.map(PathBuf::from),
             };
             Ok(ExecutionRequest::Baseline(baseline))
         }
@@ -321,7 +548,9 @@ fn build_execution_request_from_json(request: JsonRequest) -> Result<ExecutionRe
 
 fn run_scan(args: ScanArgs) -> Result<()> {
     let expanded = expand_input_args(&args.input)?;
+    check_report_source_roots(args.format, &expanded.source_roots)?;
     let selected_rule_ids = expand_rule_args(&args.rules)?;
+    let rule_options = resolve_configured_rule_options(args.config.as_deref(), &args.rule_options)?;
     let root_span_name = build_root_span_name(&expanded.input);
     let root_span_attributes = build_root_span_attributes("scan", &expanded.input);
 
@@ -339,9 +568,11 @@ fn run_scan(args: ScanArgs) -> Result<()> {
                 eprintln!("trace-id={trace_id}");
             }
             let mut analysis = analyze(
-                &expanded.input,
-                &expanded.classpath,
+                &expanded,
                 selected_rule_ids.as_ref(),
+                rule_options,
+                args.budget.analysis_budget(),
+                args.cache_dir.as_deref(),
                 telemetry.clone(),
                 args.allow_duplicate_classes,
             )?;
@@ -364,7 +595,11 @@ fn run_scan(args: ScanArgs) -> Result<()> {
                 "sarif",
                 &[KeyValue::new("inspequte.phase", "sarif")],
                 || -> Result<()> {
-                    let invocation = build_invocation(&analysis.invocation_stats);
+                    let invocation = build_invocation(
+                        &analysis.invocation_stats,
+                        analysis.rule_configuration_overrides,
+                        analysis.notifications,
+                    );
                     let sarif = build_sarif(
                         telemetry.as_deref(),
                         analysis.artifacts,
@@ -372,6 +607,7 @@ fn run_scan(args: ScanArgs) -> Result<()> {
                         analysis.rules,
                         analysis.results,
                         args.automation_details_id.clone(),
+                        expanded.srcroot.as_deref(),
                     );
                     if should_validate_sarif() {
                         validate_sarif(&sarif)?;
@@ -381,12 +617,16 @@ fn run_scan(args: ScanArgs) -> Result<()> {
                         "sarif.write",
                         &[KeyValue::new("inspequte.phase", "write")],
                         || -> Result<()> {
+                            let color = should_color_output(args.output.as_deref());
                             let mut writer = output_writer(args.output.as_deref())?;
-                            serde_json::to_writer(&mut writer, &sarif)
-                                .context("failed to serialize SARIF output")?;
-                            writer
-                                .write_all(b"\n")
-                                .context("failed to write SARIF output")?;
+                            write_report(
+                                args.format,
+                                &sarif,
+                                &analysis.sources,
+                                color,
+                                &mut writer,
+                            )?;
+                            writer.flush().context("failed to flush report output")?;
                             Ok(())
                         },
                     );
@@ -410,6 +650,7 @@ fn run_scan(args: ScanArgs) -> Result<()> {
 
 fn run_baseline(args: BaselineArgs) -> Result<()> {
     let expanded = expand_input_args(&args.input)?;
+    let rule_options = resolve_configured_rule_options(args.config.as_deref(), &args.rule_options)?;
     let root_span_name = build_root_span_name(&expanded.input);
     let root_span_attributes = build_root_span_attributes("baseline", &expanded.input);
     let telemetry = match &args.otel {
@@ -426,9 +667,11 @@ fn run_baseline(args: BaselineArgs) -> Result<()> {
                 eprintln!("trace-id={trace_id}");
             }
             let analysis = analyze(
-                &expanded.input,
-                &expanded.classpath,
+                &expanded,
                 None,
+                rule_options,
+                args.budget.analysis_budget(),
+                args.cache_dir.as_deref(),
                 telemetry.clone(),
                 args.allow_duplicate_classes,
             )?;
@@ -444,6 +687,162 @@ fn run_baseline(args: BaselineArgs) -> Result<()> {
     result
 }
 
+/// Reject file-based report formats without source roots: their paths would otherwise be
+/// package-relative or point at class files, which CI tools cannot open.
+fn check_report_source_roots(format: OutputFormat, source_roots: &[SourceRoot]) -> Result<()> {
+    if format.needs_source_roots() && source_roots.is_empty() {
+        let name = format
+            .to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default();
+        anyhow::bail!(
+            "--format {name} requires --source-root (sourceRoots in --json) so report paths point at source files"
+        );
+    }
+    Ok(())
+}
+
+fn reject_rules_request_fields(request: &JsonRequest) -> Result<()> {
+    let unsupported = [
+        ("input", !request.input.is_empty()),
+        ("classpath", !request.classpath.is_empty()),
+        ("sourceRoots", !request.source_roots.is_empty()),
+        ("rules", !request.rules.is_empty()),
+        ("baseline", request.baseline.is_some()),
+        ("output", request.output.is_some()),
+        ("format", request.format.is_some()),
+        ("allowDuplicateClasses", request.allow_duplicate_classes),
+        ("ruleOptions", !request.rule_options.is_empty()),
+        ("include", !request.include.is_empty()),
+        ("exclude", !request.exclude.is_empty()),
+        ("excludeGenerated", request.exclude_generated),
+        ("maxMethodSteps", request.max_method_steps.is_some()),
+        ("ruleTimeoutSeconds", request.rule_timeout_seconds.is_some()),
+        ("cacheDir", request.cache_dir.is_some()),
+    ];
+    if let Some((field, _)) = unsupported.iter().find(|(_, present)| *present) {
+        anyhow::bail!("invalid --json payload at {field}: not supported when command is \"rules\"");
+    }
+    Ok(())
+}
+
+/// One entry of the `rules` command JSON output.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RuleListing {
+    id: &'static str,
+    name: &'static str,
+    description: &'static str,
+    tags: &'static [&'static str],
+    default_severity: ResultLevel,
+    precision: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    security_severity: Option<f64>,
+    cwe: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requires: Option<&'static str>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    options: &'static [RuleOptionSpec],
+}
+
+impl RuleListing {
+    fn new(metadata: &RuleMetadata) -> Self {
+        Self {
+            id: metadata.id,
+            name: metadata.name,
+            description: metadata.description,
+            tags: metadata.tags,
+            default_severity: DEFAULT_RESULT_LEVEL,
+            precision: metadata.precision.as_str(),
+            security_severity: metadata.security_severity,
+            cwe: metadata.cwe.iter().map(|id| format!("CWE-{id}")).collect(),
+            requires: metadata.requires.map(RequiredLibrary::as_str),
+            options: metadata.options,
+        }
+    }
+}
+
+fn run_rules(args: RulesArgs) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    write_rule_list(args.format, &mut stdout)?;
+    stdout.flush().context("failed to flush stdout")?;
+    Ok(())
+}
+
+fn write_rule_list(format: RulesFormat, writer: &mut dyn Write) -> Result<()> {
+    let mut rules: Vec<RuleMetadata> = all_rules().iter().map(|rule| rule.metadata()).collect();
+    rules.sort_by_key(|metadata| metadata.id);
+    let listings: Vec<RuleListing> = rules.iter().map(RuleListing::new).collect();
+    match format {
+        RulesFormat::Json => {
+            serde_json::to_writer_pretty(&mut *writer, &listings)
+                .context("failed to write rule list")?;
+            writeln!(writer)?;
+        }
+        RulesFormat::Text => {
+            for (index, rule) in listings.iter().enumerate() {
+                if index > 0 {
+                    writeln!(writer)?;
+                }
+                writeln!(writer, "{} - {}", rule.id, rule.name)?;
+                writeln!(writer, "  {}", rule.description)?;
+                let mut details = vec![
+                    format!("severity: {}", level_name(rule.default_severity)),
+                    format!("tags: {}", rule.tags.join(", ")),
+                ];
+                if !rule.cwe.is_empty() {
+                    details.push(format!("cwe: {}", rule.cwe.join(", ")));
+                }
+                if let Some(library) = rule.requires {
+                    details.push(format!("requires: {library}"));
+                }
+                if !rule.options.is_empty() {
+                    let names: Vec<&str> = rule.options.iter().map(|option| option.name).collect();
+                    details.push(format!("options: {}", names.join(", ")));
+                }
+                writeln!(writer, "  {}", details.join("; "))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn level_name(level: ResultLevel) -> String {
+    serde_json::to_value(level)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn run_explain(args: ExplainArgs) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    write_rule_explanation(&args.rule_id, &mut stdout)?;
+    stdout.flush().context("failed to flush stdout")?;
+    Ok(())
+}
+
+fn write_rule_explanation(rule_id: &str, writer: &mut dyn Write) -> Result<()> {
+    let rule_id = rule_id.trim().to_ascii_uppercase();
+    let metadata = all_rules()
+        .into_iter()
+        .map(|rule| rule.metadata())
+        .find(|metadata| metadata.id == rule_id)
+        .with_context(|| format!("unknown rule ID: {rule_id}"))?;
+    match rule_spec(metadata.id) {
+        Some(spec) => {
+            writeln!(writer, "{}", spec.markdown().trim_end())?;
+            writeln!(writer)?;
+            writeln!(writer, "See also: {}", spec.help_uri())?;
+        }
+        None => {
+            writeln!(writer, "{}: {}", metadata.id, metadata.name)?;
+            writeln!(writer)?;
+            writeln!(writer, "{}", metadata.description)?;
+        }
+    }
+    Ok(())
+}
+
 fn expand_input_args(args: &InputArgs) -> Result<ExpandedInputArgs> {
     let base_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
     let input =
@@ -455,7 +854,39 @@ fn expand_input_args(args: &InputArgs) -> Result<ExpandedInputArgs> {
     let classpath = expand_path_args(&args.classpath, &base_dir)
         .context("failed to expand --classpath arguments")?;
     let classpath = filter_missing_paths("classpath entry", classpath)?;
-    Ok(ExpandedInputArgs { input, classpath })
+    let (source_roots, srcroot) = resolve_source_roots(&args.source_root, &base_dir)?;
+    let class_filter = ClassFilter::new(&args.include, &args.exclude, args.exclude_generated)?;
+    Ok(ExpandedInputArgs {
+        input,
+        classpath,
+        source_roots,
+        srcroot,
+        class_filter,
+    })
+}
+
+fn resolve_source_roots(
+    paths: &[PathBuf],
+    base_dir: &Path,
+) -> Result<(Vec<SourceRoot>, Option<PathBuf>)> {
+    if paths.is_empty() {
+        return Ok((Vec::new(), None));
+    }
+    let srcroot = base_dir
+        .canonicalize()
+        .with_context(|| format!("failed to resolve {}", base_dir.display()))?;
+    let mut source_roots = Vec::new();
+    for path in paths {
+        let path = base_dir.join(path);
+        if !path.is_dir() {
+            anyhow::bail!("source root not found: {}", path.display());
+        }
+        let path = path
+            .canonicalize()
+            .with_context(|| format!("failed to resolve source root {}", path.display()))?;
+        source_roots.push(SourceRoot::new(&path, &srcroot));
+    }
+    Ok((source_roots, Some(srcroot)))
 }
 
 fn expand_path_args(args: &[String], base_dir: &Path) -> Result<Vec<PathBuf>> {
@@ -533,21 +964,36 @@ struct AnalysisOutput {
     invocation_stats: InvocationStats,
     rules: Vec<ReportingDescriptor>,
     results: Vec<SarifResult>,
+    sources: SourcePathIndex,
+    rule_configuration_overrides: Vec<ConfigurationOverride>,
+    notifications: Vec<Notification>,
 }
 
 fn analyze(
-    input: &[PathBuf],
-    classpath: &[PathBuf],
+    inputs: &ExpandedInputArgs,
     selected_rule_ids: Option<&BTreeSet<String>>,
+    rule_options: BTreeMap<String, RuleOptions>,
+    budget: AnalysisBudget,
+    cache_dir: Option<&Path>,
     telemetry: Option<Arc<Telemetry>>,
     allow_duplicate_classes: bool,
 ) -> Result<AnalysisOutput> {
+    let cache = cache_dir
+        .map(|dir| AnalysisCache::open(dir).map(Arc::new))
+        .transpose()?;
     let scan_started_at = Instant::now();
     let scan = with_span(
         telemetry.as_deref(),
         "scan",
         &[KeyValue::new("inspequte.phase", "scan")],
-        || scan_inputs(input, classpath, telemetry.as_deref()),
+        || {
+            scan_inputs(
+                &inputs.input,
+                &inputs.classpath,
+                telemetry.as_deref(),
+                cache.as_deref(),
+            )
+        },
     )?;
     let scan_duration_ms = scan_started_at.elapsed().as_millis();
     let artifact_count = scan.artifacts.len();
@@ -562,10 +1008,21 @@ fn analyze(
     let classpath_class_count = classpath_index.classes.len();
     let artifacts = scan.artifacts;
     let classes = scan.classes;
-    let (context, context_timings) =
-        build_context_with_timings(classes, &artifacts, telemetry.clone());
+    let sources = SourcePathIndex::from_classes(&classes);
+    let (context, context_timings) = build_context_with_timings(
+        classes,
+        &artifacts,
+        &inputs.source_roots,
+        &inputs.class_filter,
+        telemetry.clone(),
+    );
+    let context = context.with_rule_options(rule_options.clone());
     let analysis_rules_started_at = Instant::now();
-    let engine = Engine::new_with_allowed_rule_ids(selected_rule_ids)?;
+    let mut engine =
+        Engine::new_with_allowed_rule_ids(selected_rule_ids)?.with_analysis_budget(budget);
+    if let Some(cache) = &cache {
+        engine = engine.with_cache(cache.clone());
+    }
     let analysis = with_span(
         telemetry.as_deref(),
         "analysis_rules",
@@ -583,15 +1040,24 @@ fn analyze(
         analysis_call_graph_edges_duration_ms: context_timings.call_graph_edges_duration_ms,
         analysis_rules_duration_ms,
         class_count: scan.class_count,
+        header_class_count: scan.header_class_count,
         artifact_count,
         classpath_class_count,
+        rule_stats: analysis.rule_stats,
+        cache_stats: cache.as_deref().map(AnalysisCache::stats),
     };
 
+    let rule_ids: Vec<&str> = analysis.rules.iter().map(|rule| rule.id.as_str()).collect();
+    let rule_configuration_overrides = rule_configuration_overrides(&rule_options, &rule_ids);
+
     Ok(AnalysisOutput {
         artifacts,
         invocation_stats,
         rules: analysis.rules,
         results: analysis.results,
+        sources,
+        rule_configuration_overrides,
+        notifications: analysis.notifications,
     })
 }
 
@@ -608,6 +1074,10 @@ fn expand_rule_args(args: &[String]) -> Result<Option<BTreeSet<String>>> {
     if rules.is_empty() {
         anyhow::bail!("--rules was provided but no rule IDs were found");
     }
+    let rules = resolve_rule_selectors(&rules)?;
+    if rules.is_empty() {
+        anyhow::bail!("--rules selectors exclude every rule");
+    }
     Ok(Some(rules))
 }
 
@@ -694,6 +1164,15 @@ fn output_writer(output: Option<&Path>) -> Result<Box<dyn Write>> {
     }
 }
 
+/// Use ANSI colors only for interactive stdout, honoring the `NO_COLOR` convention.
+fn should_color_output(output: Option<&Path>) -> bool {
+    let to_stdout = match output {
+        Some(path) => path == Path::new("-"),
+        None => true,
+    };
+    to_stdout && std::env::var_os("NO_COLOR").is_none() && io::stdout().is_terminal()
+}
+
 fn build_root_span_attributes(command: &str, inputs: &[PathBuf]) -> Vec<KeyValue> {
     let target_count = inputs.len();
     let target_kind = classify_target_kind(inputs);
@@ -797,11 +1276,20 @@ struct InvocationStats {
     analysis_call_graph_edges_duration_ms: u128,
     analysis_rules_duration_ms: u128,
     class_count: usize,
+    /// Dependency classes parsed as headers only; the rest of `class_count` was parsed in full.
+    header_class_count: usize,
     artifact_count: usize,
     classpath_class_count: usize,
+    rule_stats: Vec<RuleRunStats>,
+    /// Cache hits and misses, present when `--cache-dir` is set.
+    cache_stats: Option<CacheStats>,
 }
 
-fn build_invocation(stats: &InvocationStats) -> Invocation {
+fn build_invocation(
+    stats: &InvocationStats,
+    rule_configuration_overrides: Vec<ConfigurationOverride>,
+    notifications: Vec<Notification>,
+) -> Invocation {
     let arguments: Vec<String> = std::env::args().collect();
     let command_line = arguments.join(" ");
     let mut properties = BTreeMap::new();
@@ -841,6 +1329,14 @@ fn build_invocation(stats: &InvocationStats) -> Invocation {
         "inspequte.class_count".to_string(),
         json!(stats.class_count),
     );
+    properties.insert(
+        "inspequte.full_class_count".to_string(),
+        json!(stats.class_count - stats.header_class_count),
+    );
+    properties.insert(
+        "inspequte.header_class_count".to_string(),
+        json!(stats.header_class_count),
+    );
     properties.insert(
         "inspequte.artifact_count".to_string(),
         json!(stats.artifact_count),
@@ -849,9 +1345,30 @@ fn build_invocation(stats: &InvocationStats) -> Invocation {
         "inspequte.classpath_class_count".to_string(),
         json!(stats.classpath_class_count),
     );
+    let rule_stats: BTreeMap<&str, serde_json::Value> = stats
+        .rule_stats
+        .iter()
+        .map(|rule| {
+            let value = json!({
+                "duration_ms": rule.duration_ms,
+                "target_classes": rule.target_classes,
+                "target_methods": rule.target_methods,
+                "result_count": rule.result_count,
+            });
+            (rule.rule_id.as_str(), value)
+        })
+        .collect();
+    properties.insert("inspequte.rule_stats".to_string(), json!(rule_stats));
+    if let Some(cache_stats) = &stats.cache_stats {
+        properties.insert("inspequte.cache".to_string(), json!(cache_stats));
+    }
 
-    Invocation::builder()
-        .execution_successful(true)
+    let mut invocation = Invocation::builder()
+        .execution_successful(
+            !notifications
+                .iter()
+                .any(|notification| notification.level == Some(json!("error"))),
+        )
         .arguments(arguments)
         .command_line(command_line)
         .properties(
@@ -859,7 +1376,14 @@ fn build_invocation(stats: &InvocationStats) -> Invocation {
                 .additional_properties(properties)
                 .build(),
         )
-        .build()
+        .build();
+    if !rule_configuration_overrides.is_empty() {
+        invocation.rule_configuration_overrides = Some(rule_configuration_overrides);
+    }
+    if !notifications.is_empty() {
+        invocation.tool_execution_notifications = Some(notifications);
+    }
+    invocation
 }
 
 fn should_validate_sarif() -> bool {
@@ -893,10 +1417,12 @@ fn build_sarif(
     rules: Vec<ReportingDescriptor>,
     results: Vec<SarifResult>,
     automation_details_id: Option<String>,
+    srcroot: Option<&Path>,
 ) -> Sarif {
     with_span(telemetry, "sarif.build", &[], || {
         let semantic_version = env!("CARGO_PKG_VERSION").to_string();
-        let driver = if rules.is_empty() {
+        let taxonomy = cwe_taxonomy(&rules);
+        let mut driver = if rules.is_empty() {
             ToolComponent::builder()
                 .name("inspequte")
                 .information_uri("https://github.com/KengoTODA/inspequte")
@@ -910,6 +1436,13 @@ fn build_sarif(
                 .semantic_version(semantic_version)
                 .build()
         };
+        if taxonomy.is_some() {
+            driver.supported_taxonomies = Some(vec![
+                ToolComponentReference::builder()
+                    .name(CWE_TAXONOMY_NAME)
+                    .build(),
+            ]);
+        }
         let tool = Tool {
             driver,
             extensions: None,
@@ -927,7 +1460,7 @@ fn build_sarif(
                 )
             }
         });
-        let run = match (artifacts, automation_details) {
+        let mut run = match (artifacts, automation_details) {
             (artifacts, Some(automation_details)) if artifacts.is_empty() => Run::builder()
                 .tool(tool)
                 .invocations(vec![invocation])
@@ -953,6 +1486,18 @@ fn build_sarif(
                 .artifacts(artifacts)
                 .build(),
         };
+        // Fix regions carry columns; declare the unit instead of relying on consumer defaults.
+        run.column_kind = Some(json!("utf16CodeUnits"));
+        if let Some(taxonomy) = taxonomy {
+            run.taxonomies = Some(vec![taxonomy]);
+        }
+        if let Some(srcroot) = srcroot {
+            let base = ArtifactLocation::builder()
+                .uri(directory_uri(srcroot))
+                .build();
+            run.original_uri_base_ids =
+                Some(BTreeMap::from([(SRCROOT_URI_BASE_ID.to_string(), base)]));
+        }
 
         Sarif::builder()
             .schema(SCHEMA_URL)
@@ -1276,6 +1821,182 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cli_accepts_format_option() {
+        let cli = Cli::try_parse_from([
+            "inspequte",
+            "--input",
+            "target/classes",
+            "--format",
+            "checkstyle",
+        ])
+        .expect("parse CLI");
+
+        assert_eq!(cli.scan.format, OutputFormat::Checkstyle);
+    }
+
+    #[test]
+    fn cli_defaults_to_sarif_format() {
+        let cli =
+            Cli::try_parse_from(["inspequte", "--input", "target/classes"]).expect("parse CLI");
+
+        assert_eq!(cli.scan.format, OutputFormat::Sarif);
+    }
+
+    #[test]
+    fn cli_accepts_repeatable_source_root() {
+        let cli = Cli::try_parse_from([
+            "inspequte",
+            "--input",
+            "target/classes",
+            "--source-root",
+            "src/main/java",
+            "--source-root",
+            "src/main/kotlin",
+        ])
+        .expect("parse CLI");
+
+        assert_eq!(
+            cli.scan.input.source_root,
+            vec![
+                PathBuf::from("src/main/java"),
+                PathBuf::from("src/main/kotlin")
+            ]
+        );
+    }
+
+    #[test]
+    fn cli_accepts_repeatable_class_filter_options() {
+        let cli = Cli::try_parse_from([
+            "inspequte",
+            "--input",
+            "target/classes",
+            "--include",
+            "com.example.**",
+            "--exclude",
+            "com.example.generated.**,com.example.Legacy",
+            "--exclude",
+            "**.*Test",
+            "--exclude-generated",
+        ])
+        .expect("parse CLI");
+
+        assert_eq!(cli.scan.input.include, vec!["com.example.**"]);
+        assert_eq!(
+            cli.scan.input.exclude,
+            vec!["com.example.generated.**,com.example.Legacy", "**.*Test"]
+        );
+        assert!(cli.scan.input.exclude_generated);
+    }
+
+    #[test]
+    fn resolve_source_roots_rejects_missing_directory() {
+        let temp_dir = make_temp_test_dir();
+        fs::create_dir_all(&temp_dir).expect("create temp dir");
+
+        let error = resolve_source_roots(&[PathBuf::from("missing")], &temp_dir)
+            .expect_err("missing source root");
+
+        assert!(error.to_string().contains("source root not found"));
+        fs::remove_dir_all(&temp_dir).expect("cleanup temp dir");
+    }
+
+    #[test]
+    fn file_based_report_formats_require_source_roots() {
+        let temp_dir = make_temp_test_dir();
+        fs::create_dir_all(&temp_dir).expect("create temp dir");
+        let source_roots = vec![SourceRoot::new(&temp_dir, &temp_dir)];
+
+        for format in [OutputFormat::Checkstyle, OutputFormat::Gitlab] {
+            let error = check_report_source_roots(format, &[]).expect_err("missing source root");
+            assert!(error.to_string().contains("requires --source-root"));
+            check_report_source_roots(format, &source_roots).expect("source roots given");
+        }
+        for format in [OutputFormat::Sarif, OutputFormat::Junit, OutputFormat::Text] {
+            check_report_source_roots(format, &[]).expect("format without file paths");
+        }
+        fs::remove_dir_all(&temp_dir).expect("cleanup temp dir");
+    }
+
+    #[test]
+    fn cli_accepts_explain_subcommand() {
+        let cli = Cli::try_parse_from(["inspequte", "explain", "SYSTEM_EXIT"]).expect("parse CLI");
+
+        let Some(Command::Explain(args)) = cli.command else {
+            panic!("expected explain command");
+        };
+        assert_eq!(args.rule_id, "SYSTEM_EXIT");
+    }
+
+    #[test]
+    fn cli_accepts_rules_subcommand() {
+        let cli =
+            Cli::try_parse_from(["inspequte", "rules", "--format", "json"]).expect("parse CLI");
+
+        let Some(Command::Rules(args)) = cli.command else {
+            panic!("expected rules command");
+        };
+        assert_eq!(args.format, RulesFormat::Json);
+    }
+
+    #[test]
+    fn write_rule_list_prints_text_entries() {
+        let mut output = Vec::new();
+        write_rule_list(RulesFormat::Text, &mut output).expect("list rules");
+        let output = String::from_utf8(output).expect("utf8");
+
+        assert!(output.contains(
+            "SYSTEM_EXIT - System.exit call\n  Direct calls to System.exit(int) terminate the JVM abruptly\n  severity: warning; tags: correctness; cwe: CWE-382\n"
+        ));
+        assert!(output.contains("tags: logging; requires: slf4j\n"));
+        assert!(output.contains("tags: style; options: allowedValues\n"));
+    }
+
+    #[test]
+    fn write_rule_list_prints_json_for_every_rule() {
+        let mut output = Vec::new();
+        write_rule_list(RulesFormat::Json, &mut output).expect("list rules");
+        let value: serde_json::Value = serde_json::from_slice(&output).expect("parse JSON");
+
+        let rules = value.as_array().expect("rule array");
+        assert_eq!(rules.len(), all_rules().len());
+        let insecure_api = rules
+            .iter()
+            .find(|rule| rule["id"] == "INSECURE_API")
+            .expect("INSECURE_API listed");
+        assert_eq!(insecure_api["defaultSeverity"], "warning");
+        assert_eq!(insecure_api["tags"], json!(["security"]));
+        assert_eq!(insecure_api["securitySeverity"], 7.3);
+        assert_eq!(insecure_api["cwe"], json!(["CWE-78", "CWE-470"]));
+        assert!(insecure_api.get("requires").is_none());
+        assert_eq!(insecure_api["options"][0]["name"], "additionalMethods");
+        assert_eq!(insecure_api["options"][0]["type"], "stringList");
+        let slf4j = rules
+            .iter()
+            .find(|rule| rule["id"] == "SLF4J_UNKNOWN_ARRAY")
+            .expect("SLF4J_UNKNOWN_ARRAY listed");
+        assert_eq!(slf4j["requires"], "slf4j");
+        assert!(slf4j.get("options").is_none());
+    }
+
+    #[test]
+    fn write_rule_explanation_prints_spec() {
+        let mut output = Vec::new();
+        write_rule_explanation("system_exit", &mut output).expect("explain rule");
+        let output = String::from_utf8(output).expect("utf8");
+
+        assert!(output.starts_with("# SYSTEM_EXIT\n"));
+        assert!(output.ends_with("/src/rules/system_exit/spec.md\n"));
+    }
+
+    #[test]
+    fn write_rule_explanation_rejects_unknown_rule() {
+        let error = write_rule_explanation("RULE_DOES_NOT_EXIST", &mut Vec::new())
+            .expect_err("unknown rule");
+
+        assert!(error.to_string().contains("unknown rule ID"));
+    }
+
     #[test]
     fn cli_accepts_json_option() {
         let cli = Cli::try_parse_from([
@@ -1307,7 +2028,7 @@ mod tests {
     #[test]
     fn parse_json_execution_request_supports_inline_json() {
         let request = parse_json_execution_request(
-            "{\"command\":\"scan\",\"input\":[\"target/classes\"],\"classpath\":[\"target/lib\"],\"rules\":[\"SYSTEM_EXIT\"],\"baseline\":\"baseline.json\",\"output\":\"result.sarif\",\"allowDuplicateClasses\":true}",
+            "{\"command\":\"scan\",\"input\":[\"target/classes\"],\"classpath\":[\"target/lib\"],\"rules\":[\"SYSTEM_EXIT\"],\"baseline\":\"baseline.json\",\"output\":\"result.sarif\",\"format\":\"junit\",\"sourceRoots\":[\"src/main/java\"],\"allowDuplicateClasses\":true}",
         )
         .expect("parse json request");
 
@@ -1319,6 +2040,8 @@ mod tests {
         assert_eq!(scan.rules, vec!["SYSTEM_EXIT".to_string()]);
         assert_eq!(scan.baseline, PathBuf::from("baseline.json"));
         assert_eq!(scan.output, Some(PathBuf::from("result.sarif")));
+        assert_eq!(scan.format, OutputFormat::Junit);
+        assert_eq!(scan.input.source_root, vec![PathBuf::from("src/main/java")]);
         assert!(scan.allow_duplicate_classes);
     }
 
@@ -1387,6 +2110,150 @@ mod tests {
         assert!(message.contains("baseline"));
     }
 
+    #[test]
+    fn parse_json_request_rejects_format_for_baseline_command() {
+        let result = parse_json_execution_request(
+            "{\"command\":\"baseline\",\"input\":[\".\"],\"format\":\"junit\"}",
+        );
+
+        assert!(result.is_err());
+        let message = format!("{:#}", result.expect_err("expected parse error"));
+        assert!(message.contains("format"));
+    }
+
+    #[test]
+    fn parse_json_request_supports_rules_command_without_input() {
+        let request =
+            parse_json_execution_request("{\"command\":\"rules\"}").expect("parse rules request");
+
+        let ExecutionRequest::Rules(args) = request else {
+            panic!("expected rules request");
+        };
+        assert_eq!(args.format, RulesFormat::Json);
+    }
+
+    #[test]
+    fn parse_json_request_rejects_input_for_rules_command() {
+        let result = parse_json_execution_request("{\"command\":\"rules\",\"input\":[\".\"]}");
+
+        let message = format!("{:#}", result.expect_err("expected parse error"));
+        assert!(message.contains("invalid --json payload at input"));
+    }
+
+    #[test]
+    fn parse_json_request_carries_rule_options() {
+        let request = parse_json_execution_request(
+            "{\"command\":\"scan\",\"input\":[\".\"],\"ruleOptions\":{\"MAGIC_NUMBER\":{\"allowedValues\":[\"42\"]}}}",
+        )
+        .expect("parse json request");
+
+        let ExecutionRequest::Scan(scan) = request else {
+            panic!("expected scan request");
+        };
+        assert_eq!(
+            scan.rule_options["MAGIC_NUMBER"]["allowedValues"],
+            json!(["42"])
+        );
+        let message = format!(
+            "{:#}",
+            resolve_configured_rule_options(None, &scan.rule_options)
+                .expect_err("expected option error")
+        );
+        assert_eq!(
+            message,
+            "invalid --json payload at ruleOptions.MAGIC_NUMBER.allowedValues[0]: expected an array of integers"
+        );
+    }
+
+    #[test]
+    fn parse_json_request_rejects_rule_options_for_rules_command() {
+        let result = parse_json_execution_request(
+            "{\"command\":\"rules\",\"ruleOptions\":{\"MAGIC_NUMBER\":{}}}",
+        );
+
+        let message = format!("{:#}", result.expect_err("expected parse error"));
+        assert!(message.contains("invalid --json payload at ruleOptions"));
+    }
+
+    #[test]
+    fn parse_json_request_carries_class_filters() {
+        let request = parse_json_execution_request(
+            "{\"command\":\"scan\",\"input\":[\".\"],\"include\":[\"com.example.**\"],\"exclude\":[\"com.example.generated.**\"],\"excludeGenerated\":true}",
+        )
+        .expect("parse json request");
+
+        let ExecutionRequest::Scan(scan) = request else {
+            panic!("expected scan request");
+        };
+        assert_eq!(scan.input.include, vec!["com.example.**"]);
+        assert_eq!(scan.input.exclude, vec!["com.example.generated.**"]);
+        assert!(scan.input.exclude_generated);
+    }
+
+    #[test]
+    fn parse_json_request_rejects_class_filters_for_rules_command() {
+        let result =
+            parse_json_execution_request("{\"command\":\"rules\",\"excludeGenerated\":true}");
+
+        let message = format!("{:#}", result.expect_err("expected parse error"));
+        assert!(message.contains("invalid --json payload at excludeGenerated"));
+    }
+
+    #[test]
+    fn parse_json_request_carries_analysis_budget() {
+        let request = parse_json_execution_request(
+            "{\"command\":\"baseline\",\"input\":[\".\"],\"maxMethodSteps\":5000,\"ruleTimeoutSeconds\":30}",
+        )
+        .expect("parse json request");
+
+        let ExecutionRequest::Baseline(baseline) = request else {
+            panic!("expected baseline request");
+        };
+        assert_eq!(
+            baseline.budget.analysis_budget(),
+            AnalysisBudget {
+                max_method_steps: Some(5000),
+                rule_timeout: Some(Duration::from_secs(30)),
+            }
+        );
+        let error = parse_json_execution_request(
+            "{\"command\":\"scan\",\"input\":[\".\"],\"maxMethodSteps\":0}",
+        )
+        .expect_err("zero steps");
+        assert_eq!(
+            error.to_string(),
+            "invalid --json payload at maxMethodSteps: expected at least 1"
+        );
+    }
+
+    #[test]
+    fn parse_json_request_carries_cache_dir() {
+        let request = parse_json_execution_request(
+            "{\"command\":\"scan\",\"input\":[\".\"],\"cacheDir\":\".inspequte/cache\"}",
+        )
+        .expect("parse json request");
+
+        let ExecutionRequest::Scan(scan) = request else {
+            panic!("expected scan request");
+        };
+        assert_eq!(scan.cache_dir, Some(PathBuf::from(".inspequte/cache")));
+        let error = parse_json_execution_request("{\"command\":\"rules\",\"cacheDir\":\"x\"}")
+            .expect_err("rules with cache dir");
+        assert_eq!(
+            error.to_string(),
+            "invalid --json payload at cacheDir: not supported when command is \"rules\""
+        );
+    }
+
+    #[test]
+    fn cli_defaults_to_step_budget_without_timeout() {
+        let cli =
+            Cli::try_parse_from(["inspequte", "--input", "target/classes"]).expect("parse CLI");
+
+        assert_eq!(cli.scan.budget.analysis_budget(), AnalysisBudget::default());
+        assert!(Cli::try_parse_from(["inspequte", "--input", "x", "--rule-timeout", "0"]).is_err());
+    }
+
     #[test]
     fn expand_rule_args_supports_comma_separated_and_repeatable_values() {
         let args = vec![
@@ -1432,6 +2299,30 @@ mod tests {
         fs::remove_dir_all(&temp_dir).expect("cleanup temp dir");
     }
 
+    #[test]
+    fn expand_rule_args_resolves_tag_selectors() {
+        let args = vec!["tag:security,-tag:logging".to_string()];
+
+        let expanded = expand_rule_args(&args)
+            .expect("expand rule args")
+            .expect("selected rules");
+
+        assert!(expanded.contains("INSECURE_API"));
+        assert!(!expanded.contains("SYSTEM_EXIT"));
+    }
+
+    #[test]
+    fn expand_rule_args_rejects_selectors_excluding_every_rule() {
+        let args: Vec<String> = crate::rules::RULE_TAGS
+            .iter()
+            .map(|tag| format!("-tag:{tag}"))
+            .collect();
+
+        let result = expand_rule_args(&args);
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn expand_rule_args_rejects_comma_separated_line_in_rules_file() {
         let temp_dir = make_temp_test_dir();
@@ -1448,20 +2339,35 @@ mod tests {
 
     #[test]
     fn sarif_is_minimal_and_valid_shape() {
-        let invocation = build_invocation(&InvocationStats {
-            scan_duration_ms: 0,
-            classpath_duration_ms: 0,
-            analysis_call_graph_duration_ms: 0,
-            analysis_artifact_duration_ms: 0,
-            analysis_call_graph_hierarchy_duration_ms: 0,
-            analysis_call_graph_index_duration_ms: 0,
-            analysis_call_graph_edges_duration_ms: 0,
-            analysis_rules_duration_ms: 0,
-            class_count: 0,
-            artifact_count: 0,
-            classpath_class_count: 0,
-        });
-        let sarif = build_sarif(None, Vec::new(), invocation, Vec::new(), Vec::new(), None);
+        let invocation = build_invocation(
+            &InvocationStats {
+                scan_duration_ms: 0,
+                classpath_duration_ms: 0,
+                analysis_call_graph_duration_ms: 0,
+                analysis_artifact_duration_ms: 0,
+                analysis_call_graph_hierarchy_duration_ms: 0,
+                analysis_call_graph_index_duration_ms: 0,
+                analysis_call_graph_edges_duration_ms: 0,
+                analysis_rules_duration_ms: 0,
+                class_count: 0,
+                header_class_count: 0,
+                artifact_count: 0,
+                classpath_class_count: 0,
+                rule_stats: Vec::new(),
+                cache_stats: None,
+            },
+            Vec::new(),
+            Vec::new(),
+        );
+        let sarif = build_sarif(
+            None,
+            Vec::new(),
+            invocation,
+            Vec::new(),
+            Vec::new(),
+            None,
+            None,
+        );
         let value = serde_json::to_value(&sarif).expect("serialize SARIF");
 
         assert_eq!(value["version"], "2.1.0");
@@ -1488,21 +2394,135 @@ mod tests {
         assert!(value["runs"][0]["automationDetails"].is_null());
     }
 
+    #[test]
+    fn invocation_records_rule_configuration_overrides() {
+        let options = resolve_configured_rule_options(
+            None,
+            &serde_json::from_value(json!({"MAGIC_NUMBER": {"allowedValues": [42]}}))
+                .expect("rule options"),
+        )
+        .expect("resolve rule options");
+        let invocation = build_invocation(
+            &InvocationStats {
+                scan_duration_ms: 0,
+                classpath_duration_ms: 0,
+                analysis_call_graph_duration_ms: 0,
+                analysis_artifact_duration_ms: 0,
+                analysis_call_graph_hierarchy_duration_ms: 0,
+                analysis_call_graph_index_duration_ms: 0,
+                analysis_call_graph_edges_duration_ms: 0,
+                analysis_rules_duration_ms: 0,
+                class_count: 0,
+                header_class_count: 0,
+                artifact_count: 0,
+                classpath_class_count: 0,
+                rule_stats: Vec::new(),
+                cache_stats: None,
+            },
+            rule_configuration_overrides(&options, &["MAGIC_NUMBER", "SYSTEM_EXIT"]),
+            Vec::new(),
+        );
+
+        let value = serde_json::to_value(&invocation).expect("serialize invocation");
+        assert_eq!(
+            value["ruleConfigurationOverrides"],
+            json!([{
+                "configuration": {"parameters": {"allowedValues": [42]}},
+                "descriptor": {"id": "MAGIC_NUMBER"},
+            }])
+        );
+    }
+
+    #[test]
+    fn invocation_records_rule_stats_and_failures() {
+        let failure = Notification::builder()
+            .message(crate::rules::result_message(
+                "Rule SYSTEM_EXIT failed: broken",
+            ))
+            .level(json!("error"))
+            .build();
+        let invocation = build_invocation(
+            &InvocationStats {
+                scan_duration_ms: 0,
+                classpath_duration_ms: 0,
+                analysis_call_graph_duration_ms: 0,
+                analysis_artifact_duration_ms: 0,
+                analysis_call_graph_hierarchy_duration_ms: 0,
+                analysis_call_graph_index_duration_ms: 0,
+                analysis_call_graph_edges_duration_ms: 0,
+                analysis_rules_duration_ms: 0,
+                class_count: 5,
+                header_class_count: 3,
+                artifact_count: 0,
+                classpath_class_count: 0,
+                rule_stats: vec![RuleRunStats {
+                    rule_id: "MAGIC_NUMBER".to_string(),
+                    duration_ms: 12,
+                    target_classes: 3,
+                    target_methods: 9,
+                    result_count: 1,
+                }],
+                cache_stats: Some(CacheStats {
+                    class_hits: 4,
+                    class_misses: 1,
+                    result_hits: 2,
+                    result_misses: 3,
+                }),
+            },
+            Vec::new(),
+            vec![failure],
+        );
+
+        let value = serde_json::to_value(&invocation).expect("serialize invocation");
+        assert_eq!(value["executionSuccessful"], false);
+        assert_eq!(value["properties"]["inspequte.full_class_count"], 2);
+        assert_eq!(value["properties"]["inspequte.header_class_count"], 3);
+        assert_eq!(
+            value["properties"]["inspequte.rule_stats"]["MAGIC_NUMBER"],
+            json!({
+                "duration_ms": 12,
+                "target_classes": 3,
+                "target_methods": 9,
+                "result_count": 1,
+            })
+        );
+        assert_eq!(
+            value["properties"]["inspequte.cache"],
+            json!({
+                "class_hits": 4,
+                "class_misses": 1,
+                "result_hits": 2,
+                "result_misses": 3,
+            })
+        );
+        assert_eq!(
+            value["toolExecutionNotifications"][0]["message"]["text"],
+            "Rule SYSTEM_EXIT failed: broken"
+        );
+    }
+
     #[test]
     fn sarif_includes_automation_details_id_when_requested() {
-        let invocation = build_invocation(&InvocationStats {
-            scan_duration_ms: 0,
-            classpath_duration_ms: 0,
-            analysis_call_graph_duration_ms: 0,
-            analysis_artifact_duration_ms: 0,
-            analysis_call_graph_hierarchy_duration_ms: 0,
-            analysis_call_graph_index_duration_ms: 0,
-            analysis_call_graph_edges_duration_ms: 0,
-            analysis_rules_duration_ms: 0,
-            class_count: 0,
-            artifact_count: 0,
-            classpath_class_count: 0,
-        });
+        let invocation = build_invocation(
+            &InvocationStats {
+                scan_duration_ms: 0,
+                classpath_duration_ms: 0,
+                analysis_call_graph_duration_ms: 0,
+                analysis_artifact_duration_ms: 0,
+                analysis_call_graph_hierarchy_duration_ms: 0,
+                analysis_call_graph_index_duration_ms: 0,
+                analysis_call_graph_edges_duration_ms: 0,
+                analysis_rules_duration_ms: 0,
+                class_count: 0,
+                header_class_count: 0,
+                artifact_count: 0,
+                classpath_class_count: 0,
+                rule_stats: Vec::new(),
+                cache_stats: None,
+            },
+            Vec::new(),
+            Vec::new(),
+        );
         let sarif = build_sarif(
             None,
             Vec::new(),
@@ -1510,6 +2530,7 @@ mod tests {
             Vec::new(),
             Vec::new(),
             Some("inspequte/./main".to_string()),
+            None,
         );
         let value = serde_json::to_value(&sarif).expect("serialize SARIF");
 
@@ -1519,6 +2540,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sarif_declares_srcroot_base_when_source_roots_are_used() {
+        let invocation = Invocation::builder()
+            .execution_successful(true)
+            .arguments(Vec::<String>::new())
+            .build();
+        let sarif = build_sarif(
+            None,
+            Vec::new(),
+            invocation,
+            Vec::new(),
+            Vec::new(),
+            None,
+            Some(Path::new("/work/project")),
+        );
+        let value = serde_json::to_value(&sarif).expect("serialize SARIF");
+
+        assert_eq!(
+            value["runs"][0]["originalUriBaseIds"]["%SRCROOT%"]["uri"],
+            "file:///work/project/"
+        );
+    }
+
+    #[test]
+    fn sarif_declares_cwe_taxonomy_for_related_rules() {
+        let invocation = Invocation::builder()
+            .execution_successful(true)
+            .arguments(Vec::<String>::new())
+            .build();
+        let allowed = BTreeSet::from(["DESERIALIZATION_READ_OBJECT_CALL".to_string()]);
+        let engine = Engine::new_with_allowed_rule_ids(Some(&allowed)).expect("build engine");
+        let analysis = engine
+            .analyze(build_context(Vec::new(), &[]))
+            .expect("analysis");
+        let sarif = build_sarif(
+            None,
+            Vec::new(),
+            invocation,
+            analysis.rules,
+            Vec::new(),
+            None,
+            None,
+        );
+        let value = serde_json::to_value(&sarif).expect("serialize SARIF");
+
+        let run = &value["runs"][0];
+        let rule = &run["tool"]["driver"]["rules"][0];
+        assert_eq!(rule["properties"]["precision"], "medium");
+        assert_eq!(rule["properties"]["security-severity"], "8.1");
+        assert_eq!(
+            rule["properties"]["tags"],
+            json!(["security", "external/cwe/cwe-502"])
+        );
+        assert_eq!(rule["relationships"][0]["target"]["id"], "502");
+        assert_eq!(
+            rule["relationships"][0]["target"]["toolComponent"]["name"],
+            "CWE"
+        );
+        assert_eq!(
+            run["tool"]["driver"]["supportedTaxonomies"][0]["name"],
+            "CWE"
+        );
+        assert_eq!(run["taxonomies"][0]["name"], "CWE");
+        assert_eq!(run["taxonomies"][0]["taxa"][0]["id"], "502");
+    }
+
     #[test]
     fn sarif_callgraph_snapshot() {
         let temp_dir = make_temp_test_dir();
@@ -1529,7 +2616,8 @@ mod tests {
         fs::write(temp_dir.join("A.class"), class_a).expect("write A.class");
         fs::write(temp_dir.join("B.class"), class_b).expect("write B.class");
 
-        let scan = scan_inputs(&[temp_dir.clone()], &[], None).expect("scan classes");
+        let scan =
+            scan_inputs(std::slice::from_ref(&temp_dir), &[], None, None).expect("scan classes");
         let artifacts = scan.artifacts.clone();
         let context = build_context(scan.classes.clone(), &artifacts);
         let engine = Engine::new_with_allowed_rule_ids(None).expect("build engine");
@@ -1546,6 +2634,7 @@ mod tests {
             analysis.rules,
             analysis.results,
             None,
+            None,
         );
         let mut actual_value = serde_json::to_value(&sarif).expect("serialize SARIF");
         normalize_sarif_for_snapshot(&mut actual_value);
@@ -1580,6 +2669,15 @@ mod tests {
             "semanticVersion".to_string(),
             serde_json::Value::String("0.0.0".to_string()),
         );
+        // Rule help embeds each spec.md verbatim; spec edits should not churn the snapshot.
+        let rules = driver
+            .get_mut("rules")
+            .and_then(serde_json::Value::as_array_mut);
+        for rule in rules.into_iter().flatten() {
+            if let Some(markdown) = rule.pointer_mut("/help/markdown") {
+                *markdown = serde_json::Value::String("<spec.md>".to_string());
+            }
+        }
     }
 
     fn snapshot_path(name: &str) -> PathBuf {
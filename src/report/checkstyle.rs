@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use anyhow::Result;
+use serde_sarif::sarif::{ResultLevel, Run};
+
+use super::{Finding, SourcePathIndex, findings, run_results, xml_escape};
+
+/// Write findings as Checkstyle XML, grouped by source file.
+pub(super) fn write(
+    run: Option<&Run>,
+    sources: &SourcePathIndex,
+    writer: &mut dyn Write,
+) -> Result<()> {
+    let mut by_file: BTreeMap<String, Vec<Finding<'_>>> = BTreeMap::new();
+    for finding in findings(run_results(run), sources) {
+        by_file
+            .entry(finding.path.clone())
+            .or_default()
+            .push(finding);
+    }
+
+    writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(writer, "<checkstyle version=\"4.3\">")?;
+    for (path, findings) in &by_file {
+        writeln!(writer, "  <file name=\"{}\">", xml_escape(path))?;
+        for finding in findings {
+            write!(writer, "    <error")?;
+            if let Some(line) = finding.line {
+                write!(writer, " line=\"{line}\"")?;
+            }
+            writeln!(
+                writer,
+                " severity=\"{}\" message=\"{}\" source=\"inspequte.{}\"/>",
+                severity(finding.level),
+                xml_escape(finding.message),
+                xml_escape(finding.rule_id)
+            )?;
+        }
+        writeln!(writer, "  </file>")?;
+    }
+    writeln!(writer, "</checkstyle>")?;
+    Ok(())
+}
+
+fn severity(level: ResultLevel) -> &'static str {
+    match level {
+        ResultLevel::Error => "error",
+        ResultLevel::Warning => "warning",
+        ResultLevel::Note => "info",
+        ResultLevel::None => "ignore",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::tests::{sample_result, sample_sarif, sample_sources};
+    use crate::rules::method_location_with_line;
+
+    fn render(results: Vec<serde_sarif::sarif::Result>) -> String {
+        let sarif = sample_sarif(results);
+        let mut output = Vec::new();
+        write(sarif.runs.first(), &sample_sources(), &mut output).expect("write checkstyle");
+        String::from_utf8(output).expect("utf8")
+    }
+
+    #[test]
+    fn checkstyle_groups_errors_by_source_file() {
+        let first = method_location_with_line(
+            "com/example/ClassA",
+            "methodOne",
+            "()V",
+            Some("file:///tmp/com/example/ClassA.class"),
+            Some(10),
+        );
+        let second = method_location_with_line(
+            "com/example/ClassA$Inner",
+            "methodTwo",
+            "()V",
+            Some("file:///tmp/com/example/ClassA$Inner.class"),
+            None,
+        );
+
+        let output = render(vec![
+            sample_result("RULE_A", first, "first <finding>"),
+            sample_result("RULE_B", second, "second finding"),
+        ]);
+
+        assert_eq!(
+            output,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+<checkstyle version=\"4.3\">\n  <file name=\"com/example/ClassA.java\">\n    \
+<error line=\"10\" severity=\"warning\" message=\"first &lt;finding&gt;\" source=\"inspequte.RULE_A\"/>\n    \
+<error severity=\"warning\" message=\"second finding\" source=\"inspequte.RULE_B\"/>\n  \
+</file>\n</checkstyle>\n"
+        );
+    }
+
+    #[test]
+    fn checkstyle_without_results_has_no_file_elements() {
+        let output = render(Vec::new());
+
+        assert!(!output.contains("<file"));
+        assert!(output.ends_with("</checkstyle>\n"));
+    }
+}
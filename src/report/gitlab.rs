@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_sarif::sarif::{ResultLevel, Run};
+
+use super::{Finding, SourcePathIndex, findings, run_results};
+
+/// Code Quality issue in the Code Climate JSON shape understood by GitLab.
+#[derive(Debug, Serialize)]
+struct CodeQualityIssue {
+    description: String,
+    check_name: String,
+    fingerprint: String,
+    severity: &'static str,
+    location: CodeQualityLocation,
+}
+
+/// File location of a Code Quality issue.
+#[derive(Debug, Serialize)]
+struct CodeQualityLocation {
+    path: String,
+    lines: CodeQualityLines,
+}
+
+/// Line range of a Code Quality issue.
+#[derive(Debug, Serialize)]
+struct CodeQualityLines {
+    begin: i64,
+}
+
+/// Write findings as a GitLab Code Quality report.
+pub(super) fn write(
+    run: Option<&Run>,
+    sources: &SourcePathIndex,
+    writer: &mut dyn Write,
+) -> Result<()> {
+    let findings = findings(run_results(run), sources);
+    let fingerprints = fingerprints(&findings);
+    let issues: Vec<CodeQualityIssue> = findings
+        .iter()
+        .zip(fingerprints)
+        .map(|(finding, fingerprint)| CodeQualityIssue {
+            description: finding.message.to_string(),
+            check_name: finding.rule_id.to_string(),
+            fingerprint,
+            severity: severity(finding.level),
+            location: CodeQualityLocation {
+                path: finding.path.clone(),
+                lines: CodeQualityLines {
+                    begin: finding.line.unwrap_or(1),
+                },
+            },
+        })
+        .collect();
+    serde_json::to_writer(&mut *writer, &issues)?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Fingerprint of each finding, in `findings` order.
+///
+/// Line numbers are left out so unrelated edits above a finding keep it stable. Identical
+/// findings are told apart by their rank in line order among themselves, so the fingerprint
+/// does not depend on the order of the other findings.
+fn fingerprints(findings: &[Finding<'_>]) -> Vec<String> {
+    let mut groups: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (index, finding) in findings.iter().enumerate() {
+        groups
+            .entry(fingerprint_key(finding))
+            .or_default()
+            .push(index);
+    }
+    let mut fingerprints = vec![String::new(); findings.len()];
+    for (key, mut indices) in groups {
+        indices.sort_by_key(|index| findings[*index].line);
+        for (rank, index) in indices.into_iter().enumerate() {
+            fingerprints[index] = match rank {
+                0 => fnv1a_128(&key),
+                _ => fnv1a_128(&format!("{key}\0{}", rank + 1)),
+            };
+        }
+    }
+    fingerprints
+}
+
+fn fingerprint_key(finding: &Finding<'_>) -> String {
+    format!(
+        "{}\0{}\0{}\0{}",
+        finding.rule_id,
+        finding.logical_name.unwrap_or_default(),
+        finding.path,
+        finding.message
+    )
+}
+
+fn fnv1a_128(value: &str) -> String {
+    const FNV_OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const FNV_PRIME: u128 = 0x0000000001000000000000000000013b;
+
+    let mut hash = FNV_OFFSET_BASIS;
+    for byte in value.as_bytes() {
+        hash ^= u128::from(*byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    format!("{hash:032x}")
+}
+
+fn severity(level: ResultLevel) -> &'static str {
+    match level {
+        ResultLevel::Error => "critical",
+        ResultLevel::Warning => "major",
+        ResultLevel::Note => "minor",
+        ResultLevel::None => "info",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    use crate::report::tests::{sample_result, sample_sarif, sample_sources};
+    use crate::rules::method_location_with_line;
+
+    fn render(results: Vec<serde_sarif::sarif::Result>) -> Value {
+        let sarif = sample_sarif(results);
+        let mut output = Vec::new();
+        write(sarif.runs.first(), &sample_sources(), &mut output).expect("write gitlab");
+        serde_json::from_slice(&output).expect("valid JSON")
+    }
+
+    fn location(line: Option<u32>) -> serde_sarif::sarif::Location {
+        method_location_with_line(
+            "com/example/ClassA",
+            "methodOne",
+            "()V",
+            Some("file:///tmp/com/example/ClassA.class"),
+            line,
+        )
+    }
+
+    #[test]
+    fn gitlab_maps_findings_to_code_quality_issues() {
+        let issues = render(vec![sample_result("RULE_A", location(Some(10)), "message")]);
+
+        let issue = &issues[0];
+        assert_eq!(issue["description"], "message");
+        assert_eq!(issue["check_name"], "RULE_A");
+        assert_eq!(issue["severity"], "major");
+        assert_eq!(issue["location"]["path"], "com/example/ClassA.java");
+        assert_eq!(issue["location"]["lines"]["begin"], 10);
+        assert_eq!(
+            issue["fingerprint"].as_str().expect("fingerprint").len(),
+            32
+        );
+    }
+
+    #[test]
+    fn gitlab_fingerprints_ignore_line_shifts() {
+        let before = render(vec![sample_result("RULE_A", location(Some(10)), "message")]);
+        let after = render(vec![sample_result("RULE_A", location(Some(14)), "message")]);
+
+        assert_eq!(before[0]["fingerprint"], after[0]["fingerprint"]);
+    }
+
+    #[test]
+    fn gitlab_fingerprints_are_unique_for_repeated_findings() {
+        let issues = render(vec![
+            sample_result("RULE_A", location(Some(10)), "message"),
+            sample_result("RULE_A", location(Some(12)), "message"),
+        ]);
+
+        assert_ne!(issues[0]["fingerprint"], issues[1]["fingerprint"]);
+    }
+
+    #[test]
+    fn gitlab_fingerprints_do_not_depend_on_finding_order() {
+        let forward = render(vec![
+            sample_result("RULE_A", location(Some(10)), "message"),
+            sample_result("RULE_A", location(Some(12)), "message"),
+            sample_result("RULE_B", location(Some(11)), "other"),
+        ]);
+        let reversed = render(vec![
+            sample_result("RULE_B", location(Some(11)), "other"),
+            sample_result("RULE_A", location(Some(12)), "message"),
+            sample_result("RULE_A", location(Some(10)), "message"),
+        ]);
+
+        assert_eq!(forward[0]["fingerprint"], reversed[2]["fingerprint"]);
+        assert_eq!(forward[1]["fingerprint"], reversed[1]["fingerprint"]);
+        assert_eq!(forward[2]["fingerprint"], reversed[0]["fingerprint"]);
+    }
+
+    #[test]
+    fn gitlab_defaults_to_first_line_without_region() {
+        let issues = render(vec![sample_result("RULE_A", location(None), "message")]);
+
+        assert_eq!(issues[0]["location"]["lines"]["begin"], 1);
+    }
+}
@@ -0,0 +1,323 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use anyhow::Result;
+use serde_sarif::sarif::Run;
+
+use super::{Finding, SourcePathIndex, findings, run_results, xml_escape};
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem;color:#1f2328}\
+h1{margin-bottom:0}.meta{color:#59636e}\
+table{border-collapse:collapse;width:100%;margin:1rem 0}\
+th,td{border:1px solid #d1d9e0;padding:.35rem .6rem;text-align:left;vertical-align:top}\
+th{background:#f6f8fa}td.count{text-align:right}tr.clean td{color:#59636e}\
+code{font-size:.9em}.filters{display:flex;gap:.5rem}\
+.filters input{flex:1;padding:.3rem}details{margin:.3rem 0}summary{cursor:pointer}\
+dt{font-weight:600;margin-top:.6rem}dd{margin-left:1rem}";
+
+const SCRIPT: &str = "(function(){\
+var text=document.getElementById('finding-filter');\
+var rule=document.getElementById('rule-filter');\
+var rows=document.querySelectorAll('#findings tbody tr');\
+function apply(){var q=text.value.toLowerCase();var r=rule.value;\
+rows.forEach(function(row){\
+var show=(!r||row.dataset.rule===r)&&(!q||row.textContent.toLowerCase().indexOf(q)>=0);\
+row.hidden=!show;});}\
+text.addEventListener('input',apply);rule.addEventListener('change',apply);})();";
+
+/// Write a single self-contained HTML report.
+///
+/// Only deterministic run data is rendered (rules, results, artifact and class counts);
+/// timings and the command line are omitted so identical inputs produce identical bytes.
+pub(super) fn write(
+    run: Option<&Run>,
+    sources: &SourcePathIndex,
+    writer: &mut dyn Write,
+) -> Result<()> {
+    let findings = findings(run_results(run), sources);
+    let rules = RuleInfo::collect(run, &findings);
+    let mut by_class: BTreeMap<String, Vec<&Finding<'_>>> = BTreeMap::new();
+    for finding in &findings {
+        let class_name = finding
+            .class_name
+            .map(|name| name.replace('/', "."))
+            .unwrap_or_default();
+        by_class.entry(class_name).or_default().push(finding);
+    }
+    let version = run
+        .and_then(|run| run.tool.driver.semantic_version.as_deref())
+        .unwrap_or_default();
+
+    writeln!(writer, "<!DOCTYPE html>")?;
+    writeln!(writer, "<html lang=\"en\">")?;
+    writeln!(writer, "<head>")?;
+    writeln!(writer, "<meta charset=\"utf-8\">")?;
+    writeln!(writer, "<title>inspequte report</title>")?;
+    writeln!(writer, "<style>{STYLE}</style>")?;
+    writeln!(writer, "</head>")?;
+    writeln!(writer, "<body>")?;
+    writeln!(writer, "<h1>inspequte report</h1>")?;
+    writeln!(
+        writer,
+        "<p class=\"meta\">inspequte {} &middot; {} findings &middot; {} rules &middot; {} classes with findings</p>",
+        xml_escape(version),
+        findings.len(),
+        rules.len(),
+        by_class.len()
+    )?;
+
+    writeln!(writer, "<h2>Rule statistics</h2>")?;
+    writeln!(
+        writer,
+        "<table class=\"summary\"><thead><tr><th>Rule</th><th>Name</th><th>Findings</th></tr></thead><tbody>"
+    )?;
+    for (rule_id, rule) in &rules {
+        let class = if rule.count == 0 {
+            " class=\"clean\""
+        } else {
+            ""
+        };
+        writeln!(
+            writer,
+            "<tr{class}><td><a href=\"#rule-{id}\"><code>{id}</code></a></td><td>{}</td><td class=\"count\">{}</td></tr>",
+            xml_escape(rule.name),
+            rule.count,
+            id = xml_escape(rule_id)
+        )?;
+    }
+    writeln!(writer, "</tbody></table>")?;
+
+    writeln!(writer, "<h2>Findings</h2>")?;
+    writeln!(writer, "<div class=\"filters\">")?;
+    writeln!(
+        writer,
+        "<input id=\"finding-filter\" type=\"search\" placeholder=\"Filter findings\" aria-label=\"Filter findings\">"
+    )?;
+    writeln!(
+        writer,
+        "<select id=\"rule-filter\" aria-label=\"Filter by rule\"><option value=\"\">All rules</option>"
+    )?;
+    for (rule_id, rule) in &rules {
+        if rule.count > 0 {
+            let id = xml_escape(rule_id);
+            writeln!(writer, "<option value=\"{id}\">{id}</option>")?;
+        }
+    }
+    writeln!(writer, "</select>")?;
+    writeln!(writer, "</div>")?;
+    writeln!(
+        writer,
+        "<table id=\"findings\"><thead><tr><th>Rule</th><th>Location</th><th>Message</th></tr></thead><tbody>"
+    )?;
+    for finding in &findings {
+        let id = xml_escape(finding.rule_id);
+        writeln!(
+            writer,
+            "<tr data-rule=\"{id}\"><td><code>{id}</code></td><td><code>{}</code></td><td>{}</td></tr>",
+            xml_escape(&file_reference(finding)),
+            xml_escape(finding.message)
+        )?;
+    }
+    writeln!(writer, "</tbody></table>")?;
+
+    writeln!(writer, "<h2>Classes</h2>")?;
+    for (class_name, findings) in &by_class {
+        let label = if class_name.is_empty() {
+            "(no class)"
+        } else {
+            class_name.as_str()
+        };
+        writeln!(
+            writer,
+            "<details><summary><code>{}</code> ({})</summary><ul>",
+            xml_escape(label),
+            findings.len()
+        )?;
+        for finding in findings {
+            writeln!(
+                writer,
+                "<li><code>{}</code> <code>{}</code>: {}</li>",
+                xml_escape(finding.rule_id),
+                xml_escape(&file_reference(finding)),
+                xml_escape(finding.message)
+            )?;
+        }
+        writeln!(writer, "</ul></details>")?;
+    }
+
+    writeln!(writer, "<h2>Rules</h2>")?;
+    writeln!(writer, "<dl>")?;
+    for (rule_id, rule) in &rules {
+        let id = xml_escape(rule_id);
+        writeln!(
+            writer,
+            "<dt id=\"rule-{id}\"><code>{id}</code> {}</dt><dd>{}</dd>",
+            xml_escape(rule.name),
+            xml_escape(rule.description)
+        )?;
+    }
+    writeln!(writer, "</dl>")?;
+
+    writeln!(writer, "<h2>Analysis statistics</h2>")?;
+    writeln!(writer, "<table class=\"stats\"><tbody>")?;
+    for (key, value) in run_statistics(run) {
+        writeln!(
+            writer,
+            "<tr><th>{}</th><td class=\"count\">{value}</td></tr>",
+            xml_escape(&key)
+        )?;
+    }
+    writeln!(writer, "</tbody></table>")?;
+
+    writeln!(writer, "<script>{SCRIPT}</script>")?;
+    writeln!(writer, "</body>")?;
+    writeln!(writer, "</html>")?;
+    Ok(())
+}
+
+/// Rule descriptor fields rendered in the report, with the number of findings.
+struct RuleInfo<'a> {
+    name: &'a str,
+    description: &'a str,
+    count: usize,
+}
+
+impl<'a> RuleInfo<'a> {
+    fn collect(run: Option<&'a Run>, findings: &[Finding<'a>]) -> BTreeMap<&'a str, Self> {
+        let mut rules = BTreeMap::new();
+        if let Some(descriptors) = run.and_then(|run| run.tool.driver.rules.as_ref()) {
+            for descriptor in descriptors {
+                let description = descriptor
+                    .short_description
+                    .as_ref()
+                    .map(|description| description.text.as_str())
+                    .unwrap_or_default();
+                rules.insert(
+                    descriptor.id.as_str(),
+                    RuleInfo {
+                        name: descriptor.name.as_deref().unwrap_or_default(),
+                        description,
+                        count: 0,
+                    },
+                );
+            }
+        }
+        for finding in findings {
+            rules
+                .entry(finding.rule_id)
+                .or_insert(RuleInfo {
+                    name: "",
+                    description: "",
+                    count: 0,
+                })
+                .count += 1;
+        }
+        rules
+    }
+}
+
+/// Deterministic counters from the run: artifact count and invocation `*_count` properties.
+fn run_statistics(run: Option<&Run>) -> Vec<(String, String)> {
+    let mut statistics = Vec::new();
+    let Some(run) = run else {
+        return statistics;
+    };
+    let artifacts = run.artifacts.as_ref().map_or(0, Vec::len);
+    statistics.push(("artifacts".to_string(), artifacts.to_string()));
+    let properties = run
+        .invocations
+        .as_ref()
+        .and_then(|invocations| invocations.first())
+        .and_then(|invocation| invocation.properties.as_ref());
+    if let Some(properties) = properties {
+        for (key, value) in &properties.additional_properties {
+            let name = key.strip_prefix("inspequte.").unwrap_or(key);
+            if name.ends_with("_count") {
+                statistics.push((name.to_string(), value.to_string()));
+            }
+        }
+    }
+    statistics
+}
+
+fn file_reference(finding: &Finding<'_>) -> String {
+    match finding.line {
+        Some(line) => format!("{}:{line}", finding.path),
+        None => finding.path.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use serde_sarif::sarif::{Invocation, PropertyBag};
+
+    use crate::report::tests::{sample_result, sample_sarif, sample_sources};
+    use crate::rules::method_location_with_line;
+
+    fn sample_results() -> Vec<serde_sarif::sarif::Result> {
+        let location = method_location_with_line(
+            "com/example/ClassA",
+            "methodOne",
+            "()V",
+            Some("file:///tmp/com/example/ClassA.class"),
+            Some(10),
+        );
+        vec![sample_result("RULE_A", location, "compare <a> & <b>")]
+    }
+
+    fn render(results: Vec<serde_sarif::sarif::Result>) -> String {
+        let mut sarif = sample_sarif(results);
+        let mut properties = BTreeMap::new();
+        properties.insert("inspequte.class_count".to_string(), json!(3));
+        properties.insert("inspequte.scan_ms".to_string(), json!(42));
+        sarif.runs[0].invocations = Some(vec![
+            Invocation::builder()
+                .execution_successful(true)
+                .properties(
+                    PropertyBag::builder()
+                        .additional_properties(properties)
+                        .build(),
+                )
+                .build(),
+        ]);
+        let mut output = Vec::new();
+        write(sarif.runs.first(), &sample_sources(), &mut output).expect("write html");
+        String::from_utf8(output).expect("utf8")
+    }
+
+    #[test]
+    fn html_is_self_contained_and_escapes_messages() {
+        let output = render(sample_results());
+
+        assert!(output.starts_with("<!DOCTYPE html>\n"));
+        assert!(!output.contains("<link"));
+        assert!(!output.contains("src=\""));
+        assert!(output.contains(
+            "<tr data-rule=\"RULE_A\"><td><code>RULE_A</code></td><td><code>com/example/ClassA.java:10</code></td><td>compare &lt;a&gt; &amp; &lt;b&gt;</td></tr>"
+        ));
+    }
+
+    #[test]
+    fn html_lists_rules_without_findings_and_class_drill_down() {
+        let output = render(sample_results());
+
+        assert!(output.contains("<tr class=\"clean\"><td><a href=\"#rule-RULE_B\">"));
+        assert!(output.contains("<details><summary><code>com.example.ClassA</code> (1)</summary>"));
+        assert!(output.contains("<dt id=\"rule-RULE_B\"><code>RULE_B</code> Rule B</dt>"));
+    }
+
+    #[test]
+    fn html_reports_counts_but_not_timings() {
+        let output = render(sample_results());
+
+        assert!(output.contains("<tr><th>class_count</th><td class=\"count\">3</td></tr>"));
+        assert!(!output.contains("scan_ms"));
+    }
+
+    #[test]
+    fn html_is_deterministic() {
+        assert_eq!(render(sample_results()), render(sample_results()));
+    }
+}
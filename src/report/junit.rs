@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use anyhow::Result;
+use serde_sarif::sarif::Run;
+
+use super::{Finding, SourcePathIndex, findings, run_results, xml_escape};
+
+/// Write findings as a JUnit XML report with one test suite per rule.
+///
+/// Rules without findings are emitted as a single passing test case so CI systems
+/// show which checks ran; each finding becomes a failed test case.
+pub(super) fn write(
+    run: Option<&Run>,
+    sources: &SourcePathIndex,
+    writer: &mut dyn Write,
+) -> Result<()> {
+    let mut by_rule: BTreeMap<String, Vec<Finding<'_>>> = BTreeMap::new();
+    if let Some(rules) = run.and_then(|run| run.tool.driver.rules.as_ref()) {
+        for rule in rules {
+            by_rule.entry(rule.id.clone()).or_default();
+        }
+    }
+    let findings = findings(run_results(run), sources);
+    let failures = findings.len();
+    for finding in findings {
+        by_rule
+            .entry(finding.rule_id.to_string())
+            .or_default()
+            .push(finding);
+    }
+    let tests: usize = by_rule.values().map(|findings| findings.len().max(1)).sum();
+
+    writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(
+        writer,
+        "<testsuites name=\"inspequte\" tests=\"{tests}\" failures=\"{failures}\">"
+    )?;
+    for (rule_id, findings) in &by_rule {
+        let rule_id = xml_escape(rule_id);
+        writeln!(
+            writer,
+            "  <testsuite name=\"{rule_id}\" tests=\"{}\" failures=\"{}\">",
+            findings.len().max(1),
+            findings.len()
+        )?;
+        if findings.is_empty() {
+            writeln!(
+                writer,
+                "    <testcase classname=\"inspequte\" name=\"{rule_id}\"/>"
+            )?;
+        }
+        for finding in findings {
+            writeln!(
+                writer,
+                "    <testcase classname=\"{}\" name=\"{}\">",
+                xml_escape(&test_class_name(finding)),
+                xml_escape(&test_name(finding))
+            )?;
+            writeln!(
+                writer,
+                "      <failure message=\"{}\" type=\"{rule_id}\">{}</failure>",
+                xml_escape(finding.message),
+                xml_escape(&failure_location(finding))
+            )?;
+            writeln!(writer, "    </testcase>")?;
+        }
+        writeln!(writer, "  </testsuite>")?;
+    }
+    writeln!(writer, "</testsuites>")?;
+    Ok(())
+}
+
+fn test_class_name(finding: &Finding<'_>) -> String {
+    finding
+        .class_name
+        .map(|name| name.replace('/', "."))
+        .unwrap_or_else(|| "inspequte".to_string())
+}
+
+fn test_name(finding: &Finding<'_>) -> String {
+    let member = match (finding.class_name, finding.logical_name) {
+        (Some(class_name), Some(logical_name)) => logical_name
+            .strip_prefix(class_name)
+            .and_then(|rest| rest.strip_prefix('.')),
+        _ => None,
+    };
+    match member {
+        Some(member) => format!("{}: {member}", finding.rule_id),
+        None => finding.rule_id.to_string(),
+    }
+}
+
+fn failure_location(finding: &Finding<'_>) -> String {
+    match finding.line {
+        Some(line) => format!("{}:{line}", finding.path),
+        None => finding.path.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::tests::{sample_result, sample_sarif, sample_sources};
+    use crate::rules::method_location_with_line;
+
+    fn render(results: Vec<serde_sarif::sarif::Result>) -> String {
+        let sarif = sample_sarif(results);
+        let mut output = Vec::new();
+        write(sarif.runs.first(), &sample_sources(), &mut output).expect("write junit");
+        String::from_utf8(output).expect("utf8")
+    }
+
+    #[test]
+    fn junit_reports_findings_as_failures_and_clean_rules_as_passes() {
+        let location = method_location_with_line(
+            "com/example/ClassA",
+            "methodOne",
+            "()V",
+            Some("file:///tmp/com/example/ClassA.class"),
+            Some(10),
+        );
+
+        let output = render(vec![sample_result("RULE_A", location, "bad & wrong")]);
+
+        assert_eq!(
+            output,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+<testsuites name=\"inspequte\" tests=\"2\" failures=\"1\">\n  \
+<testsuite name=\"RULE_A\" tests=\"1\" failures=\"1\">\n    \
+<testcase classname=\"com.example.ClassA\" name=\"RULE_A: methodOne()V\">\n      \
+<failure message=\"bad &amp; wrong\" type=\"RULE_A\">com/example/ClassA.java:10</failure>\n    \
+</testcase>\n  </testsuite>\n  \
+<testsuite name=\"RULE_B\" tests=\"1\" failures=\"0\">\n    \
+<testcase classname=\"inspequte\" name=\"RULE_B\"/>\n  </testsuite>\n</testsuites>\n"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use anyhow::Result;
+use serde_sarif::sarif::Run;
+
+use super::{Finding, SourcePathIndex, findings, rule_names, run_results};
+
+/// Write findings as Markdown for PR comments: a summary table by rule followed by
+/// collapsible per-rule details.
+pub(super) fn write(
+    run: Option<&Run>,
+    sources: &SourcePathIndex,
+    writer: &mut dyn Write,
+) -> Result<()> {
+    let names = rule_names(run);
+    let mut by_rule: BTreeMap<&str, Vec<Finding<'_>>> = BTreeMap::new();
+    let findings = findings(run_results(run), sources);
+    let total = findings.len();
+    for finding in findings {
+        by_rule.entry(finding.rule_id).or_default().push(finding);
+    }
+
+    writeln!(writer, "## inspequte findings")?;
+    writeln!(writer)?;
+    if total == 0 {
+        writeln!(writer, "No findings.")?;
+        return Ok(());
+    }
+
+    writeln!(writer, "| Rule | Description | Findings |")?;
+    writeln!(writer, "| --- | --- | ---: |")?;
+    for (rule_id, findings) in &by_rule {
+        let name = names.get(rule_id).copied().unwrap_or_default();
+        writeln!(
+            writer,
+            "| `{rule_id}` | {} | {} |",
+            table_escape(name),
+            findings.len()
+        )?;
+    }
+    writeln!(writer, "| **Total** | | **{total}** |")?;
+
+    for (rule_id, findings) in &by_rule {
+        writeln!(writer)?;
+        writeln!(writer, "<details>")?;
+        write!(writer, "<summary><code>{rule_id}</code>")?;
+        if let Some(name) = names.get(rule_id) {
+            write!(writer, " {}", html_escape(name))?;
+        }
+        writeln!(writer, " ({})</summary>", findings.len())?;
+        writeln!(writer)?;
+        for finding in findings {
+            let reference = match finding.line {
+                Some(line) => format!("{}:{line}", finding.path),
+                None => finding.path.clone(),
+            };
+            writeln!(
+                writer,
+                "- `{}`: {}",
+                reference.replace('`', "'"),
+                html_escape(finding.message)
+            )?;
+        }
+        writeln!(writer)?;
+        writeln!(writer, "</details>")?;
+    }
+    Ok(())
+}
+
+/// Escape characters that would otherwise be read as inline HTML.
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn table_escape(value: &str) -> String {
+    html_escape(value).replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::tests::{sample_result, sample_sarif, sample_sources};
+    use crate::rules::method_location_with_line;
+
+    fn render(results: Vec<serde_sarif::sarif::Result>) -> String {
+        let sarif = sample_sarif(results);
+        let mut output = Vec::new();
+        write(sarif.runs.first(), &sample_sources(), &mut output).expect("write markdown");
+        String::from_utf8(output).expect("utf8")
+    }
+
+    #[test]
+    fn markdown_renders_summary_table_and_details() {
+        let location = method_location_with_line(
+            "com/example/ClassA",
+            "methodOne",
+            "()V",
+            Some("file:///tmp/com/example/ClassA.class"),
+            Some(10),
+        );
+
+        let output = render(vec![sample_result(
+            "RULE_A",
+            location,
+            "call to List<String>.get()",
+        )]);
+
+        assert_eq!(
+            output,
+            "## inspequte findings\n\n\
+| Rule | Description | Findings |\n| --- | --- | ---: |\n\
+| `RULE_A` | Rule A | 1 |\n| **Total** | | **1** |\n\n\
+<details>\n<summary><code>RULE_A</code> Rule A (1)</summary>\n\n\
+- `com/example/ClassA.java:10`: call to List&lt;String&gt;.get()\n\n</details>\n"
+        );
+    }
+
+    #[test]
+    fn markdown_reports_when_there_are_no_findings() {
+        assert_eq!(
+            render(Vec::new()),
+            "## inspequte findings\n\nNo findings.\n"
+        );
+    }
+
+    #[test]
+    fn table_escape_escapes_pipes() {
+        assert_eq!(table_escape("a | b"), "a \\| b");
+    }
+}
@@ -0,0 +1,367 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_sarif::sarif::{Location, Result as SarifResult, ResultLevel, Run, Sarif};
+
+use crate::engine::SRCROOT_URI_BASE_ID;
+use crate::ir::Class;
+use crate::rules::{DEFAULT_RESULT_LEVEL, location_class_name};
+
+mod checkstyle;
+mod gitlab;
+mod html;
+mod junit;
+mod markdown;
+mod text;
+
+/// Report formats supported by the scan command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum OutputFormat {
+    /// SARIF v2.1.0 JSON (default).
+    #[default]
+    Sarif,
+    /// Checkstyle XML, as consumed by Jenkins warnings-ng and similar tools.
+    Checkstyle,
+    /// JUnit XML test report with one test suite per rule.
+    Junit,
+    /// GitLab Code Quality (Code Climate) JSON report.
+    Gitlab,
+    /// Human-readable terminal summary grouped by rule and class.
+    Text,
+    /// Markdown summary suitable for pull request comments.
+    Markdown,
+    /// Standalone HTML report with rule statistics and a filterable findings table.
+    Html,
+}
+
+impl OutputFormat {
+    /// Whether consumers of the format open findings by file path, which only resolves for
+    /// locations mapped through `--source-root`.
+    pub(crate) fn needs_source_roots(self) -> bool {
+        matches!(self, OutputFormat::Checkstyle | OutputFormat::Gitlab)
+    }
+}
+
+/// Package-relative source file paths keyed by internal class name, derived from `SourceFile`
+/// and the package; used for locations not mapped through `--source-root`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SourcePathIndex {
+    paths: BTreeMap<String, String>,
+}
+
+impl SourcePathIndex {
+    pub(crate) fn from_classes<'a>(classes: impl IntoIterator<Item = &'a Class>) -> Self {
+        let mut paths = BTreeMap::new();
+        for class in classes {
+            let Some(source_file) = class.source_file.as_deref() else {
+                continue;
+            };
+            let path = match class.name.rsplit_once('/') {
+                Some((package, _)) => format!("{package}/{source_file}"),
+                None => source_file.to_string(),
+            };
+            paths.insert(class.name.to_string(), path);
+        }
+        Self { paths }
+    }
+
+    pub(crate) fn source_path(&self, class_name: &str) -> Option<&str> {
+        self.paths.get(class_name).map(String::as_str)
+    }
+}
+
+/// Flattened view of a SARIF result shared by the non-SARIF report formats.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Finding<'a> {
+    pub(crate) rule_id: &'a str,
+    pub(crate) message: &'a str,
+    pub(crate) level: ResultLevel,
+    /// Internal class name (e.g. `com/example/ClassA`), when the location names one.
+    pub(crate) class_name: Option<&'a str>,
+    /// Fully qualified logical location name (class or method).
+    pub(crate) logical_name: Option<&'a str>,
+    /// Source path relative to the source root, falling back to the artifact URI.
+    pub(crate) path: String,
+    pub(crate) line: Option<i64>,
+}
+
+pub(crate) fn findings<'a>(
+    results: &'a [SarifResult],
+    sources: &SourcePathIndex,
+) -> Vec<Finding<'a>> {
+    results
+        .iter()
+        .map(|result| finding(result, sources))
+        .collect()
+}
+
+fn finding<'a>(result: &'a SarifResult, sources: &SourcePathIndex) -> Finding<'a> {
+    let location = result
+        .locations
+        .as_ref()
+        .and_then(|locations| locations.first());
+    let logical_name = location.and_then(logical_name);
+    let class_name = location.and_then(location_class_name);
+    let uri = location.and_then(physical_uri);
+    // Locations mapped through `--source-root` already point at the real source file.
+    let path = location
+        .and_then(source_root_uri)
+        .map(str::to_string)
+        .or_else(|| {
+            class_name
+                .and_then(|name| sources.source_path(name))
+                .map(str::to_string)
+        })
+        .or_else(|| uri.map(str::to_string))
+        .or_else(|| class_name.map(|name| format!("{name}.class")))
+        .unwrap_or_default();
+    let line = location
+        .and_then(|location| location.physical_location.as_ref())
+        .and_then(|physical| physical.region.as_ref())
+        .and_then(|region| region.start_line);
+    Finding {
+        rule_id: result.rule_id.as_deref().unwrap_or_default(),
+        message: result.message.text.as_deref().unwrap_or_default(),
+        level: result.level.unwrap_or(DEFAULT_RESULT_LEVEL),
+        class_name,
+        logical_name,
+        path,
+        line,
+    }
+}
+
+fn logical_name(location: &Location) -> Option<&str> {
+    location
+        .logical_locations
+        .as_ref()
+        .and_then(|locations| locations.first())
+        .and_then(|logical| logical.name.as_deref())
+}
+
+fn source_root_uri(location: &Location) -> Option<&str> {
+    let artifact = location
+        .physical_location
+        .as_ref()
+        .and_then(|physical| physical.artifact_location.as_ref())?;
+    if artifact.uri_base_id.as_deref() == Some(SRCROOT_URI_BASE_ID) {
+        artifact.uri.as_deref()
+    } else {
+        None
+    }
+}
+
+fn physical_uri(location: &Location) -> Option<&str> {
+    location
+        .physical_location
+        .as_ref()
+        .and_then(|physical| physical.artifact_location.as_ref())
+        .and_then(|artifact| artifact.uri.as_deref())
+}
+
+pub(crate) fn write_report(
+    format: OutputFormat,
+    sarif: &Sarif,
+    sources: &SourcePathIndex,
+    color: bool,
+    writer: &mut dyn Write,
+) -> Result<()> {
+    match format {
+        OutputFormat::Sarif => {
+            serde_json::to_writer(&mut *writer, sarif)
+                .context("failed to serialize SARIF output")?;
+            writer
+                .write_all(b"\n")
+                .context("failed to write SARIF output")?;
+        }
+        OutputFormat::Checkstyle => {
+            checkstyle::write(first_run(sarif), sources, writer)
+                .context("failed to write Checkstyle output")?;
+        }
+        OutputFormat::Junit => {
+            junit::write(first_run(sarif), sources, writer)
+                .context("failed to write JUnit output")?;
+        }
+        OutputFormat::Gitlab => {
+            gitlab::write(first_run(sarif), sources, writer)
+                .context("failed to write GitLab Code Quality output")?;
+        }
+        OutputFormat::Text => {
+            text::write(first_run(sarif), sources, color, writer)
+                .context("failed to write text output")?;
+        }
+        OutputFormat::Markdown => {
+            markdown::write(first_run(sarif), sources, writer)
+                .context("failed to write Markdown output")?;
+        }
+        OutputFormat::Html => {
+            html::write(first_run(sarif), sources, writer)
+                .context("failed to write HTML output")?;
+        }
+    }
+    Ok(())
+}
+
+fn first_run(sarif: &Sarif) -> Option<&Run> {
+    sarif.runs.first()
+}
+
+fn run_results(run: Option<&Run>) -> &[SarifResult] {
+    run.and_then(|run| run.results.as_deref()).unwrap_or(&[])
+}
+
+/// Rule display names keyed by rule ID, taken from the run's driver descriptors.
+fn rule_names(run: Option<&Run>) -> BTreeMap<&str, &str> {
+    let mut names = BTreeMap::new();
+    if let Some(rules) = run.and_then(|run| run.tool.driver.rules.as_ref()) {
+        for rule in rules {
+            if let Some(name) = rule.name.as_deref() {
+                names.insert(rule.id.as_str(), name);
+            }
+        }
+    }
+    names
+}
+
+/// Escape text for use in XML attribute values and character data.
+fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\t' => escaped.push_str("&#9;"),
+            // Other control characters are not representable in XML 1.0.
+            ch if (ch as u32) < 0x20 => {}
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use serde_sarif::sarif::{
+        ArtifactLocation, LogicalLocation, Message, PhysicalLocation, Region, Tool, ToolComponent,
+    };
+
+    use crate::rules::{class_location, method_location_with_line};
+
+    pub(crate) fn sample_sarif(results: Vec<SarifResult>) -> Sarif {
+        let driver = ToolComponent::builder()
+            .name("inspequte")
+            .rules(vec![
+                serde_sarif::sarif::ReportingDescriptor::builder()
+                    .id("RULE_A")
+                    .name("Rule A")
+                    .build(),
+                serde_sarif::sarif::ReportingDescriptor::builder()
+                    .id("RULE_B")
+                    .name("Rule B")
+                    .build(),
+            ])
+            .build();
+        let tool = Tool {
+            driver,
+            extensions: None,
+            properties: None,
+        };
+        let run = Run::builder().tool(tool).results(results).build();
+        Sarif::builder()
+            .runs(vec![run])
+            .version(serde_json::json!("2.1.0"))
+            .build()
+    }
+
+    pub(crate) fn sample_result(rule_id: &str, location: Location, message: &str) -> SarifResult {
+        SarifResult::builder()
+            .rule_id(rule_id)
+            .message(Message::builder().text(message.to_string()).build())
+            .locations(vec![location])
+            .build()
+    }
+
+    pub(crate) fn sample_sources() -> SourcePathIndex {
+        let mut paths = BTreeMap::new();
+        paths.insert(
+            "com/example/ClassA".to_string(),
+            "com/example/ClassA.java".to_string(),
+        );
+        paths.insert(
+            "com/example/ClassA$Inner".to_string(),
+            "com/example/ClassA.java".to_string(),
+        );
+        SourcePathIndex { paths }
+    }
+
+    #[test]
+    fn finding_resolves_source_path_from_method_location() {
+        let location = method_location_with_line(
+            "com/example/ClassA$Inner",
+            "methodOne",
+            "()V",
+            Some("jar:file:///tmp/app.jar!/com/example/ClassA$Inner.class"),
+            Some(12),
+        );
+        let results = vec![sample_result("RULE_A", location, "message")];
+
+        let findings = findings(&results, &sample_sources());
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].class_name, Some("com/example/ClassA$Inner"));
+        assert_eq!(findings[0].path, "com/example/ClassA.java");
+        assert_eq!(findings[0].level, ResultLevel::Warning);
+    }
+
+    #[test]
+    fn finding_falls_back_to_artifact_uri_without_source_file() {
+        let location = class_location("com/example/ClassB", Some("file:///tmp/ClassB.class"));
+        let results = vec![sample_result("RULE_A", location, "message")];
+
+        let findings = findings(&results, &sample_sources());
+
+        assert_eq!(findings[0].class_name, Some("com/example/ClassB"));
+        assert_eq!(findings[0].path, "file:///tmp/ClassB.class");
+        assert_eq!(findings[0].line, None);
+    }
+
+    #[test]
+    fn finding_reads_line_from_region() {
+        let location = Location::builder()
+            .logical_locations(vec![
+                LogicalLocation::builder()
+                    .name("com/example/ClassA.methodOne()V")
+                    .kind("function")
+                    .build(),
+            ])
+            .physical_location(
+                PhysicalLocation::builder()
+                    .artifact_location(ArtifactLocation::builder().uri("ClassA.java").build())
+                    .region(Region::builder().start_line(7).build())
+                    .build(),
+            )
+            .build();
+        let results = vec![sample_result("RULE_A", location, "message")];
+
+        let findings = findings(&results, &sample_sources());
+
+        assert_eq!(findings[0].line, Some(7));
+        assert_eq!(findings[0].path, "com/example/ClassA.java");
+    }
+
+    #[test]
+    fn xml_escape_handles_markup_and_control_characters() {
+        assert_eq!(
+            xml_escape("a<b>&\"c\"'\n\u{1}"),
+            "a&lt;b&gt;&amp;&quot;c&quot;&apos;&#10;"
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use anyhow::Result;
+use serde_sarif::sarif::{ResultLevel, Run};
+
+use super::{Finding, SourcePathIndex, findings, rule_names, run_results};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const CYAN: &str = "\x1b[36m";
+
+/// ANSI styling that collapses to empty strings when color is disabled.
+struct Style {
+    color: bool,
+}
+
+impl Style {
+    fn paint(&self, code: &str, text: &str) -> String {
+        if self.color {
+            format!("{code}{text}{RESET}")
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Write findings as plain text grouped by rule and class, with `file:line` references.
+pub(super) fn write(
+    run: Option<&Run>,
+    sources: &SourcePathIndex,
+    color: bool,
+    writer: &mut dyn Write,
+) -> Result<()> {
+    let style = Style { color };
+    let names = rule_names(run);
+    let mut by_rule: BTreeMap<&str, BTreeMap<String, Vec<Finding<'_>>>> = BTreeMap::new();
+    let findings = findings(run_results(run), sources);
+    let total = findings.len();
+    for finding in findings {
+        let class_name = finding
+            .class_name
+            .map(|name| name.replace('/', "."))
+            .unwrap_or_default();
+        by_rule
+            .entry(finding.rule_id)
+            .or_default()
+            .entry(class_name)
+            .or_default()
+            .push(finding);
+    }
+
+    for (rule_id, by_class) in &by_rule {
+        let count: usize = by_class.values().map(Vec::len).sum();
+        let level = by_class
+            .values()
+            .flatten()
+            .map(|finding| finding.level)
+            .next()
+            .unwrap_or(ResultLevel::Warning);
+        let heading_color = match level {
+            ResultLevel::Error => RED,
+            _ => YELLOW,
+        };
+        write!(writer, "{}", style.paint(heading_color, rule_id))?;
+        if let Some(name) = names.get(rule_id) {
+            write!(writer, ": {}", style.paint(BOLD, name))?;
+        }
+        writeln!(
+            writer,
+            " {}",
+            style.paint(DIM, &format!("({})", plural(count, "finding")))
+        )?;
+        for (class_name, findings) in by_class {
+            if !class_name.is_empty() {
+                writeln!(writer, "  {}", style.paint(BOLD, class_name))?;
+            }
+            for finding in findings {
+                writeln!(
+                    writer,
+                    "    {} {}",
+                    style.paint(CYAN, &file_reference(finding)),
+                    finding.message
+                )?;
+            }
+        }
+        writeln!(writer)?;
+    }
+
+    if total == 0 {
+        writeln!(writer, "No findings.")?;
+    } else {
+        writeln!(
+            writer,
+            "{} in {}.",
+            plural(total, "finding"),
+            plural(by_rule.len(), "rule")
+        )?;
+    }
+    Ok(())
+}
+
+fn file_reference(finding: &Finding<'_>) -> String {
+    match finding.line {
+        Some(line) => format!("{}:{line}", finding.path),
+        None => finding.path.clone(),
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("{count} {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::tests::{sample_result, sample_sarif, sample_sources};
+    use crate::rules::method_location_with_line;
+
+    fn render(results: Vec<serde_sarif::sarif::Result>, color: bool) -> String {
+        let sarif = sample_sarif(results);
+        let mut output = Vec::new();
+        write(sarif.runs.first(), &sample_sources(), color, &mut output).expect("write text");
+        String::from_utf8(output).expect("utf8")
+    }
+
+    fn location(class_name: &str, line: Option<u32>) -> serde_sarif::sarif::Location {
+        method_location_with_line(
+            class_name,
+            "methodOne",
+            "()V",
+            Some("file:///tmp/com/example/ClassA.class"),
+            line,
+        )
+    }
+
+    #[test]
+    fn text_groups_findings_by_rule_and_class() {
+        let output = render(
+            vec![
+                sample_result("RULE_A", location("com/example/ClassA", Some(10)), "first"),
+                sample_result(
+                    "RULE_A",
+                    location("com/example/ClassA$Inner", Some(20)),
+                    "second",
+                ),
+                sample_result("RULE_B", location("com/example/ClassA", None), "third"),
+            ],
+            false,
+        );
+
+        assert_eq!(
+            output,
+            "RULE_A: Rule A (2 findings)\n  com.example.ClassA\n    com/example/ClassA.java:10 first\n  \
+com.example.ClassA$Inner\n    com/example/ClassA.java:20 second\n\n\
+RULE_B: Rule B (1 finding)\n  com.example.ClassA\n    com/example/ClassA.java third\n\n\
+3 findings in 2 rules.\n"
+        );
+    }
+
+    #[test]
+    fn text_reports_when_there_are_no_findings() {
+        assert_eq!(render(Vec::new(), false), "No findings.\n");
+    }
+
+    #[test]
+    fn text_uses_ansi_colors_only_when_enabled() {
+        let results = || {
+            vec![sample_result(
+                "RULE_A",
+                location("com/example/ClassA", Some(10)),
+                "first",
+            )]
+        };
+
+        assert!(render(results(), true).contains("\x1b[33mRULE_A\x1b[0m"));
+        assert!(!render(results(), false).contains('\x1b'));
+    }
+}
@@ -0,0 +1,308 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::{Value, json};
+use serde_sarif::sarif::{
+    ConfigurationOverride, PropertyBag, ReportingConfiguration, ReportingDescriptorReference,
+};
+
+use crate::rules::RuleMetadata;
+
+/// Option values per rule ID and option name, as written in a config file or JSON request.
+pub(crate) type RawRuleOptions = BTreeMap<String, BTreeMap<String, Value>>;
+
+/// Option a rule accepts, declared in [`RuleMetadata::options`].
+#[derive(Clone, Copy, Debug, Serialize)]
+pub(crate) struct RuleOptionSpec {
+    pub(crate) name: &'static str,
+    #[serde(rename = "type")]
+    pub(crate) kind: RuleOptionKind,
+    pub(crate) description: &'static str,
+}
+
+/// Value type of a rule option.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum RuleOptionKind {
+    IntegerList,
+    StringList,
+}
+
+impl RuleOptionKind {
+    fn expected(self) -> &'static str {
+        match self {
+            RuleOptionKind::IntegerList => "an array of integers",
+            RuleOptionKind::StringList => "an array of strings",
+        }
+    }
+}
+
+/// Validated option value.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub(crate) enum RuleOptionValue {
+    IntegerList(Vec<i64>),
+    StringList(Vec<String>),
+}
+
+/// Options configured for one rule; options that were not configured are absent.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub(crate) struct RuleOptions {
+    values: BTreeMap<String, RuleOptionValue>,
+}
+
+/// Options of rules without configuration.
+pub(crate) static EMPTY_RULE_OPTIONS: RuleOptions = RuleOptions {
+    values: BTreeMap::new(),
+};
+
+impl RuleOptions {
+    pub(crate) fn integer_list(&self, name: &str) -> &[i64] {
+        match self.values.get(name) {
+            Some(RuleOptionValue::IntegerList(values)) => values,
+            _ => &[],
+        }
+    }
+
+    pub(crate) fn string_list(&self, name: &str) -> &[String] {
+        match self.values.get(name) {
+            Some(RuleOptionValue::StringList(values)) => values,
+            _ => &[],
+        }
+    }
+}
+
+/// Merge option sources; options in `overrides` replace the same option in `base`.
+pub(crate) fn merge_raw_rule_options(base: &mut RawRuleOptions, overrides: RawRuleOptions) {
+    for (rule_id, options) in overrides {
+        base.entry(rule_id).or_default().extend(options);
+    }
+}
+
+/// Validate raw option values against the options each rule declares.
+///
+/// `origin` names the source in error messages (e.g. `--json payload`), which point at the
+/// offending field as `ruleOptions.<RULE_ID>.<option>[index]`.
+pub(crate) fn resolve_rule_options(
+    raw: &RawRuleOptions,
+    rules: &[RuleMetadata],
+    origin: &str,
+) -> Result<BTreeMap<String, RuleOptions>> {
+    let mut resolved = BTreeMap::new();
+    for (rule_id, options) in raw {
+        let path = format!("ruleOptions.{rule_id}");
+        let Some(metadata) = rules.iter().find(|rule| rule.id == rule_id) else {
+            anyhow::bail!("invalid {origin} at {path}: unknown rule ID");
+        };
+        let mut values = BTreeMap::new();
+        for (name, value) in options {
+            let path = format!("{path}.{name}");
+            let Some(spec) = metadata.options.iter().find(|spec| spec.name == name) else {
+                let known: Vec<&str> = metadata.options.iter().map(|spec| spec.name).collect();
+                if known.is_empty() {
+                    anyhow::bail!("invalid {origin} at {path}: {rule_id} has no options");
+                }
+                anyhow::bail!(
+                    "invalid {origin} at {path}: unknown option (expected one of: {})",
+                    known.join(", ")
+                );
+            };
+            let value = parse_option_value(spec.kind, value).map_err(|index| match index {
+                Some(index) => anyhow::anyhow!(
+                    "invalid {origin} at {path}[{index}]: expected {}",
+                    spec.kind.expected()
+                ),
+                None => {
+                    anyhow::anyhow!(
+                        "invalid {origin} at {path}: expected {}",
+                        spec.kind.expected()
+                    )
+                }
+            })?;
+            values.insert(name.clone(), value);
+        }
+        resolved.insert(rule_id.clone(), RuleOptions { values });
+    }
+    Ok(resolved)
+}
+
+/// Parses a value of `kind`; the error is the index of the first invalid array element, or
+/// `None` when the value is not an array.
+fn parse_option_value(
+    kind: RuleOptionKind,
+    value: &Value,
+) -> Result<RuleOptionValue, Option<usize>> {
+    let items = value.as_array().ok_or(None)?;
+    match kind {
+        RuleOptionKind::IntegerList => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| item.as_i64().ok_or(Some(index)))
+            .collect::<Result<_, _>>()
+            .map(RuleOptionValue::IntegerList),
+        RuleOptionKind::StringList => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| item.as_str().map(str::to_string).ok_or(Some(index)))
+            .collect::<Result<_, _>>()
+            .map(RuleOptionValue::StringList),
+    }
+}
+
+/// SARIF `invocation.ruleConfigurationOverrides` recording the options of the rules that ran.
+pub(crate) fn rule_configuration_overrides(
+    options: &BTreeMap<String, RuleOptions>,
+    rule_ids: &[&str],
+) -> Vec<ConfigurationOverride> {
+    options
+        .iter()
+        .filter(|(rule_id, options)| {
+            rule_ids.contains(&rule_id.as_str()) && !options.values.is_empty()
+        })
+        .map(|(rule_id, options)| {
+            let parameters: BTreeMap<String, Value> = options
+                .values
+                .iter()
+                .map(|(name, value)| (name.clone(), json!(value)))
+                .collect();
+            ConfigurationOverride::builder()
+                .configuration(
+                    ReportingConfiguration::builder()
+                        .parameters(
+                            PropertyBag::builder()
+                                .additional_properties(parameters)
+                                .build(),
+                        )
+                        .build(),
+                )
+                .descriptor(ReportingDescriptorReference::builder().id(rule_id).build())
+                .build()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::all_rules;
+
+    fn metadata() -> Vec<RuleMetadata> {
+        all_rules().iter().map(|rule| rule.metadata()).collect()
+    }
+
+    fn raw(json: Value) -> RawRuleOptions {
+        serde_json::from_value(json).expect("raw rule options")
+    }
+
+    #[test]
+    fn rule_option_names_are_unique_per_rule() {
+        for metadata in metadata() {
+            let mut names: Vec<_> = metadata.options.iter().map(|spec| spec.name).collect();
+            let total = names.len();
+            names.sort_unstable();
+            names.dedup();
+            assert_eq!(
+                names.len(),
+                total,
+                "{} declares an option twice",
+                metadata.id
+            );
+        }
+    }
+
+    #[test]
+    fn resolves_declared_options() {
+        let options = resolve_rule_options(
+            &raw(json!({"MAGIC_NUMBER": {"allowedValues": [42, 1000]}})),
+            &metadata(),
+            "--config file",
+        )
+        .expect("resolve options");
+
+        assert_eq!(
+            options["MAGIC_NUMBER"].integer_list("allowedValues"),
+            &[42, 1000]
+        );
+        assert!(
+            options["MAGIC_NUMBER"]
+                .string_list("allowedValues")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_options_with_field_path() {
+        let rules = metadata();
+        let error = |json| {
+            resolve_rule_options(&raw(json), &rules, "--json payload")
+                .expect_err("invalid options")
+                .to_string()
+        };
+
+        assert_eq!(
+            error(json!({"UNKNOWN_RULE": {}})),
+            "invalid --json payload at ruleOptions.UNKNOWN_RULE: unknown rule ID"
+        );
+        assert_eq!(
+            error(json!({"MAGIC_NUMBER": {"allowed": [1]}})),
+            "invalid --json payload at ruleOptions.MAGIC_NUMBER.allowed: unknown option (expected one of: allowedValues)"
+        );
+        assert_eq!(
+            error(json!({"MAGIC_NUMBER": {"allowedValues": [1, "2"]}})),
+            "invalid --json payload at ruleOptions.MAGIC_NUMBER.allowedValues[1]: expected an array of integers"
+        );
+        assert_eq!(
+            error(json!({"MAGIC_NUMBER": {"allowedValues": 1}})),
+            "invalid --json payload at ruleOptions.MAGIC_NUMBER.allowedValues: expected an array of integers"
+        );
+        assert_eq!(
+            error(json!({"SYSTEM_EXIT": {"allowedValues": [1]}})),
+            "invalid --json payload at ruleOptions.SYSTEM_EXIT.allowedValues: SYSTEM_EXIT has no options"
+        );
+    }
+
+    #[test]
+    fn later_sources_replace_options() {
+        let mut base = raw(json!({"INSECURE_API": {
+            "additionalMethods": ["java/lang/System.load"],
+            "ignoredMethods": ["java/lang/Class.forName"],
+        }}));
+
+        merge_raw_rule_options(
+            &mut base,
+            raw(json!({"INSECURE_API": {"additionalMethods": []}})),
+        );
+
+        assert_eq!(
+            base,
+            raw(json!({"INSECURE_API": {
+                "additionalMethods": [],
+                "ignoredMethods": ["java/lang/Class.forName"],
+            }}))
+        );
+    }
+
+    #[test]
+    fn configuration_overrides_cover_rules_that_ran() {
+        let options = resolve_rule_options(
+            &raw(json!({
+                "MAGIC_NUMBER": {"allowedValues": [42]},
+                "THREAD_SLEEP_CALL": {"ignoredPackages": ["com.example.retry"]},
+            })),
+            &metadata(),
+            "--config file",
+        )
+        .expect("resolve options");
+
+        let overrides = rule_configuration_overrides(&options, &["MAGIC_NUMBER"]);
+
+        assert_eq!(overrides.len(), 1);
+        let value = serde_json::to_value(&overrides[0]).expect("serialize override");
+        assert_eq!(value["descriptor"]["id"], "MAGIC_NUMBER");
+        assert_eq!(
+            value["configuration"]["parameters"]["allowedValues"],
+            json!([42])
+        );
+    }
+}
@@ -14,7 +14,7 @@ use crate::dataflow::stack_machine::StackMachine;
 use crate::engine::AnalysisContext;
 use crate::ir::{CallSite, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
 
 /// Rule that flags array comparisons using == or equals().
 #[derive(Default)]
@@ -28,6 +28,12 @@ impl Rule for ArrayEqualsRule {
             id: "ARRAY_EQUALS",
             name: "Array equals",
             description: "Array comparisons using == or equals()",
+            tags: &["correctness"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[595],
+            requires: None,
+            options: &[],
         }
     }
 
@@ -36,7 +42,7 @@ impl Rule for ArrayEqualsRule {
         let debug_enabled = opcode_semantics_debug_enabled();
         let mut rule_coverage = SemanticsCoverage::default();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
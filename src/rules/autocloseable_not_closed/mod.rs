@@ -9,14 +9,19 @@ use crate::dataflow::opcode_semantics::{
     apply_semantics,
 };
 use crate::dataflow::stack_machine::StackMachine;
+use crate::dataflow::summaries::MethodSummaries;
 use crate::dataflow::worklist::{
-    BlockEndStep, InstructionStep, WorklistSemantics, WorklistState, analyze_method,
+    BlockEndStep, InstructionStep, TraceStep, WorklistSemantics, WorklistState,
+    analyze_method_with_trace,
 };
 use crate::descriptor::{ReturnKind, method_descriptor_summary, method_return_class_name};
 use crate::engine::AnalysisContext;
 use crate::ir::{CallKind, CallSite, Class, EdgeKind, Instruction, InstructionKind, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::symbol::Symbol;
+use crate::rules::{
+    Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message, trace_code_flow,
+};
 
 const MAX_TRACKED_STACK_DEPTH: usize = 32;
 
@@ -32,6 +37,12 @@ impl Rule for UnmanagedAutocloseableRule {
             id: "AUTOCLOSEABLE_NOT_CLOSED",
             name: "AutoCloseable not closed",
             description: "Locally created AutoCloseable instances should be closed on every exit path",
+            tags: &["correctness"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[772],
+            requires: None,
+            options: &[],
         }
     }
 
@@ -39,11 +50,12 @@ impl Rule for UnmanagedAutocloseableRule {
         let mut results = Vec::new();
         let class_map = context
             .all_classes()
-            .map(|class| (class.name.clone(), class))
+            .map(|class| (class.name, class))
             .collect::<BTreeMap<_, _>>();
+        let summaries = context.method_summaries();
 
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -68,7 +80,9 @@ impl Rule for UnmanagedAutocloseableRule {
                             continue;
                         }
 
-                        for creation_offset in analyze_closeable_lifecycle(method, &class_map)? {
+                        for (creation_offset, trace) in
+                            analyze_closeable_lifecycle(method, &class_map, summaries)?
+                        {
                             let cls_name = &class.name;
                             let met_name = &method.name;
                             let met_descriptor = &method.descriptor;
@@ -77,17 +91,27 @@ impl Rule for UnmanagedAutocloseableRule {
                                 may not be closed on all paths; use {guidance} or call close() in a finally block.",
                             ));
                             let line = method.line_for_offset(creation_offset);
+                            let artifact_uri = context.class_artifact_uri(class);
                             let location = method_location_with_line(
                                 cls_name,
                                 met_name,
                                 met_descriptor,
-                                context.class_artifact_uri(class).as_deref(),
+                                artifact_uri.as_deref(),
                                 line,
                             );
+                            let code_flow = trace_code_flow(
+                                cls_name,
+                                method,
+                                artifact_uri.as_deref(),
+                                (creation_offset, "AutoCloseable created here"),
+                                &trace,
+                                "Method exits without close()",
+                            );
                             class_results.push(
                                 SarifResult::builder()
                                     .message(message)
                                     .locations(vec![location])
+                                    .code_flows(vec![code_flow])
                                     .build(),
                             );
                         }
@@ -148,7 +172,8 @@ impl WorklistState for ExecutionState {
 /// Dataflow callbacks for local AutoCloseable lifecycle analysis.
 struct CloseableLifecycleSemantics<'a> {
     entry_block: u32,
-    class_map: &'a BTreeMap<String, &'a Class>,
+    class_map: &'a BTreeMap<Symbol, &'a Class>,
+    summaries: &'a MethodSummaries,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
@@ -217,7 +242,9 @@ impl WorklistSemantics for CloseableLifecycleSemantics<'_> {
                 }
             }
             _ => match &instruction.kind {
-                InstructionKind::Invoke(call) => handle_invoke(call, state, self.class_map)?,
+                InstructionKind::Invoke(call) => {
+                    handle_invoke(call, state, self.class_map, self.summaries)?
+                }
                 InstructionKind::InvokeDynamic { descriptor, .. } => {
                     handle_invoke_dynamic(descriptor, state)?
                 }
@@ -257,10 +284,12 @@ impl WorklistSemantics for CloseableLifecycleSemantics<'_> {
     }
 }
 
+/// Creation offsets of resources that may leak, each with the first path that leaks it.
 fn analyze_closeable_lifecycle(
     method: &Method,
-    class_map: &BTreeMap<String, &Class>,
-) -> Result<Vec<u32>> {
+    class_map: &BTreeMap<Symbol, &Class>,
+    summaries: &MethodSummaries,
+) -> Result<BTreeMap<u32, Vec<TraceStep>>> {
     let entry_block = method
         .cfg
         .blocks
@@ -271,13 +300,13 @@ fn analyze_closeable_lifecycle(
     let semantics = CloseableLifecycleSemantics {
         entry_block,
         class_map,
+        summaries,
     };
-    let findings = analyze_method(method, &semantics)?;
-    Ok(findings
-        .into_iter()
-        .collect::<BTreeSet<_>>()
-        .into_iter()
-        .collect())
+    let mut leaks = BTreeMap::new();
+    for traced in analyze_method_with_trace(method, &semantics)? {
+        leaks.entry(traced.finding).or_insert(traced.trace);
+    }
+    Ok(leaks)
 }
 
 fn apply_stack_effect(method: &Method, instruction: &Instruction, state: &mut ExecutionState) {
@@ -322,7 +351,8 @@ impl SemanticsHooks<Value> for CloseableSemanticsHook {
 fn handle_invoke(
     call: &CallSite,
     state: &mut ExecutionState,
-    class_map: &BTreeMap<String, &Class>,
+    class_map: &BTreeMap<Symbol, &Class>,
+    summaries: &MethodSummaries,
 ) -> Result<()> {
     let summary = method_descriptor_summary(&call.descriptor)?;
     let mut args = Vec::with_capacity(summary.param_count);
@@ -377,10 +407,30 @@ fn handle_invoke(
         None
     };
 
+    // A helper that closes its receiver or an argument on every return closes it here.
+    let closed_by_callee = summaries.for_call(call).map_or_else(Vec::new, |callee| {
+        let receiver_count = usize::from(receiver.is_some());
+        callee
+            .closes_arguments
+            .iter()
+            .filter_map(|index| match index.checked_sub(receiver_count) {
+                None => receiver,
+                // Arguments were popped last to first.
+                Some(param) => args
+                    .len()
+                    .checked_sub(param + 1)
+                    .map(|position| args[position]),
+            })
+            .collect::<Vec<_>>()
+    });
+
     // Escape all arguments.
     for value in args {
         escape_value(value, state);
     }
+    for value in closed_by_callee {
+        escape_value(value, state);
+    }
 
     if let Some(symbol) = close_receiver {
         state.active_closeables.remove(&symbol);
@@ -466,19 +516,19 @@ fn is_close_call(call: &CallSite) -> bool {
     call.name == "close" && call.descriptor == "()V"
 }
 
-fn is_autocloseable_constructor(call: &CallSite, class_map: &BTreeMap<String, &Class>) -> bool {
+fn is_autocloseable_constructor(call: &CallSite, class_map: &BTreeMap<Symbol, &Class>) -> bool {
     call.name == "<init>" && is_autocloseable_type(&call.owner, class_map)
 }
 
-fn is_autocloseable_type(name: &str, class_map: &BTreeMap<String, &Class>) -> bool {
+fn is_autocloseable_type(name: &str, class_map: &BTreeMap<Symbol, &Class>) -> bool {
     if is_known_autocloseable_name(name) {
         return true;
     }
 
-    let mut queue = VecDeque::from([name.to_string()]);
+    let mut queue = VecDeque::from([Symbol::intern(name)]);
     let mut seen = BTreeSet::new();
     while let Some(next) = queue.pop_front() {
-        if !seen.insert(next.clone()) {
+        if !seen.insert(next) {
             continue;
         }
         if is_known_autocloseable_name(&next) {
@@ -487,12 +537,10 @@ fn is_autocloseable_type(name: &str, class_map: &BTreeMap<String, &Class>) -> bo
         let Some(class) = class_map.get(&next) else {
             continue;
         };
-        if let Some(super_name) = &class.super_name {
-            queue.push_back(super_name.clone());
-        }
-        for interface in &class.interfaces {
-            queue.push_back(interface.clone());
+        if let Some(super_name) = class.super_name {
+            queue.push_back(super_name);
         }
+        queue.extend(&class.interfaces);
     }
 
     false
@@ -1195,4 +1243,33 @@ public class ClassQ {
         );
     }
 
+    #[test]
+    fn does_not_report_close_through_final_helper_method() {
+        let sources = vec![SourceFile {
+            path: "com/example/ClassQ.java".to_string(),
+            contents: r#"
+package com.example;
+
+public class ClassQ {
+    public void methodX() {
+        ClassResource varOne = new ClassResource();
+        varOne.finish();
+    }
+
+    static class ClassResource implements AutoCloseable {
+        final void finish() {
+            close();
+        }
+
+        @Override
+        public void close() {}
+    }
+}
+"#
+            .to_string(),
+        }];
+
+        let messages = analyze_java_sources(sources);
+        assert!(messages.is_empty(), "did not expect finding: {messages:?}");
+    }
 }
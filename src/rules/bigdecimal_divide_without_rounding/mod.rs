@@ -3,7 +3,8 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_MATH_BIG_DECIMAL, Symbol};
 
 /// Rule that detects `BigDecimal.divide(BigDecimal)` calls without rounding config.
 #[derive(Default)]
@@ -17,13 +18,19 @@ impl Rule for BigDecimalDivideWithoutRoundingRule {
             id: "BIGDECIMAL_DIVIDE_WITHOUT_ROUNDING",
             name: "BigDecimal divide without rounding",
             description: "BigDecimal.divide(BigDecimal) can throw on non-terminating decimals",
+            tags: &["correctness"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,9 +39,9 @@ impl Rule for BigDecimalDivideWithoutRoundingRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
+                        for call in method.source_calls() {
                             if is_unrounded_bigdecimal_divide(
-                                &call.owner,
+                                call.owner,
                                 &call.name,
                                 &call.descriptor,
                             ) {
@@ -67,8 +74,8 @@ impl Rule for BigDecimalDivideWithoutRoundingRule {
     }
 }
 
-fn is_unrounded_bigdecimal_divide(owner: &str, name: &str, descriptor: &str) -> bool {
-    owner == "java/math/BigDecimal"
+fn is_unrounded_bigdecimal_divide(owner: Symbol, name: &str, descriptor: &str) -> bool {
+    owner == JAVA_MATH_BIG_DECIMAL
         && name == "divide"
         && descriptor == "(Ljava/math/BigDecimal;)Ljava/math/BigDecimal;"
 }
@@ -3,7 +3,8 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_MATH_BIG_DECIMAL, Symbol};
 
 /// Rule that detects direct `BigDecimal.equals(Object)` calls.
 #[derive(Default)]
@@ -17,13 +18,19 @@ impl Rule for BigDecimalEqualsCallRule {
             id: "BIGDECIMAL_EQUALS_CALL",
             name: "BigDecimal equals call",
             description: "BigDecimal.equals compares value and scale instead of numeric equality",
+            tags: &["correctness"],
+            precision: RulePrecision::Medium,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,8 +39,8 @@ impl Rule for BigDecimalEqualsCallRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
-                            if is_bigdecimal_equals_call(&call.owner, &call.name, &call.descriptor) {
+                        for call in method.source_calls() {
+                            if is_bigdecimal_equals_call(call.owner, &call.name, &call.descriptor) {
                                 let message = result_message(format!(
                                     "Avoid BigDecimal.equals() in {}.{}{}; use compareTo(...) == 0 for numeric equality.",
                                     class.name, method.name, method.descriptor
@@ -63,8 +70,8 @@ impl Rule for BigDecimalEqualsCallRule {
     }
 }
 
-fn is_bigdecimal_equals_call(owner: &str, name: &str, descriptor: &str) -> bool {
-    owner == "java/math/BigDecimal" && name == "equals" && descriptor == "(Ljava/lang/Object;)Z"
+fn is_bigdecimal_equals_call(owner: Symbol, name: &str, descriptor: &str) -> bool {
+    owner == JAVA_MATH_BIG_DECIMAL && name == "equals" && descriptor == "(Ljava/lang/Object;)Z"
 }
 
 #[cfg(test)]
@@ -3,7 +3,9 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::fix::FixSuggestion;
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_MATH_BIG_DECIMAL, Symbol};
 
 /// Rule that detects BigDecimal constructor calls that accept double values.
 #[derive(Default)]
@@ -17,13 +19,19 @@ impl Rule for BigDecimalFromDoubleRule {
             id: "BIGDECIMAL_FROM_DOUBLE",
             name: "BigDecimal from double",
             description: "BigDecimal constructors with double can introduce precision surprises",
+            tags: &["correctness"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,8 +40,8 @@ impl Rule for BigDecimalFromDoubleRule {
                 context.with_span("rule.class", &attributes, || -> Result<Vec<SarifResult>> {
                     let mut class_results = Vec::new();
                     for method in &class.methods {
-                        for call in &method.calls {
-                            if !is_bigdecimal_double_constructor(&call.owner, &call.name, &call.descriptor)
+                        for call in method.source_calls() {
+                            if !is_bigdecimal_double_constructor(call.owner, &call.name, &call.descriptor)
                             {
                                 continue;
                             }
@@ -49,12 +57,20 @@ impl Rule for BigDecimalFromDoubleRule {
                                 artifact_uri.as_deref(),
                                 line,
                             );
-                            class_results.push(
-                                SarifResult::builder()
-                                    .message(message)
-                                    .locations(vec![location])
-                                    .build(),
-                            );
+                            let mut result = SarifResult::builder()
+                                .message(message)
+                                .locations(vec![location])
+                                .build();
+                            let fix = FixSuggestion::new("Use BigDecimal.valueOf(double)");
+                            // valueOf has no MathContext overload, so only the plain constructor
+                            // gets a source edit.
+                            let fix = if call.descriptor == "(D)V" {
+                                fix.replace("new BigDecimal(", "BigDecimal.valueOf(")
+                            } else {
+                                fix
+                            };
+                            fix.attach(&mut result);
+                            class_results.push(result);
                         }
                     }
                     Ok(class_results)
@@ -65,8 +81,8 @@ impl Rule for BigDecimalFromDoubleRule {
     }
 }
 
-fn is_bigdecimal_double_constructor(owner: &str, name: &str, descriptor: &str) -> bool {
-    owner == "java/math/BigDecimal"
+fn is_bigdecimal_double_constructor(owner: Symbol, name: &str, descriptor: &str) -> bool {
+    owner == JAVA_MATH_BIG_DECIMAL
         && name == "<init>"
         && matches!(descriptor, "(D)V" | "(DLjava/math/MathContext;)V")
 }
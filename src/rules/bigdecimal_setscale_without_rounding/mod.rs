@@ -3,7 +3,8 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_MATH_BIG_DECIMAL, Symbol};
 
 /// Rule that detects `BigDecimal.setScale(int)` calls without explicit rounding.
 #[derive(Default)]
@@ -17,13 +18,19 @@ impl Rule for BigDecimalSetScaleWithoutRoundingRule {
             id: "BIGDECIMAL_SET_SCALE_WITHOUT_ROUNDING",
             name: "BigDecimal setScale without rounding",
             description: "BigDecimal.setScale(int) can throw when rounding is required",
+            tags: &["correctness"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,8 +39,8 @@ impl Rule for BigDecimalSetScaleWithoutRoundingRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
-                            if is_unrounded_set_scale(&call.owner, &call.name, &call.descriptor) {
+                        for call in method.source_calls() {
+                            if is_unrounded_set_scale(call.owner, &call.name, &call.descriptor) {
                                 let message = result_message(format!(
                                     "Avoid BigDecimal.setScale(...) without rounding in {}.{}{}; specify RoundingMode.",
                                     class.name, method.name, method.descriptor
@@ -63,8 +70,8 @@ impl Rule for BigDecimalSetScaleWithoutRoundingRule {
     }
 }
 
-fn is_unrounded_set_scale(owner: &str, name: &str, descriptor: &str) -> bool {
-    owner == "java/math/BigDecimal"
+fn is_unrounded_set_scale(owner: Symbol, name: &str, descriptor: &str) -> bool {
+    owner == JAVA_MATH_BIG_DECIMAL
         && name == "setScale"
         && descriptor == "(I)Ljava/math/BigDecimal;"
 }
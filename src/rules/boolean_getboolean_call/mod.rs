@@ -3,7 +3,8 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_LANG_BOOLEAN, Symbol};
 
 /// Rule that detects direct Boolean.getBoolean calls.
 #[derive(Default)]
@@ -17,13 +18,19 @@ impl Rule for BooleanGetbooleanCallRule {
             id: "BOOLEAN_GETBOOLEAN_CALL",
             name: "Boolean.getBoolean call",
             description: "Boolean.getBoolean reads system properties, not text booleans",
+            tags: &["correctness"],
+            precision: RulePrecision::Medium,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,9 +39,9 @@ impl Rule for BooleanGetbooleanCallRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
+                        for call in method.source_calls() {
                             if is_boolean_getboolean_call(
-                                &call.owner,
+                                call.owner,
                                 &call.name,
                                 &call.descriptor,
                             ) {
@@ -67,8 +74,8 @@ impl Rule for BooleanGetbooleanCallRule {
     }
 }
 
-fn is_boolean_getboolean_call(owner: &str, name: &str, descriptor: &str) -> bool {
-    owner == "java/lang/Boolean" && name == "getBoolean" && descriptor == "(Ljava/lang/String;)Z"
+fn is_boolean_getboolean_call(owner: Symbol, name: &str, descriptor: &str) -> bool {
+    owner == JAVA_LANG_BOOLEAN && name == "getBoolean" && descriptor == "(Ljava/lang/String;)Z"
 }
 
 #[cfg(test)]
@@ -0,0 +1,155 @@
+use anyhow::Result;
+use serde_sarif::sarif::Result as SarifResult;
+
+use crate::dataflow::taint::{MethodPattern, SERVLET_REQUEST_SOURCES, TaintSink, TaintSpec};
+use crate::engine::AnalysisContext;
+use crate::rules::{Rule, RuleMetadata, RulePrecision, taint_flow_results};
+
+/// Rule that detects request data flowing into OS commands.
+#[derive(Default)]
+pub(crate) struct CommandInjectionRule;
+
+crate::register_rule!(CommandInjectionRule);
+
+const COMMAND_SINKS: &[TaintSink] = &[
+    TaintSink {
+        method: MethodPattern::any("java/lang/Runtime", "exec"),
+        argument: 0,
+    },
+    TaintSink {
+        method: MethodPattern::any("java/lang/ProcessBuilder", "<init>"),
+        argument: 0,
+    },
+    TaintSink {
+        method: MethodPattern::any("java/lang/ProcessBuilder", "command"),
+        argument: 0,
+    },
+];
+
+const COMMAND_INJECTION: TaintSpec = TaintSpec {
+    sources: SERVLET_REQUEST_SOURCES,
+    sinks: COMMAND_SINKS,
+    sanitizers: &[],
+};
+
+impl Rule for CommandInjectionRule {
+    fn metadata(&self) -> RuleMetadata {
+        RuleMetadata {
+            id: "COMMAND_INJECTION",
+            name: "OS command injection",
+            description: "Request data used as an OS command",
+            tags: &["security"],
+            precision: RulePrecision::Medium,
+            security_severity: Some(9.8),
+            cwe: &[78],
+            requires: None,
+            options: &[],
+        }
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
+        taint_flow_results(context, &COMMAND_INJECTION, |class, method, flow| {
+            format!(
+                "OS command injection: request data reaches {}.{} in {}.{}{}; run a fixed command and validate arguments against an allow-list.",
+                flow.sink.method.owner,
+                flow.sink.method.name,
+                class.name,
+                method.name,
+                method.descriptor
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_harness::{JvmTestHarness, Language, SourceFile};
+
+    fn analyze(contents: &str) -> Vec<String> {
+        let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
+        let sources = vec![
+            SourceFile {
+                path: "jakarta/servlet/http/HttpServletRequest.java".to_string(),
+                contents: r#"
+package jakarta.servlet.http;
+public interface HttpServletRequest {
+    String getHeader(String name);
+    String[] getParameterValues(String name);
+}
+"#
+                .to_string(),
+            },
+            SourceFile {
+                path: "com/example/ClassA.java".to_string(),
+                contents: contents.to_string(),
+            },
+        ];
+        let output = harness
+            .compile_and_analyze(Language::Java, &sources, &[])
+            .expect("run harness analysis");
+        output
+            .results
+            .iter()
+            .filter(|result| result.rule_id.as_deref() == Some("COMMAND_INJECTION"))
+            .filter_map(|result| result.message.text.clone())
+            .collect()
+    }
+
+    #[test]
+    fn reports_header_passed_to_runtime_exec() {
+        let messages = analyze(
+            r#"
+package com.example;
+import jakarta.servlet.http.HttpServletRequest;
+public class ClassA {
+    public void methodX(HttpServletRequest varOne) throws Exception {
+        Runtime.getRuntime().exec("ping " + varOne.getHeader("host"));
+    }
+}
+"#,
+        );
+
+        assert_eq!(messages.len(), 1, "messages: {messages:?}");
+        assert!(messages[0].contains("java/lang/Runtime.exec in com/example/ClassA.methodX"));
+    }
+
+    #[test]
+    fn reports_parameter_stored_in_command_array() {
+        let messages = analyze(
+            r#"
+package com.example;
+import jakarta.servlet.http.HttpServletRequest;
+public class ClassA {
+    public void methodX(HttpServletRequest varOne) throws Exception {
+        String varTwo = varOne.getParameterValues("file")[0];
+        new ProcessBuilder("cat", varTwo).start();
+    }
+}
+"#,
+        );
+
+        assert_eq!(messages.len(), 1, "messages: {messages:?}");
+        assert!(messages[0].contains("java/lang/ProcessBuilder.<init>"));
+    }
+
+    #[test]
+    fn does_not_report_fixed_commands() {
+        let messages = analyze(
+            r#"
+package com.example;
+import jakarta.servlet.http.HttpServletRequest;
+public class ClassA {
+    public void methodX(HttpServletRequest varOne) throws Exception {
+        String varTwo = varOne.getHeader("host");
+        if (varTwo.isEmpty()) {
+            return;
+        }
+        Runtime.getRuntime().exec(new String[] {"uptime"});
+    }
+}
+"#,
+        );
+
+        assert!(messages.is_empty(), "messages: {messages:?}");
+    }
+}
@@ -5,7 +5,8 @@ use serde_sarif::sarif::Result as SarifResult;
 use crate::engine::AnalysisContext;
 use crate::ir::Method;
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_LANG_INTEGER, JAVA_LANG_LONG};
 
 /// Rule that detects integer subtraction used as the comparison result in `compareTo` methods,
 /// which can produce incorrect ordering for extreme values due to arithmetic overflow.
@@ -20,13 +21,19 @@ impl Rule for CompareToOverflowRule {
             id: "COMPARETO_OVERFLOW",
             name: "compareTo integer subtraction overflow",
             description: "compareTo using integer subtraction can overflow for extreme values",
+            tags: &["correctness"],
+            precision: RulePrecision::Medium,
+            security_severity: None,
+            cwe: &[190],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -79,8 +86,7 @@ fn is_compareto_returning_int(method: &Method) -> bool {
 /// which are overflow-safe alternatives to integer subtraction.
 fn calls_safe_integer_compare(method: &Method) -> bool {
     method.calls.iter().any(|call| {
-        (call.owner == "java/lang/Integer" || call.owner == "java/lang/Long")
-            && call.name == "compare"
+        (call.owner == JAVA_LANG_INTEGER || call.owner == JAVA_LANG_LONG) && call.name == "compare"
     })
 }
 
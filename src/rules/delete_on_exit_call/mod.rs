@@ -3,7 +3,8 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_IO_FILE, Symbol};
 
 /// Rule that detects direct `File.deleteOnExit()` calls.
 #[derive(Default)]
@@ -17,13 +18,19 @@ impl Rule for DeleteOnExitCallRule {
             id: "DELETE_ON_EXIT_CALL",
             name: "File.deleteOnExit call",
             description: "File.deleteOnExit can accumulate pending deletions in long-lived processes",
+            tags: &["performance"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[401],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,8 +39,8 @@ impl Rule for DeleteOnExitCallRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
-                            if is_delete_on_exit_call(&call.owner, &call.name, &call.descriptor) {
+                        for call in method.source_calls() {
+                            if is_delete_on_exit_call(call.owner, &call.name, &call.descriptor) {
                                 let message = result_message(format!(
                                     "Avoid File.deleteOnExit() in {}.{}{}; prefer explicit deletion with error handling.",
                                     class.name, method.name, method.descriptor
@@ -63,8 +70,8 @@ impl Rule for DeleteOnExitCallRule {
     }
 }
 
-fn is_delete_on_exit_call(owner: &str, name: &str, descriptor: &str) -> bool {
-    owner == "java/io/File" && name == "deleteOnExit" && descriptor == "()V"
+fn is_delete_on_exit_call(owner: Symbol, name: &str, descriptor: &str) -> bool {
+    owner == JAVA_IO_FILE && name == "deleteOnExit" && descriptor == "()V"
 }
 
 #[cfg(test)]
@@ -3,7 +3,8 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_IO_OBJECT_INPUT_STREAM, Symbol};
 
 /// Rule that detects direct Java deserialization entry-point calls.
 #[derive(Default)]
@@ -17,13 +18,19 @@ impl Rule for DeserializationReadObjectCallRule {
             id: "DESERIALIZATION_READ_OBJECT_CALL",
             name: "ObjectInputStream deserialization call",
             description: "readObject/readUnshared are high-risk Java deserialization entry points",
+            tags: &["security"],
+            precision: RulePrecision::Medium,
+            security_severity: Some(8.1),
+            cwe: &[502],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,8 +39,8 @@ impl Rule for DeserializationReadObjectCallRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
-                            if is_deserialization_entry(&call.owner, &call.name, &call.descriptor)
+                        for call in method.source_calls() {
+                            if is_deserialization_entry(call.owner, &call.name, &call.descriptor)
                             {
                                 let message = result_message(format!(
                                     "Avoid ObjectInputStream deserialization call in {}.{}{}; use safer formats or strict deserialization controls.",
@@ -65,8 +72,8 @@ impl Rule for DeserializationReadObjectCallRule {
     }
 }
 
-fn is_deserialization_entry(owner: &str, name: &str, descriptor: &str) -> bool {
-    owner == "java/io/ObjectInputStream"
+fn is_deserialization_entry(owner: Symbol, name: &str, descriptor: &str) -> bool {
+    owner == JAVA_IO_OBJECT_INPUT_STREAM
         && matches!(
             (name, descriptor),
             ("readObject", "()Ljava/lang/Object;") | ("readUnshared", "()Ljava/lang/Object;")
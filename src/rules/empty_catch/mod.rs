@@ -5,7 +5,7 @@ use serde_sarif::sarif::Result as SarifResult;
 use crate::engine::AnalysisContext;
 use crate::ir::Instruction;
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
 
 /// Rule that detects empty catch blocks.
 #[derive(Default)]
@@ -19,13 +19,19 @@ impl Rule for EmptyCatchRule {
             id: "EMPTY_CATCH",
             name: "Empty catch block",
             description: "Catch blocks with no meaningful instructions",
+            tags: &["correctness"],
+            precision: RulePrecision::Medium,
+            security_severity: None,
+            cwe: &[1069],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -141,6 +147,8 @@ mod tests {
             is_abstract: false,
             is_synthetic: false,
             is_bridge: false,
+            is_private: false,
+            is_final: false,
         }
     }
 
@@ -151,8 +159,9 @@ mod tests {
         handlers: Vec<ExceptionHandler>,
     ) -> Method {
         Method {
-            name: name.to_string(),
-            descriptor: descriptor.to_string(),
+            owner: "com/example/App".into(),
+            name: name.into(),
+            descriptor: descriptor.into(),
             signature: None,
             access: default_access(),
             nullness: MethodNullness::unknown(method_param_count(descriptor).expect("param count")),
@@ -165,12 +174,13 @@ mod tests {
             exception_handlers: handlers,
             local_variables: vec![],
             local_variable_types: Vec::new(),
+            flow_structure: Default::default(),
         }
     }
 
     fn class_with_methods(name: &str, methods: Vec<Method>) -> Class {
         Class {
-            name: name.to_string(),
+            name: name.into(),
             source_file: None,
             super_name: None,
             interfaces: Vec::new(),
@@ -181,6 +191,8 @@ mod tests {
             annotation_defaults: Vec::new(),
             artifact_index: 0,
             is_record: false,
+            is_generated: false,
+            header_location: None,
         }
     }
 
@@ -190,7 +202,7 @@ mod tests {
         methods: Vec<Method>,
     ) -> Class {
         Class {
-            name: name.to_string(),
+            name: name.into(),
             source_file: None,
             super_name: None,
             interfaces: Vec::new(),
@@ -201,6 +213,8 @@ mod tests {
             annotation_defaults: Vec::new(),
             artifact_index,
             is_record: false,
+            is_generated: false,
+            header_location: None,
         }
     }
 
@@ -306,6 +320,7 @@ fun methodOne(varOne: ClassA): Int = when (varOne) {
         let cfg = ControlFlowGraph {
             blocks: vec![block],
             edges: Vec::new(),
+            duplicates: Default::default(),
         };
         let handlers = vec![ExceptionHandler {
             start_pc: 0,
@@ -336,6 +351,7 @@ fun methodOne(varOne: ClassA): Int = when (varOne) {
         let cfg = ControlFlowGraph {
             blocks: vec![block],
             edges: Vec::new(),
+            duplicates: Default::default(),
         };
         let handlers = vec![ExceptionHandler {
             start_pc: 0,
@@ -366,6 +382,7 @@ fun methodOne(varOne: ClassA): Int = when (varOne) {
         let cfg = ControlFlowGraph {
             blocks: vec![block],
             edges: Vec::new(),
+            duplicates: Default::default(),
         };
         let handlers = vec![ExceptionHandler {
             start_pc: 0,
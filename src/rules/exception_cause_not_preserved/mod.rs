@@ -1,5 +1,5 @@
 use std::cell::{Cell, RefCell};
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::OnceLock;
 
 use anyhow::Result;
@@ -12,13 +12,16 @@ use crate::dataflow::opcode_semantics::{
 };
 use crate::dataflow::stack_machine::{StackMachine, StackMachineConfig};
 use crate::dataflow::worklist::{
-    BlockEndStep, InstructionStep, WorklistSemantics, WorklistState, analyze_method,
+    BlockEndStep, InstructionStep, TraceStep, WorklistSemantics, WorklistState,
+    analyze_method_with_trace,
 };
 use crate::descriptor::{ReturnKind, method_param_count, method_return_kind};
 use crate::engine::AnalysisContext;
 use crate::ir::{CallKind, CallSite, Instruction, InstructionKind, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{
+    Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message, trace_code_flow,
+};
 
 const MAX_TRACKED_STACK_DEPTH: usize = 24;
 const MAX_TRACKED_ALLOCATIONS: usize = 4;
@@ -35,6 +38,12 @@ impl Rule for ExceptionCauseNotPreservedRule {
             id: "EXCEPTION_CAUSE_NOT_PRESERVED",
             name: "Exception cause not preserved",
             description: "Catch handlers that throw new exceptions without preserving the cause",
+            tags: &["correctness"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
@@ -43,7 +52,7 @@ impl Rule for ExceptionCauseNotPreservedRule {
         let debug_enabled = opcode_semantics_debug_enabled();
         let mut rule_coverage = SemanticsCoverage::default();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -60,7 +69,7 @@ impl Rule for ExceptionCauseNotPreservedRule {
                         for handler_pc in handler_offsets(method) {
                             let analysis = analyze_handler(method, handler_pc)?;
                             rule_coverage.merge_from(&analysis.coverage);
-                            for throw_offset in analysis.findings {
+                            for (throw_offset, trace) in analysis.findings {
                                 if !seen_findings.insert((handler_pc, throw_offset)) {
                                     continue;
                                 }
@@ -77,10 +86,19 @@ impl Rule for ExceptionCauseNotPreservedRule {
                                     artifact_uri.as_deref(),
                                     line,
                                 );
+                                let code_flow = trace_code_flow(
+                                    &class.name,
+                                    method,
+                                    artifact_uri.as_deref(),
+                                    (handler_pc, "Exception caught here"),
+                                    &trace,
+                                    "New exception thrown without the caught cause",
+                                );
                                 class_results.push(
                                     SarifResult::builder()
                                         .message(message)
                                         .locations(vec![location])
+                                        .code_flows(vec![code_flow])
                                         .build(),
                                 );
                             }
@@ -262,21 +280,21 @@ fn handler_offsets(method: &Method) -> Vec<u32> {
 
 fn analyze_handler(method: &Method, handler_pc: u32) -> Result<HandlerAnalysis> {
     let semantics = HandlerSemantics::new(handler_pc);
-    let findings = analyze_method(method, &semantics)?;
+    let mut findings = BTreeMap::new();
+    for traced in analyze_method_with_trace(method, &semantics)? {
+        findings.entry(traced.finding).or_insert(traced.trace);
+    }
     let coverage = semantics.coverage_snapshot();
     Ok(HandlerAnalysis {
-        findings: findings
-            .into_iter()
-            .collect::<BTreeSet<_>>()
-            .into_iter()
-            .collect(),
+        findings,
         coverage,
     })
 }
 
 /// Handler-level analysis output with coverage summary for debug telemetry events.
 struct HandlerAnalysis {
-    findings: Vec<u32>,
+    /// Throw offsets that drop the cause, each with the first path reaching it.
+    findings: BTreeMap<u32, Vec<TraceStep>>,
     coverage: SemanticsCoverage,
 }
 
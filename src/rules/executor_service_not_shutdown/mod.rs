@@ -16,7 +16,8 @@ use crate::descriptor::{ReturnKind, method_param_count, method_return_kind};
 use crate::engine::AnalysisContext;
 use crate::ir::{CallKind, CallSite, Class, EdgeKind, Instruction, InstructionKind, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::symbol::Symbol;
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
 
 const MAX_TRACKED_STACK_DEPTH: usize = 32;
 
@@ -32,6 +33,12 @@ impl Rule for ExecutorServiceNotShutdownRule {
             id: "EXECUTOR_SERVICE_NOT_SHUTDOWN",
             name: "ExecutorService not shut down",
             description: "Locally created executor services should be shut down on every exit path",
+            tags: &["concurrency"],
+            precision: RulePrecision::Medium,
+            security_severity: None,
+            cwe: &[772],
+            requires: None,
+            options: &[],
         }
     }
 
@@ -39,11 +46,11 @@ impl Rule for ExecutorServiceNotShutdownRule {
         let mut results = Vec::new();
         let class_map = context
             .all_classes()
-            .map(|class| (class.name.clone(), class))
+            .map(|class| (class.name, class))
             .collect::<BTreeMap<_, _>>();
 
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -133,7 +140,7 @@ impl WorklistState for ExecutionState {
 /// Dataflow callbacks for local executor lifecycle analysis.
 struct ExecutorLifecycleSemantics<'a> {
     entry_block: u32,
-    class_map: &'a BTreeMap<String, &'a Class>,
+    class_map: &'a BTreeMap<Symbol, &'a Class>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
@@ -244,7 +251,7 @@ impl WorklistSemantics for ExecutorLifecycleSemantics<'_> {
 
 fn analyze_executor_lifecycle(
     method: &Method,
-    class_map: &BTreeMap<String, &Class>,
+    class_map: &BTreeMap<Symbol, &Class>,
 ) -> Result<Vec<u32>> {
     let entry_block = method
         .cfg
@@ -307,7 +314,7 @@ impl SemanticsHooks<Value> for ExecutorSemanticsHook {
 fn handle_invoke(
     call: &CallSite,
     state: &mut ExecutionState,
-    class_map: &BTreeMap<String, &Class>,
+    class_map: &BTreeMap<Symbol, &Class>,
 ) -> Result<()> {
     let param_count = method_param_count(&call.descriptor)?;
     let mut args = Vec::with_capacity(param_count);
@@ -482,19 +489,19 @@ fn is_executor_factory_call(call: &CallSite) -> bool {
     )
 }
 
-fn is_executor_constructor(call: &CallSite, class_map: &BTreeMap<String, &Class>) -> bool {
+fn is_executor_constructor(call: &CallSite, class_map: &BTreeMap<Symbol, &Class>) -> bool {
     call.name == "<init>" && is_executor_service_type(&call.owner, class_map)
 }
 
-fn is_executor_service_type(name: &str, class_map: &BTreeMap<String, &Class>) -> bool {
+fn is_executor_service_type(name: &str, class_map: &BTreeMap<Symbol, &Class>) -> bool {
     if is_known_executor_service_name(name) {
         return true;
     }
 
-    let mut queue = VecDeque::from([name.to_string()]);
+    let mut queue = VecDeque::from([Symbol::intern(name)]);
     let mut seen = BTreeSet::new();
     while let Some(next) = queue.pop_front() {
-        if !seen.insert(next.clone()) {
+        if !seen.insert(next) {
             continue;
         }
         if is_known_executor_service_name(&next) {
@@ -503,12 +510,10 @@ fn is_executor_service_type(name: &str, class_map: &BTreeMap<String, &Class>) ->
         let Some(class) = class_map.get(&next) else {
             continue;
         };
-        if let Some(super_name) = &class.super_name {
-            queue.push_back(super_name.clone());
-        }
-        for interface in &class.interfaces {
-            queue.push_back(interface.clone());
+        if let Some(super_name) = class.super_name {
+            queue.push_back(super_name);
         }
+        queue.extend(&class.interfaces);
     }
 
     false
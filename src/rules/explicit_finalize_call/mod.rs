@@ -4,7 +4,7 @@ use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
 use crate::ir::CallKind;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
 
 /// Rule that detects explicit virtual calls to `finalize()` on object instances.
 #[derive(Default)]
@@ -18,13 +18,19 @@ impl Rule for ExplicitFinalizeCallRule {
             id: "EXPLICIT_FINALIZE_CALL",
             name: "Explicit finalize call",
             description: "Direct virtual calls to finalize() bypass GC lifecycle and indicate broken resource cleanup",
+            tags: &["correctness"],
+            precision: RulePrecision::VeryHigh,
+            security_severity: None,
+            cwe: &[586],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -33,7 +39,7 @@ impl Rule for ExplicitFinalizeCallRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
+                        for call in method.source_calls() {
                             if is_explicit_finalize_call(&call.name, &call.descriptor, call.kind) {
                                 let message = result_message(format!(
                                     "Explicit call to finalize() in {}.{}{}; use AutoCloseable with try-with-resources or java.lang.ref.Cleaner for deterministic resource cleanup.",
@@ -3,7 +3,7 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
 
 /// Rule that detects explicit garbage collection API calls.
 #[derive(Default)]
@@ -17,13 +17,19 @@ impl Rule for ExplicitGcCallRule {
             id: "EXPLICIT_GC_CALL",
             name: "Explicit GC call",
             description: "Direct calls to explicit GC APIs should be avoided",
+            tags: &["performance"],
+            precision: RulePrecision::VeryHigh,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,7 +38,7 @@ impl Rule for ExplicitGcCallRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
+                        for call in method.source_calls() {
                             if is_explicit_gc_call(&call.owner, &call.name, &call.descriptor) {
                                 let message = result_message(format!(
                                     "Avoid explicit GC call in {}.{}{}; let the JVM manage garbage collection.",
@@ -124,6 +130,32 @@ public class ClassA {
         );
     }
 
+    #[test]
+    fn explicit_gc_reports_call_in_finally_once() {
+        let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
+        let sources = vec![SourceFile {
+            path: "com/example/ClassA.java".to_string(),
+            contents: r#"
+package com.example;
+public class ClassA {
+    public void methodX(Runnable varOne) {
+        try {
+            varOne.run();
+        } finally {
+            System.gc();
+        }
+    }
+}
+"#
+            .to_string(),
+        }];
+
+        let output = compile_and_analyze(&harness, &sources, &[]);
+        let messages = explicit_gc_messages(&output);
+
+        assert_eq!(messages.len(), 1, "expected a single finding, got {messages:?}");
+    }
+
     #[test]
     fn explicit_gc_reports_runtime_gc() {
         let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
@@ -3,7 +3,7 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
 
 /// Rule that detects timeout-free blocking Future.get calls.
 #[derive(Default)]
@@ -17,13 +17,19 @@ impl Rule for FutureGetWithoutTimeoutRule {
             id: "FUTURE_GET_WITHOUT_TIMEOUT",
             name: "Future.get without timeout",
             description: "Timeout-free Future.get calls can block indefinitely",
+            tags: &["concurrency"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,7 +38,7 @@ impl Rule for FutureGetWithoutTimeoutRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
+                        for call in method.source_calls() {
                             if is_timeout_free_future_get(&call.owner, &call.name, &call.descriptor)
                             {
                                 let message = result_message(format!(
@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
 use anyhow::{Context, Result};
@@ -13,7 +13,14 @@ use crate::descriptor::{ReturnKind, method_return_kind};
 use crate::engine::AnalysisContext;
 use crate::ir::{CallKind, CallSite, FieldRef, Instruction, InstructionKind, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{
+    Rule, RuleMetadata, RulePrecision, add_related_locations, method_location_with_line,
+    related_location, result_message,
+};
+use crate::symbol::{
+    JAVA_UTIL_CONCURRENT_COMPLETABLE_FUTURE, JAVA_UTIL_CONCURRENT_FORK_JOIN_TASK,
+    JAVA_UTIL_CONCURRENT_FUTURE, JAVA_UTIL_CONCURRENT_FUTURE_TASK, Symbol,
+};
 
 /// Rule that detects future waits while the current method still holds a lock.
 #[derive(Default)]
@@ -27,13 +34,19 @@ impl Rule for FutureWaitWhileHoldingLockRule {
             id: "FUTURE_WAIT_WHILE_HOLDING_LOCK",
             name: "Future wait while holding lock",
             description: "Blocking Future waits should not happen while a lock is still held",
+            tags: &["concurrency"],
+            precision: RulePrecision::Medium,
+            security_severity: None,
+            cwe: &[833],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -48,7 +61,7 @@ impl Rule for FutureWaitWhileHoldingLockRule {
                         if !method_needs_analysis(method) {
                             continue;
                         }
-                        for offset in reportable_wait_offsets(method)? {
+                        for (offset, acquired_at) in reportable_wait_offsets(method)? {
                             let message = result_message(
                                 "Do not wait on a Future while holding a lock; release the lock before calling get()/join(), or move the wait outside the synchronized or locked section.",
                             );
@@ -60,12 +73,25 @@ impl Rule for FutureWaitWhileHoldingLockRule {
                                 artifact_uri.as_deref(),
                                 line,
                             );
-                            class_results.push(
-                                SarifResult::builder()
-                                    .message(message)
-                                    .locations(vec![location])
-                                    .build(),
-                            );
+                            let mut result = SarifResult::builder()
+                                .message(message)
+                                .locations(vec![location])
+                                .build();
+                            // Synchronized methods hold their monitor without an acquisition site.
+                            if let Some(acquired_at) = acquired_at {
+                                let acquisition = method_location_with_line(
+                                    &class.name,
+                                    &method.name,
+                                    &method.descriptor,
+                                    artifact_uri.as_deref(),
+                                    method.line_for_offset(acquired_at),
+                                );
+                                add_related_locations(
+                                    &mut result,
+                                    [related_location(acquisition, "Lock acquired here")],
+                                );
+                            }
+                            class_results.push(result);
                         }
                     }
                     Ok(class_results)
@@ -87,14 +113,14 @@ enum Value {
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 enum LockIdentity {
     StaticField {
-        owner: String,
-        name: String,
-        descriptor: String,
+        owner: Symbol,
+        name: Symbol,
+        descriptor: Symbol,
     },
     ThisField {
-        owner: String,
-        name: String,
-        descriptor: String,
+        owner: Symbol,
+        name: Symbol,
+        descriptor: Symbol,
     },
     Param(usize),
 }
@@ -104,7 +130,10 @@ struct WaitState {
     block_start: u32,
     instruction_index: usize,
     monitor_depth: u8,
-    held_locks: BTreeSet<LockIdentity>,
+    /// Offsets of the `monitorenter` instructions for the monitors currently held.
+    monitor_sites: Vec<u32>,
+    /// Explicit locks currently held, with the offset of their `lock()` call.
+    held_locks: BTreeMap<LockIdentity, u32>,
     locals: Vec<Value>,
     stack: Vec<Value>,
 }
@@ -128,6 +157,8 @@ impl WorklistState for WaitState {
 struct WaitObservation {
     offset: u32,
     lock_held: bool,
+    /// Offset of the most recent lock acquisition still held at the wait.
+    acquired_at: Option<u32>,
 }
 
 /// Worklist semantics that record whether a wait site is reached with a lock held on every path.
@@ -149,7 +180,8 @@ impl WorklistSemantics for FutureWaitSemantics {
                 .unwrap_or(0),
             instruction_index: 0,
             monitor_depth: u8::from(method.access.is_synchronized),
-            held_locks: BTreeSet::new(),
+            monitor_sites: Vec::new(),
+            held_locks: BTreeMap::new(),
             locals: self.initial_locals.clone(),
             stack: Vec::new(),
         }]
@@ -209,10 +241,12 @@ impl WorklistSemantics for FutureWaitSemantics {
             opcodes::MONITORENTER => {
                 pop_value(&mut state.stack);
                 state.monitor_depth = state.monitor_depth.saturating_add(1);
+                state.monitor_sites.push(instruction.offset);
             }
             opcodes::MONITOREXIT => {
                 pop_value(&mut state.stack);
                 state.monitor_depth = state.monitor_depth.saturating_sub(1);
+                state.monitor_sites.pop();
             }
             opcodes::NEW => state.stack.push(Value::Other),
             _ => {}
@@ -222,25 +256,33 @@ impl WorklistSemantics for FutureWaitSemantics {
     }
 }
 
-fn reportable_wait_offsets(method: &Method) -> Result<Vec<u32>> {
+/// Returns wait offsets reached with a lock held on every path, paired with the innermost
+/// acquisition site still held there (the earliest one when paths disagree).
+fn reportable_wait_offsets(method: &Method) -> Result<Vec<(u32, Option<u32>)>> {
     let semantics = FutureWaitSemantics {
         initial_locals: initial_locals(method)?,
     };
     let observations = analyze_method(method, &semantics)?;
-    let mut by_offset = BTreeMap::<u32, (bool, bool)>::new();
+    let mut by_offset = BTreeMap::<u32, (bool, bool, Option<u32>)>::new();
     for observation in observations {
-        let entry = by_offset.entry(observation.offset).or_insert((false, false));
+        let entry = by_offset
+            .entry(observation.offset)
+            .or_insert((false, false, None));
         if observation.lock_held {
             entry.0 = true;
         } else {
             entry.1 = true;
         }
+        entry.2 = match (entry.2, observation.acquired_at) {
+            (Some(current), Some(acquired_at)) => Some(current.min(acquired_at)),
+            (current, acquired_at) => current.or(acquired_at),
+        };
     }
 
     Ok(by_offset
         .into_iter()
-        .filter_map(|(offset, (seen_locked, seen_unlocked))| {
-            (seen_locked && !seen_unlocked).then_some(offset)
+        .filter_map(|(offset, (seen_locked, seen_unlocked, acquired_at))| {
+            (seen_locked && !seen_unlocked).then_some((offset, acquired_at))
         })
         .collect())
 }
@@ -256,10 +298,16 @@ fn transfer_invoke(
         step = step.with_finding(WaitObservation {
             offset: call.offset,
             lock_held: state.monitor_depth > 0 || !state.held_locks.is_empty(),
+            acquired_at: state
+                .monitor_sites
+                .iter()
+                .chain(state.held_locks.values())
+                .max()
+                .copied(),
         });
     } else if is_lock_call(call) {
         if let Some(Value::Lock(lock)) = receiver {
-            state.held_locks.insert(lock);
+            state.held_locks.entry(lock).or_insert(call.offset);
         }
     } else if is_unlock_call(call)
         && let Some(Value::Lock(lock)) = receiver
@@ -315,9 +363,9 @@ fn value_for_instance_field(field: &FieldRef, receiver: &Value) -> Value {
     if is_lock_descriptor(&field.descriptor) {
         if matches!(receiver, Value::This) {
             return Value::Lock(LockIdentity::ThisField {
-                owner: field.owner.clone(),
-                name: field.name.clone(),
-                descriptor: field.descriptor.clone(),
+                owner: field.owner,
+                name: field.name,
+                descriptor: field.descriptor,
             });
         }
     }
@@ -327,9 +375,9 @@ fn value_for_instance_field(field: &FieldRef, receiver: &Value) -> Value {
 fn value_for_static_field(field: &FieldRef) -> Value {
     if is_lock_descriptor(&field.descriptor) {
         Value::Lock(LockIdentity::StaticField {
-            owner: field.owner.clone(),
-            name: field.name.clone(),
-            descriptor: field.descriptor.clone(),
+            owner: field.owner,
+            name: field.name,
+            descriptor: field.descriptor,
         })
     } else {
         Value::Other
@@ -376,9 +424,9 @@ fn is_wait_call(call: &CallSite) -> bool {
     match (call.name.as_str(), call.descriptor.as_str()) {
         ("get", "()Ljava/lang/Object;")
         | ("get", "(JLjava/util/concurrent/TimeUnit;)Ljava/lang/Object;") => {
-            is_future_owner(&call.owner)
+            is_future_owner(call.owner)
         }
-        ("join", "()Ljava/lang/Object;") => call.owner == "java/util/concurrent/CompletableFuture",
+        ("join", "()Ljava/lang/Object;") => call.owner == JAVA_UTIL_CONCURRENT_COMPLETABLE_FUTURE,
         _ => false,
     }
 }
@@ -391,10 +439,10 @@ fn is_unlock_call(call: &CallSite) -> bool {
     call.name == "unlock" && call.descriptor == "()V" && is_lock_owner(&call.owner)
 }
 
-fn is_future_owner(owner: &str) -> bool {
-    owner == "java/util/concurrent/Future"
-        || owner == "java/util/concurrent/FutureTask"
-        || owner == "java/util/concurrent/ForkJoinTask"
+fn is_future_owner(owner: Symbol) -> bool {
+    owner == JAVA_UTIL_CONCURRENT_FUTURE
+        || owner == JAVA_UTIL_CONCURRENT_FUTURE_TASK
+        || owner == JAVA_UTIL_CONCURRENT_FORK_JOIN_TASK
         || (owner.starts_with("java/util/concurrent/") && owner.ends_with("Future"))
 }
 
@@ -455,20 +503,48 @@ fn pop_value(stack: &mut Vec<Value>) -> Value {
 
 #[cfg(test)]
 mod tests {
+    use serde_sarif::sarif::Result as SarifResult;
+
     use crate::test_harness::{JvmTestHarness, Language, SourceFile};
 
-    fn messages_for_sources(sources: Vec<SourceFile>) -> Vec<String> {
+    fn results_for_sources(sources: Vec<SourceFile>) -> Vec<SarifResult> {
         let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
         let output = harness
             .compile_and_analyze(Language::Java, &sources, &[])
             .expect("run harness analysis");
         output
             .results
-            .iter()
+            .into_iter()
             .filter(|result| {
                 result.rule_id.as_deref() == Some("FUTURE_WAIT_WHILE_HOLDING_LOCK")
             })
-            .filter_map(|result| result.message.text.clone())
+            .collect()
+    }
+
+    fn messages_for_sources(sources: Vec<SourceFile>) -> Vec<String> {
+        results_for_sources(sources)
+            .into_iter()
+            .filter_map(|result| result.message.text)
+            .collect()
+    }
+
+    fn related_lines(result: &SarifResult) -> Vec<(Option<String>, Option<i64>)> {
+        result
+            .related_locations
+            .iter()
+            .flatten()
+            .map(|location| {
+                let message = location
+                    .message
+                    .as_ref()
+                    .and_then(|message| message.text.clone());
+                let line = location
+                    .physical_location
+                    .as_ref()
+                    .and_then(|physical| physical.region.as_ref())
+                    .and_then(|region| region.start_line);
+                (message, line)
+            })
             .collect()
     }
 
@@ -746,4 +822,48 @@ public class ClassJ {
         let messages = messages_for_sources(sources);
         assert_eq!(messages.len(), 1);
     }
+
+    #[test]
+    fn relates_wait_to_lock_acquisition_site() {
+        let sources = vec![SourceFile {
+            path: "com/example/ClassK.java".to_string(),
+            contents: r#"
+package com.example;
+
+import java.util.concurrent.Future;
+import java.util.concurrent.locks.Lock;
+
+public class ClassK {
+    private final Object varOne = new Object();
+
+    public void methodA(Future<String> varTwo) throws Exception {
+        synchronized (varOne) {
+            varTwo.get();
+        }
+    }
+
+    public void methodB(Lock varOne, Future<String> varTwo) throws Exception {
+        varOne.lock();
+        try {
+            varTwo.get();
+        } finally {
+            varOne.unlock();
+        }
+    }
+
+    public synchronized void methodC(Future<String> varTwo) throws Exception {
+        varTwo.get();
+    }
+}
+"#
+            .to_string(),
+        }];
+
+        let results = results_for_sources(sources);
+        let mut related: Vec<_> = results.iter().map(related_lines).collect();
+        related.sort();
+
+        let acquired = |line| vec![(Some("Lock acquired here".to_string()), Some(line))];
+        assert_eq!(related, vec![vec![], acquired(11), acquired(17)]);
+    }
 }
@@ -3,7 +3,7 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, class_location, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, class_location, result_message};
 
 /// Rule that flags classes overriding equals or hashCode alone.
 #[derive(Default)]
@@ -17,13 +17,19 @@ impl Rule for IneffectiveEqualsRule {
             id: "INEFFECTIVE_EQUALS_HASHCODE",
             name: "Ineffective equals/hashCode",
             description: "Classes with equals without hashCode or vice versa",
+            tags: &["correctness"],
+            precision: RulePrecision::VeryHigh,
+            security_severity: None,
+            cwe: &[581],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -81,13 +87,15 @@ mod tests {
         ControlFlowGraph {
             blocks: Vec::new(),
             edges: Vec::new(),
+            duplicates: Default::default(),
         }
     }
 
     fn method_with(name: &str, descriptor: &str) -> Method {
         Method {
-            name: name.to_string(),
-            descriptor: descriptor.to_string(),
+            owner: "com/example/Value".into(),
+            name: name.into(),
+            descriptor: descriptor.into(),
             signature: None,
             access: MethodAccess {
                 is_public: true,
@@ -96,6 +104,8 @@ mod tests {
                 is_abstract: false,
                 is_synthetic: false,
                 is_bridge: false,
+                is_private: false,
+                is_final: false,
             },
             nullness: MethodNullness::unknown(method_param_count(descriptor).expect("param count")),
             type_use: None,
@@ -107,12 +117,13 @@ mod tests {
             exception_handlers: Vec::new(),
             local_variables: vec![],
             local_variable_types: Vec::new(),
+            flow_structure: Default::default(),
         }
     }
 
     fn class_with_methods(name: &str, methods: Vec<Method>) -> Class {
         Class {
-            name: name.to_string(),
+            name: name.into(),
             source_file: None,
             super_name: None,
             interfaces: Vec::new(),
@@ -123,6 +134,8 @@ mod tests {
             annotation_defaults: Vec::new(),
             artifact_index: 0,
             is_record: false,
+            is_generated: false,
+            header_location: None,
         }
     }
 
@@ -1,9 +1,17 @@
+use std::collections::BTreeSet;
+
 use anyhow::Result;
 use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rule_options::{RuleOptionKind, RuleOptionSpec};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+
+/// Option listing extra `owner.method` names to report.
+const ADDITIONAL_METHODS_OPTION: &str = "additionalMethods";
+/// Option listing built-in `owner.method` names to stop reporting.
+const IGNORED_METHODS_OPTION: &str = "ignoredMethods";
 
 /// Rule that detects insecure API usage.
 #[derive(Default)]
@@ -17,13 +25,33 @@ impl Rule for InsecureApiRule {
             id: "INSECURE_API",
             name: "Insecure API usage",
             description: "Calls to insecure process or reflection APIs",
+            tags: &["security"],
+            precision: RulePrecision::Medium,
+            security_severity: Some(7.3),
+            cwe: &[78, 470],
+            requires: None,
+            options: &[
+                RuleOptionSpec {
+                    name: ADDITIONAL_METHODS_OPTION,
+                    kind: RuleOptionKind::StringList,
+                    description: "Extra methods to report, as `owner.method` (e.g. `java.lang.System.load`)",
+                },
+                RuleOptionSpec {
+                    name: IGNORED_METHODS_OPTION,
+                    kind: RuleOptionKind::StringList,
+                    description: "Built-in methods to stop reporting, as `owner.method` (e.g. `java.lang.Class.forName`)",
+                },
+            ],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
+        let options = context.rule_options(self.metadata().id);
+        let additional = method_names(options.string_list(ADDITIONAL_METHODS_OPTION));
+        let ignored = method_names(options.string_list(IGNORED_METHODS_OPTION));
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -31,12 +59,16 @@ impl Rule for InsecureApiRule {
                 context.with_span("class", &attributes, || -> Result<Vec<SarifResult>> {
                     let mut class_results = Vec::new();
                     for method in &class.methods {
-                        for call in &method.calls {
-                            if is_insecure_call(&call.owner, &call.name) {
-                                let message = result_message(format!(
-                                    "Insecure API usage: {}.{}",
-                                    call.owner, call.name
-                                ));
+                        for call in method.source_calls() {
+                            let method_name = format!("{}.{}", call.owner, call.name);
+                            let reported = if is_insecure_call(&call.owner, &call.name) {
+                                !ignored.contains(&method_name)
+                            } else {
+                                additional.contains(&method_name)
+                            };
+                            if reported {
+                                let message =
+                                    result_message(format!("Insecure API usage: {method_name}"));
                                 let line = method.line_for_offset(call.offset);
                                 let artifact_uri = context.class_artifact_uri(class);
                                 let location = method_location_with_line(
@@ -63,6 +95,17 @@ impl Rule for InsecureApiRule {
     }
 }
 
+/// Normalizes `owner.method` option entries to the internal `owner/Name.method` form.
+fn method_names(entries: &[String]) -> BTreeSet<String> {
+    entries
+        .iter()
+        .map(|entry| match entry.rsplit_once('.') {
+            Some((owner, name)) => format!("{}.{name}", owner.replace('.', "/")),
+            None => entry.clone(),
+        })
+        .collect()
+}
+
 fn is_insecure_call(owner: &str, name: &str) -> bool {
     matches!(
         (owner, name),
@@ -80,6 +123,7 @@ mod tests {
     use super::*;
     use crate::descriptor::method_param_count;
     use crate::engine::build_context;
+    use crate::rule_options::resolve_rule_options;
     use crate::ir::{
         CallKind, CallSite, Class, ControlFlowGraph, Method, MethodAccess, MethodNullness,
     };
@@ -90,13 +134,15 @@ mod tests {
         ControlFlowGraph {
             blocks: Vec::new(),
             edges: Vec::new(),
+            duplicates: Default::default(),
         }
     }
 
     fn method_with(name: &str, calls: Vec<CallSite>) -> Method {
         Method {
-            name: name.to_string(),
-            descriptor: "()V".to_string(),
+            owner: "com/example/App".into(),
+            name: name.into(),
+            descriptor: "()V".into(),
             signature: None,
             access: MethodAccess {
                 is_public: true,
@@ -105,6 +151,8 @@ mod tests {
                 is_abstract: false,
                 is_synthetic: false,
                 is_bridge: false,
+                is_private: false,
+                is_final: false,
             },
             nullness: MethodNullness::unknown(method_param_count("()V").expect("param count")),
             type_use: None,
@@ -116,12 +164,13 @@ mod tests {
             exception_handlers: Vec::new(),
             local_variables: vec![],
             local_variable_types: Vec::new(),
+            flow_structure: Default::default(),
         }
     }
 
     fn class_with_methods(name: &str, methods: Vec<Method>) -> Class {
         Class {
-            name: name.to_string(),
+            name: name.into(),
             source_file: None,
             super_name: None,
             interfaces: Vec::new(),
@@ -132,6 +181,8 @@ mod tests {
             annotation_defaults: Vec::new(),
             artifact_index: 0,
             is_record: false,
+            is_generated: false,
+            header_location: None,
         }
     }
 
@@ -141,7 +192,7 @@ mod tests {
         methods: Vec<Method>,
     ) -> Class {
         Class {
-            name: name.to_string(),
+            name: name.into(),
             source_file: None,
             super_name: None,
             interfaces: Vec::new(),
@@ -152,6 +203,8 @@ mod tests {
             annotation_defaults: Vec::new(),
             artifact_index,
             is_record: false,
+            is_generated: false,
+            header_location: None,
         }
     }
 
@@ -171,9 +224,9 @@ mod tests {
         let method = method_with(
             "run",
             vec![CallSite {
-                owner: "java/lang/Runtime".to_string(),
-                name: "exec".to_string(),
-                descriptor: "(Ljava/lang/String;)V".to_string(),
+                owner: "java/lang/Runtime".into(),
+                name: "exec".into(),
+                descriptor: "(Ljava/lang/String;)V".into(),
                 kind: CallKind::Virtual,
                 offset: 0,
             }],
@@ -190,14 +243,62 @@ mod tests {
         assert!(message.contains("Insecure API usage: java/lang/Runtime.exec"));
     }
 
+    #[test]
+    fn insecure_api_rule_applies_method_options() {
+        let call = |owner: &str, name: &str| CallSite {
+            owner: owner.into(),
+            name: name.into(),
+            descriptor: "(Ljava/lang/String;)V".into(),
+            kind: CallKind::Static,
+            offset: 0,
+        };
+        let method = method_with(
+            "run",
+            vec![
+                call("java/lang/Class", "forName"),
+                call("java/lang/System", "load"),
+                call("java/lang/Runtime", "exec"),
+            ],
+        );
+        let classes = vec![class_with_methods("com/example/App", vec![method])];
+        let metadata = [InsecureApiRule.metadata()];
+        let options = resolve_rule_options(
+            &serde_json::from_value(serde_json::json!({"INSECURE_API": {
+                "additionalMethods": ["java.lang.System.load"],
+                "ignoredMethods": ["java/lang/Class.forName"],
+            }}))
+            .expect("raw options"),
+            &metadata,
+            "--config file",
+        )
+        .expect("resolve options");
+        let context = context_for(classes).with_rule_options(options);
+
+        let results = InsecureApiRule
+            .run(&context)
+            .expect("insecure api rule run");
+
+        let messages: Vec<_> = results
+            .iter()
+            .filter_map(|result| result.message.text.as_deref())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Insecure API usage: java/lang/System.load",
+                "Insecure API usage: java/lang/Runtime.exec",
+            ]
+        );
+    }
+
     #[test]
     fn insecure_api_rule_ignores_safe_calls() {
         let method = method_with(
             "run",
             vec![CallSite {
-                owner: "java/lang/String".to_string(),
-                name: "length".to_string(),
-                descriptor: "()I".to_string(),
+                owner: "java/lang/String".into(),
+                name: "length".into(),
+                descriptor: "()I".into(),
                 kind: CallKind::Virtual,
                 offset: 0,
             }],
@@ -215,16 +316,16 @@ mod tests {
     #[test]
     fn insecure_api_rule_skips_non_target_classes() {
         let target_calls = vec![CallSite {
-            owner: "java/lang/Runtime".to_string(),
-            name: "exec".to_string(),
-            descriptor: "(Ljava/lang/String;)Ljava/lang/Process;".to_string(),
+            owner: "java/lang/Runtime".into(),
+            name: "exec".into(),
+            descriptor: "(Ljava/lang/String;)Ljava/lang/Process;".into(),
             kind: CallKind::Virtual,
             offset: 0,
         }];
         let dependency_calls = vec![CallSite {
-            owner: "java/lang/Class".to_string(),
-            name: "forName".to_string(),
-            descriptor: "(Ljava/lang/String;)Ljava/lang/Class;".to_string(),
+            owner: "java/lang/Class".into(),
+            name: "forName".into(),
+            descriptor: "(Ljava/lang/String;)Ljava/lang/Class;".into(),
             kind: CallKind::Static,
             offset: 0,
         }];
@@ -3,7 +3,8 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_LANG_INTEGER, Symbol};
 
 /// Rule that detects direct Integer.getInteger calls.
 #[derive(Default)]
@@ -17,13 +18,19 @@ impl Rule for IntegerGetintegerCallRule {
             id: "INTEGER_GETINTEGER_CALL",
             name: "Integer.getInteger call",
             description: "Integer.getInteger reads system properties, not numeric input strings",
+            tags: &["correctness"],
+            precision: RulePrecision::Medium,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,8 +39,8 @@ impl Rule for IntegerGetintegerCallRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
-                            if is_integer_getinteger_call(&call.owner, &call.name, &call.descriptor)
+                        for call in method.source_calls() {
+                            if is_integer_getinteger_call(call.owner, &call.name, &call.descriptor)
                             {
                                 let message = result_message(format!(
                                     "Avoid Integer.getInteger() in {}.{}{}; use Integer.parseInt()/valueOf() for numeric parsing or keep it only for system property reads.",
@@ -64,8 +71,8 @@ impl Rule for IntegerGetintegerCallRule {
     }
 }
 
-fn is_integer_getinteger_call(owner: &str, name: &str, descriptor: &str) -> bool {
-    owner == "java/lang/Integer"
+fn is_integer_getinteger_call(owner: Symbol, name: &str, descriptor: &str) -> bool {
+    owner == JAVA_LANG_INTEGER
         && name == "getInteger"
         && matches!(
             descriptor,
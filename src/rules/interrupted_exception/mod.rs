@@ -4,13 +4,15 @@ use anyhow::Result;
 use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
+use crate::dataflow::lattice::AbstractState;
 use crate::dataflow::worklist::{
-    InstructionStep, WorklistSemantics, WorklistState, analyze_method,
+    InstructionStep, WorklistSemantics, WorklistState, analyze_method_fixpoint,
 };
 use crate::engine::AnalysisContext;
 use crate::ir::{Instruction, InstructionKind, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::JAVA_LANG_THREAD;
 
 /// Rule that ensures InterruptedException handlers restore interrupt status.
 #[derive(Default)]
@@ -40,6 +42,17 @@ impl WorklistState for ReachableInstructionState {
     }
 }
 
+/// Reachability needs no per-path facts, so every block is visited once.
+impl AbstractState for ReachableInstructionState {
+    type Value = ();
+
+    fn value(&self) -> &Self::Value {
+        &()
+    }
+
+    fn set_value(&mut self, _value: Self::Value) {}
+}
+
 /// Dataflow callbacks for collecting instruction offsets reachable from a handler entry.
 struct ReachableInstructionSemantics {
     handler_pc: u32,
@@ -72,13 +85,19 @@ impl Rule for InterruptedExceptionRule {
             id: "INTERRUPTED_EXCEPTION_NOT_RESTORED",
             name: "InterruptedException not properly handled",
             description: "Restore interrupt status when catching InterruptedException",
+            tags: &["concurrency"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -144,7 +163,7 @@ fn collect_reachable_instructions<'a>(
     handler_pc: u32,
 ) -> Result<Vec<&'a Instruction>> {
     let semantics = ReachableInstructionSemantics { handler_pc };
-    let instruction_offsets = analyze_method(method, &semantics)?;
+    let instruction_offsets = analyze_method_fixpoint(method, &semantics)?;
     let mut instruction_map: BTreeMap<u32, &Instruction> = BTreeMap::new();
     for block in &method.cfg.blocks {
         for instruction in &block.instructions {
@@ -167,18 +186,19 @@ fn handler_restores_interrupt(instructions: &[&Instruction]) -> bool {
         let InstructionKind::Invoke(call) = &instruction.kind else {
             continue;
         };
-        if call.owner == "java/lang/Thread"
+        if call.owner == JAVA_LANG_THREAD
             && call.name == "currentThread"
             && call.descriptor == "()Ljava/lang/Thread;"
         {
             seen_current_thread = true;
             continue;
         }
-        if call.owner == "java/lang/Thread" && call.name == "interrupt" && call.descriptor == "()V"
+        if call.owner == JAVA_LANG_THREAD
+            && call.name == "interrupt"
+            && call.descriptor == "()V"
+            && seen_current_thread
         {
-            if seen_current_thread {
-                return true;
-            }
+            return true;
         }
     }
     false
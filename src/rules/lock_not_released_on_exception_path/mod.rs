@@ -5,11 +5,14 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::dataflow::worklist::{
-    BlockEndStep, InstructionStep, WorklistSemantics, WorklistState, analyze_method,
+    BlockEndStep, InstructionStep, TraceStep, WorklistSemantics, WorklistState,
+    analyze_method_with_trace,
 };
 use crate::engine::AnalysisContext;
 use crate::ir::{Instruction, InstructionKind, Method};
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{
+    Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message, trace_code_flow,
+};
 
 /// Rule that detects lock acquisitions without guaranteed unlock on all reachable exits.
 #[derive(Default)]
@@ -99,13 +102,19 @@ impl Rule for LockNotReleasedOnExceptionPathRule {
             id: "LOCK_NOT_RELEASED_ON_EXCEPTION_PATH",
             name: "Lock acquired without guaranteed release",
             description: "Lock.lock() must be followed by unlock() on every reachable exit path",
+            tags: &["concurrency"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[667],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -129,7 +138,7 @@ impl Rule for LockNotReleasedOnExceptionPathRule {
                             if !seen_offsets.insert(site.offset) {
                                 continue;
                             }
-                            if has_exit_path_without_unlock(method, site)? {
+                            if let Some(trace) = exit_path_without_unlock(method, site)? {
                                 let message = result_message(format!(
                                     "Lock acquired in {}.{}{} may exit without unlock(); release it in a finally block.",
                                     class.name, method.name, method.descriptor
@@ -143,10 +152,19 @@ impl Rule for LockNotReleasedOnExceptionPathRule {
                                     artifact_uri.as_deref(),
                                     line,
                                 );
+                                let code_flow = trace_code_flow(
+                                    &class.name,
+                                    method,
+                                    artifact_uri.as_deref(),
+                                    (site.offset, "Lock acquired here"),
+                                    &trace,
+                                    "Method exits without unlock()",
+                                );
                                 class_results.push(
                                     SarifResult::builder()
                                         .message(message)
                                         .locations(vec![location])
+                                        .code_flows(vec![code_flow])
                                         .build(),
                                 );
                             }
@@ -177,10 +195,11 @@ fn lock_sites(method: &Method) -> Vec<LockSite> {
     sites
 }
 
-fn has_exit_path_without_unlock(method: &Method, site: LockSite) -> Result<bool> {
+/// Returns the trace of the first exit path reached without `unlock()`, if any.
+fn exit_path_without_unlock(method: &Method, site: LockSite) -> Result<Option<Vec<TraceStep>>> {
     let semantics = LockPathSemantics { site };
-    let findings = analyze_method(method, &semantics)?;
-    Ok(!findings.is_empty())
+    let findings = analyze_method_with_trace(method, &semantics)?;
+    Ok(findings.into_iter().next().map(|traced| traced.trace))
 }
 
 fn is_lock_invocation(instruction: &Instruction) -> bool {
@@ -260,6 +279,56 @@ public class ClassA {
         assert!(messages[0].contains("finally block"));
     }
 
+    #[test]
+    fn reports_code_flow_from_lock_to_exit() {
+        let sources = vec![SourceFile {
+            path: "com/example/ClassA.java".to_string(),
+            contents: r#"
+package com.example;
+
+import java.util.concurrent.locks.Lock;
+import java.util.concurrent.locks.ReentrantLock;
+
+public class ClassA {
+    private final Lock varOne = new ReentrantLock();
+
+    public void methodX(boolean varTwo) {
+        varOne.lock();
+        if (varTwo) {
+            throw new IllegalStateException("tmpValue");
+        }
+        varOne.unlock();
+    }
+}
+"#
+            .to_string(),
+        }];
+
+        let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
+        let output = harness
+            .compile_and_analyze(Language::Java, &sources, &[])
+            .expect("run harness analysis");
+        let result = output
+            .results
+            .iter()
+            .find(|result| {
+                result.rule_id.as_deref() == Some("LOCK_NOT_RELEASED_ON_EXCEPTION_PATH")
+            })
+            .expect("lock finding");
+        let flow = serde_json::to_value(result.code_flows.as_ref().expect("code flows"))
+            .expect("serialize code flows");
+        let locations = flow[0]["threadFlows"][0]["locations"]
+            .as_array()
+            .expect("thread flow locations");
+
+        let first = &locations[0]["location"];
+        assert_eq!(first["message"]["text"], "Lock acquired here");
+        assert_eq!(first["physicalLocation"]["region"]["startLine"], 11);
+        let last = &locations[locations.len() - 1]["location"];
+        assert_eq!(last["message"]["text"], "Method exits without unlock()");
+        assert_eq!(last["physicalLocation"]["region"]["startLine"], 13);
+    }
+
     #[test]
     fn does_not_report_lock_released_in_finally() {
         let sources = vec![SourceFile {
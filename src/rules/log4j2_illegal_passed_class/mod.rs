@@ -10,7 +10,10 @@ use crate::descriptor::{ReturnKind, method_return_kind};
 use crate::engine::AnalysisContext;
 use crate::ir::{CallKind, InstructionKind, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{
+    RequiredLibrary, Rule, RuleMetadata, RulePrecision, add_related_locations, class_location,
+    method_location_with_line, related_location, result_message,
+};
 
 /// Rule that checks Log4j2 LogManager.getLogger(Class) arguments.
 #[derive(Default)]
@@ -24,6 +27,12 @@ impl Rule for Log4j2IllegalPassedClassRule {
             id: "LOG4J2_ILLEGAL_PASSED_CLASS",
             name: "Log4j2 illegal passed class",
             description: "LogManager.getLogger should be called with the caller class",
+            tags: &["logging"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: Some(RequiredLibrary::Log4j2),
+            options: &[],
         }
     }
 
@@ -34,7 +43,7 @@ impl Rule for Log4j2IllegalPassedClassRule {
 
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -47,6 +56,7 @@ impl Rule for Log4j2IllegalPassedClassRule {
                         }
                         let artifact_uri = context.class_artifact_uri(class);
                         class_results.extend(analyze_method(
+                            context,
                             &class.name,
                             method,
                             artifact_uri.as_deref(),
@@ -67,6 +77,7 @@ enum ValueKind {
 }
 
 fn analyze_method(
+    context: &AnalysisContext,
     class_name: &str,
     method: &Method,
     artifact_uri: Option<&str>,
@@ -164,12 +175,20 @@ fn analyze_method(
                                     artifact_uri,
                                     line,
                                 );
-                                results.push(
-                                    SarifResult::builder()
-                                        .message(message)
-                                        .locations(vec![location])
-                                        .build(),
+                                let mut result = SarifResult::builder()
+                                    .message(message)
+                                    .locations(vec![location])
+                                    .build();
+                                let passed_class_uri =
+                                    context.class_artifact_uri_by_name(&passed_class);
+                                add_related_locations(
+                                    &mut result,
+                                    [related_location(
+                                        class_location(&passed_class, passed_class_uri.as_deref()),
+                                        "Class passed to LogManager.getLogger",
+                                    )],
                                 );
+                                results.push(result);
                             }
                         }
                     }
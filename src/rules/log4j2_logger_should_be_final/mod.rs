@@ -3,7 +3,10 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, class_location, result_message};
+use crate::rules::{
+    RequiredLibrary, Rule, RuleMetadata, RulePrecision, class_location, logger_field_fix,
+    result_message,
+};
 
 /// Rule that ensures Log4j2 logger fields are final.
 #[derive(Default)]
@@ -17,6 +20,12 @@ impl Rule for Log4j2LoggerShouldBeFinalRule {
             id: "LOG4J2_LOGGER_SHOULD_BE_FINAL",
             name: "Log4j2 logger should be final",
             description: "Log4j2 Logger fields should be final",
+            tags: &["logging", "style"],
+            precision: RulePrecision::VeryHigh,
+            security_severity: None,
+            cwe: &[],
+            requires: Some(RequiredLibrary::Log4j2),
+            options: &[],
         }
     }
 
@@ -27,7 +36,7 @@ impl Rule for Log4j2LoggerShouldBeFinalRule {
 
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -47,12 +56,12 @@ impl Rule for Log4j2LoggerShouldBeFinalRule {
                         ));
                         let artifact_uri = context.class_artifact_uri(class);
                         let location = class_location(&class.name, artifact_uri.as_deref());
-                        class_results.push(
-                            SarifResult::builder()
-                                .message(message)
-                                .locations(vec![location])
-                                .build(),
-                        );
+                        let mut result = SarifResult::builder()
+                            .message(message)
+                            .locations(vec![location])
+                            .build();
+                        logger_field_fix(field).attach(&mut result);
+                        class_results.push(result);
                     }
                     Ok(class_results)
                 })?;
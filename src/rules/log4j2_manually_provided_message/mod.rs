@@ -10,7 +10,10 @@ use crate::descriptor::{ReturnKind, method_return_kind};
 use crate::engine::AnalysisContext;
 use crate::ir::{CallKind, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{
+    RequiredLibrary, Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message,
+};
+use crate::symbol::JAVA_LANG_STRING;
 
 /// Rule that detects Log4j2 log messages assembled manually instead of placeholders.
 #[derive(Default)]
@@ -24,6 +27,12 @@ impl Rule for Log4j2ManuallyProvidedMessageRule {
             id: "LOG4J2_MANUALLY_PROVIDED_MESSAGE",
             name: "Log4j2 preformatted message",
             description: "Log4j2 messages should use placeholders instead of manual formatting",
+            tags: &["logging", "performance"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: Some(RequiredLibrary::Log4j2),
+            options: &[],
         }
     }
 
@@ -34,7 +43,7 @@ impl Rule for Log4j2ManuallyProvidedMessageRule {
 
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -278,7 +287,7 @@ fn log4j2_format_index(param_types: &[jdescriptor::TypeDescriptor]) -> Option<us
 }
 
 fn is_manual_message_call(call: &crate::ir::CallSite) -> bool {
-    if call.owner == "java/lang/String" && call.name == "format" {
+    if call.owner == JAVA_LANG_STRING && call.name == "format" {
         return call.descriptor.ends_with(")Ljava/lang/String;");
     }
     if matches!(
@@ -9,7 +9,9 @@ use serde_sarif::sarif::Result as SarifResult;
 use crate::engine::AnalysisContext;
 use crate::ir::{CallKind, InstructionKind, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{
+    RequiredLibrary, Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message,
+};
 
 /// Rule that flags Log4j2 format strings that are only placeholders.
 #[derive(Default)]
@@ -23,6 +25,12 @@ impl Rule for Log4j2SignOnlyFormatRule {
             id: "LOG4J2_SIGN_ONLY_FORMAT",
             name: "Log4j2 placeholder-only format",
             description: "Log4j2 format strings should include descriptive text",
+            tags: &["logging", "style"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: Some(RequiredLibrary::Log4j2),
+            options: &[],
         }
     }
 
@@ -33,7 +41,7 @@ impl Rule for Log4j2SignOnlyFormatRule {
 
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -3,7 +3,8 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_LANG_LONG, Symbol};
 
 /// Rule that detects direct Long.getLong calls.
 #[derive(Default)]
@@ -17,13 +18,19 @@ impl Rule for LongGetlongCallRule {
             id: "LONG_GETLONG_CALL",
             name: "Long.getLong call",
             description: "Long.getLong reads system properties, not numeric input strings",
+            tags: &["correctness"],
+            precision: RulePrecision::Medium,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,8 +39,8 @@ impl Rule for LongGetlongCallRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
-                            if is_long_getlong_call(&call.owner, &call.name, &call.descriptor) {
+                        for call in method.source_calls() {
+                            if is_long_getlong_call(call.owner, &call.name, &call.descriptor) {
                                 let message = result_message(format!(
                                     "Avoid Long.getLong() in {}.{}{}; use Long.parseLong()/valueOf() for numeric parsing or keep it only for system property reads.",
                                     class.name, method.name, method.descriptor
@@ -63,8 +70,8 @@ impl Rule for LongGetlongCallRule {
     }
 }
 
-fn is_long_getlong_call(owner: &str, name: &str, descriptor: &str) -> bool {
-    owner == "java/lang/Long"
+fn is_long_getlong_call(owner: Symbol, name: &str, descriptor: &str) -> bool {
+    owner == JAVA_LANG_LONG
         && name == "getLong"
         && matches!(
             descriptor,
@@ -7,10 +7,14 @@ use serde_sarif::sarif::Result as SarifResult;
 use crate::engine::AnalysisContext;
 use crate::ir::{AnnotationDefaultNumeric, CallKind, Class, InstructionKind, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rule_options::{RuleOptionKind, RuleOptionSpec};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
 
 const KOTLIN_DEFAULT_BUFFER_SIZE: i64 = 8192;
 
+/// Option listing integer literals that are accepted in addition to the built-in allow-list.
+const ALLOWED_VALUES_OPTION: &str = "allowedValues";
+
 /// Rule that detects magic numbers in method bytecode.
 #[derive(Default)]
 pub(crate) struct MagicNumberRule;
@@ -23,15 +27,30 @@ impl Rule for MagicNumberRule {
             id: "MAGIC_NUMBER",
             name: "Magic number",
             description: "Numeric literals used directly in method bodies reduce readability and maintainability; extract them into named constants",
+            tags: &["style"],
+            precision: RulePrecision::Low,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[RuleOptionSpec {
+                name: ALLOWED_VALUES_OPTION,
+                kind: RuleOptionKind::IntegerList,
+                description: "Integer literals to accept in addition to the built-in allow-list",
+            }],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
-        let allowlist = build_allowlist();
+        let mut allowlist = build_allowlist();
+        allowlist.extend(
+            context
+                .rule_options(self.metadata().id)
+                .integer_list(ALLOWED_VALUES_OPTION),
+        );
         let mut results = Vec::new();
 
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -693,6 +712,43 @@ public class ClassA {
         );
     }
 
+    #[test]
+    fn allowed_values_option_extends_allowlist() {
+        let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
+        let sources = vec![SourceFile {
+            path: "com/example/ClassA.java".to_string(),
+            contents: r#"
+package com.example;
+public class ClassA {
+    public void methodOne(int varOne) {
+        if (varOne > 3600 || varOne < -86400) {
+            System.out.println("timeout");
+        }
+    }
+}
+"#
+            .to_string(),
+        }];
+
+        let output = harness
+            .compile_and_analyze_with_rule_options(
+                Language::Java,
+                &sources,
+                &[],
+                serde_json::json!({"MAGIC_NUMBER": {"allowedValues": [3600]}}),
+            )
+            .expect("run harness analysis");
+        let messages = magic_number_messages(&output);
+        assert!(
+            !messages.iter().any(|msg| msg.contains("3600")),
+            "expected 3600 to be allowed, got {messages:?}"
+        );
+        assert!(
+            messages.iter().any(|msg| msg.contains("-86400")),
+            "expected magic number -86400 finding, got {messages:?}"
+        );
+    }
+
     #[test]
     fn reports_non_allowlisted_float() {
         let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
@@ -1,13 +1,26 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::OnceLock;
+
 use anyhow::Result;
+use opentelemetry::KeyValue;
 use serde_sarif::sarif::{
-    ArtifactLocation, Location, LogicalLocation, Message, PhysicalLocation, Region,
-    Result as SarifResult,
+    ArtifactLocation, CodeFlow, Location, LogicalLocation, Message, PhysicalLocation, Region,
+    Result as SarifResult, ResultLevel, ThreadFlow, ThreadFlowLocation,
 };
 
+use crate::dataflow::taint::{TaintFlow, TaintSpec, analyze_taint};
+use crate::dataflow::worklist::TraceStep;
 use crate::engine::AnalysisContext;
+use crate::fix::FixSuggestion;
+use crate::ir::{Class, EdgeKind, Field, Method};
+use crate::rule_options::RuleOptionSpec;
 
 // Rule modules are auto-discovered by build.rs — do not edit manually.
 include!(concat!(env!("OUT_DIR"), "/rule_modules.rs"));
+// `(module, spec.md contents)` pairs embedded by build.rs.
+include!(concat!(env!("OUT_DIR"), "/rule_specs.rs"));
+
+const RULE_SPEC_BASE_URL: &str = "https://github.com/KengoTODA/inspequte/blob/main/src/rules";
 
 /// Metadata describing an analysis rule.
 #[derive(Clone, Debug)]
@@ -15,6 +28,67 @@ pub(crate) struct RuleMetadata {
     pub(crate) id: &'static str,
     pub(crate) name: &'static str,
     pub(crate) description: &'static str,
+    /// Categories from [`RULE_TAGS`], selectable with `--rules tag:<name>`.
+    pub(crate) tags: &'static [&'static str],
+    pub(crate) precision: RulePrecision,
+    /// GitHub code scanning `security-severity` score (0.0-10.0); only set for security rules.
+    pub(crate) security_severity: Option<f64>,
+    /// CWE identifiers for the weakness the rule detects.
+    pub(crate) cwe: &'static [u32],
+    /// Optional library the analyzed classes must use for the rule to report anything.
+    pub(crate) requires: Option<RequiredLibrary>,
+    /// Options users may set under `ruleOptions.<RULE_ID>`.
+    pub(crate) options: &'static [RuleOptionSpec],
+}
+
+/// Optional libraries detected on the analysis classpath.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RequiredLibrary {
+    Slf4j,
+    Log4j2,
+}
+
+impl RequiredLibrary {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            RequiredLibrary::Slf4j => "slf4j",
+            RequiredLibrary::Log4j2 => "log4j2",
+        }
+    }
+}
+
+/// Level of results that leave `level` unset, matching the SARIF default.
+pub(crate) const DEFAULT_RESULT_LEVEL: ResultLevel = ResultLevel::Warning;
+
+/// Tags a rule may carry in [`RuleMetadata::tags`].
+pub(crate) const RULE_TAGS: &[&str] = &[
+    "concurrency",
+    "correctness",
+    "logging",
+    "performance",
+    "security",
+    "style",
+];
+
+/// How often a rule's findings are expected to be true positives.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RulePrecision {
+    VeryHigh,
+    High,
+    Medium,
+    Low,
+}
+
+impl RulePrecision {
+    /// The SARIF `precision` property value.
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            RulePrecision::VeryHigh => "very-high",
+            RulePrecision::High => "high",
+            RulePrecision::Medium => "medium",
+            RulePrecision::Low => "low",
+        }
+    }
 }
 
 /// Rule interface for analysis execution.
@@ -24,7 +98,9 @@ pub(crate) trait Rule {
 }
 
 /// Wrapper struct for rule factory functions to enable inventory collection.
-pub(crate) struct RuleFactory(pub fn() -> Box<dyn Rule + Sync>);
+///
+/// The second field is the registering module path, used to find the rule's `spec.md`.
+pub(crate) struct RuleFactory(pub fn() -> Box<dyn Rule + Sync>, pub &'static str);
 
 inventory::collect!(RuleFactory);
 
@@ -36,7 +112,7 @@ inventory::collect!(RuleFactory);
 macro_rules! register_rule {
     ($rule_type:ty) => {
         inventory::submit! {
-            $crate::rules::RuleFactory(|| Box::new(<$rule_type>::default()))
+            $crate::rules::RuleFactory(|| Box::new(<$rule_type>::default()), module_path!())
         }
     };
 }
@@ -49,6 +125,122 @@ pub(crate) fn all_rules() -> Vec<Box<dyn Rule + Sync>> {
         .collect()
 }
 
+/// Resolves `--rules` selectors into rule IDs.
+///
+/// Plain rule IDs and `tag:<name>` add rules; `-tag:<name>` removes every rule carrying that
+/// tag, including explicitly listed ones. When only exclusions are given they apply to the full
+/// rule set. Unknown rule IDs are passed through so the engine can report them.
+pub(crate) fn resolve_rule_selectors(selectors: &BTreeSet<String>) -> Result<BTreeSet<String>> {
+    let metadata: Vec<RuleMetadata> = all_rules().iter().map(|rule| rule.metadata()).collect();
+    let rules_with_tag = |tag: &str| {
+        metadata
+            .iter()
+            .filter(move |rule| rule.tags.contains(&tag))
+            .map(|rule| rule.id.to_string())
+            .collect::<Vec<_>>()
+    };
+    let mut selected = BTreeSet::new();
+    let mut excluded = BTreeSet::new();
+    let mut has_inclusion = false;
+    for selector in selectors {
+        if let Some(tag) = selector.strip_prefix("-tag:") {
+            excluded.extend(rules_with_tag(known_rule_tag(tag)?));
+        } else if let Some(tag) = selector.strip_prefix("tag:") {
+            has_inclusion = true;
+            selected.extend(rules_with_tag(known_rule_tag(tag)?));
+        } else {
+            has_inclusion = true;
+            selected.insert(selector.clone());
+        }
+    }
+    if !has_inclusion {
+        selected.extend(metadata.iter().map(|rule| rule.id.to_string()));
+    }
+    selected.retain(|id| !excluded.contains(id));
+    Ok(selected)
+}
+
+fn known_rule_tag(tag: &str) -> Result<&str> {
+    if RULE_TAGS.contains(&tag) {
+        Ok(tag)
+    } else {
+        anyhow::bail!(
+            "unknown rule tag in --rules: {tag} (expected one of: {})",
+            RULE_TAGS.join(", ")
+        )
+    }
+}
+
+/// Fix rewriting a logger field declaration to `private static final`; instance loggers
+/// stay non-static because their initializer may depend on the instance.
+pub(crate) fn logger_field_fix(field: &Field) -> FixSuggestion {
+    let modifiers = if field.access.is_static {
+        "private static final"
+    } else {
+        "private final"
+    };
+    FixSuggestion::new(format!(
+        "Declare logger field {} as {modifiers}",
+        field.name
+    ))
+    .field_modifiers(&field.name, modifiers)
+}
+
+/// Rule documentation embedded from the rule directory's `spec.md`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RuleSpec {
+    module: &'static str,
+    markdown: &'static str,
+}
+
+impl RuleSpec {
+    pub(crate) fn markdown(&self) -> &'static str {
+        self.markdown
+    }
+
+    pub(crate) fn help_uri(&self) -> String {
+        format!("{RULE_SPEC_BASE_URL}/{}/spec.md", self.module)
+    }
+
+    /// The `Problem:` or `Description:` bullet of the spec summary, with wrapped
+    /// continuation lines joined.
+    pub(crate) fn summary(&self) -> Option<String> {
+        let mut lines = self.markdown.lines();
+        let first = lines.find_map(|line| {
+            let item = line.strip_prefix("- ")?;
+            item.strip_prefix("Problem:")
+                .or_else(|| item.strip_prefix("Description:"))
+        })?;
+        let mut summary = first.trim().to_string();
+        for line in lines {
+            if !line.starts_with("  ") || line.trim_start().starts_with("- ") {
+                break;
+            }
+            summary.push(' ');
+            summary.push_str(line.trim());
+        }
+        (!summary.is_empty()).then_some(summary)
+    }
+}
+
+/// Returns the embedded `spec.md` for a rule ID.
+pub(crate) fn rule_spec(rule_id: &str) -> Option<RuleSpec> {
+    static INDEX: OnceLock<BTreeMap<String, RuleSpec>> = OnceLock::new();
+    let index = INDEX.get_or_init(|| {
+        let specs: BTreeMap<&str, &str> = RULE_SPECS.iter().copied().collect();
+        inventory::iter::<RuleFactory>
+            .into_iter()
+            .filter_map(|factory| {
+                let module = factory.1.rsplit("::").next()?;
+                let (module, markdown) = specs.get_key_value(module)?;
+                let spec = RuleSpec { module, markdown };
+                Some(((factory.0)().metadata().id.to_string(), spec))
+            })
+            .collect()
+    });
+    index.get(rule_id).copied()
+}
+
 pub(crate) fn method_location_with_line(
     class_name: &str,
     method_name: &str,
@@ -106,6 +298,25 @@ pub(crate) fn method_location_with_line(
     Location::builder().logical_locations(vec![logical]).build()
 }
 
+/// Label a location for a result's `relatedLocations`.
+pub(crate) fn related_location(mut location: Location, message: impl Into<String>) -> Location {
+    location.message = Some(result_message(message));
+    location
+}
+
+/// Append related locations to a result, numbering them after any existing ones so
+/// messages can link to them as `[text](id)`.
+pub(crate) fn add_related_locations(
+    result: &mut SarifResult,
+    locations: impl IntoIterator<Item = Location>,
+) {
+    let related = result.related_locations.get_or_insert_with(Vec::new);
+    for mut location in locations {
+        location.id = Some(related.len() as i64 + 1);
+        related.push(location);
+    }
+}
+
 fn jar_container_uri(uri: &str) -> Option<String> {
     let rest = uri.strip_prefix("jar:")?;
     let container = rest.split("!/").next()?;
@@ -155,13 +366,169 @@ pub(crate) fn class_location(class_name: &str, artifact_uri: Option<&str>) -> Lo
     Location::builder().logical_locations(vec![logical]).build()
 }
 
+/// Class name of a location produced by `method_location_with_line` or `class_location`.
+pub(crate) fn location_class_name(location: &Location) -> Option<&str> {
+    let logical = location
+        .logical_locations
+        .as_ref()
+        .and_then(|locations| locations.first())?;
+    let name = logical.name.as_deref()?;
+    match logical.kind.as_deref() {
+        // Method locations are rendered as `{class}.{method}{descriptor}`; internal class
+        // names never contain '.', so the first segment is the class.
+        Some("function") => name.split_once('.').map(|(class, _)| class),
+        _ => Some(name),
+    }
+}
+
 pub(crate) fn result_message(text: impl Into<String>) -> Message {
     Message::builder().text(text.into()).build()
 }
 
+/// Build a single-threaded SARIF code flow from a worklist trace inside one method.
+///
+/// The flow starts at `origin`, skips trace steps recorded before the origin's block was
+/// entered, labels branch and exception edges taken along the way (fall-through edges are
+/// omitted), including the edge into the sink's block, and ends with `sink_message` at the
+/// last traced point.
+pub(crate) fn trace_code_flow(
+    class_name: &str,
+    method: &Method,
+    artifact_uri: Option<&str>,
+    origin: (u32, &str),
+    trace: &[TraceStep],
+    sink_message: &str,
+) -> CodeFlow {
+    let (origin_offset, origin_message) = origin;
+    let origin_block = method
+        .cfg
+        .blocks
+        .iter()
+        .find(|block| {
+            block
+                .instructions
+                .iter()
+                .any(|instruction| instruction.offset == origin_offset)
+        })
+        .map(|block| block.start_offset);
+    let start = origin_block
+        .and_then(|block_start| {
+            trace
+                .iter()
+                .position(|step| step.block_start == block_start)
+        })
+        .unwrap_or(0);
+    let path = trace.get(start..).unwrap_or_default();
+
+    let mut steps = vec![(origin_offset, origin_message.to_string())];
+    for window in path.windows(2) {
+        let (from, to) = (window[0], window[1]);
+        // Only block entries follow an edge; a final step inside the block is the sink.
+        if to.offset != to.block_start {
+            continue;
+        }
+        let label = method
+            .cfg
+            .edges
+            .iter()
+            .find(|edge| edge.from == from.block_start && edge.to == to.block_start)
+            .and_then(|edge| match edge.kind {
+                EdgeKind::Branch => Some("Branch taken"),
+                EdgeKind::Exception => Some("Exception thrown; handler entered"),
+                EdgeKind::FallThrough => None,
+            });
+        if let Some(label) = label {
+            steps.push((to.offset, label.to_string()));
+        }
+    }
+    let sink_offset = path.last().map_or(origin_offset, |step| step.offset);
+    steps.push((sink_offset, sink_message.to_string()));
+
+    let locations = steps
+        .into_iter()
+        .enumerate()
+        .map(|(index, (offset, message))| {
+            let mut location = method_location_with_line(
+                class_name,
+                &method.name,
+                &method.descriptor,
+                artifact_uri,
+                method.line_for_offset(offset),
+            );
+            location.message = Some(result_message(message));
+            ThreadFlowLocation::builder()
+                .location(location)
+                .execution_order(index as i64)
+                .build()
+        })
+        .collect::<Vec<_>>();
+    CodeFlow::builder()
+        .thread_flows(vec![ThreadFlow::builder().locations(locations).build()])
+        .build()
+}
+
+/// Report every flow of `spec` in the analysis target classes at its sink, with a code flow
+/// from the source; `message` renders the result message of one flow.
+pub(crate) fn taint_flow_results(
+    context: &AnalysisContext,
+    spec: &TaintSpec,
+    message: impl Fn(&Class, &Method, &TaintFlow) -> String,
+) -> Result<Vec<SarifResult>> {
+    let mut results = Vec::new();
+    for class in context.analysis_target_classes() {
+        let artifact_uri = context.class_artifact_uri(class);
+        let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
+        if let Some(uri) = &artifact_uri {
+            attributes.push(KeyValue::new("inspequte.artifact_uri", uri.clone()));
+        }
+        let class_results =
+            context.with_span("rule.class", &attributes, || -> Result<Vec<SarifResult>> {
+                let mut class_results = Vec::new();
+                for method in &class.methods {
+                    if method.bytecode.is_empty() || method.cfg.blocks.is_empty() {
+                        continue;
+                    }
+                    for flow in analyze_taint(method, spec)? {
+                        let sink = &flow.sink.method;
+                        let location = method_location_with_line(
+                            &class.name,
+                            &method.name,
+                            &method.descriptor,
+                            artifact_uri.as_deref(),
+                            method.line_for_offset(flow.sink_offset),
+                        );
+                        let code_flow = trace_code_flow(
+                            &class.name,
+                            method,
+                            artifact_uri.as_deref(),
+                            (flow.source_offset, "Untrusted data enters here"),
+                            &flow.trace,
+                            &format!("Untrusted data reaches {}.{}", sink.owner, sink.name),
+                        );
+                        class_results.push(
+                            SarifResult::builder()
+                                .message(result_message(message(class, method, &flow)))
+                                .locations(vec![location])
+                                .code_flows(vec![code_flow])
+                                .build(),
+                        );
+                    }
+                }
+                Ok(class_results)
+            })?;
+        results.extend(class_results);
+    }
+    Ok(results)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ir::{
+        BasicBlock, ControlFlowGraph, FlowEdge, Instruction, InstructionKind, LineNumber,
+        MethodAccess, MethodNullness,
+    };
+    use crate::opcodes;
 
     #[test]
     fn all_rules_have_unique_ids() {
@@ -188,6 +555,273 @@ mod tests {
         }
     }
 
+    #[test]
+    fn all_rules_have_embedded_specs() {
+        for rule in all_rules() {
+            let id = rule.metadata().id;
+            let spec = rule_spec(id).unwrap_or_else(|| panic!("{id} must have a spec.md"));
+            assert!(
+                spec.summary().is_some(),
+                "{id} spec.md must have a Problem or Description summary"
+            );
+        }
+    }
+
+    #[test]
+    fn all_rules_have_known_tags_and_security_severity_only_for_security_rules() {
+        for rule in all_rules() {
+            let meta = rule.metadata();
+            assert!(
+                !meta.tags.is_empty(),
+                "{} must have at least one tag",
+                meta.id
+            );
+            for tag in meta.tags {
+                assert!(RULE_TAGS.contains(tag), "{} has unknown tag {tag}", meta.id);
+            }
+            let is_security = meta.tags.contains(&"security");
+            assert_eq!(
+                meta.security_severity.is_some(),
+                is_security,
+                "{} must set security-severity exactly when tagged security",
+                meta.id
+            );
+        }
+    }
+
+    #[test]
+    fn resolve_rule_selectors_expands_tags() {
+        let selectors = BTreeSet::from(["tag:security".to_string(), "SYSTEM_EXIT".to_string()]);
+
+        let resolved = resolve_rule_selectors(&selectors).expect("resolve selectors");
+
+        assert!(resolved.contains("INSECURE_API"));
+        assert!(resolved.contains("DESERIALIZATION_READ_OBJECT_CALL"));
+        assert!(resolved.contains("SYSTEM_EXIT"));
+        assert!(!resolved.contains("MAGIC_NUMBER"));
+    }
+
+    #[test]
+    fn resolve_rule_selectors_applies_exclusions_to_all_rules() {
+        let selectors = BTreeSet::from(["-tag:style".to_string()]);
+
+        let resolved = resolve_rule_selectors(&selectors).expect("resolve selectors");
+
+        assert!(!resolved.contains("MAGIC_NUMBER"));
+        assert!(resolved.contains("SYSTEM_EXIT"));
+        assert_eq!(
+            resolved.len(),
+            all_rules()
+                .iter()
+                .filter(|rule| !rule.metadata().tags.contains(&"style"))
+                .count()
+        );
+    }
+
+    #[test]
+    fn resolve_rule_selectors_rejects_unknown_tag() {
+        let selectors = BTreeSet::from(["tag:nope".to_string()]);
+
+        let error = resolve_rule_selectors(&selectors).expect_err("unknown tag");
+
+        assert!(
+            error
+                .to_string()
+                .contains("unknown rule tag in --rules: nope")
+        );
+    }
+
+    #[test]
+    fn rule_spec_joins_wrapped_summary_lines() {
+        let spec = RuleSpec {
+            module: "rule_a",
+            markdown: "# RULE_A\n\n## Summary\n- Rule ID: `RULE_A`\n- Description: first part\n  second part.\n- Annotation policy: none\n",
+        };
+
+        assert_eq!(spec.summary().as_deref(), Some("first part second part."));
+        assert_eq!(
+            spec.help_uri(),
+            "https://github.com/KengoTODA/inspequte/blob/main/src/rules/rule_a/spec.md"
+        );
+    }
+
+    fn flow_messages(code_flow: &CodeFlow) -> Vec<(Option<i64>, String)> {
+        code_flow.thread_flows[0]
+            .locations
+            .iter()
+            .map(|step| {
+                let location = step.location.as_ref().expect("location");
+                let line = location
+                    .physical_location
+                    .as_ref()
+                    .and_then(|physical| physical.region.as_ref())
+                    .and_then(|region| region.start_line);
+                let message = location
+                    .message
+                    .as_ref()
+                    .and_then(|message| message.text.clone())
+                    .unwrap_or_default();
+                (line, message)
+            })
+            .collect()
+    }
+
+    fn branching_method() -> Method {
+        let instruction = |offset, opcode| Instruction {
+            offset,
+            opcode,
+            kind: InstructionKind::Other(opcode),
+        };
+        let block = |start_offset, end_offset, instructions| BasicBlock {
+            start_offset,
+            end_offset,
+            instructions,
+        };
+        Method {
+            owner: "com/example/ClassA".into(),
+            name: "run".into(),
+            descriptor: "(Ljava/lang/Object;)V".into(),
+            signature: None,
+            access: MethodAccess {
+                is_public: true,
+                is_static: true,
+                is_synchronized: false,
+                is_abstract: false,
+                is_synthetic: false,
+                is_bridge: false,
+                is_private: false,
+                is_final: false,
+            },
+            nullness: MethodNullness::unknown(1),
+            type_use: None,
+            bytecode: Vec::new(),
+            line_numbers: vec![
+                LineNumber {
+                    start_pc: 0,
+                    line: 10,
+                },
+                LineNumber {
+                    start_pc: 4,
+                    line: 11,
+                },
+                LineNumber {
+                    start_pc: 7,
+                    line: 12,
+                },
+            ],
+            cfg: ControlFlowGraph {
+                blocks: vec![
+                    block(
+                        0,
+                        4,
+                        vec![
+                            instruction(0, opcodes::ALOAD_0),
+                            instruction(1, opcodes::IFNONNULL),
+                        ],
+                    ),
+                    block(4, 7, vec![instruction(4, opcodes::RETURN)]),
+                    block(
+                        7,
+                        9,
+                        vec![
+                            instruction(7, opcodes::ALOAD_0),
+                            instruction(8, opcodes::ARRAYLENGTH),
+                        ],
+                    ),
+                ],
+                edges: vec![
+                    FlowEdge {
+                        from: 0,
+                        to: 4,
+                        kind: EdgeKind::FallThrough,
+                    },
+                    FlowEdge {
+                        from: 0,
+                        to: 7,
+                        kind: EdgeKind::Branch,
+                    },
+                ],
+                duplicates: Default::default(),
+            },
+            calls: Vec::new(),
+            string_literals: Vec::new(),
+            exception_handlers: Vec::new(),
+            local_variables: Vec::new(),
+            local_variable_types: Vec::new(),
+            flow_structure: Default::default(),
+        }
+    }
+
+    #[test]
+    fn trace_code_flow_labels_edge_into_sink_block() {
+        let method = branching_method();
+        let trace = [
+            TraceStep {
+                block_start: 0,
+                offset: 0,
+            },
+            TraceStep {
+                block_start: 7,
+                offset: 7,
+            },
+        ];
+
+        let code_flow = trace_code_flow(
+            "com/example/ClassA",
+            &method,
+            Some("file:///tmp/com/example/ClassA.class"),
+            (0, "Loaded here"),
+            &trace,
+            "Used here",
+        );
+
+        assert_eq!(
+            flow_messages(&code_flow),
+            vec![
+                (Some(10), "Loaded here".to_string()),
+                (Some(12), "Branch taken".to_string()),
+                (Some(12), "Used here".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn trace_code_flow_ends_at_sink_inside_entered_block() {
+        let method = branching_method();
+        let trace = [
+            TraceStep {
+                block_start: 0,
+                offset: 0,
+            },
+            TraceStep {
+                block_start: 7,
+                offset: 7,
+            },
+            TraceStep {
+                block_start: 7,
+                offset: 8,
+            },
+        ];
+
+        let code_flow = trace_code_flow(
+            "com/example/ClassA",
+            &method,
+            Some("file:///tmp/com/example/ClassA.class"),
+            (0, "Loaded here"),
+            &trace,
+            "Used here",
+        );
+
+        assert_eq!(
+            flow_messages(&code_flow),
+            vec![
+                (Some(10), "Loaded here".to_string()),
+                (Some(12), "Branch taken".to_string()),
+                (Some(12), "Used here".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn jar_container_uri_extracts_container() {
         let uri = "jar:file:///tmp/app.jar!/com/example/ClassA.class";
@@ -196,4 +830,47 @@ mod tests {
             Some("file:///tmp/app.jar".to_string())
         );
     }
+
+    #[test]
+    fn add_related_locations_numbers_after_existing_entries() {
+        let mut result = SarifResult::builder()
+            .message(result_message("finding"))
+            .build();
+        add_related_locations(
+            &mut result,
+            [related_location(
+                class_location("com/example/ClassA", None),
+                "first",
+            )],
+        );
+        add_related_locations(
+            &mut result,
+            [
+                related_location(class_location("com/example/ClassB", None), "second"),
+                related_location(class_location("com/example/ClassC", None), "third"),
+            ],
+        );
+
+        let related = result.related_locations.expect("related locations");
+        let labels: Vec<_> = related
+            .iter()
+            .map(|location| {
+                (
+                    location.id,
+                    location
+                        .message
+                        .as_ref()
+                        .and_then(|message| message.text.clone()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                (Some(1), Some("first".to_string())),
+                (Some(2), Some("second".to_string())),
+                (Some(3), Some("third".to_string())),
+            ]
+        );
+    }
 }
@@ -1,16 +1,12 @@
-use std::collections::BTreeMap;
-use std::str::FromStr;
-
-use anyhow::{Context, Result};
-use jdescriptor::MethodDescriptor;
+use anyhow::Result;
 use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
-use crate::descriptor::{ReturnKind, method_return_kind};
+use crate::dataflow::types::TypeOrigin;
 use crate::engine::AnalysisContext;
-use crate::ir::{CallKind, CallSite, Method};
-use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::ir::{CallKind, CallSite, Class, Method};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::JAVA_UTIL_STREAM_STREAM;
 
 /// Rule that detects mutations applied to known-unmodifiable collections.
 #[derive(Default)]
@@ -24,13 +20,19 @@ impl Rule for MutateUnmodifiableCollectionRule {
             id: "MUTATE_UNMODIFIABLE_COLLECTION",
             name: "Mutation on unmodifiable collection",
             description: "Mutation calls on known JDK unmodifiable collection values",
+            tags: &["correctness"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -41,11 +43,7 @@ impl Rule for MutateUnmodifiableCollectionRule {
                         if method.bytecode.is_empty() {
                             continue;
                         }
-                        class_results.extend(analyze_method(
-                            &class.name,
-                            method,
-                            context.class_artifact_uri(class).as_deref(),
-                        )?);
+                        class_results.extend(analyze_method(context, class, method)?);
                     }
                     Ok(class_results)
                 })?;
@@ -55,135 +53,39 @@ impl Rule for MutateUnmodifiableCollectionRule {
     }
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-enum ValueKind {
-    Unknown,
-    KnownUnmodifiable,
-}
-
 fn analyze_method(
-    class_name: &str,
+    context: &AnalysisContext,
+    class: &Class,
     method: &Method,
-    artifact_uri: Option<&str>,
 ) -> Result<Vec<SarifResult>> {
     let mut results = Vec::new();
-    let mut callsites = BTreeMap::new();
-    for call in &method.calls {
-        callsites.insert(call.offset, call);
+    let mutations: Vec<&CallSite> = method
+        .source_calls()
+        .filter(|call| is_mutator_call(call))
+        .collect();
+    if mutations.is_empty() {
+        return Ok(results);
     }
 
-    let mut locals = initial_locals(method)?;
-    let mut stack = Vec::new();
-    let mut offset = 0usize;
-    while offset < method.bytecode.len() {
-        let opcode = method.bytecode[offset];
-        match opcode {
-            opcodes::ACONST_NULL | opcodes::LDC | opcodes::LDC_W | opcodes::LDC2_W => {
-                stack.push(ValueKind::Unknown);
-            }
-            opcodes::ALOAD => {
-                let index = method.bytecode.get(offset + 1).copied().unwrap_or(0) as usize;
-                ensure_local(&mut locals, index);
-                stack.push(locals[index]);
-            }
-            opcodes::ALOAD_0 | opcodes::ALOAD_1 | opcodes::ALOAD_2 | opcodes::ALOAD_3 => {
-                let index = (opcode - opcodes::ALOAD_0) as usize;
-                ensure_local(&mut locals, index);
-                stack.push(locals[index]);
-            }
-            opcodes::ASTORE => {
-                let index = method.bytecode.get(offset + 1).copied().unwrap_or(0) as usize;
-                ensure_local(&mut locals, index);
-                locals[index] = stack.pop().unwrap_or(ValueKind::Unknown);
-            }
-            opcodes::ASTORE_0 | opcodes::ASTORE_1 | opcodes::ASTORE_2 | opcodes::ASTORE_3 => {
-                let index = (opcode - opcodes::ASTORE_0) as usize;
-                ensure_local(&mut locals, index);
-                locals[index] = stack.pop().unwrap_or(ValueKind::Unknown);
-            }
-            opcodes::NEW | opcodes::ANEWARRAY | opcodes::NEWARRAY | opcodes::MULTIANEWARRAY => {
-                stack.push(ValueKind::Unknown);
-            }
-            opcodes::DUP => {
-                if let Some(value) = stack.last().copied() {
-                    stack.push(value);
-                }
-            }
-            opcodes::POP => {
-                stack.pop();
-            }
-            opcodes::POP2 => {
-                stack.pop();
-                stack.pop();
-            }
-            opcodes::AASTORE => {
-                stack.pop();
-                stack.pop();
-                stack.pop();
-            }
-            opcodes::AALOAD => {
-                stack.pop();
-                stack.pop();
-                stack.push(ValueKind::Unknown);
-            }
-            opcodes::INVOKEVIRTUAL
-            | opcodes::INVOKEINTERFACE
-            | opcodes::INVOKESPECIAL
-            | opcodes::INVOKESTATIC => {
-                if let Some(call) = callsites.get(&(offset as u32)) {
-                    handle_call(
-                        class_name,
-                        method,
-                        artifact_uri,
-                        offset as u32,
-                        call,
-                        &mut stack,
-                        &mut results,
-                    )?;
-                }
-            }
-            _ => {}
+    let types = context.method_types(class, method)?;
+    let artifact_uri = context.class_artifact_uri(class);
+    for call in mutations {
+        let from_factory = types.receiver(call).is_some_and(|receiver| {
+            matches!(&receiver.origin, TypeOrigin::Call(factory) if is_unmodifiable_factory_call(factory))
+        });
+        if !from_factory {
+            continue;
         }
-        let length = crate::scan::opcode_length(&method.bytecode, offset)?;
-        offset += length;
-    }
-
-    Ok(results)
-}
-
-fn handle_call(
-    class_name: &str,
-    method: &Method,
-    artifact_uri: Option<&str>,
-    offset: u32,
-    call: &CallSite,
-    stack: &mut Vec<ValueKind>,
-    results: &mut Vec<SarifResult>,
-) -> Result<()> {
-    let descriptor =
-        MethodDescriptor::from_str(&call.descriptor).context("parse call descriptor")?;
-    let param_count = descriptor.parameter_types().len();
-    for _ in 0..param_count {
-        stack.pop();
-    }
-
-    let receiver = if call.kind != CallKind::Static {
-        stack.pop().unwrap_or(ValueKind::Unknown)
-    } else {
-        ValueKind::Unknown
-    };
-
-    if receiver == ValueKind::KnownUnmodifiable && is_mutator_call(call) {
         let message = result_message(format!(
             "Unmodifiable collection is mutated in {}.{}{}; create a mutable copy before calling {}().",
-            class_name, method.name, method.descriptor, call.name
+            class.name, method.name, method.descriptor, call.name
         ));
-        let line = method.line_for_offset(offset);
+        let line = method.line_for_offset(call.offset);
         let location = method_location_with_line(
-            class_name,
+            &class.name,
             &method.name,
             &method.descriptor,
-            artifact_uri,
+            artifact_uri.as_deref(),
             line,
         );
         results.push(
@@ -194,23 +96,11 @@ fn handle_call(
         );
     }
 
-    let return_value = if is_unmodifiable_factory_call(call) {
-        Some(ValueKind::KnownUnmodifiable)
-    } else {
-        match method_return_kind(&call.descriptor)? {
-            ReturnKind::Void => None,
-            ReturnKind::Primitive | ReturnKind::Reference => Some(ValueKind::Unknown),
-        }
-    };
-    if let Some(value) = return_value {
-        stack.push(value);
-    }
-
-    Ok(())
+    Ok(results)
 }
 
 fn is_unmodifiable_factory_call(call: &CallSite) -> bool {
-    if call.owner == "java/util/stream/Stream"
+    if call.owner == JAVA_UTIL_STREAM_STREAM
         && call.name == "toList"
         && call.descriptor == "()Ljava/util/List;"
     {
@@ -259,25 +149,6 @@ fn is_mutator_call(call: &CallSite) -> bool {
     )
 }
 
-fn initial_locals(method: &Method) -> Result<Vec<ValueKind>> {
-    let mut locals = Vec::new();
-    if !method.access.is_static {
-        locals.push(ValueKind::Unknown);
-    }
-    let descriptor =
-        MethodDescriptor::from_str(&method.descriptor).context("parse method descriptor")?;
-    for _ in descriptor.parameter_types() {
-        locals.push(ValueKind::Unknown);
-    }
-    Ok(locals)
-}
-
-fn ensure_local(locals: &mut Vec<ValueKind>, index: usize) {
-    if index >= locals.len() {
-        locals.resize(index + 1, ValueKind::Unknown);
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use crate::test_harness::{JvmTestHarness, Language, SourceFile};
@@ -345,6 +216,33 @@ public class ClassB {
         assert!(messages[0].contains("methodY"));
     }
 
+    #[test]
+    fn mutate_unmodifiable_collection_reports_mutation_after_cast() {
+        let sources = vec![SourceFile {
+            path: "com/example/ClassA.java".to_string(),
+            contents: r#"
+package com.example;
+
+import java.util.Collection;
+import java.util.List;
+
+public class ClassA {
+    public void methodX(boolean varOne) {
+        Collection<String> varTwo = List.of("tmpValue");
+        if (varOne) {
+            ((List<String>) varTwo).set(0, "varThree");
+        }
+    }
+}
+"#
+            .to_string(),
+        }];
+
+        let messages = analyze_sources(sources);
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("calling set()"));
+    }
+
     #[test]
     fn mutate_unmodifiable_collection_ignores_mutable_copy() {
         let sources = vec![SourceFile {
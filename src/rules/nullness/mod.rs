@@ -4,13 +4,20 @@ use anyhow::Result;
 use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
+use crate::dataflow::budget::MethodMeter;
+use crate::dataflow::summaries::MethodSummaries;
+use crate::dataflow::worklist::{ProgramPoint, Tracer};
 use crate::descriptor::{
     MethodDescriptorSummary, ReturnKind, method_descriptor_summary, method_param_count,
 };
 use crate::engine::AnalysisContext;
 use crate::ir::{CallKind, Class, ClassTypeUse, Method, Nullness, TypeUse, TypeUseKind};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{
+    Rule, RuleMetadata, RulePrecision, add_related_locations, method_location_with_line,
+    related_location, result_message, trace_code_flow,
+};
+use crate::symbol::Symbol;
 
 // TODO: refer Checkerframework stubs or something like it to handle nullness of standard APIs
 
@@ -26,6 +33,12 @@ impl Rule for NullnessRule {
             id: "NULLNESS",
             name: "Nullness checks",
             description: "Nullness issues guided by JSpecify annotations",
+            tags: &["correctness"],
+            precision: RulePrecision::Medium,
+            security_severity: None,
+            cwe: &[476],
+            requires: None,
+            options: &[],
         }
     }
 
@@ -34,7 +47,7 @@ impl Rule for NullnessRule {
         let class_map = context.with_span("nullness.preprocess", &preprocess_attributes, || {
             let mut class_map = BTreeMap::new();
             for class in context.all_classes() {
-                class_map.insert(class.name.clone(), class);
+                class_map.insert(class.name, class);
             }
             class_map
         });
@@ -43,7 +56,7 @@ impl Rule for NullnessRule {
         let results = context.with_span("nullness.analyze", &analyze_attributes, || {
             let mut results = Vec::new();
             for class in context.analysis_target_classes() {
-                let mut class_attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+                let mut class_attributes = vec![KeyValue::new("inspequte.class", class.name)];
                 let artifact_uri = context.class_artifact_uri(class);
                 if let Some(uri) = artifact_uri.as_ref() {
                     class_attributes.push(KeyValue::new("inspequte.artifact_uri", uri.clone()));
@@ -53,7 +66,7 @@ impl Rule for NullnessRule {
                         let mut class_results = Vec::new();
                         let override_results =
                             context.with_span("nullness.override_check", &[], || {
-                                check_overrides(class, &class_map)
+                                check_overrides(context, class, &class_map)
                             });
                         class_results.extend(override_results);
 
@@ -137,7 +150,11 @@ fn result_dedup_key(result: &SarifResult) -> (String, String, i64, String) {
     (message, artifact_uri, line, logical)
 }
 
-fn check_overrides(class: &Class, class_map: &BTreeMap<String, &Class>) -> Vec<SarifResult> {
+fn check_overrides(
+    context: &AnalysisContext,
+    class: &Class,
+    class_map: &BTreeMap<Symbol, &Class>,
+) -> Vec<SarifResult> {
     let mut results = Vec::new();
     let supertypes = collect_supertypes(class, class_map);
     for method in &class.methods {
@@ -146,9 +163,10 @@ fn check_overrides(class: &Class, class_map: &BTreeMap<String, &Class>) -> Vec<S
             else {
                 continue;
             };
-            if base_method.type_use.is_some() && method.type_use.is_some() {
+            let mut method_results = if base_method.type_use.is_some() && method.type_use.is_some()
+            {
                 // Type-use metadata covers top-level nullness too, so avoid duplicate reports.
-                results.extend(check_type_use_overrides(
+                check_type_use_overrides(
                     class,
                     method,
                     base_method,
@@ -159,15 +177,44 @@ fn check_overrides(class: &Class, class_map: &BTreeMap<String, &Class>) -> Vec<S
                         None,
                         None,
                     ),
-                ));
+                )
             } else {
-                results.extend(check_signature_overrides(class, method, base_method));
+                check_signature_overrides(class, method, base_method)
+            };
+            for result in &mut method_results {
+                add_related_locations(
+                    result,
+                    [overridden_method_location(context, super_class, base_method)],
+                );
             }
+            results.extend(method_results);
         }
     }
     results
 }
 
+fn overridden_method_location(
+    context: &AnalysisContext,
+    super_class: &Class,
+    base_method: &Method,
+) -> serde_sarif::sarif::Location {
+    let artifact_uri = context.class_artifact_uri(super_class);
+    let location = method_location_with_line(
+        &super_class.name,
+        &base_method.name,
+        &base_method.descriptor,
+        artifact_uri.as_deref(),
+        base_method.line_for_offset(0),
+    );
+    related_location(
+        location,
+        format!(
+            "Overridden method {}.{}{}",
+            super_class.name, base_method.name, base_method.descriptor
+        ),
+    )
+}
+
 fn check_signature_overrides(
     class: &Class,
     method: &Method,
@@ -358,19 +405,19 @@ fn class_type_use_conflict(
 
 fn collect_supertypes<'a>(
     class: &'a Class,
-    class_map: &'a BTreeMap<String, &'a Class>,
+    class_map: &'a BTreeMap<Symbol, &'a Class>,
 ) -> Vec<&'a Class> {
     let mut result = Vec::new();
     let mut seen = BTreeSet::new();
     let mut queue = VecDeque::new();
     if let Some(super_name) = &class.super_name {
-        queue.push_back(super_name.clone());
+        queue.push_back(*super_name);
     }
     for interface in &class.interfaces {
-        queue.push_back(interface.clone());
+        queue.push_back(*interface);
     }
     while let Some(name) = queue.pop_front() {
-        if !seen.insert(name.clone()) {
+        if !seen.insert(name) {
             continue;
         }
         let Some(super_class) = class_map.get(&name) else {
@@ -378,10 +425,10 @@ fn collect_supertypes<'a>(
         };
         result.push(*super_class);
         if let Some(super_name) = &super_class.super_name {
-            queue.push_back(super_name.clone());
+            queue.push_back(*super_name);
         }
         for interface in &super_class.interfaces {
-            queue.push_back(interface.clone());
+            queue.push_back(*interface);
         }
     }
     result
@@ -398,24 +445,25 @@ fn check_method_flow(
     context: &AnalysisContext,
     class: &Class,
     method: &Method,
-    class_map: &BTreeMap<String, &Class>,
+    class_map: &BTreeMap<Symbol, &Class>,
     artifact_uri: Option<&str>,
 ) -> Result<Vec<SarifResult>> {
     let method_attributes = [
-        KeyValue::new("inspequte.class", class.name.clone()),
-        KeyValue::new("inspequte.method", method.name.clone()),
-        KeyValue::new("inspequte.descriptor", method.descriptor.clone()),
+        KeyValue::new("inspequte.class", class.name),
+        KeyValue::new("inspequte.method", method.name),
+        KeyValue::new("inspequte.descriptor", method.descriptor),
         KeyValue::new("inspequte.bytecode_len", method.bytecode.len() as i64),
         KeyValue::new("inspequte.block_count", method.cfg.blocks.len() as i64),
     ];
     let (call_infos, call_index_by_offset, entry_state, block_map, predecessors, successors) =
         context.with_span("nullness.method_preprocess", &method_attributes, || {
-            let call_infos = build_method_call_infos(method, class_map)?;
+            let call_infos = build_method_call_infos(method, class_map, context.method_summaries())?;
             let call_index_by_offset = build_callsite_index_by_offset(method, &call_infos);
 
             let local_count = local_count(method)?;
             let mut initial_locals = vec![Nullness::Unknown; local_count];
             let mut initial_local_type_use = vec![None; local_count];
+            let mut initial_local_origins = vec![None; local_count];
             if !method.access.is_static && !initial_locals.is_empty() {
                 initial_locals[0] = Nullness::NonNull;
                 initial_local_type_use[0] = Some(this_type_use(class));
@@ -426,6 +474,11 @@ fn check_method_flow(
                 if let Some(local) = initial_locals.get_mut(local_index) {
                     *local = *nullness;
                 }
+                if *nullness == Nullness::Nullable
+                    && let Some(origin) = initial_local_origins.get_mut(local_index)
+                {
+                    *origin = Some(NullOrigin::Parameter);
+                }
             }
             if let Some(method_type_use) = method.type_use.as_ref() {
                 for (index, parameter) in method_type_use.parameters.iter().enumerate() {
@@ -438,6 +491,7 @@ fn check_method_flow(
             let entry_state = State {
                 locals: initial_locals,
                 local_type_use: initial_local_type_use,
+                local_origins: initial_local_origins,
                 stack: Vec::new(),
             };
 
@@ -466,19 +520,26 @@ fn check_method_flow(
         })?;
 
     let analyze_attributes = [
-        KeyValue::new("inspequte.class", class.name.clone()),
-        KeyValue::new("inspequte.method", method.name.clone()),
-        KeyValue::new("inspequte.descriptor", method.descriptor.clone()),
+        KeyValue::new("inspequte.class", class.name),
+        KeyValue::new("inspequte.method", method.name),
+        KeyValue::new("inspequte.descriptor", method.descriptor),
         KeyValue::new("inspequte.edge_count", method.cfg.edges.len() as i64),
         KeyValue::new("inspequte.call_count", method.calls.len() as i64),
     ];
     context.with_span("nullness.method_analyze", &analyze_attributes, || {
+        let Some(mut meter) = MethodMeter::start(method) else {
+            return Ok(Vec::new());
+        };
         let mut results = Vec::new();
         let mut in_states: BTreeMap<u32, State> = BTreeMap::new();
         let mut out_states: BTreeMap<u32, State> = BTreeMap::new();
+        // Block entries remember the block they were first reached from; the chain back to
+        // the method entry is the path reported as a finding's code flow.
+        let mut tracer = Tracer::new(true);
         let mut worklist = VecDeque::new();
         if block_map.contains_key(&0) {
             in_states.insert(0, entry_state.clone());
+            tracer.record(None, &ProgramPoint::new(0, 0));
             worklist.push_back(0);
         }
 
@@ -486,6 +547,12 @@ fn check_method_flow(
             let Some(block) = block_map.get(&block_start) else {
                 continue;
             };
+            // Each instruction of a block pass counts as one step, as in the shared engine.
+            for _ in &block.instructions {
+                if meter.step().is_some() {
+                    return Ok(Vec::new());
+                }
+            }
             let in_state = match predecessors.get(&block_start) {
                 Some(preds) if block_start != 0 => {
                     let mut merged: Option<State> = None;
@@ -529,7 +596,13 @@ fn check_method_flow(
                     }
                     let updated = match in_states.get(succ) {
                         Some(existing) => join_states(existing, &next_state),
-                        None => next_state,
+                        None => {
+                            tracer.record(
+                                Some(&ProgramPoint::new(block_start, 0)),
+                                &ProgramPoint::new(*succ, 0),
+                            );
+                            next_state
+                        }
                     };
                     let should_push = match in_states.get(succ) {
                         Some(existing) => &updated != existing,
@@ -542,7 +615,30 @@ fn check_method_flow(
                 }
             }
 
-            results.extend(transfer.results);
+            for finding in transfer.findings {
+                let sink_index = block
+                    .instructions
+                    .iter()
+                    .position(|instruction| instruction.offset == finding.offset)
+                    .unwrap_or(0);
+                let trace = tracer.trace_in(
+                    method,
+                    &ProgramPoint::new(block_start, 0),
+                    Some(&ProgramPoint::new(block_start, sink_index)),
+                );
+                let mut result = finding.result;
+                if let Some(origin) = finding.origin {
+                    result.code_flows = Some(vec![trace_code_flow(
+                        &class.name,
+                        method,
+                        artifact_uri,
+                        origin.step(),
+                        &trace,
+                        finding.sink_message,
+                    )]);
+                }
+                results.push(result);
+            }
         }
 
         Ok::<Vec<SarifResult>, anyhow::Error>(results)
@@ -554,6 +650,7 @@ fn check_method_flow(
 struct State {
     locals: Vec<Nullness>,
     local_type_use: Vec<Option<TypeUse>>,
+    local_origins: Vec<Option<NullOrigin>>,
     stack: Vec<StackValue>,
 }
 
@@ -564,6 +661,36 @@ struct StackValue {
     type_use: Option<TypeUse>,
     local: Option<usize>,
     is_null_literal: bool,
+    origin: Option<NullOrigin>,
+}
+
+/// Where a nullable value was introduced, used as the first step of a finding's code flow.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum NullOrigin {
+    Parameter,
+    Literal(u32),
+    Call(u32),
+    Check(u32),
+}
+
+impl NullOrigin {
+    fn step(self) -> (u32, &'static str) {
+        match self {
+            NullOrigin::Parameter => (0, "Nullable parameter received here"),
+            NullOrigin::Literal(offset) => (offset, "Null assigned here"),
+            NullOrigin::Call(offset) => (offset, "Nullable value returned here"),
+            NullOrigin::Check(offset) => (offset, "Value compared with null here"),
+        }
+    }
+}
+
+/// Nullness issue found in one block, located at the offending instruction.
+#[derive(Clone)]
+struct FlowFinding {
+    offset: u32,
+    origin: Option<NullOrigin>,
+    sink_message: &'static str,
+    result: SarifResult,
 }
 
 /// Descriptor-derived callsite summary cached for flow transfer.
@@ -579,6 +706,8 @@ struct MethodCallInfo<'a> {
     call: &'a crate::ir::CallSite,
     descriptor: CallDescriptorInfo,
     target: Option<ResolvedCallTarget<'a>>,
+    /// The callee cannot be overridden and its summary shows every return is non-null.
+    returns_non_null: bool,
 }
 
 /// Resolved owner/method pair for a callsite.
@@ -593,7 +722,7 @@ struct ResolvedCallTarget<'a> {
 struct BlockTransfer {
     out_state: State,
     branch_refinement: Option<BranchRefinement>,
-    results: Vec<SarifResult>,
+    findings: Vec<FlowFinding>,
 }
 
 /// Nullness refinement applied on conditional branch edges.
@@ -602,6 +731,8 @@ struct BranchRefinement {
     local: usize,
     branch_nullness: Nullness,
     fallthrough_nullness: Nullness,
+    /// Origin given to the local on the edge where it becomes nullable.
+    origin: NullOrigin,
 }
 
 enum BranchKind {
@@ -619,13 +750,17 @@ impl BranchRefinement {
         if let Some(local) = next.locals.get_mut(self.local) {
             *local = value;
         }
+        if let Some(origin) = next.local_origins.get_mut(self.local) {
+            *origin = (value == Nullness::Nullable).then_some(self.origin);
+        }
         next
     }
 }
 
 fn build_method_call_infos<'a>(
     method: &'a Method,
-    class_map: &'a BTreeMap<String, &'a Class>,
+    class_map: &'a BTreeMap<Symbol, &'a Class>,
+    summaries: &MethodSummaries,
 ) -> Result<Vec<MethodCallInfo<'a>>> {
     let mut infos = Vec::with_capacity(method.calls.len());
     let mut descriptor_cache: HashMap<&str, CallDescriptorInfo> = HashMap::new();
@@ -648,6 +783,9 @@ fn build_method_call_infos<'a>(
             call,
             descriptor,
             target: resolve_call_target(class_map, call),
+            returns_non_null: summaries
+                .for_call(call)
+                .is_some_and(|summary| summary.returns_non_null),
         });
     }
     Ok(infos)
@@ -668,7 +806,7 @@ fn build_callsite_index_by_offset(
 }
 
 fn resolve_call_target<'a>(
-    class_map: &'a BTreeMap<String, &'a Class>,
+    class_map: &'a BTreeMap<Symbol, &'a Class>,
     call: &crate::ir::CallSite,
 ) -> Option<ResolvedCallTarget<'a>> {
     let class = class_map.get(&call.owner).copied()?;
@@ -689,7 +827,7 @@ fn transfer_block(
     artifact_uri: Option<&str>,
 ) -> Result<BlockTransfer> {
     let mut state = input.clone();
-    let mut results = Vec::new();
+    let mut findings = Vec::new();
     let mut branch_refinement = None;
     for inst in &block.instructions {
         match inst.opcode {
@@ -699,6 +837,7 @@ fn transfer_block(
                     type_use: None,
                     local: None,
                     is_null_literal: true,
+                    origin: Some(NullOrigin::Literal(inst.offset)),
                 });
             }
             opcodes::ALOAD => {
@@ -713,11 +852,13 @@ fn transfer_block(
                     .copied()
                     .unwrap_or(Nullness::Unknown);
                 let type_use = state.local_type_use.get(local_index).cloned().flatten();
+                let origin = state.local_origins.get(local_index).copied().flatten();
                 state.stack.push(StackValue {
                     nullness,
                     type_use,
                     local: Some(local_index),
                     is_null_literal: false,
+                    origin,
                 });
             }
             opcodes::ALOAD_0 | opcodes::ALOAD_1 | opcodes::ALOAD_2 | opcodes::ALOAD_3 => {
@@ -728,11 +869,13 @@ fn transfer_block(
                     .copied()
                     .unwrap_or(Nullness::Unknown);
                 let type_use = state.local_type_use.get(local_index).cloned().flatten();
+                let origin = state.local_origins.get(local_index).copied().flatten();
                 state.stack.push(StackValue {
                     nullness,
                     type_use,
                     local: Some(local_index),
                     is_null_literal: false,
+                    origin,
                 });
             }
             opcodes::ASTORE => {
@@ -746,6 +889,7 @@ fn transfer_block(
                     type_use: None,
                     local: None,
                     is_null_literal: false,
+                    origin: None,
                 });
                 if let Some(local) = state.locals.get_mut(local_index) {
                     *local = value.nullness;
@@ -753,6 +897,9 @@ fn transfer_block(
                 if let Some(local_type_use) = state.local_type_use.get_mut(local_index) {
                     *local_type_use = value.type_use;
                 }
+                if let Some(local_origin) = state.local_origins.get_mut(local_index) {
+                    *local_origin = value.origin;
+                }
             }
             opcodes::ASTORE_0 | opcodes::ASTORE_1 | opcodes::ASTORE_2 | opcodes::ASTORE_3 => {
                 let local_index = (inst.opcode - opcodes::ASTORE_0) as usize;
@@ -761,6 +908,7 @@ fn transfer_block(
                     type_use: None,
                     local: None,
                     is_null_literal: false,
+                    origin: None,
                 });
                 if let Some(local) = state.locals.get_mut(local_index) {
                     *local = value.nullness;
@@ -768,6 +916,9 @@ fn transfer_block(
                 if let Some(local_type_use) = state.local_type_use.get_mut(local_index) {
                     *local_type_use = value.type_use;
                 }
+                if let Some(local_origin) = state.local_origins.get_mut(local_index) {
+                    *local_origin = value.origin;
+                }
             }
             opcodes::POP => {
                 state.stack.pop();
@@ -783,6 +934,7 @@ fn transfer_block(
                     type_use: None,
                     local: None,
                     is_null_literal: false,
+                    origin: None,
                 });
             }
             opcodes::IFNULL | opcodes::IFNONNULL => {
@@ -791,6 +943,7 @@ fn transfer_block(
                     type_use: None,
                     local: None,
                     is_null_literal: false,
+                    origin: None,
                 });
                 if let Some(local) = value.local {
                     let (branch_nullness, fallthrough_nullness) = if inst.opcode == opcodes::IFNULL
@@ -799,14 +952,14 @@ fn transfer_block(
                     } else {
                         (Nullness::NonNull, Nullness::Nullable)
                     };
-                    branch_refinement = Some(BranchRefinement {
+                    let refinement = BranchRefinement {
                         local,
                         branch_nullness,
                         fallthrough_nullness,
-                    });
-                    if let Some(local_ref) = state.locals.get_mut(local) {
-                        *local_ref = fallthrough_nullness;
-                    }
+                        origin: value.origin.unwrap_or(NullOrigin::Check(inst.offset)),
+                    };
+                    state = refinement.apply_to(&state, BranchKind::FallThrough);
+                    branch_refinement = Some(refinement);
                 }
             }
             opcodes::IF_ACMPEQ | opcodes::IF_ACMPNE => {
@@ -815,12 +968,14 @@ fn transfer_block(
                     type_use: None,
                     local: None,
                     is_null_literal: false,
+                    origin: None,
                 });
                 let left = state.stack.pop().unwrap_or(StackValue {
                     nullness: Nullness::Unknown,
                     type_use: None,
                     local: None,
                     is_null_literal: false,
+                    origin: None,
                 });
                 let (local, null_literal) = if left.local.is_some() && right.is_null_literal {
                     (left.local, true)
@@ -837,14 +992,20 @@ fn transfer_block(
                             } else {
                                 (Nullness::NonNull, Nullness::Nullable)
                             };
-                        branch_refinement = Some(BranchRefinement {
+                        let origin = state
+                            .local_origins
+                            .get(local)
+                            .copied()
+                            .flatten()
+                            .unwrap_or(NullOrigin::Check(inst.offset));
+                        let refinement = BranchRefinement {
                             local,
                             branch_nullness,
                             fallthrough_nullness,
-                        });
-                        if let Some(local_ref) = state.locals.get_mut(local) {
-                            *local_ref = fallthrough_nullness;
-                        }
+                            origin,
+                        };
+                        state = refinement.apply_to(&state, BranchKind::FallThrough);
+                        branch_refinement = Some(refinement);
                     }
                 }
             }
@@ -865,6 +1026,7 @@ fn transfer_block(
                             type_use: None,
                             local: None,
                             is_null_literal: false,
+                            origin: None,
                         }))
                     } else {
                         None
@@ -885,25 +1047,35 @@ fn transfer_block(
                                 artifact_uri,
                                 line,
                             );
-                            results.push(
-                                SarifResult::builder()
+                            findings.push(FlowFinding {
+                                offset: inst.offset,
+                                origin: receiver.origin,
+                                sink_message: "Null value dereferenced here",
+                                result: SarifResult::builder()
                                     .message(message)
                                     .locations(vec![location])
                                     .build(),
-                            );
+                            });
                         }
                     }
                     if call_info.descriptor.return_kind == ReturnKind::Reference {
-                        let (return_nullness, return_type_use) = lookup_return_value(
+                        let (mut return_nullness, return_type_use) = lookup_return_value(
                             call_info.target.as_ref(),
                             call_info.call.kind,
                             receiver.as_ref(),
                         );
+                        // A non-overridable helper whose every return is non-null never
+                        // yields null, whatever its declaration says.
+                        if call_info.returns_non_null {
+                            return_nullness = Nullness::NonNull;
+                        }
                         state.stack.push(StackValue {
                             nullness: return_nullness,
                             type_use: return_type_use,
                             local: None,
                             is_null_literal: false,
+                            origin: (return_nullness == Nullness::Nullable)
+                                .then_some(NullOrigin::Call(inst.offset)),
                         });
                     }
                 }
@@ -914,6 +1086,7 @@ fn transfer_block(
                     type_use: None,
                     local: None,
                     is_null_literal: false,
+                    origin: None,
                 });
                 if method.nullness.return_nullness == Nullness::NonNull
                     && value.nullness == Nullness::Nullable
@@ -930,12 +1103,15 @@ fn transfer_block(
                         artifact_uri,
                         line,
                     );
-                    results.push(
-                        SarifResult::builder()
+                    findings.push(FlowFinding {
+                        offset: inst.offset,
+                        origin: value.origin,
+                        sink_message: "Null value returned here",
+                        result: SarifResult::builder()
                             .message(message)
                             .locations(vec![location])
                             .build(),
-                    );
+                    });
                 }
             }
             _ => {}
@@ -945,7 +1121,7 @@ fn transfer_block(
     Ok(BlockTransfer {
         out_state: state,
         branch_refinement,
-        results,
+        findings,
     })
 }
 
@@ -975,6 +1151,7 @@ fn join_states(left: &State, right: &State) -> State {
     let max_locals = left.locals.len().max(right.locals.len());
     let mut locals = Vec::with_capacity(max_locals);
     let mut local_type_use = Vec::with_capacity(max_locals);
+    let mut local_origins = Vec::with_capacity(max_locals);
     for index in 0..max_locals {
         let l = left.locals.get(index).copied().unwrap_or(Nullness::Unknown);
         let r = right
@@ -987,6 +1164,10 @@ fn join_states(left: &State, right: &State) -> State {
             left.local_type_use.get(index).and_then(Option::as_ref),
             right.local_type_use.get(index).and_then(Option::as_ref),
         ));
+        local_origins.push(join_origin(
+            left.local_origins.get(index).copied().flatten(),
+            right.local_origins.get(index).copied().flatten(),
+        ));
     }
     let stack = if left.stack.len() == right.stack.len() {
         left.stack
@@ -997,6 +1178,7 @@ fn join_states(left: &State, right: &State) -> State {
                 type_use: join_type_use(l.type_use.as_ref(), r.type_use.as_ref()),
                 local: if l.local == r.local { l.local } else { None },
                 is_null_literal: l.is_null_literal && r.is_null_literal,
+                origin: join_origin(l.origin, r.origin),
             })
             .collect()
     } else {
@@ -1005,6 +1187,7 @@ fn join_states(left: &State, right: &State) -> State {
     State {
         locals,
         local_type_use,
+        local_origins,
         stack,
     }
 }
@@ -1017,6 +1200,14 @@ fn join_nullness(left: Nullness, right: Nullness) -> Nullness {
     }
 }
 
+/// Keep the earliest origin so joins stay deterministic and converge.
+fn join_origin(left: Option<NullOrigin>, right: Option<NullOrigin>) -> Option<NullOrigin> {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(left.min(right)),
+        (origin, None) | (None, origin) => origin,
+    }
+}
+
 fn join_type_use(left: Option<&TypeUse>, right: Option<&TypeUse>) -> Option<TypeUse> {
     if left == right {
         return left.cloned();
@@ -1028,7 +1219,7 @@ fn this_type_use(class: &Class) -> TypeUse {
     TypeUse {
         nullness: Nullness::NonNull,
         kind: TypeUseKind::Class(ClassTypeUse {
-            name: class.name.clone(),
+            name: class.name.to_string(),
             type_arguments: class
                 .type_parameters
                 .iter()
@@ -1197,6 +1388,7 @@ fn substitute_type_variables(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dataflow::budget::{AnalysisBudget, BudgetLimit, with_rule_budget};
     use crate::engine::build_context;
     use crate::ir::{
         BasicBlock, CallKind, CallSite, Class, ControlFlowGraph, Instruction, InstructionKind,
@@ -1215,8 +1407,9 @@ mod tests {
     ) -> Method {
         let end_offset = bytecode.len() as u32;
         Method {
-            name: name.to_string(),
-            descriptor: descriptor.to_string(),
+            owner: "com/example/Caller".into(),
+            name: name.into(),
+            descriptor: descriptor.into(),
             signature: None,
             access,
             nullness,
@@ -1230,20 +1423,22 @@ mod tests {
                     instructions,
                 }],
                 edges: Vec::new(),
+                duplicates: Default::default(),
             },
             calls,
             string_literals: Vec::new(),
             exception_handlers: Vec::new(),
             local_variables: vec![],
             local_variable_types: Vec::new(),
+            flow_structure: Default::default(),
         }
     }
 
     fn class_with_methods(name: &str, super_name: Option<&str>, methods: Vec<Method>) -> Class {
         Class {
-            name: name.to_string(),
+            name: name.into(),
             source_file: None,
-            super_name: super_name.map(str::to_string),
+            super_name: super_name.map(Into::into),
             interfaces: Vec::new(),
             type_parameters: Vec::new(),
             referenced_classes: Vec::new(),
@@ -1252,6 +1447,8 @@ mod tests {
             annotation_defaults: Vec::new(),
             artifact_index: 0,
             is_record: false,
+            is_generated: false,
+            header_location: None,
         }
     }
 
@@ -1388,8 +1585,9 @@ public @interface NullnessUnspecified {}
     #[test]
     fn nullness_override_reports_return_mismatch() {
         let base_method = Method {
-            name: "value".to_string(),
-            descriptor: "()Ljava/lang/String;".to_string(),
+            owner: "com/example/Base".into(),
+            name: "value".into(),
+            descriptor: "()Ljava/lang/String;".into(),
             signature: None,
             access: MethodAccess {
                 is_public: true,
@@ -1398,6 +1596,8 @@ public @interface NullnessUnspecified {}
                 is_abstract: false,
                 is_synthetic: false,
                 is_bridge: false,
+                is_private: false,
+                is_final: false,
             },
             nullness: MethodNullness {
                 return_nullness: Nullness::NonNull,
@@ -1409,16 +1609,19 @@ public @interface NullnessUnspecified {}
             cfg: ControlFlowGraph {
                 blocks: Vec::new(),
                 edges: Vec::new(),
+                duplicates: Default::default(),
             },
             calls: Vec::new(),
             string_literals: Vec::new(),
             exception_handlers: Vec::new(),
             local_variables: vec![],
             local_variable_types: Vec::new(),
+            flow_structure: Default::default(),
         };
         let override_method = Method {
-            name: "value".to_string(),
-            descriptor: "()Ljava/lang/String;".to_string(),
+            owner: "com/example/Derived".into(),
+            name: "value".into(),
+            descriptor: "()Ljava/lang/String;".into(),
             signature: None,
             access: MethodAccess {
                 is_public: true,
@@ -1427,6 +1630,8 @@ public @interface NullnessUnspecified {}
                 is_abstract: false,
                 is_synthetic: false,
                 is_bridge: false,
+                is_private: false,
+                is_final: false,
             },
             nullness: MethodNullness {
                 return_nullness: Nullness::Nullable,
@@ -1438,12 +1643,14 @@ public @interface NullnessUnspecified {}
             cfg: ControlFlowGraph {
                 blocks: Vec::new(),
                 edges: Vec::new(),
+                duplicates: Default::default(),
             },
             calls: Vec::new(),
             string_literals: Vec::new(),
             exception_handlers: Vec::new(),
             local_variables: vec![],
             local_variable_types: Vec::new(),
+            flow_structure: Default::default(),
         };
         let base = class_with_methods("com/example/Base", None, vec![base_method]);
         let derived = class_with_methods(
@@ -1458,13 +1665,36 @@ public @interface NullnessUnspecified {}
         assert_eq!(1, results.len());
         let message = results[0].message.text.as_deref().unwrap_or("");
         assert!(message.contains("returns @Nullable but overrides @NonNull"));
+        let related = results[0]
+            .related_locations
+            .as_ref()
+            .expect("related locations");
+        assert_eq!(1, related.len());
+        assert_eq!(Some(1), related[0].id);
+        assert_eq!(
+            Some("Overridden method com/example/Base.value()Ljava/lang/String;"),
+            related[0]
+                .message
+                .as_ref()
+                .and_then(|message| message.text.as_deref())
+        );
+        let logical = related[0]
+            .logical_locations
+            .as_ref()
+            .and_then(|locations| locations.first())
+            .and_then(|location| location.name.as_deref());
+        assert_eq!(
+            Some("com/example/Base.value()Ljava/lang/String;"),
+            logical
+        );
     }
 
     #[test]
     fn nullness_override_reports_parameter_mismatch() {
         let base_method = Method {
-            name: "set".to_string(),
-            descriptor: "(Ljava/lang/String;)V".to_string(),
+            owner: "com/example/Base".into(),
+            name: "set".into(),
+            descriptor: "(Ljava/lang/String;)V".into(),
             signature: None,
             access: MethodAccess {
                 is_public: true,
@@ -1473,6 +1703,8 @@ public @interface NullnessUnspecified {}
                 is_abstract: false,
                 is_synthetic: false,
                 is_bridge: false,
+                is_private: false,
+                is_final: false,
             },
             nullness: MethodNullness {
                 return_nullness: Nullness::Unknown,
@@ -1484,16 +1716,19 @@ public @interface NullnessUnspecified {}
             cfg: ControlFlowGraph {
                 blocks: Vec::new(),
                 edges: Vec::new(),
+                duplicates: Default::default(),
             },
             calls: Vec::new(),
             string_literals: Vec::new(),
             exception_handlers: Vec::new(),
             local_variables: vec![],
             local_variable_types: Vec::new(),
+            flow_structure: Default::default(),
         };
         let override_method = Method {
-            name: "set".to_string(),
-            descriptor: "(Ljava/lang/String;)V".to_string(),
+            owner: "com/example/Derived".into(),
+            name: "set".into(),
+            descriptor: "(Ljava/lang/String;)V".into(),
             signature: None,
             access: MethodAccess {
                 is_public: true,
@@ -1502,6 +1737,8 @@ public @interface NullnessUnspecified {}
                 is_abstract: false,
                 is_synthetic: false,
                 is_bridge: false,
+                is_private: false,
+                is_final: false,
             },
             nullness: MethodNullness {
                 return_nullness: Nullness::Unknown,
@@ -1513,12 +1750,14 @@ public @interface NullnessUnspecified {}
             cfg: ControlFlowGraph {
                 blocks: Vec::new(),
                 edges: Vec::new(),
+                duplicates: Default::default(),
             },
             calls: Vec::new(),
             string_literals: Vec::new(),
             exception_handlers: Vec::new(),
             local_variables: vec![],
             local_variable_types: Vec::new(),
+            flow_structure: Default::default(),
         };
         let base = class_with_methods("com/example/Base", None, vec![base_method]);
         let derived = class_with_methods(
@@ -1535,9 +1774,8 @@ public @interface NullnessUnspecified {}
         assert!(message.contains("parameter 0 is @NonNull but overrides @Nullable"));
     }
 
-    #[test]
-    fn nullness_flow_reports_returning_null() {
-        let method = method_with(
+    fn method_returning_null() -> Method {
+        method_with(
             "value",
             "()Ljava/lang/String;",
             MethodAccess {
@@ -1547,6 +1785,8 @@ public @interface NullnessUnspecified {}
                 is_abstract: false,
                 is_synthetic: false,
                 is_bridge: false,
+                is_private: false,
+                is_final: false,
             },
             MethodNullness {
                 return_nullness: Nullness::NonNull,
@@ -1566,8 +1806,16 @@ public @interface NullnessUnspecified {}
                 },
             ],
             Vec::new(),
+        )
+    }
+
+    #[test]
+    fn nullness_flow_reports_returning_null() {
+        let class = class_with_methods(
+            "com/example/ReturnNull",
+            None,
+            vec![method_returning_null()],
         );
-        let class = class_with_methods("com/example/ReturnNull", None, vec![method]);
         let context = context_for(vec![class]);
 
         let results = NullnessRule.run(&context).expect("nullness rule run");
@@ -1577,6 +1825,29 @@ public @interface NullnessUnspecified {}
         assert!(message.contains("returns null but is @NonNull"));
     }
 
+    #[test]
+    fn nullness_flow_drops_findings_of_method_over_step_budget() {
+        let class = class_with_methods(
+            "com/example/ReturnNull",
+            None,
+            vec![method_returning_null()],
+        );
+        let context = context_for(vec![class]);
+        let budget = AnalysisBudget {
+            max_method_steps: Some(1),
+            rule_timeout: None,
+        };
+
+        let (results, exceeded) = with_rule_budget(budget, || {
+            NullnessRule.run(&context).expect("nullness rule run")
+        });
+
+        assert!(results.is_empty());
+        assert_eq!(exceeded.len(), 1);
+        assert_eq!(exceeded[0].method_name, "value");
+        assert_eq!(exceeded[0].limit, BudgetLimit::MethodSteps(1));
+    }
+
     #[test]
     fn nullness_flow_reports_nullable_receiver() {
         let method = method_with(
@@ -1589,6 +1860,8 @@ public @interface NullnessUnspecified {}
                 is_abstract: false,
                 is_synthetic: false,
                 is_bridge: false,
+                is_private: false,
+                is_final: false,
             },
             MethodNullness {
                 return_nullness: Nullness::Unknown,
@@ -1619,9 +1892,9 @@ public @interface NullnessUnspecified {}
                 },
             ],
             vec![CallSite {
-                owner: "com/example/Target".to_string(),
-                name: "run".to_string(),
-                descriptor: "()V".to_string(),
+                owner: "com/example/Target".into(),
+                name: "run".into(),
+                descriptor: "()V".into(),
                 kind: CallKind::Virtual,
                 offset: 1,
             }],
@@ -1648,6 +1921,8 @@ public @interface NullnessUnspecified {}
                 is_abstract: false,
                 is_synthetic: false,
                 is_bridge: false,
+                is_private: false,
+                is_final: false,
             },
             MethodNullness {
                 return_nullness: Nullness::NonNull,
@@ -1672,11 +1947,11 @@ public @interface NullnessUnspecified {}
             interface_bounds: Vec::new(),
         }];
         let mut class_map = BTreeMap::new();
-        class_map.insert(callee_class.name.clone(), &callee_class);
+        class_map.insert(callee_class.name, &callee_class);
         let call = CallSite {
-            owner: "com/example/ClassB".to_string(),
-            name: "methodOne".to_string(),
-            descriptor: "()Ljava/lang/Object;".to_string(),
+            owner: "com/example/ClassB".into(),
+            name: "methodOne".into(),
+            descriptor: "()Ljava/lang/Object;".into(),
             kind: CallKind::Virtual,
             offset: 0,
         };
@@ -1700,6 +1975,7 @@ public @interface NullnessUnspecified {}
             }),
             local: None,
             is_null_literal: false,
+            origin: None,
         };
 
         let (return_nullness, return_type_use) =
@@ -1727,6 +2003,8 @@ public @interface NullnessUnspecified {}
                 is_abstract: false,
                 is_synthetic: false,
                 is_bridge: false,
+                is_private: false,
+                is_final: false,
             },
             MethodNullness {
                 return_nullness: Nullness::NonNull,
@@ -1751,11 +2029,11 @@ public @interface NullnessUnspecified {}
             interface_bounds: Vec::new(),
         }];
         let mut class_map = BTreeMap::new();
-        class_map.insert(callee_class.name.clone(), &callee_class);
+        class_map.insert(callee_class.name, &callee_class);
         let call = CallSite {
-            owner: "com/example/ClassB".to_string(),
-            name: "methodOne".to_string(),
-            descriptor: "()Ljava/lang/Object;".to_string(),
+            owner: "com/example/ClassB".into(),
+            name: "methodOne".into(),
+            descriptor: "()Ljava/lang/Object;".into(),
             kind: CallKind::Virtual,
             offset: 0,
         };
@@ -1840,6 +2118,62 @@ public class ClassA {
         assert!(messages.is_empty(), "messages: {messages:?}");
     }
 
+    #[test]
+    fn nullness_flow_reports_code_flow_from_null_to_dereference() {
+        let sources = vec![SourceFile {
+            path: "com/example/ClassA.java".to_string(),
+            contents: r#"
+package com.example;
+public class ClassA {
+    public int methodOne(boolean varOne) {
+        String varTwo = null;
+        if (varOne) {
+            return 0;
+        }
+        return varTwo.length();
+    }
+}
+"#
+            .to_string(),
+        }];
+
+        let output = analyze_with_harness(sources);
+        let result = output
+            .results
+            .iter()
+            .find(|result| result.rule_id.as_deref() == Some("NULLNESS"))
+            .expect("nullness finding");
+        let flow = serde_json::to_value(result.code_flows.as_ref().expect("code flows"))
+            .expect("serialize code flows");
+        let locations = flow[0]["threadFlows"][0]["locations"]
+            .as_array()
+            .expect("thread flow locations");
+        let steps: Vec<(String, i64)> = locations
+            .iter()
+            .map(|location| {
+                let location = &location["location"];
+                (
+                    location["message"]["text"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                    location["physicalLocation"]["region"]["startLine"]
+                        .as_i64()
+                        .unwrap_or_default(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            steps,
+            vec![
+                ("Null assigned here".to_string(), 5),
+                ("Branch taken".to_string(), 9),
+                ("Null value dereferenced here".to_string(), 9),
+            ]
+        );
+    }
+
     #[test]
     fn nullness_rule_skips_unmarked_class_returning_null() {
         let mut sources = jspecify_stubs();
@@ -2309,4 +2643,51 @@ public class ClassA {
             "messages: {messages:?}"
         );
     }
+
+    #[test]
+    fn nullness_flow_trusts_summary_of_private_helper_over_nullable_declaration() {
+        let mut sources = jspecify_stubs();
+        sources.push(SourceFile {
+            path: "com/example/ClassA.java".to_string(),
+            contents: r#"
+package com.example;
+import org.jspecify.annotations.NullMarked;
+import org.jspecify.annotations.Nullable;
+@NullMarked
+public class ClassA {
+    public int methodOne() {
+        return helperOne().length();
+    }
+
+    public int methodTwo() {
+        return helperTwo().length();
+    }
+
+    private @Nullable String helperOne() {
+        return "text";
+    }
+
+    protected @Nullable String helperTwo() {
+        return "text";
+    }
+}
+"#
+            .to_string(),
+        });
+
+        let output = analyze_with_harness(sources);
+        let messages: Vec<String> = output
+            .results
+            .iter()
+            .filter(|result| result.rule_id.as_deref() == Some("NULLNESS"))
+            .filter_map(|result| result.message.text.clone())
+            .collect();
+
+        // Only the overridable helper keeps its declared nullness.
+        assert_eq!(messages.len(), 1, "messages: {messages:?}");
+        assert!(
+            messages[0].contains("possible null receiver"),
+            "messages: {messages:?}"
+        );
+    }
 }
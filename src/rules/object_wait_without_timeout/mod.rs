@@ -3,7 +3,8 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_LANG_OBJECT, Symbol};
 
 /// Rule that detects timeout-free Object.wait calls.
 #[derive(Default)]
@@ -17,13 +18,19 @@ impl Rule for ObjectWaitWithoutTimeoutRule {
             id: "OBJECT_WAIT_WITHOUT_TIMEOUT",
             name: "Object.wait without timeout",
             description: "Timeout-free Object.wait calls can block indefinitely",
+            tags: &["concurrency"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,8 +39,8 @@ impl Rule for ObjectWaitWithoutTimeoutRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
-                            if is_timeout_free_wait(&call.owner, &call.name, &call.descriptor) {
+                        for call in method.source_calls() {
+                            if is_timeout_free_wait(call.owner, &call.name, &call.descriptor) {
                                 let message = result_message(format!(
                                     "Avoid timeout-free Object.wait() in {}.{}{}; use a timed wait and explicit condition checks.",
                                     class.name, method.name, method.descriptor
@@ -63,8 +70,8 @@ impl Rule for ObjectWaitWithoutTimeoutRule {
     }
 }
 
-fn is_timeout_free_wait(owner: &str, name: &str, descriptor: &str) -> bool {
-    owner == "java/lang/Object" && name == "wait" && descriptor == "()V"
+fn is_timeout_free_wait(owner: Symbol, name: &str, descriptor: &str) -> bool {
+    owner == JAVA_LANG_OBJECT && name == "wait" && descriptor == "()V"
 }
 
 #[cfg(test)]
@@ -7,7 +7,7 @@ use serde_sarif::sarif::Result as SarifResult;
 use crate::engine::AnalysisContext;
 use crate::ir::{CallSite, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
 
 /// Rule that detects direct getter calls on Optional APIs.
 #[derive(Default)]
@@ -21,13 +21,19 @@ impl Rule for OptionalGetCallRule {
             id: "OPTIONAL_GET_CALL",
             name: "Optional direct getter call",
             description: "Optional.get/getAs* can throw when empty",
+            tags: &["correctness"],
+            precision: RulePrecision::Medium,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -37,7 +43,7 @@ impl Rule for OptionalGetCallRule {
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
                         let guarded_getter_offsets = guarded_optional_getter_offsets(method)?;
-                        for call in &method.calls {
+                        for call in method.source_calls() {
                             if is_optional_getter_call(&call.owner, &call.name, &call.descriptor) {
                                 if guarded_getter_offsets.contains(&call.offset) {
                                     continue;
@@ -113,7 +119,7 @@ fn guarded_optional_getter_offsets(method: &Method) -> Result<BTreeSet<u32>> {
     let guard_ranges = collect_non_empty_guard_ranges(method, &instructions)?;
 
     let mut guarded_offsets = BTreeSet::new();
-    for call in &method.calls {
+    for call in method.source_calls() {
         if !is_optional_getter_call(&call.owner, &call.name, &call.descriptor) {
             continue;
         }
@@ -6,7 +6,9 @@ use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
 use crate::ir::{Class, Method};
-use crate::rules::{Rule, RuleMetadata, class_location, method_location_with_line, result_message};
+use crate::rules::{
+    Rule, RuleMetadata, RulePrecision, class_location, method_location_with_line, result_message,
+};
 
 const TARGET_COLLECTION_TYPES: [&str; 5] = [
     "java/util/Set",
@@ -28,6 +30,12 @@ impl Rule for PreferEnumSetRule {
             id: "PREFER_ENUMSET",
             name: "Prefer EnumSet for enum collections",
             description: "Using EnumSet for enum types provides better performance than general collections",
+            tags: &["performance"],
+            precision: RulePrecision::Medium,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
@@ -52,7 +60,7 @@ impl Rule for PreferEnumSetRule {
                 })
                 .count();
             let mut attributes = vec![
-                KeyValue::new("inspequte.class", class.name.clone()),
+                KeyValue::new("inspequte.class", class.name),
                 KeyValue::new(
                     "inspequte.prefer_enumset.local_variable_entries",
                     local_variable_entries as i64,
@@ -334,7 +342,7 @@ fn identify_enum_types(context: &AnalysisContext) -> BTreeSet<String> {
     context
         .all_classes()
         .filter(|class| class.super_name.as_deref() == Some("java/lang/Enum"))
-        .map(|class| class.name.clone())
+        .map(|class| class.name.to_string())
         .collect()
 }
 
@@ -3,7 +3,8 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_LANG_THROWABLE, Symbol};
 
 /// Rule that detects direct calls to `Throwable.printStackTrace`.
 #[derive(Default)]
@@ -17,13 +18,19 @@ impl Rule for PrintStackTraceRule {
             id: "PRINT_STACK_TRACE",
             name: "Direct printStackTrace call",
             description: "Throwable.printStackTrace should be replaced with structured logging",
+            tags: &["logging"],
+            precision: RulePrecision::VeryHigh,
+            security_severity: None,
+            cwe: &[209],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,8 +39,8 @@ impl Rule for PrintStackTraceRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
-                            if is_print_stack_trace_call(&call.owner, &call.name, &call.descriptor)
+                        for call in method.source_calls() {
+                            if is_print_stack_trace_call(call.owner, &call.name, &call.descriptor)
                             {
                                 let message = result_message(format!(
                                     "Avoid printStackTrace() in {}.{}{}; log exceptions with context instead.",
@@ -64,7 +71,7 @@ impl Rule for PrintStackTraceRule {
     }
 }
 
-fn is_print_stack_trace_call(owner: &str, name: &str, descriptor: &str) -> bool {
+fn is_print_stack_trace_call(owner: Symbol, name: &str, descriptor: &str) -> bool {
     if name != "printStackTrace" {
         return false;
     }
@@ -74,7 +81,7 @@ fn is_print_stack_trace_call(owner: &str, name: &str, descriptor: &str) -> bool
     ) {
         return false;
     }
-    owner == "java/lang/Throwable"
+    owner == JAVA_LANG_THROWABLE
         || owner.ends_with("/Throwable")
         || owner.ends_with("Exception")
         || owner.ends_with("Error")
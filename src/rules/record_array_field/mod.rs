@@ -3,7 +3,7 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, class_location, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, class_location, result_message};
 
 /// Rule that flags record components that use array types.
 #[derive(Default)]
@@ -17,6 +17,12 @@ impl Rule for RecordArrayFieldRule {
             id: "RECORD_ARRAY_FIELD",
             name: "Record array field",
             description: "Records should not use array-typed components",
+            tags: &["correctness"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
@@ -26,7 +32,7 @@ impl Rule for RecordArrayFieldRule {
             if !class.is_record {
                 continue;
             }
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -10,7 +10,7 @@ use crate::dataflow::worklist::{
 use crate::engine::AnalysisContext;
 use crate::ir::{Instruction, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
 
 /// Rule that detects return statements executed inside finally blocks.
 #[derive(Default)]
@@ -75,13 +75,19 @@ impl Rule for ReturnInFinallyRule {
             id: "RETURN_IN_FINALLY",
             name: "Return in finally",
             description: "Return statements in finally blocks override exceptions or prior returns",
+            tags: &["correctness"],
+            precision: RulePrecision::VeryHigh,
+            security_severity: None,
+            cwe: &[584],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -3,7 +3,8 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_LANG_RUNTIME, Symbol};
 
 /// Rule that detects direct calls to `Runtime.halt(int)`.
 #[derive(Default)]
@@ -17,13 +18,19 @@ impl Rule for RuntimeHaltCallRule {
             id: "RUNTIME_HALT_CALL",
             name: "Runtime.halt call",
             description: "Direct Runtime.halt(int) calls bypass graceful JVM shutdown",
+            tags: &["correctness"],
+            precision: RulePrecision::VeryHigh,
+            security_severity: None,
+            cwe: &[382],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,8 +39,8 @@ impl Rule for RuntimeHaltCallRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
-                            if is_runtime_halt_call(&call.owner, &call.name, &call.descriptor) {
+                        for call in method.source_calls() {
+                            if is_runtime_halt_call(call.owner, &call.name, &call.descriptor) {
                                 let message = result_message(format!(
                                     "Avoid Runtime.halt() in {}.{}{}; prefer orderly shutdown and explicit error handling.",
                                     class.name, method.name, method.descriptor
@@ -63,8 +70,8 @@ impl Rule for RuntimeHaltCallRule {
     }
 }
 
-fn is_runtime_halt_call(owner: &str, name: &str, descriptor: &str) -> bool {
-    owner == "java/lang/Runtime" && name == "halt" && descriptor == "(I)V"
+fn is_runtime_halt_call(owner: Symbol, name: &str, descriptor: &str) -> bool {
+    owner == JAVA_LANG_RUNTIME && name == "halt" && descriptor == "(I)V"
 }
 
 #[cfg(test)]
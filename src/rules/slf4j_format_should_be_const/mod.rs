@@ -9,7 +9,9 @@ use serde_sarif::sarif::Result as SarifResult;
 use crate::engine::AnalysisContext;
 use crate::ir::{CallKind, InstructionKind, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{
+    RequiredLibrary, Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message,
+};
 
 /// Rule that ensures SLF4J format strings are compile-time constants.
 #[derive(Default)]
@@ -23,6 +25,12 @@ impl Rule for Slf4jFormatShouldBeConstRule {
             id: "SLF4J_FORMAT_SHOULD_BE_CONST",
             name: "SLF4J format should be const",
             description: "SLF4J format strings should be compile-time constants",
+            tags: &["logging"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: Some(RequiredLibrary::Slf4j),
+            options: &[],
         }
     }
 
@@ -33,7 +41,7 @@ impl Rule for Slf4jFormatShouldBeConstRule {
 
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
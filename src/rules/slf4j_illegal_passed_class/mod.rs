@@ -10,7 +10,11 @@ use crate::descriptor::{ReturnKind, method_return_kind};
 use crate::engine::AnalysisContext;
 use crate::ir::{CallKind, InstructionKind, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{
+    RequiredLibrary, Rule, RuleMetadata, RulePrecision, add_related_locations, class_location,
+    method_location_with_line, related_location, result_message,
+};
+use crate::symbol::{JAVA_LANG_OBJECT, KOTLIN_JVM_INTERNAL_INTRINSICS};
 
 /// Rule that checks illegal classes passed to LoggerFactory.getLogger(Class).
 #[derive(Default)]
@@ -24,6 +28,12 @@ impl Rule for Slf4jIllegalPassedClassRule {
             id: "SLF4J_ILLEGAL_PASSED_CLASS",
             name: "SLF4J illegal passed class",
             description: "LoggerFactory.getLogger should be called with the caller class",
+            tags: &["logging"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: Some(RequiredLibrary::Slf4j),
+            options: &[],
         }
     }
 
@@ -34,7 +44,7 @@ impl Rule for Slf4jIllegalPassedClassRule {
 
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -47,6 +57,7 @@ impl Rule for Slf4jIllegalPassedClassRule {
                         }
                         let artifact_uri = context.class_artifact_uri(class);
                         class_results.extend(analyze_method(
+                            context,
                             &class.name,
                             method,
                             artifact_uri.as_deref(),
@@ -68,6 +79,7 @@ enum ValueKind {
 }
 
 fn analyze_method(
+    context: &AnalysisContext,
     class_name: &str,
     method: &Method,
     artifact_uri: Option<&str>,
@@ -189,12 +201,20 @@ fn analyze_method(
                                     artifact_uri,
                                     line,
                                 );
-                                results.push(
-                                    SarifResult::builder()
-                                        .message(message)
-                                        .locations(vec![location])
-                                        .build(),
+                                let mut result = SarifResult::builder()
+                                    .message(message)
+                                    .locations(vec![location])
+                                    .build();
+                                let passed_class_uri =
+                                    context.class_artifact_uri_by_name(&passed_class);
+                                add_related_locations(
+                                    &mut result,
+                                    [related_location(
+                                        class_location(&passed_class, passed_class_uri.as_deref()),
+                                        "Class passed to LoggerFactory.getLogger",
+                                    )],
                                 );
+                                results.push(result);
                             }
                         }
                     }
@@ -258,13 +278,13 @@ fn is_get_logger_call(
 }
 
 fn is_get_class_call(call: &crate::ir::CallSite) -> bool {
-    call.owner == "java/lang/Object"
+    call.owner == JAVA_LANG_OBJECT
         && call.name == "getClass"
         && call.descriptor == "()Ljava/lang/Class;"
 }
 
 fn is_kotlin_reified_operation_marker(call: &crate::ir::CallSite) -> bool {
-    call.owner == "kotlin/jvm/internal/Intrinsics"
+    call.owner == KOTLIN_JVM_INTERNAL_INTRINSICS
         && call.name == "reifiedOperationMarker"
         && call.descriptor == "(ILjava/lang/String;)V"
 }
@@ -287,18 +307,26 @@ fn is_acceptable_class(caller_class: &str, passed_class: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
+    use serde_sarif::sarif::Result as SarifResult;
+
     use crate::test_harness::{JvmTestHarness, Language, SourceFile};
 
-    fn analyze_sources(language: Language, sources: Vec<SourceFile>) -> Vec<String> {
+    fn analyze_results(language: Language, sources: Vec<SourceFile>) -> Vec<SarifResult> {
         let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
         let output = harness
             .compile_and_analyze(language, &sources, &[])
             .expect("run harness analysis");
         output
             .results
-            .iter()
+            .into_iter()
             .filter(|result| result.rule_id.as_deref() == Some("SLF4J_ILLEGAL_PASSED_CLASS"))
-            .filter_map(|result| result.message.text.clone())
+            .collect()
+    }
+
+    fn analyze_sources(language: Language, sources: Vec<SourceFile>) -> Vec<String> {
+        analyze_results(language, sources)
+            .into_iter()
+            .filter_map(|result| result.message.text)
             .collect()
     }
 
@@ -351,6 +379,45 @@ public class ClassA {
         assert!(messages.iter().any(|msg| msg.contains("ClassB")));
     }
 
+    #[test]
+    fn slf4j_illegal_passed_class_relates_passed_class() {
+        let sources = slf4j_sources(
+            r#"
+package com.example;
+import org.slf4j.Logger;
+import org.slf4j.LoggerFactory;
+class ClassB {}
+public class ClassA {
+    private final Logger fieldA = LoggerFactory.getLogger(ClassB.class);
+}
+"#,
+        );
+
+        let results = analyze_results(Language::Java, sources);
+
+        assert_eq!(results.len(), 1);
+        let related = results[0]
+            .related_locations
+            .as_ref()
+            .expect("related locations");
+        assert_eq!(related.len(), 1);
+        assert_eq!(
+            related[0]
+                .message
+                .as_ref()
+                .and_then(|message| message.text.as_deref()),
+            Some("Class passed to LoggerFactory.getLogger")
+        );
+        assert_eq!(
+            related[0]
+                .logical_locations
+                .as_ref()
+                .and_then(|locations| locations.first())
+                .and_then(|location| location.name.as_deref()),
+            Some("com/example/ClassB")
+        );
+    }
+
     #[test]
     fn slf4j_illegal_passed_class_allows_expected_classes() {
         let sources = slf4j_sources(
@@ -10,7 +10,10 @@ use crate::descriptor::{ReturnKind, method_return_kind};
 use crate::engine::AnalysisContext;
 use crate::ir::{CallKind, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{
+    RequiredLibrary, Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message,
+};
+use crate::symbol::JAVA_LANG_STRING;
 
 /// Rule that detects SLF4J log messages assembled manually instead of placeholders.
 #[derive(Default)]
@@ -24,6 +27,12 @@ impl Rule for Slf4jManuallyProvidedMessageRule {
             id: "SLF4J_MANUALLY_PROVIDED_MESSAGE",
             name: "SLF4J preformatted message",
             description: "SLF4J messages should use placeholders instead of manual formatting",
+            tags: &["logging", "performance"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: Some(RequiredLibrary::Slf4j),
+            options: &[],
         }
     }
 
@@ -34,7 +43,7 @@ impl Rule for Slf4jManuallyProvidedMessageRule {
 
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -275,7 +284,7 @@ fn slf4j_format_index(param_types: &[jdescriptor::TypeDescriptor]) -> Option<usi
 }
 
 fn is_manual_message_call(call: &crate::ir::CallSite) -> bool {
-    if call.owner == "java/lang/String" && call.name == "format" {
+    if call.owner == JAVA_LANG_STRING && call.name == "format" {
         return call.descriptor.ends_with(")Ljava/lang/String;");
     }
     if matches!(
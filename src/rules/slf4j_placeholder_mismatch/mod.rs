@@ -1,4 +1,5 @@
-use std::collections::BTreeMap;
+use std::cell::OnceCell;
+use std::collections::{BTreeMap, BTreeSet};
 use std::str::FromStr;
 
 use anyhow::{Context, Result};
@@ -6,11 +7,16 @@ use jdescriptor::MethodDescriptor;
 use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
+use crate::dataflow::constants::{ConstantValue, MethodConstants};
+use crate::dataflow::types::MethodTypes;
 use crate::descriptor::{ReturnKind, method_return_kind};
 use crate::engine::AnalysisContext;
-use crate::ir::{CallKind, InstructionKind, Method};
+use crate::ir::{CallKind, Class, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{
+    RequiredLibrary, Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message,
+};
+use crate::symbol::Symbol;
 
 /// Rule that detects SLF4J placeholder and argument count mismatches.
 #[derive(Default)]
@@ -24,6 +30,12 @@ impl Rule for Slf4jPlaceholderMismatchRule {
             id: "SLF4J_PLACEHOLDER_MISMATCH",
             name: "SLF4J placeholder mismatch",
             description: "SLF4J placeholder count does not match arguments",
+            tags: &["correctness", "logging"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: Some(RequiredLibrary::Slf4j),
+            options: &[],
         }
     }
 
@@ -32,9 +44,12 @@ impl Rule for Slf4jPlaceholderMismatchRule {
             return Ok(Vec::new());
         }
 
+        // Built only when a trailing argument's type is not a known JDK throwable.
+        let class_map = OnceCell::new();
+        let is_throwable = |class_name: &str| is_throwable_type(context, &class_map, class_name);
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -46,9 +61,14 @@ impl Rule for Slf4jPlaceholderMismatchRule {
                             continue;
                         }
                         let artifact_uri = context.class_artifact_uri(class);
+                        let constants = context.method_constants(method)?;
+                        let types = context.method_types(class, method)?;
                         class_results.extend(analyze_method(
                             &class.name,
                             method,
+                            &constants,
+                            &types,
+                            &is_throwable,
                             artifact_uri.as_deref(),
                         )?);
                     }
@@ -63,7 +83,6 @@ impl Rule for Slf4jPlaceholderMismatchRule {
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 enum ValueKind {
     Unknown,
-    FormatString { placeholders: usize },
     IntConst { value: usize },
     Array { len: Option<usize> },
 }
@@ -71,6 +90,9 @@ enum ValueKind {
 fn analyze_method(
     class_name: &str,
     method: &Method,
+    constants: &MethodConstants,
+    types: &MethodTypes,
+    is_throwable: &dyn Fn(&str) -> bool,
     artifact_uri: Option<&str>,
 ) -> Result<Vec<SarifResult>> {
     let mut results = Vec::new();
@@ -79,15 +101,6 @@ fn analyze_method(
         callsites.insert(call.offset, call);
     }
 
-    let mut const_strings = BTreeMap::new();
-    for block in &method.cfg.blocks {
-        for inst in &block.instructions {
-            if let InstructionKind::ConstString(value) = &inst.kind {
-                const_strings.insert(inst.offset, value.clone());
-            }
-        }
-    }
-
     let mut locals = initial_locals(method)?;
     let mut stack: Vec<ValueKind> = Vec::new();
     let mut offset = 0usize;
@@ -149,15 +162,7 @@ fn analyze_method(
                 let value = stack.pop().unwrap_or(ValueKind::Unknown);
                 locals[index] = value;
             }
-            opcodes::LDC | opcodes::LDC_W | opcodes::LDC2_W => {
-                if let Some(value) = const_strings.get(&(offset as u32)) {
-                    stack.push(ValueKind::FormatString {
-                        placeholders: count_placeholders(value),
-                    });
-                } else {
-                    stack.push(ValueKind::Unknown);
-                }
-            }
+            opcodes::LDC | opcodes::LDC_W | opcodes::LDC2_W => stack.push(ValueKind::Unknown),
             opcodes::DUP => {
                 if let Some(value) = stack.last().copied() {
                     stack.push(value);
@@ -197,7 +202,19 @@ fn analyze_method(
                     }
 
                     if is_slf4j_logger_call(call) {
-                        if let Some(mismatch) = placeholder_mismatch(param_types, &args) {
+                        let constant_argument =
+                            |index| constants.argument(offset as u32, param_types.len(), index);
+                        let throwable_argument = |index| {
+                            types
+                                .argument(offset as u32, param_types.len(), index)
+                                .is_some_and(|inferred| is_throwable(&inferred.class_name))
+                        };
+                        if let Some(mismatch) = placeholder_mismatch(
+                            param_types,
+                            &args,
+                            constant_argument,
+                            throwable_argument,
+                        ) {
                             let message = result_message(format!(
                                 "SLF4J placeholder mismatch: expected {} argument(s) but found {}",
                                 mismatch.expected, mismatch.found
@@ -271,9 +288,14 @@ struct PlaceholderMismatch {
     found: usize,
 }
 
-fn placeholder_mismatch(
+/// Compare placeholders in the constant format argument with the arguments passed after
+/// it; `constant_argument` looks up constant arguments by index and `throwable_argument`
+/// tells whether an argument's static type is a throwable.
+fn placeholder_mismatch<'a>(
     param_types: &[jdescriptor::TypeDescriptor],
     args: &[ValueKind],
+    constant_argument: impl Fn(usize) -> Option<&'a ConstantValue>,
+    throwable_argument: impl Fn(usize) -> bool,
 ) -> Option<PlaceholderMismatch> {
     if param_types.is_empty() || args.is_empty() {
         return None;
@@ -290,14 +312,7 @@ fn placeholder_mismatch(
     if !is_string {
         return None;
     }
-    let format = match args
-        .get(format_index)
-        .copied()
-        .unwrap_or(ValueKind::Unknown)
-    {
-        ValueKind::FormatString { placeholders } => placeholders,
-        ValueKind::Unknown | ValueKind::IntConst { .. } | ValueKind::Array { .. } => return None,
-    };
+    let format = count_placeholders(constant_argument(format_index)?.as_str()?);
 
     if param_types.len() == format_index + 2 {
         if let jdescriptor::TypeDescriptor::Array(inner, _) = &param_types[format_index + 1] {
@@ -331,7 +346,11 @@ fn placeholder_mismatch(
         }
     }
 
-    if format == arg_count {
+    // SLF4J logs an unused trailing argument as the exception when it is a throwable.
+    let trailing_throwable = arg_count == format + 1
+        && matches!(param_types.last(), Some(jdescriptor::TypeDescriptor::Object(class)) if class.as_str() == "java/lang/Object")
+        && throwable_argument(param_types.len() - 1);
+    if format == arg_count || trailing_throwable {
         None
     } else {
         Some(PlaceholderMismatch {
@@ -341,6 +360,74 @@ fn placeholder_mismatch(
     }
 }
 
+/// Whether `class_name` is `java/lang/Throwable` or a subclass, following superclasses
+/// through the analyzed and classpath classes until a known JDK throwable is reached.
+fn is_throwable_type<'a>(
+    context: &'a AnalysisContext,
+    class_map: &OnceCell<BTreeMap<Symbol, &'a Class>>,
+    class_name: &str,
+) -> bool {
+    let mut next = Some(Symbol::intern(class_name));
+    let mut seen = BTreeSet::new();
+    while let Some(name) = next {
+        if is_known_throwable_name(&name) {
+            return true;
+        }
+        if !seen.insert(name) {
+            return false;
+        }
+        let class_map = class_map.get_or_init(|| {
+            context
+                .all_classes()
+                .map(|class| (class.name, class))
+                .collect()
+        });
+        next = class_map.get(&name).and_then(|class| class.super_name);
+    }
+    false
+}
+
+/// JDK throwables commonly passed to a logger; JDK classes are usually not on the classpath,
+/// so their hierarchy cannot be followed.
+fn is_known_throwable_name(name: &str) -> bool {
+    matches!(
+        name,
+        "java/lang/Throwable"
+            | "java/lang/Exception"
+            | "java/lang/RuntimeException"
+            | "java/lang/Error"
+            | "java/lang/ArithmeticException"
+            | "java/lang/ArrayIndexOutOfBoundsException"
+            | "java/lang/AssertionError"
+            | "java/lang/ClassCastException"
+            | "java/lang/ClassNotFoundException"
+            | "java/lang/CloneNotSupportedException"
+            | "java/lang/IllegalArgumentException"
+            | "java/lang/IllegalStateException"
+            | "java/lang/IndexOutOfBoundsException"
+            | "java/lang/InterruptedException"
+            | "java/lang/LinkageError"
+            | "java/lang/NoSuchFieldException"
+            | "java/lang/NoSuchMethodException"
+            | "java/lang/NullPointerException"
+            | "java/lang/NumberFormatException"
+            | "java/lang/OutOfMemoryError"
+            | "java/lang/ReflectiveOperationException"
+            | "java/lang/SecurityException"
+            | "java/lang/StackOverflowError"
+            | "java/lang/UnsupportedOperationException"
+            | "java/io/FileNotFoundException"
+            | "java/io/IOException"
+            | "java/io/UncheckedIOException"
+            | "java/util/ConcurrentModificationException"
+            | "java/util/NoSuchElementException"
+            | "java/util/concurrent/CancellationException"
+            | "java/util/concurrent/CompletionException"
+            | "java/util/concurrent/ExecutionException"
+            | "java/util/concurrent/TimeoutException"
+    )
+}
+
 fn count_placeholders(text: &str) -> usize {
     let bytes = text.as_bytes();
     let mut index = 0usize;
@@ -547,4 +634,88 @@ public class ClassA {
 
         assert!(messages.is_empty());
     }
+
+    #[test]
+    fn slf4j_placeholder_mismatch_allows_only_throwable_as_unused_trailing_arg() {
+        let sources = slf4j_sources(
+            r#"
+package com.example;
+import org.slf4j.Logger;
+public class ClassA {
+    private final Logger fieldA;
+    public ClassA(Logger varOne) {
+        this.fieldA = varOne;
+    }
+    public void methodOne(String varTwo, Object varThree) {
+        fieldA.info("User {}", varTwo, varThree);
+        try {
+            varTwo.length();
+        } catch (IllegalStateException varFour) {
+            fieldA.info("User {}", varTwo, varFour);
+        }
+    }
+}
+"#,
+        );
+
+        let messages = analyze_sources(sources);
+
+        assert_eq!(messages.len(), 1, "unexpected messages: {messages:?}");
+        assert!(messages[0].contains("expected 1 argument(s) but found 2"));
+    }
+
+    #[test]
+    fn slf4j_placeholder_mismatch_follows_superclasses_to_a_throwable() {
+        let sources = slf4j_sources(
+            r#"
+package com.example;
+import org.slf4j.Logger;
+public class ClassA {
+    static class ClassB extends IllegalStateException {}
+    static class ClassC extends ClassB {}
+    static class ParseError {}
+    private final Logger fieldA;
+    public ClassA(Logger varOne) {
+        this.fieldA = varOne;
+    }
+    public void methodOne(String varTwo, ClassC varThree, ParseError varFour) {
+        fieldA.info("User {}", varTwo, varThree);
+        fieldA.info("User {}", varTwo, varFour);
+    }
+}
+"#,
+        );
+
+        let messages = analyze_sources(sources);
+
+        assert_eq!(messages.len(), 1, "unexpected messages: {messages:?}");
+        assert!(messages[0].contains("expected 1 argument(s) but found 2"));
+    }
+
+    #[test]
+    fn slf4j_placeholder_mismatch_reports_built_format_and_constant_extra_arg() {
+        let sources = slf4j_sources(
+            r#"
+package com.example;
+import org.slf4j.Logger;
+public class ClassA {
+    private final Logger fieldA;
+    public ClassA(Logger varOne) {
+        this.fieldA = varOne;
+    }
+    public void methodOne() {
+        String varTwo = new StringBuilder("Built {}").append(" {}").toString();
+        fieldA.info(varTwo, "one");
+        fieldA.info("Hello {}", "one", "two");
+    }
+}
+"#,
+        );
+
+        let messages = analyze_sources(sources);
+
+        assert_eq!(messages.len(), 2, "unexpected messages: {messages:?}");
+        assert!(messages.iter().any(|msg| msg.contains("expected 2")));
+        assert!(messages.iter().any(|msg| msg.contains("expected 1")));
+    }
 }
@@ -10,7 +10,9 @@ use crate::descriptor::{ReturnKind, method_return_kind};
 use crate::engine::AnalysisContext;
 use crate::ir::{CallKind, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{
+    RequiredLibrary, Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message,
+};
 
 /// Rule that reports SLF4J varargs calls with unknown argument array length.
 #[derive(Default)]
@@ -24,6 +26,12 @@ impl Rule for Slf4jUnknownArrayRule {
             id: "SLF4J_UNKNOWN_ARRAY",
             name: "SLF4J unknown array",
             description: "SLF4J varargs calls with unknown argument arrays",
+            tags: &["logging"],
+            precision: RulePrecision::Medium,
+            security_severity: None,
+            cwe: &[],
+            requires: Some(RequiredLibrary::Slf4j),
+            options: &[],
         }
     }
 
@@ -34,7 +42,7 @@ impl Rule for Slf4jUnknownArrayRule {
 
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
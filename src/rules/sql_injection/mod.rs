@@ -0,0 +1,202 @@
+use anyhow::Result;
+use serde_sarif::sarif::Result as SarifResult;
+
+use crate::dataflow::taint::{MethodPattern, SERVLET_REQUEST_SOURCES, TaintSink, TaintSpec};
+use crate::engine::AnalysisContext;
+use crate::rules::{Rule, RuleMetadata, RulePrecision, taint_flow_results};
+
+/// Rule that detects request data flowing into SQL text.
+#[derive(Default)]
+pub(crate) struct SqlInjectionRule;
+
+crate::register_rule!(SqlInjectionRule);
+
+const SQL_SINKS: &[TaintSink] = &[
+    sql_sink("java/sql/Statement", "execute"),
+    sql_sink("java/sql/Statement", "executeQuery"),
+    sql_sink("java/sql/Statement", "executeUpdate"),
+    sql_sink("java/sql/Statement", "executeLargeUpdate"),
+    sql_sink("java/sql/Statement", "addBatch"),
+    sql_sink("java/sql/Connection", "prepareStatement"),
+    sql_sink("java/sql/Connection", "prepareCall"),
+    sql_sink("java/sql/Connection", "nativeSQL"),
+];
+
+const SQL_INJECTION: TaintSpec = TaintSpec {
+    sources: SERVLET_REQUEST_SOURCES,
+    sinks: SQL_SINKS,
+    sanitizers: &[],
+};
+
+/// Every overload of a call taking the SQL text as its first argument.
+const fn sql_sink(owner: &'static str, name: &'static str) -> TaintSink {
+    TaintSink {
+        method: MethodPattern::any(owner, name),
+        argument: 0,
+    }
+}
+
+impl Rule for SqlInjectionRule {
+    fn metadata(&self) -> RuleMetadata {
+        RuleMetadata {
+            id: "SQL_INJECTION",
+            name: "SQL injection",
+            description: "Request data used as SQL text",
+            tags: &["security"],
+            precision: RulePrecision::Medium,
+            security_severity: Some(8.8),
+            cwe: &[89],
+            requires: None,
+            options: &[],
+        }
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
+        taint_flow_results(context, &SQL_INJECTION, |class, method, flow| {
+            format!(
+                "SQL injection: request data reaches {}.{} in {}.{}{}; use a PreparedStatement with bound parameters.",
+                flow.sink.method.owner,
+                flow.sink.method.name,
+                class.name,
+                method.name,
+                method.descriptor
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_harness::{JvmTestHarness, Language, SourceFile};
+
+    fn servlet_stub() -> SourceFile {
+        SourceFile {
+            path: "javax/servlet/http/HttpServletRequest.java".to_string(),
+            contents: r#"
+package javax.servlet.http;
+public interface HttpServletRequest {
+    String getParameter(String name);
+}
+"#
+            .to_string(),
+        }
+    }
+
+    fn analyze(contents: &str) -> Vec<serde_sarif::sarif::Result> {
+        let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
+        let sources = vec![
+            servlet_stub(),
+            SourceFile {
+                path: "com/example/ClassA.java".to_string(),
+                contents: contents.to_string(),
+            },
+        ];
+        let output = harness
+            .compile_and_analyze(Language::Java, &sources, &[])
+            .expect("run harness analysis");
+        output
+            .results
+            .into_iter()
+            .filter(|result| result.rule_id.as_deref() == Some("SQL_INJECTION"))
+            .collect()
+    }
+
+    fn messages(results: &[serde_sarif::sarif::Result]) -> Vec<String> {
+        results
+            .iter()
+            .filter_map(|result| result.message.text.clone())
+            .collect()
+    }
+
+    #[test]
+    fn reports_concatenated_parameter_with_code_flow() {
+        let results = analyze(
+            r#"
+package com.example;
+import java.sql.Connection;
+import java.sql.ResultSet;
+import javax.servlet.http.HttpServletRequest;
+public class ClassA {
+    public ResultSet methodX(HttpServletRequest varOne, Connection varTwo) throws Exception {
+        String varThree = varOne.getParameter("id");
+        return varTwo.createStatement().executeQuery("SELECT * FROM t WHERE id = " + varThree);
+    }
+}
+"#,
+        );
+
+        assert_eq!(results.len(), 1, "results: {:?}", messages(&results));
+        assert!(messages(&results)[0].contains(
+            "SQL injection: request data reaches java/sql/Statement.executeQuery in com/example/ClassA.methodX"
+        ));
+        let flow_messages: Vec<String> = results[0]
+            .code_flows
+            .as_ref()
+            .and_then(|flows| flows.first())
+            .map(|flow| {
+                flow.thread_flows[0]
+                    .locations
+                    .iter()
+                    .filter_map(|location| location.location.as_ref()?.message.as_ref()?.text.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        assert_eq!(
+            flow_messages,
+            vec![
+                "Untrusted data enters here".to_string(),
+                "Untrusted data reaches java/sql/Statement.executeQuery".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_parameter_built_into_query_through_builder_and_field() {
+        let results = analyze(
+            r#"
+package com.example;
+import java.sql.Connection;
+import javax.servlet.http.HttpServletRequest;
+public class ClassA {
+    private String filter;
+
+    public void methodX(HttpServletRequest varOne, Connection varTwo) throws Exception {
+        filter = varOne.getParameter("name").trim();
+        StringBuilder varThree = new StringBuilder("SELECT * FROM t WHERE name = '");
+        varThree.append(filter).append("'");
+        varTwo.prepareStatement(varThree.toString());
+    }
+}
+"#,
+        );
+
+        assert_eq!(results.len(), 1, "results: {:?}", messages(&results));
+        assert!(messages(&results)[0].contains("java/sql/Connection.prepareStatement"));
+    }
+
+    #[test]
+    fn does_not_report_bound_parameters_or_constant_queries() {
+        let results = analyze(
+            r#"
+package com.example;
+import java.sql.Connection;
+import java.sql.PreparedStatement;
+import javax.servlet.http.HttpServletRequest;
+public class ClassA {
+    public void methodX(HttpServletRequest varOne, Connection varTwo) throws Exception {
+        PreparedStatement varThree = varTwo.prepareStatement("SELECT * FROM t WHERE id = ?");
+        varThree.setString(1, varOne.getParameter("id"));
+        varThree.executeQuery();
+        varTwo.createStatement().executeQuery("SELECT count(*) FROM t");
+    }
+
+    public void methodY(String varOne, Connection varTwo) throws Exception {
+        varTwo.createStatement().execute("DELETE FROM t WHERE id = " + varOne);
+    }
+}
+"#,
+        );
+
+        assert!(results.is_empty(), "results: {:?}", messages(&results));
+    }
+}
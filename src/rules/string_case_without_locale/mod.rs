@@ -3,7 +3,9 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::fix::FixSuggestion;
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::JAVA_LANG_STRING;
 
 /// Rule that detects String case conversion calls without an explicit Locale.
 #[derive(Default)]
@@ -17,13 +19,19 @@ impl Rule for StringCaseWithoutLocaleRule {
             id: "STRING_CASE_WITHOUT_LOCALE",
             name: "String case conversion without explicit locale",
             description: "String.toLowerCase()/toUpperCase() calls without Locale argument",
+            tags: &["correctness"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,7 +40,7 @@ impl Rule for StringCaseWithoutLocaleRule {
                     let mut class_results = Vec::new();
                     for method in &class.methods {
                         let artifact_uri = context.class_artifact_uri(class);
-                        for call in &method.calls {
+                        for call in method.source_calls() {
                             if is_locale_less_case_call(call) {
                                 let message = result_message(format!(
                                     "String case conversion in {}.{}{} uses default locale; pass Locale.ROOT (or an explicit Locale) to make behavior deterministic.",
@@ -46,12 +54,20 @@ impl Rule for StringCaseWithoutLocaleRule {
                                     artifact_uri.as_deref(),
                                     line,
                                 );
-                                class_results.push(
-                                    SarifResult::builder()
-                                        .message(message)
-                                        .locations(vec![location])
-                                        .build(),
-                                );
+                                let mut result = SarifResult::builder()
+                                    .message(message)
+                                    .locations(vec![location])
+                                    .build();
+                                FixSuggestion::new(format!(
+                                    "Pass Locale.ROOT to String.{}",
+                                    call.name
+                                ))
+                                .replace(
+                                    format!("{}()", call.name),
+                                    format!("{}(java.util.Locale.ROOT)", call.name),
+                                )
+                                .attach(&mut result);
+                                class_results.push(result);
                             }
                         }
                     }
@@ -64,7 +80,7 @@ impl Rule for StringCaseWithoutLocaleRule {
 }
 
 fn is_locale_less_case_call(call: &crate::ir::CallSite) -> bool {
-    call.owner == "java/lang/String"
+    call.owner == JAVA_LANG_STRING
         && call.descriptor == "()Ljava/lang/String;"
         && matches!(call.name.as_str(), "toLowerCase" | "toUpperCase")
 }
@@ -3,7 +3,8 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::JAVA_LANG_STRING;
 
 /// Rule that detects locale-dependent string formatting calls.
 #[derive(Default)]
@@ -17,13 +18,19 @@ impl Rule for StringFormatLocaleMissingRule {
             id: "STRING_FORMAT_LOCALE_MISSING",
             name: "String/Formatter formatting without explicit locale",
             description: "String.format(...) and Formatter usage without Locale can vary by runtime locale",
+            tags: &["correctness"],
+            precision: RulePrecision::Medium,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,7 +39,7 @@ impl Rule for StringFormatLocaleMissingRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
+                        for call in method.source_calls() {
                             if is_locale_missing_format_call(call) {
                                 let message_text = if call.name == "<init>" {
                                     format!(
@@ -76,7 +83,7 @@ fn is_locale_missing_format_call(call: &crate::ir::CallSite) -> bool {
 }
 
 fn is_string_format_without_locale(call: &crate::ir::CallSite) -> bool {
-    call.owner == "java/lang/String"
+    call.owner == JAVA_LANG_STRING
         && call.name == "format"
         && call.descriptor == "(Ljava/lang/String;[Ljava/lang/Object;)Ljava/lang/String;"
 }
@@ -344,9 +351,9 @@ class ClassA {
 
         for descriptor in descriptors {
             let call = CallSite {
-                owner: "java/util/Formatter".to_string(),
-                name: "<init>".to_string(),
-                descriptor: descriptor.to_string(),
+                owner: "java/util/Formatter".into(),
+                name: "<init>".into(),
+                descriptor: descriptor.into(),
                 kind: CallKind::Special,
                 offset: 0,
             };
@@ -3,7 +3,8 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_LANG_STRING, Symbol};
 
 /// Rule that detects direct String.intern calls.
 #[derive(Default)]
@@ -17,13 +18,19 @@ impl Rule for StringInternCallRule {
             id: "STRING_INTERN_CALL",
             name: "String intern call",
             description: "String.intern can increase global pool pressure and contention",
+            tags: &["performance"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,8 +39,8 @@ impl Rule for StringInternCallRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
-                            if is_string_intern_call(&call.owner, &call.name, &call.descriptor) {
+                        for call in method.source_calls() {
+                            if is_string_intern_call(call.owner, &call.name, &call.descriptor) {
                                 let message = result_message(format!(
                                     "Avoid String.intern() in {}.{}{}; use bounded caching or explicit canonicalization instead.",
                                     class.name, method.name, method.descriptor
@@ -63,8 +70,8 @@ impl Rule for StringInternCallRule {
     }
 }
 
-fn is_string_intern_call(owner: &str, name: &str, descriptor: &str) -> bool {
-    owner == "java/lang/String" && name == "intern" && descriptor == "()Ljava/lang/String;"
+fn is_string_intern_call(owner: Symbol, name: &str, descriptor: &str) -> bool {
+    owner == JAVA_LANG_STRING && name == "intern" && descriptor == "()Ljava/lang/String;"
 }
 
 #[cfg(test)]
@@ -3,8 +3,10 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
+use crate::fix::FixSuggestion;
 use crate::ir::{CallSite, Method};
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::JAVA_LANG_STRING;
 
 /// Rule that detects direct `String.trim().isEmpty()` call chains.
 #[derive(Default)]
@@ -18,13 +20,19 @@ impl Rule for StringTrimIsEmptyRule {
             id: "STRING_TRIM_IS_EMPTY",
             name: "String trim followed by isEmpty",
             description: "String.trim().isEmpty() can be ambiguous; prefer String.isBlank()",
+            tags: &["style"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -46,12 +54,14 @@ impl Rule for StringTrimIsEmptyRule {
                                 artifact_uri.as_deref(),
                                 line,
                             );
-                            class_results.push(
-                                SarifResult::builder()
-                                    .message(message)
-                                    .locations(vec![location])
-                                    .build(),
-                            );
+                            let mut result = SarifResult::builder()
+                                .message(message)
+                                .locations(vec![location])
+                                .build();
+                            FixSuggestion::new("Replace trim().isEmpty() with isBlank()")
+                                .replace(".trim().isEmpty()", ".isBlank()")
+                                .attach(&mut result);
+                            class_results.push(result);
                         }
                     }
                     Ok(class_results)
@@ -83,13 +93,13 @@ fn direct_trim_is_empty_offsets(method: &Method) -> Vec<u32> {
 }
 
 fn is_string_trim_call(call: &CallSite) -> bool {
-    call.owner == "java/lang/String"
+    call.owner == JAVA_LANG_STRING
         && call.name == "trim"
         && call.descriptor == "()Ljava/lang/String;"
 }
 
 fn is_string_is_empty_call(call: &CallSite) -> bool {
-    call.owner == "java/lang/String" && call.name == "isEmpty" && call.descriptor == "()Z"
+    call.owner == JAVA_LANG_STRING && call.name == "isEmpty" && call.descriptor == "()Z"
 }
 
 #[cfg(test)]
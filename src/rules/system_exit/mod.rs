@@ -3,7 +3,8 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_LANG_SYSTEM, KOTLIN_SYSTEM_PROCESS_KT, Symbol};
 
 /// Rule that detects direct JVM termination via `System.exit(int)`.
 #[derive(Default)]
@@ -17,6 +18,12 @@ impl Rule for SystemExitRule {
             id: "SYSTEM_EXIT",
             name: "System.exit call",
             description: "Direct calls to System.exit(int) terminate the JVM abruptly",
+            tags: &["correctness"],
+            precision: RulePrecision::VeryHigh,
+            security_severity: None,
+            cwe: &[382],
+            requires: None,
+            options: &[],
         }
     }
 
@@ -27,7 +34,7 @@ impl Rule for SystemExitRule {
                 .methods
                 .iter()
                 .any(|method| is_java_entrypoint_main_with_args(method));
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -39,8 +46,8 @@ impl Rule for SystemExitRule {
                         if is_allowed_main_method(method, has_main_with_args) {
                             continue;
                         }
-                        for call in &method.calls {
-                            if is_process_termination_call(&call.owner, &call.name, &call.descriptor)
+                        for call in method.source_calls() {
+                            if is_process_termination_call(call.owner, &call.name, &call.descriptor)
                             {
                                 let message = result_message(format!(
                                     "Avoid System.exit() in {}.{}{}; return an error or throw an exception instead.",
@@ -71,11 +78,11 @@ impl Rule for SystemExitRule {
     }
 }
 
-fn is_process_termination_call(owner: &str, name: &str, descriptor: &str) -> bool {
-    if owner == "java/lang/System" && name == "exit" && descriptor == "(I)V" {
+fn is_process_termination_call(owner: Symbol, name: &str, descriptor: &str) -> bool {
+    if owner == JAVA_LANG_SYSTEM && name == "exit" && descriptor == "(I)V" {
         return true;
     }
-    owner == "kotlin/system/ProcessKt"
+    owner == KOTLIN_SYSTEM_PROCESS_KT
         && name == "exitProcess"
         && matches!(descriptor, "(I)Ljava/lang/Void;" | "(I)V")
 }
@@ -4,7 +4,8 @@ use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
 use crate::ir::{CallKind, CallSite, Method};
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::JAVA_LANG_THREAD;
 
 /// Rule that detects direct calls to `Thread.run()`.
 #[derive(Default)]
@@ -18,13 +19,19 @@ impl Rule for ThreadRunDirectCallRule {
             id: "THREAD_RUN_DIRECT_CALL",
             name: "Thread.run direct call",
             description: "Direct Thread.run() calls execute synchronously on the current thread",
+            tags: &["concurrency"],
+            precision: RulePrecision::VeryHigh,
+            security_severity: None,
+            cwe: &[572],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -33,7 +40,7 @@ impl Rule for ThreadRunDirectCallRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
+                        for call in method.source_calls() {
                             if !is_thread_run_call(call) {
                                 continue;
                             }
@@ -70,7 +77,7 @@ impl Rule for ThreadRunDirectCallRule {
 }
 
 fn is_thread_run_call(call: &CallSite) -> bool {
-    call.owner == "java/lang/Thread" && call.name == "run" && call.descriptor == "()V"
+    call.owner == JAVA_LANG_THREAD && call.name == "run" && call.descriptor == "()V"
 }
 
 fn is_allowed_super_run_call(method: &Method, call: &CallSite) -> bool {
@@ -3,7 +3,12 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rule_options::{RuleOptionKind, RuleOptionSpec};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_LANG_THREAD, Symbol};
+
+/// Option listing packages whose classes may call `Thread.sleep`.
+const IGNORED_PACKAGES_OPTION: &str = "ignoredPackages";
 
 /// Rule that detects direct Thread.sleep calls.
 #[derive(Default)]
@@ -17,13 +22,29 @@ impl Rule for ThreadSleepCallRule {
             id: "THREAD_SLEEP_CALL",
             name: "Thread.sleep call",
             description: "Thread.sleep introduces timing-coupled blocking",
+            tags: &["concurrency"],
+            precision: RulePrecision::Medium,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[RuleOptionSpec {
+                name: IGNORED_PACKAGES_OPTION,
+                kind: RuleOptionKind::StringList,
+                description: "Packages (e.g. `com.example.retry`) whose classes, including subpackages, are not reported",
+            }],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
+        let ignored_packages = context
+            .rule_options(self.metadata().id)
+            .string_list(IGNORED_PACKAGES_OPTION);
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            if is_in_packages(&class.name, ignored_packages) {
+                continue;
+            }
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,8 +53,8 @@ impl Rule for ThreadSleepCallRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
-                            if is_thread_sleep_call(&call.owner, &call.name, &call.descriptor) {
+                        for call in method.source_calls() {
+                            if is_thread_sleep_call(call.owner, &call.name, &call.descriptor) {
                                 let message = result_message(format!(
                                     "Avoid Thread.sleep() in {}.{}{}; prefer explicit synchronization or scheduler abstractions over timing-based sleeps.",
                                     class.name, method.name, method.descriptor
@@ -63,10 +84,18 @@ impl Rule for ThreadSleepCallRule {
     }
 }
 
-fn is_thread_sleep_call(owner: &str, name: &str, descriptor: &str) -> bool {
-    owner == "java/lang/Thread"
-        && name == "sleep"
-        && (descriptor == "(J)V" || descriptor == "(JI)V")
+/// Returns true when the internal class name lies in one of the dotted packages or below.
+fn is_in_packages(class_name: &str, packages: &[String]) -> bool {
+    packages.iter().any(|package| {
+        let prefix = package.replace('.', "/");
+        class_name
+            .strip_prefix(prefix.as_str())
+            .is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+fn is_thread_sleep_call(owner: Symbol, name: &str, descriptor: &str) -> bool {
+    owner == JAVA_LANG_THREAD && name == "sleep" && (descriptor == "(J)V" || descriptor == "(JI)V")
 }
 
 #[cfg(test)]
@@ -146,6 +175,43 @@ public class ClassB {
         );
     }
 
+    #[test]
+    fn thread_sleep_call_skips_ignored_packages() {
+        let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
+        let sleeper = |package: &str, class: &str| SourceFile {
+            path: format!("{}/{class}.java", package.replace('.', "/")),
+            contents: format!(
+                r#"
+package {package};
+public class {class} {{
+    public void methodX() throws InterruptedException {{
+        Thread.sleep(10L);
+    }}
+}}
+"#
+            ),
+        };
+        let sources = vec![
+            sleeper("com.example.retry", "ClassA"),
+            sleeper("com.example.retry.backoff", "ClassB"),
+            sleeper("com.example.retrying", "ClassC"),
+        ];
+
+        let output = harness
+            .compile_and_analyze_with_rule_options(
+                Language::Java,
+                &sources,
+                &[],
+                serde_json::json!({
+                    "THREAD_SLEEP_CALL": {"ignoredPackages": ["com.example.retry"]}
+                }),
+            )
+            .expect("run harness analysis");
+        let messages = sleep_messages(&output);
+        assert_eq!(messages.len(), 1, "unexpected findings: {messages:?}");
+        assert!(messages[0].contains("com/example/retrying/ClassC"));
+    }
+
     #[test]
     fn thread_sleep_call_ignores_current_thread() {
         let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
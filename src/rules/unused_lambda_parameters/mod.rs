@@ -8,7 +8,7 @@ use crate::descriptor::{method_param_slots, method_param_start_slots};
 use crate::engine::AnalysisContext;
 use crate::ir::{Class, InstructionKind, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
 
 /// Rule that detects unused lambda parameters in Java and Kotlin lambda expressions.
 #[derive(Default)]
@@ -22,13 +22,19 @@ impl Rule for UnusedLambdaParametersRule {
             id: "UNUSED_LAMBDA_PARAMETERS",
             name: "Unused lambda parameter",
             description: "Reports lambda parameters that are never referenced in the lambda body",
+            tags: &["style"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -100,6 +106,7 @@ fn check_java_lambdas(class: &Class, artifact_uri: Option<&str>) -> Result<Vec<S
                 if let InstructionKind::InvokeDynamic {
                     descriptor,
                     impl_method: Some(impl_name),
+                    ..
                 } = &instr.kind
                 {
                     let captured_slots = method_param_slots(descriptor).unwrap_or(0);
@@ -113,7 +120,7 @@ fn check_java_lambdas(class: &Class, artifact_uri: Option<&str>) -> Result<Vec<S
         // Find matching invokedynamic info: the method must be referenced as an
         // impl_method by an invokedynamic instruction (covers both Java `lambda$`
         // and Kotlin `$lambda-` naming patterns).
-        let Some(&captured_slots) = lambda_info.get(&method.name) else {
+        let Some(&captured_slots) = lambda_info.get(method.name.as_str()) else {
             continue;
         };
 
@@ -3,7 +3,8 @@ use opentelemetry::KeyValue;
 use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_NET_URL, Symbol};
 
 /// Rule that detects direct `URL.hashCode()` calls.
 #[derive(Default)]
@@ -17,13 +18,19 @@ impl Rule for UrlHashcodeCallRule {
             id: "URL_HASHCODE_CALL",
             name: "URL hashCode call",
             description: "URL.hashCode may trigger host resolution and surprising hash semantics",
+            tags: &["correctness", "performance"],
+            precision: RulePrecision::VeryHigh,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -32,8 +39,8 @@ impl Rule for UrlHashcodeCallRule {
                     let mut class_results = Vec::new();
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
-                        for call in &method.calls {
-                            if is_url_hashcode_call(&call.owner, &call.name, &call.descriptor) {
+                        for call in method.source_calls() {
+                            if is_url_hashcode_call(call.owner, &call.name, &call.descriptor) {
                                 let message = result_message(format!(
                                     "Avoid URL.hashCode() in {}.{}{}; hash normalized URI values or explicit URL components instead.",
                                     class.name, method.name, method.descriptor
@@ -63,8 +70,8 @@ impl Rule for UrlHashcodeCallRule {
     }
 }
 
-fn is_url_hashcode_call(owner: &str, name: &str, descriptor: &str) -> bool {
-    owner == "java/net/URL" && name == "hashCode" && descriptor == "()I"
+fn is_url_hashcode_call(owner: Symbol, name: &str, descriptor: &str) -> bool {
+    owner == JAVA_NET_URL && name == "hashCode" && descriptor == "()I"
 }
 
 #[cfg(test)]
@@ -4,7 +4,8 @@ use serde_sarif::sarif::Result as SarifResult;
 
 use crate::engine::AnalysisContext;
 use crate::ir::Method;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
+use crate::symbol::{JAVA_NET_URL, Symbol};
 
 /// Rule that detects direct URL.openStream calls.
 #[derive(Default)]
@@ -18,13 +19,19 @@ impl Rule for UrlOpenstreamCallRule {
             id: "URL_OPENSTREAM_CALL",
             name: "URL.openStream call",
             description: "URL.openStream can hide timeout and connection configuration",
+            tags: &["correctness"],
+            precision: RulePrecision::Medium,
+            security_severity: None,
+            cwe: &[],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -34,7 +41,7 @@ impl Rule for UrlOpenstreamCallRule {
                     let artifact_uri = context.class_artifact_uri(class);
                     for method in &class.methods {
                         for (call_index, call) in method.calls.iter().enumerate() {
-                            if is_url_openstream_call(&call.owner, &call.name, &call.descriptor) {
+                            if is_url_openstream_call(call.owner, &call.name, &call.descriptor) {
                                 if is_classpath_resource_openstream(method, call_index) {
                                     continue;
                                 }
@@ -67,8 +74,8 @@ impl Rule for UrlOpenstreamCallRule {
     }
 }
 
-fn is_url_openstream_call(owner: &str, name: &str, descriptor: &str) -> bool {
-    owner == "java/net/URL" && name == "openStream" && descriptor == "()Ljava/io/InputStream;"
+fn is_url_openstream_call(owner: Symbol, name: &str, descriptor: &str) -> bool {
+    owner == JAVA_NET_URL && name == "openStream" && descriptor == "()Ljava/io/InputStream;"
 }
 
 fn is_classpath_resource_openstream(method: &Method, openstream_index: usize) -> bool {
@@ -7,7 +7,8 @@ use serde_sarif::sarif::Result as SarifResult;
 use crate::engine::AnalysisContext;
 use crate::ir::{Class, FieldRef, Instruction, InstructionKind, Method};
 use crate::opcodes;
-use crate::rules::{Rule, RuleMetadata, method_location_with_line, result_message};
+use crate::symbol::Symbol;
+use crate::rules::{Rule, RuleMetadata, RulePrecision, method_location_with_line, result_message};
 
 /// Rule that detects non-atomic read-modify-write updates on volatile fields.
 #[derive(Default)]
@@ -18,9 +19,9 @@ crate::register_rule!(VolatileIncrementNonAtomicRule);
 /// Field identity used while matching volatile field update bytecode sequences.
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
 struct FieldKey {
-    owner: String,
-    name: String,
-    descriptor: String,
+    owner: Symbol,
+    name: Symbol,
+    descriptor: Symbol,
     is_static: bool,
 }
 
@@ -37,13 +38,19 @@ impl Rule for VolatileIncrementNonAtomicRule {
             id: "VOLATILE_INCREMENT_NON_ATOMIC",
             name: "Non-atomic update on volatile field",
             description: "Read-modify-write updates on volatile fields can lose concurrent updates",
+            tags: &["concurrency"],
+            precision: RulePrecision::High,
+            security_severity: None,
+            cwe: &[362],
+            requires: None,
+            options: &[],
         }
     }
 
     fn run(&self, context: &AnalysisContext) -> Result<Vec<SarifResult>> {
         let mut results = Vec::new();
         for class in context.analysis_target_classes() {
-            let mut attributes = vec![KeyValue::new("inspequte.class", class.name.clone())];
+            let mut attributes = vec![KeyValue::new("inspequte.class", class.name)];
             if let Some(uri) = context.class_artifact_uri(class) {
                 attributes.push(KeyValue::new("inspequte.artifact_uri", uri));
             }
@@ -92,9 +99,9 @@ fn volatile_fields(class: &Class) -> BTreeSet<FieldKey> {
         .iter()
         .filter(|field| field.access.is_volatile)
         .map(|field| FieldKey {
-            owner: class.name.clone(),
-            name: field.name.clone(),
-            descriptor: field.descriptor.clone(),
+            owner: class.name,
+            name: Symbol::intern(&field.name),
+            descriptor: Symbol::intern(&field.descriptor),
             is_static: field.access.is_static,
         })
         .collect()
@@ -127,7 +134,7 @@ fn find_non_atomic_update_sites(method: &Method, volatile_fields: &BTreeSet<Fiel
             .any(|candidate| read_field_key(candidate, volatile_fields) == Some(write_field.clone()));
         if has_matching_read && seen_offsets.insert(instruction.offset) {
             sites.push(UpdateSite {
-                field_name: write_field.name.clone(),
+                field_name: write_field.name.to_string(),
                 offset: instruction.offset,
             });
         }
@@ -171,9 +178,9 @@ fn instruction_field(instruction: &Instruction) -> Option<FieldKey> {
         return None;
     };
     Some(FieldKey {
-        owner: owner.clone(),
-        name: name.clone(),
-        descriptor: descriptor.clone(),
+        owner: *owner,
+        name: *name,
+        descriptor: *descriptor,
         is_static: instruction.opcode == opcodes::GETSTATIC || instruction.opcode == opcodes::PUTSTATIC,
     })
 }
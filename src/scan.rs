@@ -3,6 +3,7 @@ use std::fs;
 use std::io::{Cursor, Read, Seek};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
 
 use anyhow::{Context, Result};
 use jclassfile::class_file;
@@ -10,6 +11,7 @@ use jclassfile::constant_pool::ConstantPool;
 use jclassfile::fields::FieldFlags;
 use jclassfile::methods::MethodFlags;
 use jdescriptor::{MethodDescriptor, TypeDescriptor};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use serde_sarif::sarif::{Artifact, ArtifactLocation, ArtifactRoles};
 use zip::ZipArchive;
@@ -18,21 +20,25 @@ use opentelemetry::Context as OtelContext;
 use opentelemetry::KeyValue;
 use rayon::prelude::*;
 
-use crate::cfg::build_cfg;
+use crate::cache::{AnalysisCache, CacheKind, ContentHash, KeyHasher};
+use crate::cfg::{build_cfg, find_duplicated_code};
 use crate::descriptor::method_param_count;
 use crate::ir::{
-    AnnotationDefaultNumeric, AnnotationDefaultValue, CallKind, CallSite, Class, ClassTypeUse,
-    ExceptionHandler, Field, FieldAccess, FieldRef, Instruction, InstructionKind, LineNumber,
-    LocalVariable, LocalVariableType, Method, MethodAccess, MethodNullness, MethodTypeUse,
-    Nullness, TypeParameterUse, TypeUse, TypeUseKind,
+    AnnotationDefaultNumeric, AnnotationDefaultValue, CallKind, CallSite, Class, ClassFileLocation,
+    ClassTypeUse, ControlFlowGraph, ExceptionHandler, Field, FieldAccess, FieldRef, Instruction,
+    InstructionKind, LineNumber, LocalVariable, LocalVariableType, Method, MethodAccess,
+    MethodNullness, MethodTypeUse, Nullness, TypeParameterUse, TypeUse, TypeUseKind,
 };
 use crate::opcodes;
+use crate::symbol::Symbol;
 use crate::telemetry::Telemetry;
 
 /// Snapshot of parsed artifacts, classes, and counts for a scan.
 pub(crate) struct ScanOutput {
     pub(crate) artifacts: Vec<Artifact>,
     pub(crate) class_count: usize,
+    /// Classes parsed as headers only, a subset of `class_count`.
+    pub(crate) header_class_count: usize,
     pub(crate) classes: Vec<Class>,
 }
 
@@ -40,6 +46,7 @@ pub(crate) fn scan_inputs(
     input: &[PathBuf],
     classpath: &[PathBuf],
     telemetry: Option<&Telemetry>,
+    cache: Option<&AnalysisCache>,
 ) -> Result<ScanOutput> {
     // Keep deterministic ordering by sorting classpath entries and directory listings.
     let mut classpath_entries = classpath.to_vec();
@@ -80,11 +87,15 @@ pub(crate) fn scan_inputs(
             let mut artifacts = Vec::new();
             let mut class_count = 0;
             let mut classes = Vec::new();
+            let options = ScanOptions {
+                is_input: target.is_input,
+                telemetry,
+                cache,
+            };
             scan_path(
                 &target.path,
-                target.is_input,
                 true,
-                telemetry,
+                options,
                 &mut artifacts,
                 &mut class_count,
                 &mut classes,
@@ -94,6 +105,7 @@ pub(crate) fn scan_inputs(
                 ScanOutput {
                     artifacts,
                     class_count,
+                    header_class_count: if target.is_input { 0 } else { class_count },
                     classes,
                 },
             ))
@@ -104,6 +116,7 @@ pub(crate) fn scan_inputs(
 
     let mut artifacts = Vec::new();
     let mut class_count = 0;
+    let mut header_class_count = 0;
     let mut classes = Vec::new();
     for (_, mut output) in results {
         let offset = artifacts.len() as i64;
@@ -118,11 +131,13 @@ pub(crate) fn scan_inputs(
             classes.push(class);
         }
         class_count += output.class_count;
+        header_class_count += output.header_class_count;
     }
 
     Ok(ScanOutput {
         artifacts,
         class_count,
+        header_class_count,
         classes,
     })
 }
@@ -133,32 +148,53 @@ struct ScanTarget {
     is_input: bool,
 }
 
+/// Settings shared by every class read from one scan target.
+#[derive(Clone, Copy)]
+struct ScanOptions<'a> {
+    /// Whether the target is an `--input`, whose classes are analysis targets.
+    is_input: bool,
+    telemetry: Option<&'a Telemetry>,
+    cache: Option<&'a AnalysisCache>,
+}
+
+impl ScanOptions<'_> {
+    /// SARIF roles of the artifacts read from the target.
+    fn roles(&self) -> Option<Vec<Value>> {
+        self.is_input.then(|| {
+            vec![
+                serde_json::to_value(ArtifactRoles::AnalysisTarget)
+                    .expect("serialize artifact role"),
+            ]
+        })
+    }
+
+    fn depth(&self) -> ParseDepth {
+        if self.is_input {
+            ParseDepth::Full
+        } else {
+            ParseDepth::Header
+        }
+    }
+}
+
 fn scan_path(
     path: &Path,
-    is_input: bool,
     strict: bool,
-    telemetry: Option<&Telemetry>,
+    options: ScanOptions<'_>,
     artifacts: &mut Vec<Artifact>,
     class_count: &mut usize,
     classes: &mut Vec<Class>,
 ) -> Result<()> {
     if path.is_dir() {
-        scan_dir(path, is_input, telemetry, artifacts, class_count, classes)?;
+        scan_dir(path, options, artifacts, class_count, classes)?;
         return Ok(());
     }
 
     let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
-    let roles = if is_input {
-        Some(vec![
-            serde_json::to_value(ArtifactRoles::AnalysisTarget).expect("serialize artifact role"),
-        ])
-    } else {
-        None
-    };
 
     match extension {
-        "class" => scan_class_file(path, roles, telemetry, artifacts, class_count, classes),
-        "jar" => scan_jar_file(path, roles, telemetry, artifacts, class_count, classes),
+        "class" => scan_class_file(path, options, artifacts, class_count, classes),
+        "jar" => scan_jar_file(path, options, artifacts, class_count, classes),
         _ => {
             if strict {
                 anyhow::bail!("unsupported input file: {}", path.display())
@@ -171,8 +207,7 @@ fn scan_path(
 
 fn scan_dir(
     path: &Path,
-    is_input: bool,
-    telemetry: Option<&Telemetry>,
+    options: ScanOptions<'_>,
     artifacts: &mut Vec<Artifact>,
     class_count: &mut usize,
     classes: &mut Vec<Class>,
@@ -190,17 +225,9 @@ fn scan_dir(
 
     for entry in entries {
         if entry.is_dir() {
-            scan_dir(&entry, is_input, telemetry, artifacts, class_count, classes)?;
+            scan_dir(&entry, options, artifacts, class_count, classes)?;
         } else {
-            scan_path(
-                &entry,
-                is_input,
-                false,
-                telemetry,
-                artifacts,
-                class_count,
-                classes,
-            )?;
+            scan_path(&entry, false, options, artifacts, class_count, classes)?;
         }
     }
 
@@ -209,13 +236,13 @@ fn scan_dir(
 
 fn scan_class_file(
     path: &Path,
-    roles: Option<Vec<Value>>,
-    telemetry: Option<&Telemetry>,
+    options: ScanOptions<'_>,
     artifacts: &mut Vec<Artifact>,
     class_count: &mut usize,
     classes: &mut Vec<Class>,
 ) -> Result<()> {
-    let (data, parsed) = match telemetry {
+    let depth = options.depth();
+    let (data, parsed) = match options.telemetry {
         Some(telemetry) => {
             let span_attributes = [KeyValue::new(
                 "inspequte.class_path",
@@ -227,7 +254,7 @@ fn scan_class_file(
                 || -> Result<(Vec<u8>, ParsedClass)> {
                     let data = fs::read(path)
                         .with_context(|| format!("failed to read {}", path.display()))?;
-                    let parsed = parse_class_bytes(&data)
+                    let parsed = parse_class_bytes_cached(&data, depth, options.cache)
                         .with_context(|| format!("failed to parse {}", path.display()))?;
                     Ok((data, parsed))
                 },
@@ -236,15 +263,15 @@ fn scan_class_file(
         None => {
             let data =
                 fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
-            let parsed = parse_class_bytes(&data)
+            let parsed = parse_class_bytes_cached(&data, depth, options.cache)
                 .with_context(|| format!("failed to parse {}", path.display()))?;
             (data, parsed)
         }
     };
     *class_count += 1;
 
-    let artifact_index = if roles.is_some() {
-        push_path_artifact(path, roles, data.len() as u64, None, artifacts)?
+    let artifact_index = if options.is_input {
+        push_path_artifact(path, options.roles(), data.len() as u64, None, artifacts)?
     } else {
         -1
     };
@@ -260,14 +287,18 @@ fn scan_class_file(
         annotation_defaults: parsed.annotation_defaults,
         artifact_index,
         is_record: parsed.is_record,
+        is_generated: parsed.is_generated,
+        header_location: (depth == ParseDepth::Header).then(|| ClassFileLocation {
+            file: Arc::from(path),
+            entries: Vec::new(),
+        }),
     });
     Ok(())
 }
 
 fn scan_jar_file(
     path: &Path,
-    roles: Option<Vec<Value>>,
-    telemetry: Option<&Telemetry>,
+    options: ScanOptions<'_>,
     artifacts: &mut Vec<Artifact>,
     class_count: &mut usize,
     classes: &mut Vec<Class>,
@@ -276,26 +307,18 @@ fn scan_jar_file(
         "inspequte.jar_path",
         path.display().to_string(),
     )];
-    let result = match telemetry {
+    let result = match options.telemetry {
         Some(telemetry) => telemetry.in_span("scan.jar", &jar_span_attributes, || {
-            scan_jar_file_inner(
-                path,
-                roles,
-                Some(telemetry),
-                artifacts,
-                class_count,
-                classes,
-            )
+            scan_jar_file_inner(path, options, artifacts, class_count, classes)
         }),
-        None => scan_jar_file_inner(path, roles, None, artifacts, class_count, classes),
+        None => scan_jar_file_inner(path, options, artifacts, class_count, classes),
     };
     result
 }
 
 fn scan_jar_file_inner(
     path: &Path,
-    roles: Option<Vec<Value>>,
-    telemetry: Option<&Telemetry>,
+    options: ScanOptions<'_>,
     artifacts: &mut Vec<Artifact>,
     class_count: &mut usize,
     classes: &mut Vec<Class>,
@@ -310,17 +333,22 @@ fn scan_jar_file_inner(
     let jar_len = fs::metadata(path)
         .with_context(|| format!("failed to read {}", path.display()))?
         .len();
-    let jar_index = push_path_artifact(path, roles, jar_len, None, artifacts)?;
+    let jar_index = push_path_artifact(path, options.roles(), jar_len, None, artifacts)?;
     let jar_uri = path_to_uri(path);
+    let jar_location = ClassFileLocation {
+        file: Arc::from(path),
+        entries: Vec::new(),
+    };
     let entries = jar_entries(&jar_path, &mut archive)?;
     let class_entry_bytes =
         read_jar_entries_bytes(&mut archive, &entries.class_entries, &jar_path)?;
     parse_jar_classes(
         &jar_path,
         &jar_path,
+        &jar_location,
         class_entry_bytes,
         jar_index,
-        telemetry,
+        options,
         Some(&parent_cx),
         class_count,
         classes,
@@ -329,9 +357,10 @@ fn scan_jar_file_inner(
         &mut archive,
         &jar_path,
         &jar_uri,
+        &jar_location,
         jar_index,
         entries.jar_entries,
-        telemetry,
+        options,
         &parent_cx,
         artifacts,
         class_count,
@@ -404,23 +433,25 @@ fn read_jar_entries_bytes<R: Read + Seek>(
 fn parse_jar_classes(
     jar_display: &str,
     jar_path_attribute: &str,
+    jar_location: &ClassFileLocation,
     entries: Vec<(String, Vec<u8>)>,
     jar_index: i64,
-    telemetry: Option<&Telemetry>,
+    options: ScanOptions<'_>,
     parent_cx: Option<&OtelContext>,
     class_count: &mut usize,
     classes: &mut Vec<Class>,
 ) -> Result<()> {
+    let depth = options.depth();
     let mut parsed = entries
         .par_iter()
-        .map(|(name, data)| match telemetry {
+        .map(|(name, data)| match options.telemetry {
             Some(telemetry) => {
                 let class_span_attributes = [
                     KeyValue::new("inspequte.jar_path", jar_path_attribute.to_string()),
                     KeyValue::new("inspequte.jar_entry", name.clone()),
                 ];
                 let parse = || {
-                    parse_class_bytes(data)
+                    parse_class_bytes_cached(data, depth, options.cache)
                         .with_context(|| format!("failed to parse {}:{}", jar_display, name))
                 };
                 match parent_cx {
@@ -432,7 +463,7 @@ fn parse_jar_classes(
                         .map(|parsed| (name.clone(), parsed)),
                 }
             }
-            None => parse_class_bytes(data)
+            None => parse_class_bytes_cached(data, depth, options.cache)
                 .with_context(|| format!("failed to parse {}:{}", jar_display, name))
                 .map(|parsed| (name.clone(), parsed)),
         })
@@ -443,7 +474,12 @@ fn parse_jar_classes(
     parsed.sort_by(|a, b| a.0.cmp(&b.0));
     *class_count += parsed.len();
 
-    for (_, parsed) in parsed {
+    for (name, parsed) in parsed {
+        let header_location = (depth == ParseDepth::Header).then(|| {
+            let mut location = jar_location.clone();
+            location.entries.push(name);
+            location
+        });
         classes.push(Class {
             name: parsed.name,
             source_file: parsed.source_file,
@@ -456,6 +492,8 @@ fn parse_jar_classes(
             annotation_defaults: parsed.annotation_defaults,
             artifact_index: jar_index,
             is_record: parsed.is_record,
+            is_generated: parsed.is_generated,
+            header_location,
         });
     }
 
@@ -466,9 +504,10 @@ fn scan_nested_jars(
     archive: &mut ZipArchive<fs::File>,
     jar_display: &str,
     jar_uri: &str,
+    jar_location: &ClassFileLocation,
     parent_index: i64,
     jar_entries: Vec<String>,
-    telemetry: Option<&Telemetry>,
+    options: ScanOptions<'_>,
     parent_cx: &OtelContext,
     artifacts: &mut Vec<Artifact>,
     class_count: &mut usize,
@@ -492,8 +531,9 @@ fn scan_nested_jars(
             &jar_bytes,
             jar_display,
             jar_uri,
+            jar_location,
             parent_index,
-            telemetry,
+            options,
             parent_cx,
             artifacts,
             class_count,
@@ -514,8 +554,9 @@ fn scan_nested_jar_entry(
     jar_bytes: &[u8],
     parent_jar_display: &str,
     parent_jar_uri: &str,
+    parent_jar_location: &ClassFileLocation,
     parent_index: i64,
-    telemetry: Option<&Telemetry>,
+    options: ScanOptions<'_>,
     parent_cx: &OtelContext,
     artifacts: &mut Vec<Artifact>,
     class_count: &mut usize,
@@ -523,6 +564,8 @@ fn scan_nested_jar_entry(
 ) -> Result<Vec<String>> {
     let jar_display = format!("{parent_jar_display}!/{entry_name}");
     let jar_uri = jar_entry_uri(parent_jar_uri, entry_name);
+    let mut jar_location = parent_jar_location.clone();
+    jar_location.entries.push(entry_name.to_string());
     let jar_len = jar_bytes.len() as u64;
     let jar_index = push_artifact(
         jar_uri.clone(),
@@ -540,9 +583,10 @@ fn scan_nested_jar_entry(
     parse_jar_classes(
         &jar_display,
         &jar_uri,
+        &jar_location,
         class_entry_bytes,
         jar_index,
-        telemetry,
+        options,
         Some(parent_cx),
         class_count,
         classes,
@@ -555,8 +599,8 @@ fn scan_nested_jar_entry(
         .collect())
 }
 
-fn read_jar_entry_bytes(
-    archive: &mut ZipArchive<fs::File>,
+fn read_jar_entry_bytes<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
     jar_display: &str,
     entry_name: &str,
 ) -> Result<Vec<u8>> {
@@ -788,20 +832,83 @@ fn is_jar_path(path: &Path) -> bool {
 }
 
 /// Parsed class data extracted from class file bytes.
+#[derive(Serialize, Deserialize)]
 struct ParsedClass {
-    name: String,
+    name: Symbol,
     source_file: Option<String>,
-    super_name: Option<String>,
-    interfaces: Vec<String>,
+    super_name: Option<Symbol>,
+    interfaces: Vec<Symbol>,
     type_parameters: Vec<TypeParameterUse>,
-    referenced_classes: Vec<String>,
+    referenced_classes: Vec<Symbol>,
     fields: Vec<crate::ir::Field>,
     methods: Vec<Method>,
     annotation_defaults: Vec<AnnotationDefaultValue>,
     is_record: bool,
+    is_generated: bool,
 }
 
-fn parse_class_bytes(data: &[u8]) -> Result<ParsedClass> {
+/// How much of a class file to parse.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ParseDepth {
+    /// Everything, including bytecode, control flow graphs and call sites of method bodies.
+    Full,
+    /// Supertypes, member signatures and annotations, leaving method bodies empty. Rules only
+    /// analyze the bodies of analysis target classes, so dependency classes stop here.
+    Header,
+}
+
+/// Parse class bytes, reusing the IR cached for identical bytes when a cache is given.
+fn parse_class_bytes_cached(
+    data: &[u8],
+    depth: ParseDepth,
+    cache: Option<&AnalysisCache>,
+) -> Result<ParsedClass> {
+    let Some(cache) = cache else {
+        return parse_class_bytes(data, depth);
+    };
+    let hash = ContentHash::of(data);
+    let mut key = KeyHasher::new(CacheKind::Class);
+    key.write_hash(hash);
+    key.write_u64(u64::from(depth == ParseDepth::Header));
+    let key = key.finish();
+    let parsed = match cache.load(CacheKind::Class, key) {
+        Some(parsed) => parsed,
+        None => {
+            let parsed = parse_class_bytes(data, depth)?;
+            cache.store(CacheKind::Class, key, &parsed);
+            parsed
+        }
+    };
+    cache.record_class(&parsed.name, hash);
+    Ok(parsed)
+}
+
+/// Re-read the class file at `location` and parse its methods, bodies included.
+pub(crate) fn read_method_bodies(location: &ClassFileLocation) -> Result<Vec<Method>> {
+    let mut display = location.file.display().to_string();
+    let mut data = match location.entries.first() {
+        Some(entry) => {
+            let file = fs::File::open(&location.file)
+                .with_context(|| format!("failed to open {display}"))?;
+            let mut archive =
+                ZipArchive::new(file).with_context(|| format!("failed to read {display}"))?;
+            read_jar_entry_bytes(&mut archive, &display, entry)?
+        }
+        None => fs::read(&location.file).with_context(|| format!("failed to read {display}"))?,
+    };
+    // Nested JARs are read from their parent's bytes.
+    for (parent, entry) in location.entries.iter().zip(location.entries.iter().skip(1)) {
+        display = format!("{display}!/{parent}");
+        let mut archive = ZipArchive::new(Cursor::new(data))
+            .with_context(|| format!("failed to read {display}"))?;
+        data = read_jar_entry_bytes(&mut archive, &display, entry)?;
+    }
+    Ok(parse_class_bytes(&data, ParseDepth::Full)
+        .with_context(|| format!("failed to parse {location}"))?
+        .methods)
+}
+
+fn parse_class_bytes(data: &[u8], depth: ParseDepth) -> Result<ParsedClass> {
     let class_file = match class_file::parse(data) {
         Ok(parsed) => parsed,
         Err(err) => {
@@ -813,31 +920,32 @@ fn parse_class_bytes(data: &[u8]) -> Result<ParsedClass> {
         }
     };
     let constant_pool = class_file.constant_pool();
-    let class_name =
-        resolve_class_name(constant_pool, class_file.this_class()).context("resolve class name")?;
+    let class_name = resolve_class_symbol(constant_pool, class_file.this_class())
+        .context("resolve class name")?;
     let source_file =
         parse_source_file(class_file.attributes(), constant_pool).context("parse source file")?;
     let super_name = if class_file.super_class() == 0 {
         None
     } else {
         Some(
-            resolve_class_name(constant_pool, class_file.super_class())
+            resolve_class_symbol(constant_pool, class_file.super_class())
                 .context("resolve super class name")?,
         )
     };
     let mut interfaces = Vec::new();
     for interface in class_file.interfaces() {
-        interfaces
-            .push(resolve_class_name(constant_pool, *interface).context("resolve interface name")?);
+        interfaces.push(
+            resolve_class_symbol(constant_pool, *interface).context("resolve interface name")?,
+        );
     }
 
     let mut referenced = std::collections::BTreeSet::new();
     for entry in constant_pool {
         if let ConstantPool::Class { name_index } = entry {
-            let name = resolve_utf8(constant_pool, *name_index)
+            let name = resolve_utf8_str(constant_pool, *name_index)
                 .context("resolve referenced class name")?;
-            if let Some(normalized) = normalize_class_name(&name) {
-                referenced.insert(normalized);
+            if let Some(normalized) = normalize_class_name(name) {
+                referenced.insert(Symbol::from(normalized));
             }
         }
     }
@@ -847,6 +955,8 @@ fn parse_class_bytes(data: &[u8]) -> Result<ParsedClass> {
         .attributes()
         .iter()
         .any(|attr| matches!(attr, jclassfile::attributes::Attribute::Record { .. }));
+    let is_generated = parse_is_generated(class_file.attributes(), constant_pool)
+        .context("parse generated annotation")?;
     let default_nullness = parse_default_nullness(class_file.attributes(), constant_pool)
         .context("parse class nullness")?;
     let class_signature =
@@ -868,9 +978,11 @@ fn parse_class_bytes(data: &[u8]) -> Result<ParsedClass> {
         .context("parse fields")?;
     let methods = parse_methods(
         constant_pool,
+        class_name,
         class_file.methods(),
         default_nullness,
         &bootstrap_methods,
+        depth,
     )
     .context("parse method bytecode")?;
     let annotation_defaults = parse_annotation_defaults(constant_pool, class_file.methods())
@@ -887,6 +999,7 @@ fn parse_class_bytes(data: &[u8]) -> Result<ParsedClass> {
         methods,
         annotation_defaults,
         is_record,
+        is_generated,
     })
 }
 
@@ -901,11 +1014,30 @@ fn resolve_class_name(constant_pool: &[ConstantPool], class_index: u16) -> Resul
 }
 
 fn resolve_utf8(constant_pool: &[ConstantPool], index: u16) -> Result<String> {
+    resolve_utf8_str(constant_pool, index).map(str::to_string)
+}
+
+/// Like `resolve_class_name`, interning the name without an intermediate copy.
+fn resolve_class_symbol(constant_pool: &[ConstantPool], class_index: u16) -> Result<Symbol> {
+    let entry = constant_pool
+        .get(class_index as usize)
+        .context("missing class entry")?;
+    match entry {
+        ConstantPool::Class { name_index } => resolve_symbol(constant_pool, *name_index),
+        _ => anyhow::bail!("unexpected class entry"),
+    }
+}
+
+fn resolve_symbol(constant_pool: &[ConstantPool], index: u16) -> Result<Symbol> {
+    resolve_utf8_str(constant_pool, index).map(Symbol::intern)
+}
+
+fn resolve_utf8_str(constant_pool: &[ConstantPool], index: u16) -> Result<&str> {
     let entry = constant_pool
         .get(index as usize)
         .context("missing utf8 entry")?;
     match entry {
-        ConstantPool::Utf8 { value } => Ok(value.clone()),
+        ConstantPool::Utf8 { value } => Ok(value),
         _ => anyhow::bail!("unexpected utf8 entry"),
     }
 }
@@ -966,16 +1098,17 @@ fn parse_class_bytes_minimal(data: &[u8]) -> Result<ParsedClass> {
     referenced.remove(&class_name);
 
     Ok(ParsedClass {
-        name: class_name,
+        name: class_name.into(),
         source_file: None,
-        super_name,
-        interfaces,
+        super_name: super_name.map(Symbol::from),
+        interfaces: interfaces.into_iter().map(Symbol::from).collect(),
         type_parameters: Vec::new(),
-        referenced_classes: referenced.into_iter().collect(),
+        referenced_classes: referenced.into_iter().map(Symbol::from).collect(),
         fields: Vec::new(),
         methods: Vec::new(),
         annotation_defaults: Vec::new(),
         is_record: false,
+        is_generated: false,
     })
 }
 
@@ -1172,15 +1305,17 @@ fn parse_fields(
 
 fn parse_methods(
     constant_pool: &[ConstantPool],
+    owner: Symbol,
     methods: &[jclassfile::methods::MethodInfo],
     default_nullness: DefaultNullness,
     bootstrap_methods: &[&jclassfile::attributes::BootstrapMethodRecord],
+    depth: ParseDepth,
 ) -> Result<Vec<Method>> {
     let mut parsed = Vec::new();
     for method in methods {
         let name =
-            resolve_utf8(constant_pool, method.name_index()).context("resolve method name")?;
-        let descriptor = resolve_utf8(constant_pool, method.descriptor_index())
+            resolve_symbol(constant_pool, method.name_index()).context("resolve method name")?;
+        let descriptor = resolve_symbol(constant_pool, method.descriptor_index())
             .context("resolve method descriptor")?;
         let signature = parse_signature(method.attributes(), constant_pool)
             .context("parse method signature")?;
@@ -1192,6 +1327,8 @@ fn parse_methods(
             is_abstract: access_flags.contains(MethodFlags::ACC_ABSTRACT),
             is_synthetic: access_flags.contains(MethodFlags::ACC_SYNTHETIC),
             is_bridge: access_flags.contains(MethodFlags::ACC_BRIDGE),
+            is_private: access_flags.contains(MethodFlags::ACC_PRIVATE),
+            is_final: access_flags.contains(MethodFlags::ACC_FINAL),
         };
         let nullness = parse_method_nullness(
             constant_pool,
@@ -1223,6 +1360,27 @@ fn parse_methods(
         let Some((code, exception_table, code_attributes)) = code else {
             continue;
         };
+        if depth == ParseDepth::Header {
+            parsed.push(Method {
+                owner,
+                name,
+                descriptor,
+                signature,
+                access,
+                nullness,
+                type_use,
+                bytecode: Vec::new(),
+                line_numbers: Vec::new(),
+                cfg: ControlFlowGraph::default(),
+                calls: Vec::new(),
+                string_literals: Vec::new(),
+                exception_handlers: Vec::new(),
+                local_variables: Vec::new(),
+                local_variable_types: Vec::new(),
+                flow_structure: OnceLock::new(),
+            });
+            continue;
+        }
         let line_numbers =
             parse_line_numbers(code_attributes, constant_pool).context("parse line numbers")?;
         let (instructions, calls, string_literals) =
@@ -1238,9 +1396,17 @@ fn parse_methods(
             .iter()
             .map(|handler| handler.handler_pc)
             .collect::<Vec<_>>();
-        let cfg =
+        let mut cfg =
             build_cfg(code, &instructions, &handler_offsets).context("build control flow graph")?;
+        cfg.duplicates = find_duplicated_code(
+            &cfg,
+            code,
+            &instructions,
+            &exception_handlers,
+            &line_numbers,
+        );
         parsed.push(Method {
+            owner,
             name,
             descriptor,
             signature,
@@ -1255,6 +1421,7 @@ fn parse_methods(
             exception_handlers,
             local_variables,
             local_variable_types,
+            flow_structure: OnceLock::new(),
         });
     }
     Ok(parsed)
@@ -1417,6 +1584,36 @@ fn parse_default_nullness(
     Ok(DefaultNullness::Inherit)
 }
 
+/// Whether the class carries a `@Generated`-style annotation.
+///
+/// Follows the JaCoCo convention: any class-level annotation with `CLASS` or `RUNTIME`
+/// retention whose simple name contains `Generated` (`javax.annotation.processing.Generated`
+/// has `SOURCE` retention and never reaches the class file).
+fn parse_is_generated(
+    attributes: &[jclassfile::attributes::Attribute],
+    constant_pool: &[ConstantPool],
+) -> Result<bool> {
+    for attribute in attributes {
+        let annotations = match attribute {
+            jclassfile::attributes::Attribute::RuntimeVisibleAnnotations {
+                annotations, ..
+            }
+            | jclassfile::attributes::Attribute::RuntimeInvisibleAnnotations { annotations } => {
+                annotations
+            }
+            _ => continue,
+        };
+        for annotation in annotations {
+            let name = annotation_class_name(constant_pool, annotation)?;
+            let simple_name = name.rsplit(['/', '$']).next().unwrap_or(&name);
+            if simple_name.contains("Generated") {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
 fn parse_method_nullness(
     constant_pool: &[ConstantPool],
     attributes: &[jclassfile::attributes::Attribute],
@@ -2263,6 +2460,17 @@ fn parse_bytecode(
                     InstructionKind::Other(opcode)
                 }
             }
+            opcodes::NEW
+            | opcodes::ANEWARRAY
+            | opcodes::CHECKCAST
+            | opcodes::INSTANCEOF
+            | opcodes::MULTIANEWARRAY => {
+                let index = read_u16(code, offset + 1)?;
+                match resolve_class_literal(constant_pool, index)? {
+                    Some(name) => InstructionKind::TypeRef(name),
+                    None => InstructionKind::Other(opcode),
+                }
+            }
             opcodes::INVOKEDYNAMIC => {
                 let call_site_index = read_u16(code, offset + 1)?;
                 let descriptor = resolve_invoke_dynamic_descriptor(constant_pool, call_site_index)
@@ -2272,9 +2480,12 @@ fn parse_bytecode(
                     call_site_index,
                     bootstrap_methods,
                 );
+                let concat_recipe =
+                    resolve_string_concat_recipe(constant_pool, call_site_index, bootstrap_methods);
                 InstructionKind::InvokeDynamic {
                     descriptor,
                     impl_method,
+                    concat_recipe,
                 }
             }
             _ => InstructionKind::Other(opcode),
@@ -2292,16 +2503,16 @@ fn parse_bytecode(
 
 /// Resolved constant pool method reference.
 struct MethodRef {
-    owner: String,
-    name: String,
-    descriptor: String,
+    owner: Symbol,
+    name: Symbol,
+    descriptor: Symbol,
 }
 
 /// Resolved constant pool field reference.
 struct ResolvedFieldRef {
-    owner: String,
-    name: String,
-    descriptor: String,
+    owner: Symbol,
+    name: Symbol,
+    descriptor: Symbol,
 }
 
 fn resolve_field_ref(constant_pool: &[ConstantPool], index: u16) -> Result<ResolvedFieldRef> {
@@ -2315,11 +2526,11 @@ fn resolve_field_ref(constant_pool: &[ConstantPool], index: u16) -> Result<Resol
         } => (*class_index, *name_and_type_index),
         _ => anyhow::bail!("unexpected field ref entry"),
     };
-    let owner = resolve_class_name(constant_pool, class_index).context("resolve owner")?;
+    let owner = resolve_class_symbol(constant_pool, class_index).context("resolve owner")?;
     let (name_index, descriptor_index) = resolve_name_and_type(constant_pool, name_and_type_index)?;
-    let name = resolve_utf8(constant_pool, name_index).context("resolve field name")?;
+    let name = resolve_symbol(constant_pool, name_index).context("resolve field name")?;
     let descriptor =
-        resolve_utf8(constant_pool, descriptor_index).context("resolve field descriptor")?;
+        resolve_symbol(constant_pool, descriptor_index).context("resolve field descriptor")?;
     Ok(ResolvedFieldRef {
         owner,
         name,
@@ -2342,11 +2553,11 @@ fn resolve_method_ref(constant_pool: &[ConstantPool], index: u16) -> Result<Meth
         } => (*class_index, *name_and_type_index),
         _ => anyhow::bail!("unexpected method ref entry"),
     };
-    let owner = resolve_class_name(constant_pool, class_index).context("resolve owner")?;
+    let owner = resolve_class_symbol(constant_pool, class_index).context("resolve owner")?;
     let (name_index, descriptor_index) = resolve_name_and_type(constant_pool, name_and_type_index)?;
-    let name = resolve_utf8(constant_pool, name_index).context("resolve method name")?;
+    let name = resolve_symbol(constant_pool, name_index).context("resolve method name")?;
     let descriptor =
-        resolve_utf8(constant_pool, descriptor_index).context("resolve method descriptor")?;
+        resolve_symbol(constant_pool, descriptor_index).context("resolve method descriptor")?;
     Ok(MethodRef {
         owner,
         name,
@@ -2410,6 +2621,62 @@ fn resolve_invoke_dynamic_impl_method(
     resolve_utf8(constant_pool, name_index).ok()
 }
 
+/// Resolve the recipe of a `StringConcatFactory.makeConcatWithConstants` call site.
+///
+/// Constants referenced by `\u{2}` tags are substituted into the recipe; returns `None`
+/// for other bootstrap methods and for constants that cannot be rendered as Java would.
+fn resolve_string_concat_recipe(
+    constant_pool: &[ConstantPool],
+    call_site_index: u16,
+    bootstrap_methods: &[&jclassfile::attributes::BootstrapMethodRecord],
+) -> Option<String> {
+    let bsm_index = match constant_pool.get(call_site_index as usize)? {
+        ConstantPool::InvokeDynamic {
+            bootstrap_method_attr_index,
+            ..
+        } => *bootstrap_method_attr_index,
+        _ => return None,
+    };
+    let bsm = bootstrap_methods.get(bsm_index as usize)?;
+    let reference_index = match constant_pool.get(bsm.bootstrap_method_ref() as usize)? {
+        ConstantPool::MethodHandle {
+            reference_index, ..
+        } => *reference_index,
+        _ => return None,
+    };
+    let (class_index, name_and_type_index) = match constant_pool.get(reference_index as usize)? {
+        ConstantPool::Methodref {
+            class_index,
+            name_and_type_index,
+        } => (*class_index, *name_and_type_index),
+        _ => return None,
+    };
+    let (name_index, _) = resolve_name_and_type(constant_pool, name_and_type_index).ok()?;
+    if resolve_class_name(constant_pool, class_index).ok()?
+        != "java/lang/invoke/StringConcatFactory"
+        || resolve_utf8(constant_pool, name_index).ok()? != "makeConcatWithConstants"
+    {
+        return None;
+    }
+    let (recipe_index, constant_indexes) = bsm.bootstrap_arguments().split_first()?;
+    let recipe = resolve_string_literal(constant_pool, *recipe_index).ok()??;
+    let mut constants = constant_indexes.iter();
+    let mut resolved = String::with_capacity(recipe.len());
+    for ch in recipe.chars() {
+        if ch != '\u{2}' {
+            resolved.push(ch);
+            continue;
+        }
+        let index = *constants.next()?;
+        match constant_pool.get(index as usize)? {
+            ConstantPool::Integer { value } => resolved.push_str(&value.to_string()),
+            ConstantPool::Long { value } => resolved.push_str(&value.to_string()),
+            _ => resolved.push_str(&resolve_string_literal(constant_pool, index).ok()??),
+        }
+    }
+    Some(resolved)
+}
+
 fn resolve_name_and_type(constant_pool: &[ConstantPool], index: u16) -> Result<(u16, u16)> {
     let entry = constant_pool
         .get(index as usize)
@@ -2641,6 +2908,7 @@ fn parse_exception_handlers(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::BTreeMap;
     use std::io::Cursor;
     use std::io::Read;
     use std::io::Write;
@@ -2649,6 +2917,8 @@ mod tests {
     use zip::ZipArchive;
     use zip::write::SimpleFileOptions;
 
+    use crate::test_harness::{JvmTestHarness, Language, SourceFile};
+
     #[test]
     fn scan_inputs_rejects_invalid_class_file() {
         let temp_dir = std::env::temp_dir().join(format!(
@@ -2662,16 +2932,203 @@ mod tests {
         let class_path = temp_dir.join("bad.class");
         fs::write(&class_path, b"nope").expect("write test class");
 
-        let result = scan_inputs(&[class_path.clone()], &[], None);
+        let result = scan_inputs(std::slice::from_ref(&class_path), &[], None, None);
 
         assert!(result.is_err());
         fs::remove_dir_all(&temp_dir).expect("cleanup temp dir");
     }
 
+    #[test]
+    fn scan_inputs_reuses_cached_class_ir() {
+        let temp_dir = std::env::temp_dir().join(format!(
+            "inspequte-test-{}",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("time")
+                .as_nanos()
+        ));
+        fs::create_dir_all(&temp_dir).expect("create temp dir");
+        let class_path = temp_dir.join("FakeClass.class");
+        fs::write(&class_path, build_class_with_unknown_attribute()).expect("write test class");
+        let scan = || {
+            let cache = AnalysisCache::open(&temp_dir.join("cache")).expect("open cache");
+            let output = scan_inputs(std::slice::from_ref(&class_path), &[], None, Some(&cache))
+                .expect("scan class");
+            (output, cache)
+        };
+
+        let (cold, cold_cache) = scan();
+        let (warm, warm_cache) = scan();
+
+        assert_eq!(cold_cache.stats().class_misses, 1);
+        assert_eq!(warm_cache.stats().class_hits, 1);
+        assert_eq!(warm.classes.len(), 1);
+        assert_eq!(warm.classes[0].name, cold.classes[0].name);
+        assert_eq!(warm.classes[0].super_name, cold.classes[0].super_name);
+        assert_eq!(
+            warm_cache.class_hashes(),
+            BTreeMap::from([(
+                "com/example/FakeClass".to_string(),
+                ContentHash::of(&build_class_with_unknown_attribute())
+            )])
+        );
+        fs::remove_dir_all(&temp_dir).expect("cleanup temp dir");
+    }
+
+    #[test]
+    fn scan_inputs_parses_classpath_classes_as_headers() {
+        let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
+        let library = harness
+            .compile(
+                Language::Java,
+                &[SourceFile {
+                    path: "com/example/Library.java".to_string(),
+                    contents: r#"
+package com.example;
+
+public class Library {
+    public String greet(String name) {
+        return name.trim();
+    }
+}
+"#
+                    .to_string(),
+                }],
+                &[],
+            )
+            .expect("compile library");
+        let classpath = vec![library.classes_dir().to_path_buf()];
+        let app = harness
+            .compile(
+                Language::Java,
+                &[SourceFile {
+                    path: "com/example/App.java".to_string(),
+                    contents: r#"
+package com.example;
+
+public class App {
+    public String run(Library library) {
+        return library.greet("app");
+    }
+}
+"#
+                    .to_string(),
+                }],
+                &classpath,
+            )
+            .expect("compile app");
+
+        let result = scan_inputs(&[app.classes_dir().to_path_buf()], &classpath, None, None)
+            .expect("scan classes");
+
+        assert_eq!(result.class_count, 2);
+        assert_eq!(result.header_class_count, 1);
+        let class = |name: &str| {
+            result
+                .classes
+                .iter()
+                .find(|class| class.name == name)
+                .expect("scanned class")
+        };
+        let app_class = class("com/example/App");
+        let run = app_class
+            .methods
+            .iter()
+            .find(|method| method.name == "run")
+            .expect("run method");
+        assert!(!run.calls.is_empty());
+
+        let library_class = class("com/example/Library");
+        let greet = library_class
+            .methods
+            .iter()
+            .find(|method| method.name == "greet")
+            .expect("greet method");
+        assert_eq!(greet.descriptor, "(Ljava/lang/String;)Ljava/lang/String;");
+        assert!(greet.bytecode.is_empty());
+        assert!(greet.cfg.blocks.is_empty());
+        assert!(greet.calls.is_empty());
+        assert!(app_class.header_location.is_none());
+
+        let context = crate::engine::build_context(result.classes.clone(), &result.artifacts);
+        let bodies = context
+            .method_bodies(library_class)
+            .expect("parse method bodies");
+        let greet = bodies
+            .iter()
+            .find(|method| method.name == "greet")
+            .expect("greet body");
+        assert!(!greet.bytecode.is_empty());
+        assert!(greet.calls.iter().any(|call| call.name == "trim"));
+    }
+
+    #[test]
+    fn read_method_bodies_rereads_classes_of_nested_classpath_jars() {
+        let harness = JvmTestHarness::new().expect("JAVA_HOME must be set for harness tests");
+        let library = harness
+            .compile(
+                Language::Java,
+                &[SourceFile {
+                    path: "com/example/Library.java".to_string(),
+                    contents: r#"
+package com.example;
+
+public class Library {
+    public String greet(String name) {
+        return name.trim();
+    }
+}
+"#
+                    .to_string(),
+                }],
+                &[],
+            )
+            .expect("compile library");
+        let class_bytes = fs::read(library.classes_dir().join("com/example/Library.class"))
+            .expect("read library class");
+        let inner_jar = build_jar_bytes_with_class(None, "com/example/Library.class", &class_bytes)
+            .expect("build inner jar");
+        let temp_dir = std::env::temp_dir().join(format!(
+            "inspequte-test-{}",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("time")
+                .as_nanos()
+        ));
+        fs::create_dir_all(&temp_dir).expect("create temp dir");
+        let outer_path = temp_dir.join("outer.jar");
+        create_outer_jar_with_entries(&outer_path, &[("lib/inner.jar", inner_jar)])
+            .expect("create outer jar");
+
+        let result = scan_inputs(&[], std::slice::from_ref(&outer_path), None, None)
+            .expect("scan outer jar");
+
+        assert_eq!(result.header_class_count, 1);
+        let location = result.classes[0]
+            .header_location
+            .as_ref()
+            .expect("header location");
+        assert_eq!(
+            location.entries,
+            vec![
+                "lib/inner.jar".to_string(),
+                "com/example/Library.class".to_string()
+            ]
+        );
+        let bodies = read_method_bodies(location).expect("parse method bodies");
+        let greet = bodies
+            .iter()
+            .find(|method| method.name == "greet")
+            .expect("greet body");
+        assert!(greet.calls.iter().any(|call| call.name == "trim"));
+        fs::remove_dir_all(&temp_dir).expect("cleanup temp dir");
+    }
+
     #[test]
     fn scan_inputs_accepts_valid_jar() {
         let jar_path = jspecify_jar_path().expect("download jar");
-        let result = scan_inputs(&[jar_path.clone()], &[], None).expect("scan jar");
+        let result =
+            scan_inputs(std::slice::from_ref(&jar_path), &[], None, None).expect("scan jar");
 
         assert!(result.class_count > 0);
         assert_eq!(result.artifacts.len(), 1);
@@ -2701,7 +3158,8 @@ mod tests {
         let class_path = temp_dir.join("Sample.class");
         fs::write(&class_path, class_bytes).expect("write class file");
 
-        let result = scan_inputs(&[class_path.clone()], &[], None).expect("scan class");
+        let result =
+            scan_inputs(std::slice::from_ref(&class_path), &[], None, None).expect("scan class");
 
         assert_eq!(result.class_count, 1);
         assert_eq!(result.artifacts.len(), 1);
@@ -2724,7 +3182,8 @@ mod tests {
         let class_path = temp_dir.join("Sample.class");
         fs::write(&class_path, class_bytes).expect("write class file");
 
-        let result = scan_inputs(&[temp_dir.clone()], &[], None).expect("scan directory");
+        let result =
+            scan_inputs(std::slice::from_ref(&temp_dir), &[], None, None).expect("scan directory");
 
         assert_eq!(result.class_count, 1);
         assert_eq!(result.artifacts.len(), 1);
@@ -2758,7 +3217,7 @@ mod tests {
         let jar_path = temp_dir.join("main.jar");
         create_manifest_jar(&jar_path, Some("dep.jar")).expect("create main jar");
 
-        let result = scan_inputs(&[jar_path.clone()], &[], None);
+        let result = scan_inputs(std::slice::from_ref(&jar_path), &[], None, None);
 
         assert!(result.is_ok());
         fs::remove_dir_all(&temp_dir).expect("cleanup temp dir");
@@ -2778,7 +3237,7 @@ mod tests {
         let jar_path = temp_dir.join("main.jar");
         create_manifest_jar(&jar_path, Some("missing.jar")).expect("create main jar");
 
-        let result = scan_inputs(&[jar_path.clone()], &[], None);
+        let result = scan_inputs(std::slice::from_ref(&jar_path), &[], None, None);
 
         assert!(result.is_err());
         fs::remove_dir_all(&temp_dir).expect("cleanup temp dir");
@@ -2813,7 +3272,8 @@ mod tests {
         )
         .expect("create outer jar");
 
-        let result = scan_inputs(&[outer_path.clone()], &[], None).expect("scan outer jar");
+        let result = scan_inputs(std::slice::from_ref(&outer_path), &[], None, None)
+            .expect("scan outer jar");
 
         assert_eq!(result.class_count, 2);
         assert_eq!(result.artifacts.len(), 3);
@@ -2874,6 +3334,45 @@ mod tests {
         assert_eq!(nullness.return_nullness, Nullness::NonNull);
     }
 
+    #[test]
+    fn generated_annotations_match_by_simple_name() {
+        let constant_pool = vec![
+            ConstantPool::Utf8 {
+                value: String::new(),
+            },
+            ConstantPool::Utf8 {
+                value: "Llombok/Generated;".to_string(),
+            },
+            ConstantPool::Utf8 {
+                value: "Lcom/example/Generator$AutoGenerated;".to_string(),
+            },
+            ConstantPool::Utf8 {
+                value: "Lorg/jspecify/annotations/NullMarked;".to_string(),
+            },
+            ConstantPool::Utf8 {
+                value: "Lcom/example/GeneratedBy$Marker;".to_string(),
+            },
+        ];
+        let annotation = |index| jclassfile::attributes::Annotation::new(index, Vec::new());
+        let visible = |index| jclassfile::attributes::Attribute::RuntimeVisibleAnnotations {
+            annotations: vec![annotation(index)],
+            raw: Vec::new(),
+        };
+        let invisible = |index| jclassfile::attributes::Attribute::RuntimeInvisibleAnnotations {
+            annotations: vec![annotation(index)],
+        };
+
+        let is_generated = |attributes: &[jclassfile::attributes::Attribute]| {
+            parse_is_generated(attributes, &constant_pool).expect("generated annotation")
+        };
+
+        assert!(is_generated(&[visible(1)]));
+        assert!(is_generated(&[invisible(2)]));
+        assert!(!is_generated(&[visible(3)]));
+        assert!(!is_generated(&[invisible(4)]));
+        assert!(!is_generated(&[]));
+    }
+
     #[test]
     fn nullunmarked_overrides_class_default() {
         let constant_pool = vec![
@@ -2991,7 +3490,8 @@ mod tests {
     #[test]
     fn parse_class_bytes_falls_back_on_unknown_attribute() {
         let data = build_class_with_unknown_attribute();
-        let parsed = parse_class_bytes(&data).expect("should fall back to minimal parser");
+        let parsed =
+            parse_class_bytes(&data, ParseDepth::Full).expect("should fall back to minimal parser");
         assert_eq!(parsed.name, "com/example/FakeClass");
         assert_eq!(parsed.super_name.as_deref(), Some("java/lang/Object"));
         // Minimal parser skips methods — verify graceful degradation
@@ -0,0 +1,282 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Interning shards, picked by a hash of the text so parallel class parsing rarely waits on
+/// one lock.
+const SHARD_COUNT: usize = 16;
+
+type Shard = RwLock<HashSet<&'static str>>;
+
+/// Declares symbols interned before any other, so rules comparing call owners against them
+/// compare pointers instead of text.
+macro_rules! well_known_symbols {
+    ($($name:ident = $text:literal;)*) => {
+        $(pub(crate) static $name: Symbol = Symbol($text);)*
+
+        static WELL_KNOWN: &[&Symbol] = &[$(&$name),*];
+    };
+}
+
+well_known_symbols! {
+    JAVA_IO_FILE = "java/io/File";
+    JAVA_IO_OBJECT_INPUT_STREAM = "java/io/ObjectInputStream";
+    JAVA_LANG_BOOLEAN = "java/lang/Boolean";
+    JAVA_LANG_INTEGER = "java/lang/Integer";
+    JAVA_LANG_LONG = "java/lang/Long";
+    JAVA_LANG_OBJECT = "java/lang/Object";
+    JAVA_LANG_RUNTIME = "java/lang/Runtime";
+    JAVA_LANG_STRING = "java/lang/String";
+    JAVA_LANG_STRING_BUFFER = "java/lang/StringBuffer";
+    JAVA_LANG_STRING_BUILDER = "java/lang/StringBuilder";
+    JAVA_LANG_SYSTEM = "java/lang/System";
+    JAVA_LANG_THREAD = "java/lang/Thread";
+    JAVA_LANG_THROWABLE = "java/lang/Throwable";
+    JAVA_MATH_BIG_DECIMAL = "java/math/BigDecimal";
+    JAVA_NET_URL = "java/net/URL";
+    JAVA_UTIL_CONCURRENT_COMPLETABLE_FUTURE = "java/util/concurrent/CompletableFuture";
+    JAVA_UTIL_CONCURRENT_FORK_JOIN_TASK = "java/util/concurrent/ForkJoinTask";
+    JAVA_UTIL_CONCURRENT_FUTURE = "java/util/concurrent/Future";
+    JAVA_UTIL_CONCURRENT_FUTURE_TASK = "java/util/concurrent/FutureTask";
+    JAVA_UTIL_STREAM_STREAM = "java/util/stream/Stream";
+    KOTLIN_JVM_INTERNAL_INTRINSICS = "kotlin/jvm/internal/Intrinsics";
+    KOTLIN_SYSTEM_PROCESS_KT = "kotlin/system/ProcessKt";
+}
+
+/// Interned class, member or descriptor name.
+///
+/// Each distinct text is stored once for the rest of the process, so a symbol is a pointer:
+/// copying, equality and hashing never look at the text. Ordering still compares the text so
+/// maps keyed by symbols iterate in the same deterministic order as maps keyed by strings.
+#[derive(Clone, Copy)]
+pub(crate) struct Symbol(&'static str);
+
+impl Symbol {
+    pub(crate) fn intern(value: &str) -> Self {
+        let shard = &shards()[shard_index(value)];
+        if let Some(interned) = shard
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(value)
+        {
+            return Symbol(interned);
+        }
+        let mut shard = shard.write().unwrap_or_else(PoisonError::into_inner);
+        if let Some(interned) = shard.get(value) {
+            return Symbol(interned);
+        }
+        let interned: &'static str = Box::leak(value.into());
+        shard.insert(interned);
+        Symbol(interned)
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        self.0
+    }
+}
+
+fn shards() -> &'static [Shard; SHARD_COUNT] {
+    static SHARDS: OnceLock<[Shard; SHARD_COUNT]> = OnceLock::new();
+    SHARDS.get_or_init(|| {
+        let mut shards: [Shard; SHARD_COUNT] = std::array::from_fn(|_| RwLock::new(HashSet::new()));
+        for symbol in WELL_KNOWN {
+            shards[shard_index(symbol.0)]
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(symbol.0);
+        }
+        shards
+    })
+}
+
+fn shard_index(value: &str) -> usize {
+    let hash = value.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    });
+    (hash % SHARD_COUNT as u64) as usize
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        // Interning makes equal texts share one allocation.
+        std::ptr::eq(self.0, other.0)
+    }
+}
+
+impl Eq for Symbol {}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::ptr::hash(self.0, state);
+    }
+}
+
+impl Ord for Symbol {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self == other {
+            Ordering::Equal
+        } else {
+            self.0.cmp(other.0)
+        }
+    }
+}
+
+impl PartialOrd for Symbol {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<String> for Symbol {
+    fn eq(&self, other: &String) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<Symbol> for str {
+    fn eq(&self, other: &Symbol) -> bool {
+        self == other.0
+    }
+}
+
+impl PartialEq<Symbol> for &str {
+    fn eq(&self, other: &Symbol) -> bool {
+        *self == other.0
+    }
+}
+
+impl PartialEq<Symbol> for String {
+    fn eq(&self, other: &Symbol) -> bool {
+        self == other.0
+    }
+}
+
+impl Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.0
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.0, f)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(value: &str) -> Self {
+        Symbol::intern(value)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(value: String) -> Self {
+        Symbol::intern(&value)
+    }
+}
+
+impl From<&String> for Symbol {
+    fn from(value: &String) -> Self {
+        Symbol::intern(value)
+    }
+}
+
+impl From<Symbol> for String {
+    fn from(value: Symbol) -> Self {
+        value.0.to_string()
+    }
+}
+
+impl Serialize for Symbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Symbol::from)
+    }
+}
+
+impl From<Symbol> for opentelemetry::Value {
+    fn from(value: Symbol) -> Self {
+        opentelemetry::Value::from(value.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intern_shares_one_allocation_per_text() {
+        let first = Symbol::intern("java/lang/String");
+        let second = Symbol::from("java/lang/String".to_string());
+        assert_eq!(first, second);
+        assert!(std::ptr::eq(first.as_str(), second.as_str()));
+        assert_ne!(first, Symbol::intern("java/lang/Object"));
+        assert_eq!(first, "java/lang/String");
+        assert_eq!("java/lang/String", first);
+    }
+
+    #[test]
+    fn intern_returns_well_known_symbols() {
+        let owner = Symbol::from("java/lang/String".to_string());
+        assert_eq!(owner, JAVA_LANG_STRING);
+        assert!(std::ptr::eq(owner.as_str(), JAVA_LANG_STRING.as_str()));
+        assert_ne!(Symbol::intern("java/lang/Object"), JAVA_LANG_STRING);
+    }
+
+    #[test]
+    fn ordering_follows_text() {
+        let mut symbols = [
+            Symbol::intern("b"),
+            Symbol::intern("a"),
+            Symbol::intern("c"),
+        ];
+        symbols.sort();
+        let texts: Vec<&str> = symbols.iter().map(|symbol| symbol.as_str()).collect();
+        assert_eq!(texts, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn serde_round_trip_interns_text() {
+        let symbol = Symbol::intern("(Ljava/lang/String;)V");
+        let json = serde_json::to_string(&symbol).expect("serialize");
+        assert_eq!(json, "\"(Ljava/lang/String;)V\"");
+        let restored: Symbol = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(restored, symbol);
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::BTreeSet;
+
+use serde_sarif::sarif::{
+    MultiformatMessageString, ReportingDescriptor, ReportingDescriptorReference,
+    ReportingDescriptorRelationship, ToolComponent, ToolComponentReference,
+};
+
+/// Name of the SARIF taxonomy component holding CWE entries.
+pub(crate) const CWE_TAXONOMY_NAME: &str = "CWE";
+
+/// CWE entries referenced by rule metadata, as `(id, name)` pairs sorted by ID.
+const CWE_NAMES: &[(u32, &str)] = &[
+    (
+        78,
+        "Improper Neutralization of Special Elements used in an OS Command ('OS Command Injection')",
+    ),
+    (
+        89,
+        "Improper Neutralization of Special Elements used in an SQL Command ('SQL Injection')",
+    ),
+    (190, "Integer Overflow or Wraparound"),
+    (
+        209,
+        "Generation of Error Message Containing Sensitive Information",
+    ),
+    (
+        362,
+        "Concurrent Execution using Shared Resource with Improper Synchronization ('Race Condition')",
+    ),
+    (382, "J2EE Bad Practices: Use of System.exit()"),
+    (401, "Missing Release of Memory after Effective Lifetime"),
+    (
+        470,
+        "Use of Externally-Controlled Input to Select Classes or Code ('Unsafe Reflection')",
+    ),
+    (476, "NULL Pointer Dereference"),
+    (502, "Deserialization of Untrusted Data"),
+    (572, "Call to Thread run() instead of start()"),
+    (
+        581,
+        "Object Model Violation: Just One of Equals and Hashcode Defined",
+    ),
+    (584, "Return Inside Finally Block"),
+    (586, "Explicit Call to Finalize()"),
+    (
+        595,
+        "Comparison of Object References Instead of Object Contents",
+    ),
+    (667, "Improper Locking"),
+    (772, "Missing Release of Resource after Effective Lifetime"),
+    (833, "Deadlock"),
+    (1069, "Empty Exception Block"),
+];
+
+/// Returns the CWE entry name for an ID listed in [`CWE_NAMES`].
+pub(crate) fn cwe_name(id: u32) -> Option<&'static str> {
+    CWE_NAMES
+        .binary_search_by_key(&id, |(cwe, _)| *cwe)
+        .ok()
+        .map(|index| CWE_NAMES[index].1)
+}
+
+/// The `properties.tags` entry GitHub code scanning uses to group results by CWE.
+pub(crate) fn cwe_tag(id: u32) -> String {
+    format!("external/cwe/cwe-{id}")
+}
+
+/// Relationships linking a rule descriptor to its CWE taxa.
+pub(crate) fn cwe_relationships(cwe: &[u32]) -> Vec<ReportingDescriptorRelationship> {
+    cwe.iter()
+        .map(|id| {
+            let target = ReportingDescriptorReference::builder()
+                .id(id.to_string())
+                .tool_component(
+                    ToolComponentReference::builder()
+                        .name(CWE_TAXONOMY_NAME)
+                        .build(),
+                )
+                .build();
+            ReportingDescriptorRelationship::builder()
+                .target(target)
+                .kinds(vec!["superset".to_string()])
+                .build()
+        })
+        .collect()
+}
+
+/// Builds the CWE taxonomy holding every taxon the given rules relate to.
+pub(crate) fn cwe_taxonomy(rules: &[ReportingDescriptor]) -> Option<ToolComponent> {
+    let ids: BTreeSet<u32> = rules
+        .iter()
+        .flat_map(|rule| rule.relationships.iter().flatten())
+        .filter(|relationship| {
+            relationship
+                .target
+                .tool_component
+                .as_ref()
+                .and_then(|component| component.name.as_deref())
+                == Some(CWE_TAXONOMY_NAME)
+        })
+        .filter_map(|relationship| relationship.target.id.as_deref()?.parse().ok())
+        .collect();
+    if ids.is_empty() {
+        return None;
+    }
+    let taxa = ids
+        .into_iter()
+        .map(|id| {
+            let mut taxon = ReportingDescriptor::builder()
+                .id(id.to_string())
+                .help_uri(format!("https://cwe.mitre.org/data/definitions/{id}.html"))
+                .build();
+            if let Some(name) = cwe_name(id) {
+                taxon.short_description =
+                    Some(MultiformatMessageString::builder().text(name).build());
+            }
+            taxon
+        })
+        .collect::<Vec<_>>();
+    Some(
+        ToolComponent::builder()
+            .name(CWE_TAXONOMY_NAME)
+            .organization("MITRE")
+            .information_uri("https://cwe.mitre.org/")
+            .short_description(
+                MultiformatMessageString::builder()
+                    .text("The MITRE Common Weakness Enumeration")
+                    .build(),
+            )
+            .taxa(taxa)
+            .build(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::all_rules;
+
+    #[test]
+    fn cwe_names_are_sorted_and_cover_rule_metadata() {
+        assert!(CWE_NAMES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        for rule in all_rules() {
+            let metadata = rule.metadata();
+            for id in metadata.cwe {
+                assert!(
+                    cwe_name(*id).is_some(),
+                    "{} references CWE-{id} without a name in CWE_NAMES",
+                    metadata.id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn cwe_taxonomy_collects_related_taxa() {
+        let mut rule = ReportingDescriptor::builder().id("RULE_A").build();
+        rule.relationships = Some(cwe_relationships(&[502, 78]));
+
+        let taxonomy = cwe_taxonomy(&[rule]).expect("taxonomy");
+
+        let taxa = taxonomy.taxa.expect("taxa");
+        let ids: Vec<_> = taxa.iter().map(|taxon| taxon.id.as_str()).collect();
+        assert_eq!(ids, vec!["78", "502"]);
+        assert_eq!(
+            taxa[1]
+                .short_description
+                .as_ref()
+                .map(|text| text.text.as_str()),
+            Some("Deserialization of Untrusted Data")
+        );
+    }
+
+    #[test]
+    fn cwe_taxonomy_is_omitted_without_relationships() {
+        let rule = ReportingDescriptor::builder().id("RULE_A").build();
+
+        assert!(cwe_taxonomy(&[rule]).is_none());
+    }
+}
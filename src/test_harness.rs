@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -6,6 +7,8 @@ use anyhow::{Context, Result};
 use tempfile::TempDir;
 
 use crate::engine::{Engine, EngineOutput, build_context};
+use crate::rule_options::{RuleOptions, resolve_rule_options};
+use crate::rules::all_rules;
 use crate::scan::scan_inputs;
 
 /// Supported JVM source languages for the harness.
@@ -109,10 +112,19 @@ impl JvmTestHarness {
         &self,
         classes_dir: &Path,
         classpath: &[PathBuf],
+    ) -> Result<EngineOutput> {
+        self.analyze_with_rule_options(classes_dir, classpath, BTreeMap::new())
+    }
+
+    fn analyze_with_rule_options(
+        &self,
+        classes_dir: &Path,
+        classpath: &[PathBuf],
+        rule_options: BTreeMap<String, RuleOptions>,
     ) -> Result<EngineOutput> {
         let inputs = vec![classes_dir.to_path_buf()];
-        let scan = scan_inputs(&inputs, classpath, None).context("scan classes")?;
-        let context = build_context(scan.classes, &scan.artifacts);
+        let scan = scan_inputs(&inputs, classpath, None, None).context("scan classes")?;
+        let context = build_context(scan.classes, &scan.artifacts).with_rule_options(rule_options);
         let engine = Engine::new_with_allowed_rule_ids(None).expect("build engine");
         engine.analyze(context).context("run analysis")
     }
@@ -126,6 +138,21 @@ impl JvmTestHarness {
         let output = self.compile(language, sources, classpath)?;
         self.analyze(output.classes_dir(), classpath)
     }
+
+    /// Like [`Self::compile_and_analyze`], with `ruleOptions` given as JSON.
+    pub(crate) fn compile_and_analyze_with_rule_options(
+        &self,
+        language: Language,
+        sources: &[SourceFile],
+        classpath: &[PathBuf],
+        rule_options: serde_json::Value,
+    ) -> Result<EngineOutput> {
+        let raw = serde_json::from_value(rule_options).context("parse rule options")?;
+        let metadata: Vec<_> = all_rules().iter().map(|rule| rule.metadata()).collect();
+        let rule_options = resolve_rule_options(&raw, &metadata, "rule options")?;
+        let output = self.compile(language, sources, classpath)?;
+        self.analyze_with_rule_options(output.classes_dir(), classpath, rule_options)
+    }
 }
 
 fn javac_path() -> Result<PathBuf> {
@@ -97,3 +97,33 @@ fn json_scan_and_legacy_scan_have_equivalent_results_and_rules() {
         json_value["runs"][0]["tool"]["driver"]["rules"]
     );
 }
+
+#[test]
+fn json_scan_supports_checkstyle_format() {
+    let temp_dir = tempdir().expect("temp dir");
+    let request = format!(
+        "{{\"command\":\"scan\",\"input\":[\"{0}\"],\"format\":\"checkstyle\",\"sourceRoots\":[\"{0}\"]}}",
+        temp_dir.path().display()
+    );
+
+    let output = run_inspequte(&["--json", &request], None);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("stdout");
+    assert!(stdout.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle"));
+}
+
+#[test]
+fn json_scan_rejects_checkstyle_format_without_source_roots() {
+    let temp_dir = tempdir().expect("temp dir");
+    let request = format!(
+        "{{\"command\":\"scan\",\"input\":[\"{}\"],\"format\":\"checkstyle\"}}",
+        temp_dir.path().display()
+    );
+
+    let output = run_inspequte(&["--json", &request], None);
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).expect("stderr");
+    assert!(stderr.contains("requires --source-root"));
+}